@@ -0,0 +1,48 @@
+// benches/octonion_mul.rs
+// Schoolbook (Cayley-Dickson, 64 mults) vs Zorn vector-matrix (32 mults)
+// octonion multiplication over Goldilocks, alone and inside the VDF loop.
+//   cargo bench --bench octonion_mul
+// Reference run: one product 253 ns -> 110 ns (200 ns converting both ways);
+// 1000 VDF steps 1.35 ms -> 0.84 ms.
+// batch_65536: a serial loop vs algebra::octonion::mul_batch (16.7 ms vs
+// 17.7 ms on a single core: rayon costs little, the gain scales with cores).
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use olc_research::algebra::octonion::mul_batch;
+use olc_research::vdf::{evaluate_vdf_with, MulBackend, Octonion, Zorn};
+
+fn multiply(c: &mut Criterion) {
+    let (x, y) = (Octonion::from_seed(1), Octonion::from_seed(2));
+    let (zx, zy) = (Zorn::from_octonion(&x), Zorn::from_octonion(&y));
+
+    let mut group = c.benchmark_group("octonion_mul");
+    group.bench_function("schoolbook", |b| b.iter(|| black_box(x) * black_box(y)));
+    group.bench_function("zorn", |b| b.iter(|| black_box(zx).mul(&black_box(zy))));
+    group.bench_function("zorn_with_conversion", |b| {
+        b.iter(|| Zorn::from_octonion(&black_box(x)).mul(&Zorn::from_octonion(&black_box(y))).to_octonion())
+    });
+    group.finish();
+}
+
+fn grind(c: &mut Criterion) {
+    let (z0, k) = (Octonion::from_seed(11), Octonion::from_seed(12));
+
+    let mut group = c.benchmark_group("vdf_grind_1000");
+    for (name, backend) in [("schoolbook", MulBackend::Schoolbook), ("zorn", MulBackend::Zorn)] {
+        group.bench_function(name, |b| b.iter(|| evaluate_vdf_with(black_box(z0), k, 1000, backend).final_state));
+    }
+    group.finish();
+}
+
+fn batch(c: &mut Criterion) {
+    let xs: Vec<Octonion> = (0..1 << 16).map(Octonion::from_seed).collect();
+    let ys: Vec<Octonion> = (1 << 16..1 << 17).map(Octonion::from_seed).collect();
+
+    let mut group = c.benchmark_group("batch_65536");
+    group.bench_function("serial", |b| b.iter(|| xs.iter().zip(&ys).map(|(&x, &y)| x * y).collect::<Vec<_>>()));
+    group.bench_function("mul_batch", |b| b.iter(|| mul_batch(black_box(&xs), black_box(&ys))));
+    group.finish();
+}
+
+criterion_group!(benches, multiply, grind, batch);
+criterion_main!(benches);
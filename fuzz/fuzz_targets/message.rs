@@ -0,0 +1,13 @@
+// fuzz/fuzz_targets/message.rs
+// Decoding must never panic, and anything that decodes must re-encode to
+// exactly the same bytes (canonical encoding).
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use olc_research::wire::{Message, Wire};
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(value) = Message::from_bytes(data) {
+        assert_eq!(value.to_bytes(), data);
+    }
+});
@@ -0,0 +1,14 @@
+// fuzz/fuzz_targets/witness.rs
+// Decoding must never panic, and anything that decodes must re-encode to
+// exactly the same bytes (canonical encoding).
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use olc_research::horizon::Witness;
+use olc_research::wire::Wire;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(value) = Witness::from_bytes(data) {
+        assert_eq!(value.to_bytes(), data);
+    }
+});
@@ -0,0 +1,141 @@
+// src/address.rs
+// ADDRESSES: what a payer types in, and what a Horizon UTXO is locked to.
+//
+// An address is the Gsh32 digest (GSH-256, first 32 bytes) of the owner's
+// wire-encoded JordanSchnorr key - exactly the owner hash the UTXO leaf has
+// always committed to, so leaves keep their schema. The key itself only
+// appears when the output is spent, and the validator checks it hashes to
+// the address before checking the signature.
+//
+// Horizon keys, not Flutter ones: Flutter public keys all end on the same
+// attractor (hdwallet/path.rs), so addresses over them would all be equal.
+//
+// Text form is bech32m (BIP-350):
+//   hrp || "1" || base32(version || hash) || checksum
+// The hrp names the network, so a testnet address pasted into a mainnet
+// wallet is refused instead of paid, and the checksum catches typos.
+// Version 0 is the JD-Light key hash; a new key type gets a new version.
+
+use crate::algebra::encoding::{bech32_decode, bech32_encode, Bech32Variant};
+use crate::algebra::DecodeError;
+use crate::jordan_sig::PublicKey;
+use crate::merkle::Gsh32;
+use crate::wire::Wire;
+use std::fmt;
+
+pub const ADDRESS_VERSION: u8 = 0;
+pub const ADDRESS_BYTES: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Network {
+    Main,
+    Test,
+}
+
+impl Network {
+    pub const ALL: [Network; 2] = [Network::Main, Network::Test];
+
+    pub const fn hrp(self) -> &'static str {
+        match self {
+            Network::Main => "olc",
+            Network::Test => "tolc",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressError {
+    // Not bech32m, or the checksum fails
+    Encoding(DecodeError),
+    UnknownNetwork,
+    WrongNetwork { expected: Network, got: Network },
+    UnknownVersion { got: u8 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Address(pub [u8; ADDRESS_BYTES]);
+
+impl Address {
+    pub fn of(key: &PublicKey) -> Self {
+        Address(Gsh32::leaf(&key.to_bytes()))
+    }
+
+    pub fn to_bech32(&self, network: Network) -> String {
+        let mut data = vec![ADDRESS_VERSION];
+        data.extend(self.0);
+        bech32_encode(network.hrp(), &data, Bech32Variant::Bech32m)
+    }
+
+    /// Any network's address, and which network it names
+    pub fn parse(s: &str) -> Result<(Network, Self), AddressError> {
+        let s = s.trim();
+        let hrp = s.rsplit_once('1').map(|(hrp, _)| hrp.to_ascii_lowercase()).unwrap_or_default();
+        let network = Network::ALL.into_iter().find(|n| n.hrp() == hrp).ok_or(AddressError::UnknownNetwork)?;
+        let data = bech32_decode(network.hrp(), s, Bech32Variant::Bech32m).map_err(AddressError::Encoding)?;
+        let (&version, hash) = data.split_first().ok_or(AddressError::Encoding(DecodeError::InvalidBech32))?;
+        if version != ADDRESS_VERSION {
+            return Err(AddressError::UnknownVersion { got: version });
+        }
+        let hash = hash
+            .try_into()
+            .map_err(|_| AddressError::Encoding(DecodeError::Length { expected: 1 + ADDRESS_BYTES, got: data.len() }))?;
+        Ok((network, Address(hash)))
+    }
+
+    /// An address for `network` only
+    pub fn parse_for(s: &str, network: Network) -> Result<Self, AddressError> {
+        match Self::parse(s)? {
+            (got, address) if got == network => Ok(address),
+            (got, _) => Err(AddressError::WrongNetwork { expected: network, got }),
+        }
+    }
+}
+
+// Hex, for logs; payers get `to_bech32`
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&Gsh32::to_hex(&self.0))
+    }
+}
+
+impl PublicKey {
+    pub fn address(&self) -> Address {
+        Address::of(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jordan_sig::JordanSchnorr;
+    use rand::prelude::*;
+
+    #[test]
+    fn addresses_round_trip_per_network() {
+        let mut rng = StdRng::seed_from_u64(4332);
+        let key = JordanSchnorr::keygen(&mut rng).pub_key;
+        let address = Address::of(&key);
+        let text = address.to_bech32(Network::Main);
+        assert!(text.starts_with("olc1"));
+        assert_eq!(Address::parse(&text), Ok((Network::Main, address)));
+        assert_eq!(Address::parse(&text.to_uppercase()), Ok((Network::Main, address)));
+        assert_eq!(Address::parse_for(&address.to_bech32(Network::Test), Network::Test), Ok(address));
+        assert_eq!(
+            Address::parse_for(&text, Network::Test),
+            Err(AddressError::WrongNetwork { expected: Network::Test, got: Network::Main })
+        );
+    }
+
+    #[test]
+    fn typos_and_foreign_strings_are_refused() {
+        let text = Address([7; 32]).to_bech32(Network::Main);
+        let typo: String = text.chars().enumerate().map(|(i, c)| if i == 10 { if c == 'q' { 'p' } else { 'q' } } else { c }).collect();
+        assert_eq!(Address::parse(&typo), Err(AddressError::Encoding(DecodeError::InvalidBech32)));
+        // Bech32 (not m) under the right hrp
+        let legacy = bech32_encode("olc", &[&[0u8][..], &[7; 32]].concat(), Bech32Variant::Bech32);
+        assert_eq!(Address::parse(&legacy), Err(AddressError::Encoding(DecodeError::InvalidBech32)));
+        assert_eq!(Address::parse("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"), Err(AddressError::UnknownNetwork));
+        let future = bech32_encode("olc", &[&[1u8][..], &[7; 32]].concat(), Bech32Variant::Bech32m);
+        assert_eq!(Address::parse(&future), Err(AddressError::UnknownVersion { got: 1 }));
+    }
+}
@@ -1,314 +1,926 @@
-// src/albert.rs
-use rand::prelude::*;
-use rand_distr::{Distribution, Weibull};
-use std::ops::{Add, Sub, Mul};
-
-// --- CONFIGURATION ---
-// Modulus for the Lattice Cryptography (2^15)
-pub const Q: u64 = 32768; 
-pub type Scalar = u64;
-
-// --- 8-DIM OCTONION ---
-#[derive(Clone, Copy, Debug, PartialEq, Default)]
-pub struct Octonion {
-    pub c: [Scalar; 8],
-}
-
-impl Octonion {
-    pub fn new(c: [Scalar; 8]) -> Self {
-        Octonion { c }
-    }
-
-    pub fn zero() -> Self {
-        Octonion { c: [0; 8] }
-    }
-    
-    // Conjugate: Reals stay same, Imaginary parts negated mod Q
-    pub fn conjugate(&self) -> Self {
-        let mut new_c = [0; 8];
-        new_c[0] = self.c[0];
-        for i in 1..8 {
-            if self.c[i] == 0 {
-                new_c[i] = 0;
-            } else {
-                new_c[i] = Q - self.c[i];
-            }
-        }
-        Octonion::new(new_c)
-    }
-
-    /// Returns the L2 norm squared of the octonion coefficients
-    pub fn norm_sq(&self) -> f64 {
-        self.c.iter().map(|&x| (x as f64).powi(2)).sum()
-    }
-}
-
-// --- OCTONION ARITHMETIC (Modular) ---
-
-impl Add for Octonion {
-    type Output = Self;
-    fn add(self, other: Self) -> Self {
-        let mut res = [0; 8];
-        for i in 0..8 {
-            res[i] = (self.c[i] + other.c[i]) % Q;
-        }
-        Octonion::new(res)
-    }
-}
-
-impl Sub for Octonion {
-    type Output = Self;
-    fn sub(self, other: Self) -> Self {
-        let mut res = [0; 8];
-        for i in 0..8 {
-            // Add Q to prevent underflow before modulo
-            res[i] = (self.c[i] + Q - other.c[i]) % Q;
-        }
-        Octonion::new(res)
-    }
-}
-
-// Cayley-Dickson Multiplication
-// (a, b)(c, d) = (ac - d*b_conj, a_conj*d + cb)
-impl Mul for Octonion {
-    type Output = Self;
-    fn mul(self, other: Self) -> Self {
-        let split = |o: &Octonion| -> ([Scalar; 4], [Scalar; 4]) {
-            let mut a = [0; 4];
-            let mut b = [0; 4];
-            a.copy_from_slice(&o.c[0..4]);
-            b.copy_from_slice(&o.c[4..8]);
-            (a, b)
-        };
-
-        let (a, b) = split(&self);
-        let (c, d) = split(&other);
-
-        // Quaternion helpers (Mod Q)
-        let qadd = |x: [Scalar;4], y: [Scalar;4]| -> [Scalar;4] {
-            [ (x[0]+y[0])%Q, (x[1]+y[1])%Q, (x[2]+y[2])%Q, (x[3]+y[3])%Q ]
-        };
-        
-        let qsub = |x: [Scalar;4], y: [Scalar;4]| -> [Scalar;4] {
-            [ (x[0]+Q-y[0])%Q, (x[1]+Q-y[1])%Q, (x[2]+Q-y[2])%Q, (x[3]+Q-y[3])%Q ]
-        };
-
-        let qconj = |x: [Scalar;4]| -> [Scalar;4] {
-            [ x[0], (Q-x[1])%Q, (Q-x[2])%Q, (Q-x[3])%Q ]
-        };
-
-        let qmul = |x: [Scalar;4], y: [Scalar;4]| -> [Scalar;4] {
-            // r = x0y0 - x1y1 - x2y2 - x3y3
-            let r = (x[0]*y[0] + Q - (x[1]*y[1])%Q + Q - (x[2]*y[2])%Q + Q - (x[3]*y[3])%Q) % Q;
-            // i = x0y1 + x1y0 + x2y3 - x3y2
-            let i = (x[0]*y[1] + x[1]*y[0] + x[2]*y[3] + Q - (x[3]*y[2])%Q) % Q;
-            // j = x0y2 - x1y3 + x2y0 + x3y1
-            let j = (x[0]*y[2] + Q - (x[1]*y[3])%Q + x[2]*y[0] + x[3]*y[1]) % Q;
-            // k = x0y3 + x1y2 - x2y1 + x3y0
-            let k = (x[0]*y[3] + x[1]*y[2] + Q - (x[2]*y[1])%Q + x[3]*y[0]) % Q;
-            [r, i, j, k]
-        };
-
-        // 1. ac - d * b_conj
-        let ac = qmul(a, c);
-        let b_conj = qconj(b);
-        let d_b_conj = qmul(d, b_conj);
-        let first = qsub(ac, d_b_conj);
-
-        // 2. a_conj * d + c * b
-        let a_conj = qconj(a);
-        let a_conj_d = qmul(a_conj, d);
-        let cb = qmul(c, b);
-        let second = qadd(a_conj_d, cb);
-
-        let mut res = [0; 8];
-        res[0..4].copy_from_slice(&first);
-        res[4..8].copy_from_slice(&second);
-        Octonion::new(res)
-    }
-}
-
-// --- 27-DIM ALBERT ELEMENT ---
-#[derive(Clone, Copy, Debug, PartialEq)]
-pub struct AlbertElement {
-    pub alpha: Scalar, 
-    pub beta: Scalar, 
-    pub gamma: Scalar,
-    pub a: Octonion,
-    pub b: Octonion,
-    pub c: Octonion,
-}
-
-impl AlbertElement {
-    pub fn zero() -> Self {
-        AlbertElement {
-            alpha: 0, beta: 0, gamma: 0,
-            a: Octonion::zero(),
-            b: Octonion::zero(),
-            c: Octonion::zero(),
-        }
-    }
-
-    /// Sample Uniform Noise (Symmetric Phase)
-    pub fn sample_uniform<R: Rng + ?Sized>(rng: &mut R, shape_beta: f64, scale: f64) -> Self {
-        let dist = Weibull::new(scale, shape_beta).unwrap();
-        let sample = |r: &mut R| -> u64 { (dist.sample(r) as u64) % Q };
-        
-        let mut el = Self::zero();
-        el.alpha = sample(rng);
-        el.beta = sample(rng);
-        el.gamma = sample(rng);
-
-        for i in 0..8 { el.a.c[i] = sample(rng); }
-        for i in 0..8 { el.b.c[i] = sample(rng); }
-        for i in 0..8 { el.c.c[i] = sample(rng); }
-        el
-    }
-
-    /// Sample Structured Noise (Broken Symmetry Phase)
-    pub fn sample_structured<R: Rng + ?Sized>(
-        rng: &mut R, 
-        shape_beta: f64, 
-        scale_diag: f64, 
-        scale_bulk: f64 
-    ) -> Self {
-        let dist_diag = Weibull::new(scale_diag, shape_beta).unwrap();
-        let dist_bulk = Weibull::new(scale_bulk, shape_beta).unwrap();
-        
-        let s_diag = |r: &mut R| -> u64 { (dist_diag.sample(r) as u64) % Q };
-        let s_bulk = |r: &mut R| -> u64 { (dist_bulk.sample(r) as u64) % Q };
-
-        let mut el = Self::zero();
-        el.alpha = s_diag(rng);
-        el.beta = s_diag(rng);
-        el.gamma = s_diag(rng);
-        
-        for i in 0..8 { el.a.c[i] = s_bulk(rng); }
-        for i in 0..8 { el.b.c[i] = s_bulk(rng); }
-        for i in 0..8 { el.c.c[i] = s_bulk(rng); }
-        el
-    }
-    
-    // --- JORDAN ALGEBRA OPERATIONS ---
-
-    // Scale by a scalar (Modulo Q)
-    // IMPORTANT: Because 'factor' is a scalar (Real number), this operation 
-    // is associative with matrix multiplication: A(s*c) = (As)c.
-    pub fn scale(&self, factor: Scalar) -> Self {
-        let f = factor % Q;
-        let mut res = Self::zero();
-        res.alpha = (self.alpha * f) % Q;
-        res.beta = (self.beta * f) % Q;
-        res.gamma = (self.gamma * f) % Q;
-        
-        let scale_oct = |o: Octonion| -> Octonion {
-            let mut c = [0; 8];
-            for i in 0..8 { c[i] = (o.c[i] * f) % Q; }
-            Octonion::new(c)
-        };
-        
-        res.a = scale_oct(self.a);
-        res.b = scale_oct(self.b);
-        res.c = scale_oct(self.c);
-        res
-    }
-
-    // Jordan Product: X o Y = XY + YX
-    // Note: We use the symmetrized product without the 1/2 factor to stay in the integer ring.
-    pub fn jordan_product(&self, other: &Self) -> Self {
-        // Helpers for 3x3 matrix extraction
-        let get_row = |m: &AlbertElement, i: usize| -> [Octonion; 3] {
-            let to_oct = |s: Scalar| -> Octonion { 
-                let mut c = [0; 8]; c[0] = s; Octonion::new(c) 
-            };
-            match i {
-                0 => [to_oct(m.alpha), m.c, m.b], // Row 1: [a, c, b] (Note: c is (1,2), b is (1,3) in this notation)
-                1 => [m.c.conjugate(), to_oct(m.beta), m.a], // Row 2: [c*, b, a]
-                2 => [m.b.conjugate(), m.a.conjugate(), to_oct(m.gamma)], // Row 3: [b*, a*, g]
-                _ => panic!("Invalid row")
-            }
-        };
-
-        // Dot product of vector of octonions
-        let dot = |r: [Octonion; 3], c: [Octonion; 3]| -> Octonion {
-            (r[0] * c[0]) + (r[1] * c[1]) + (r[2] * c[2])
-        };
-
-        let x = self;
-        let y = other;
-
-        // Calculate Diagonal 1 (Alpha)
-        // (XY)_11 + (YX)_11
-        // (XY)_11 = Row1(X) . Col1(Y). Note Col1(Y) is Row1(Y)* (Conjugate transpose)
-        // Since Albert elements are Hermitian, Col(i) is Row(i) conjugated.
-        // let row_x_0 = get_row(x, 0);
-        // let row_y_0 = get_row(y, 0);
-        
-        // Helper to get column j from element m
-        let get_col = |m: &AlbertElement, j: usize| -> [Octonion; 3] {
-            let r = get_row(m, j);
-            [r[0].conjugate(), r[1].conjugate(), r[2].conjugate()]
-        };
-
-        // Diagonals (Real part of Octonion result)
-        let d1 = dot(get_row(x, 0), get_col(y, 0)) + dot(get_row(y, 0), get_col(x, 0));
-        let d2 = dot(get_row(x, 1), get_col(y, 1)) + dot(get_row(y, 1), get_col(x, 1));
-        let d3 = dot(get_row(x, 2), get_col(y, 2)) + dot(get_row(y, 2), get_col(x, 2));
-
-        // Off-Diagonals
-        // (XY)_12 + (YX)_12
-        let od_c = dot(get_row(x, 0), get_col(y, 1)) + dot(get_row(y, 0), get_col(x, 1)); // (1,2) -> c
-        let od_b = dot(get_row(x, 0), get_col(y, 2)) + dot(get_row(y, 0), get_col(x, 2)); // (1,3) -> b
-        let od_a = dot(get_row(x, 1), get_col(y, 2)) + dot(get_row(y, 1), get_col(x, 2)); // (2,3) -> a
-
-        AlbertElement {
-            alpha: d1.c[0], // Extract real part
-            beta: d2.c[0],
-            gamma: d3.c[0],
-            c: od_c,
-            b: od_b,
-            a: od_a,
-        }
-    }
-
-    // Check bounds (L-infinity norm) for rejection sampling
-    pub fn exceeds_bound(&self, bound: Scalar) -> bool {
-        if self.alpha > bound || self.beta > bound || self.gamma > bound { return true; }
-        
-        let check_oct = |o: &Octonion| -> bool {
-            o.c.iter().any(|&x| x > bound)
-        };
-        
-        check_oct(&self.a) || check_oct(&self.b) || check_oct(&self.c)
-    }
-}
-
-// --- ALBERT ARITHMETIC ---
-
-impl Add for AlbertElement {
-    type Output = Self;
-    fn add(self, other: Self) -> Self {
-        AlbertElement {
-            alpha: (self.alpha + other.alpha) % Q,
-            beta: (self.beta + other.beta) % Q,
-            gamma: (self.gamma + other.gamma) % Q,
-            a: self.a + other.a,
-            b: self.b + other.b,
-            c: self.c + other.c,
-        }
-    }
-}
-
-impl Sub for AlbertElement {
-    type Output = Self;
-    fn sub(self, other: Self) -> Self {
-        AlbertElement {
-            alpha: (self.alpha + Q - other.alpha) % Q,
-            beta: (self.beta + Q - other.beta) % Q,
-            gamma: (self.gamma + Q - other.gamma) % Q,
-            a: self.a - other.a,
-            b: self.b - other.b,
-            c: self.c - other.c,
-        }
-    }
-}
\ No newline at end of file
+// src/albert.rs
+use rand::prelude::*;
+#[cfg(feature = "research")]
+use rand_distr::{Distribution, Weibull};
+use crate::algebra::fano::CANONICAL;
+use crate::gsh::GSH256;
+use std::ops::{Add, Sub};
+use zeroize::Zeroize;
+
+pub mod ring;
+pub mod selftest;
+
+// --- CONFIGURATION ---
+// Modulus for the Lattice Cryptography (2^15)
+pub const Q: u64 = 32768; 
+pub type Scalar = u64;
+
+// NTT-friendly prime preset, Dilithium's q = 2^23 - 2^13 + 1. A power of two
+// has no roots of unity and leaks the low bits of products; q - 1 is
+// divisible by 512, so q has the 512th roots a negacyclic NTT of length
+// 256 needs. Use it as AlbertElement<Q_DILITHIUM>, JordanSchnorr<Q_DILITHIUM>.
+pub const Q_DILITHIUM: u64 = 8_380_417;
+
+// --- COEFFICIENT RING Z_M ---
+// Octonion coefficients live in [0, M). The modulus is a const generic, so
+// every reduction below is compiled against a known constant: for the
+// protocol's Q = 2^15 (the default) `% M` is a mask, for any other M a
+// multiply-shift, and never a runtime divide. A different parameter set is
+// just a different M: AlbertElement<12289>, say. Sums of two residues must
+// fit in a u64, so M <= 2^63, which is checked when the type is used.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct Zq<const M: u64 = Q>(pub Scalar);
+
+impl<const M: u64> Zq<M> {
+    const VALID: () = assert!(M >= 2 && M <= 1 << 63, "modulus must be in [2, 2^63]");
+    pub const MODULUS: u64 = M;
+}
+
+// a * b mod M for a, b < M. While M <= 2^32 the product fits in a u64 and
+// keeps the cheap constant reduction; above that it is widened to u128
+// (the branch is on a constant and folds away), so a cryptographic-size
+// modulus never wraps silently.
+#[inline(always)]
+pub fn mul_mod<const M: u64>(a: Scalar, b: Scalar) -> Scalar {
+    if M <= 1 << 32 {
+        (a * b) % M
+    } else {
+        ((a as u128 * b as u128) % M as u128) as u64
+    }
+}
+
+impl<const M: u64> crate::algebra::Scalar for Zq<M> {
+    fn zero() -> Self { Zq(0) }
+    fn one() -> Self { Zq(1) }
+    fn add(self, rhs: Self) -> Self { Zq((self.0 + rhs.0) % M) }
+    // Add M to prevent underflow before modulo
+    fn sub(self, rhs: Self) -> Self { Zq((self.0 + M - rhs.0) % M) }
+    fn mul(self, rhs: Self) -> Self {
+        let () = Self::VALID;
+        Zq(mul_mod::<M>(self.0, rhs.0))
+    }
+    fn neg(self) -> Self { Zq((M - self.0) % M) }
+}
+
+// --- 8-DIM OCTONION ---
+// The canonical algebra::Octonion over Z_M.
+pub type Octonion<const M: u64 = Q> = crate::algebra::Octonion<Zq<M>>;
+
+impl<const M: u64> Octonion<M> {
+    /// Returns the L2 norm squared of the (unreduced) octonion coefficients
+    pub fn l2_norm_sq(&self) -> f64 {
+        self.coeffs.iter().map(|&x| (x.0 as f64).powi(2)).sum()
+    }
+}
+
+// --- 27-DIM ALBERT ELEMENT ---
+// One contiguous array, laid out as alpha, beta, gamma, then a, b and c
+// coefficient by coefficient. Arithmetic, hashing, encoding and equality
+// all run over the single slice; the accessors below are views of it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AlbertElement<const M: u64 = Q> {
+    pub coeffs: [Scalar; 27],
+}
+
+// (alpha, beta, gamma, a, b, c)
+pub type Parts<const M: u64 = Q> = (Scalar, Scalar, Scalar, Octonion<M>, Octonion<M>, Octonion<M>);
+
+// Offsets into AlbertElement::coeffs
+pub const ALPHA: usize = 0;
+pub const BETA: usize = 1;
+pub const GAMMA: usize = 2;
+pub const A: usize = 3;
+pub const B: usize = 11;
+pub const C: usize = 19;
+
+impl<const M: u64> AlbertElement<M> {
+    pub fn zero() -> Self {
+        AlbertElement { coeffs: [0; 27] }
+    }
+
+    pub fn from_parts(alpha: Scalar, beta: Scalar, gamma: Scalar, a: Octonion<M>, b: Octonion<M>, c: Octonion<M>) -> Self {
+        let mut e = Self::zero();
+        e.coeffs[..3].copy_from_slice(&[alpha, beta, gamma]);
+        e.set_a(a);
+        e.set_b(b);
+        e.set_c(c);
+        e
+    }
+
+    // --- FIELDWISE VIEWS ---
+    pub fn alpha(&self) -> Scalar { self.coeffs[ALPHA] }
+    pub fn beta(&self) -> Scalar { self.coeffs[BETA] }
+    pub fn gamma(&self) -> Scalar { self.coeffs[GAMMA] }
+    pub fn a(&self) -> Octonion<M> { self.octonion(A) }
+    pub fn b(&self) -> Octonion<M> { self.octonion(B) }
+    pub fn c(&self) -> Octonion<M> { self.octonion(C) }
+
+    pub fn set_alpha(&mut self, x: Scalar) { self.coeffs[ALPHA] = x; }
+    pub fn set_beta(&mut self, x: Scalar) { self.coeffs[BETA] = x; }
+    pub fn set_gamma(&mut self, x: Scalar) { self.coeffs[GAMMA] = x; }
+    pub fn set_a(&mut self, o: Octonion<M>) { self.set_octonion(A, o); }
+    pub fn set_b(&mut self, o: Octonion<M>) { self.set_octonion(B, o); }
+    pub fn set_c(&mut self, o: Octonion<M>) { self.set_octonion(C, o); }
+
+    // In-place views, for code that wrote through the old public fields
+    // (`x.alpha += d`, `x.a.coeffs[i] = Zq(v)` is now `x.a_mut()[i] = v`)
+    pub fn alpha_mut(&mut self) -> &mut Scalar { &mut self.coeffs[ALPHA] }
+    pub fn beta_mut(&mut self) -> &mut Scalar { &mut self.coeffs[BETA] }
+    pub fn gamma_mut(&mut self) -> &mut Scalar { &mut self.coeffs[GAMMA] }
+    pub fn a_mut(&mut self) -> &mut [Scalar; 8] { self.octonion_mut(A) }
+    pub fn b_mut(&mut self) -> &mut [Scalar; 8] { self.octonion_mut(B) }
+    pub fn c_mut(&mut self) -> &mut [Scalar; 8] { self.octonion_mut(C) }
+
+    pub fn parts(&self) -> Parts<M> {
+        (self.alpha(), self.beta(), self.gamma(), self.a(), self.b(), self.c())
+    }
+
+    fn octonion(&self, at: usize) -> Octonion<M> {
+        Octonion::<M>::new(std::array::from_fn(|i| Zq(self.coeffs[at + i])))
+    }
+
+    fn octonion_mut(&mut self, at: usize) -> &mut [Scalar; 8] {
+        (&mut self.coeffs[at..at + 8]).try_into().unwrap()
+    }
+
+    fn set_octonion(&mut self, at: usize, o: Octonion<M>) {
+        for (x, z) in self.coeffs[at..at + 8].iter_mut().zip(o.coeffs) {
+            *x = z.0;
+        }
+    }
+
+    // Equality that reads every coefficient whatever differs, for secrets
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        self.coeffs.iter().zip(&other.coeffs).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+    }
+
+    // --- SAMPLERS ---
+    // The lattice distributions (NOISE below), drawn centered and reduced.
+
+    // Every coefficient uniform in [0, M): public matrices and generators
+    pub fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::from_centered(&std::array::from_fn(|_| rng.gen_range(0..M) as i64))
+    }
+
+    // A uniform element with N(X) a unit mod M (rank 3 and invertible), by
+    // rejection. A degenerate generator (N(X) = 0, or even mod 2^15)
+    // multiplies the key space into a smaller one.
+    pub fn sample_invertible<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        loop {
+            let x = Self::random(rng);
+            if x.is_invertible() {
+                return x;
+            }
+        }
+    }
+
+    // Every coefficient uniform in [0, M), derived from msg under domain:
+    // GSH-XOF words cut to COEFF_BITS bits and rejected when >= M, so no
+    // value is likelier than another (for M = Q nothing is ever rejected).
+    // Public values only - the number of draws depends on the output.
+    pub fn hash_to_element(msg: &[u8], domain: &[u8]) -> Self {
+        let mut xof = GSH256::xof(&[&(domain.len() as u64).to_le_bytes(), domain, msg]);
+        let mask = (1u64 << Self::COEFF_BITS) - 1;
+        let mut draw = || loop {
+            let x = xof.next_u64() & mask;
+            if x < M {
+                return x;
+            }
+        };
+        AlbertElement { coeffs: std::array::from_fn(|_| draw()) }
+    }
+
+    // Every coefficient from the centered binomial B(eta), in [-eta, eta]
+    pub fn sample_cbd<R: Rng + ?Sized>(rng: &mut R, eta: u32) -> Self {
+        Self::from_centered(&std::array::from_fn(|_| cbd(rng, eta)))
+    }
+
+    // Every coefficient from the discrete Gaussian D_sigma (constant time)
+    pub fn sample_gaussian<R: Rng + ?Sized>(rng: &mut R, sigma: f64) -> Self {
+        let dist = DiscreteGaussian::new(sigma);
+        Self::from_centered(&std::array::from_fn(|_| dist.sample(rng)))
+    }
+
+    // The original heavy-tailed experiments; not lattice noise, so they
+    // are kept for research only.
+    /// Sample Uniform Noise (Symmetric Phase)
+    #[cfg(feature = "research")]
+    pub fn sample_uniform<R: Rng + ?Sized>(rng: &mut R, shape_beta: f64, scale: f64) -> Self {
+        let dist = Weibull::new(scale, shape_beta).unwrap();
+        let sample = |r: &mut R| -> u64 { (dist.sample(r) as u64) % M };
+        
+        AlbertElement { coeffs: std::array::from_fn(|_| sample(rng)) }
+    }
+
+    /// Sample Structured Noise (Broken Symmetry Phase)
+    #[cfg(feature = "research")]
+    pub fn sample_structured<R: Rng + ?Sized>(
+        rng: &mut R, 
+        shape_beta: f64, 
+        scale_diag: f64, 
+        scale_bulk: f64 
+    ) -> Self {
+        let dist_diag = Weibull::new(scale_diag, shape_beta).unwrap();
+        let dist_bulk = Weibull::new(scale_bulk, shape_beta).unwrap();
+        
+        let s_diag = |r: &mut R| -> u64 { (dist_diag.sample(r) as u64) % M };
+        let s_bulk = |r: &mut R| -> u64 { (dist_bulk.sample(r) as u64) % M };
+
+        AlbertElement { coeffs: std::array::from_fn(|i| if i < A { s_diag(rng) } else { s_bulk(rng) }) }
+    }
+    
+    // --- JORDAN ALGEBRA OPERATIONS ---
+
+    // Scale by a scalar (Modulo M)
+    // IMPORTANT: Because 'factor' is a scalar (Real number), this operation 
+    // is associative with matrix multiplication: A(s*c) = (As)c.
+    pub fn scale(&self, factor: Scalar) -> Self {
+        let f = factor % M;
+        AlbertElement { coeffs: self.coeffs.map(|x| mul_mod::<M>(x % M, f)) }
+    }
+
+    // Jordan Product: X o Y = XY + YX
+    // Note: We use the symmetrized product without the 1/2 factor to stay in the integer ring.
+    //
+    // Expanded entry by entry, most of the 54 octonion products of the two
+    // matrix products are scalar multiples or cancel to inner products:
+    //   alpha'' = 2 (alpha alpha' + <b, b'> + <c, c'>)      (beta'', gamma'' alike)
+    //   c'' = (alpha + beta) c' + (alpha' + beta') c + b a'* + b' a*
+    //   b'' = (alpha + gamma) b' + (alpha' + gamma') b + c a' + c' a
+    //   a'' = (beta + gamma) a' + (beta' + gamma') a + c* b' + c'* b
+    // Each output coefficient is summed unreduced in an i128 and reduced
+    // once. For M <= 2^32 every term is a raw u64 product (< 2^64) and the
+    // at most 34 of them cannot overflow; above that the terms are reduced
+    // first. jordan_product_matrix is the direct form, kept as the reference.
+    pub fn jordan_product(&self, other: &Self) -> Self {
+        let (x, y) = (self, other);
+        let (xa, xb, xc, ya, yb, yc) = (x.a(), x.b(), x.c(), y.a(), y.b(), y.c());
+        let term = |p: u64, q: u64| -> i128 {
+            if M <= 1 << 32 { ((p % M) * (q % M)) as i128 } else { mul_mod::<M>(p % M, q % M) as i128 }
+        };
+        let reduce = |acc: i128| acc.rem_euclid(M as i128) as Scalar;
+        let dot = |u: &Octonion<M>, v: &Octonion<M>| -> i128 { (0..8).map(|i| term(u.coeffs[i].0, v.coeffs[i].0)).sum() };
+        // acc += u v with either factor conjugated, from the canonical table
+        let mul_acc = |acc: &mut [i128; 8], u: &Octonion<M>, conj_u: bool, v: &Octonion<M>, conj_v: bool| {
+            for i in 0..8 {
+                for j in 0..8 {
+                    let (k, sign) = CANONICAL.product(i, j);
+                    let flips = (sign < 0) ^ (conj_u && i > 0) ^ (conj_v && j > 0);
+                    let t = term(u.coeffs[i].0, v.coeffs[j].0);
+                    acc[k] += if flips { -t } else { t };
+                }
+            }
+        };
+        let scaled_acc = |acc: &mut [i128; 8], s: Scalar, v: &Octonion<M>| {
+            for (a, c) in acc.iter_mut().zip(&v.coeffs) {
+                *a += term(s, c.0);
+            }
+        };
+
+        let (ab, bb, cb) = (dot(&xa, &ya), dot(&xb, &yb), dot(&xc, &yc));
+        let diag = |s: Scalar, t: Scalar, u: i128, v: i128| reduce(2 * (term(s, t) + u + v));
+
+        let mut c = [0i128; 8];
+        scaled_acc(&mut c, x.alpha(), &yc);
+        scaled_acc(&mut c, x.beta(), &yc);
+        scaled_acc(&mut c, y.alpha(), &xc);
+        scaled_acc(&mut c, y.beta(), &xc);
+        mul_acc(&mut c, &xb, false, &ya, true);
+        mul_acc(&mut c, &yb, false, &xa, true);
+
+        let mut b = [0i128; 8];
+        scaled_acc(&mut b, x.alpha(), &yb);
+        scaled_acc(&mut b, x.gamma(), &yb);
+        scaled_acc(&mut b, y.alpha(), &xb);
+        scaled_acc(&mut b, y.gamma(), &xb);
+        mul_acc(&mut b, &xc, false, &ya, false);
+        mul_acc(&mut b, &yc, false, &xa, false);
+
+        let mut a = [0i128; 8];
+        scaled_acc(&mut a, x.beta(), &ya);
+        scaled_acc(&mut a, x.gamma(), &ya);
+        scaled_acc(&mut a, y.beta(), &xa);
+        scaled_acc(&mut a, y.gamma(), &xa);
+        mul_acc(&mut a, &xc, true, &yb, false);
+        mul_acc(&mut a, &yc, true, &xb, false);
+
+        let mut out = Self::zero();
+        out.coeffs[ALPHA] = diag(x.alpha(), y.alpha(), bb, cb);
+        out.coeffs[BETA] = diag(x.beta(), y.beta(), cb, ab);
+        out.coeffs[GAMMA] = diag(x.gamma(), y.gamma(), ab, bb);
+        for (i, acc) in a.iter().chain(&b).chain(&c).enumerate() {
+            out.coeffs[A + i] = reduce(*acc);
+        }
+        out
+    }
+
+    // The same product as 3x3 matrix products, row by column
+    pub fn jordan_product_matrix(&self, other: &Self) -> Self {
+        // Helpers for 3x3 matrix extraction
+        let get_row = |m: &AlbertElement<M>, i: usize| -> [Octonion<M>; 3] {
+            let to_oct = |s: Scalar| -> Octonion<M> { Octonion::<M>::from_real(Zq(s)) };
+            match i {
+                0 => [to_oct(m.alpha()), m.c(), m.b()], // Row 1: [a, c, b] (Note: c is (1,2), b is (1,3) in this notation)
+                1 => [m.c().conj(), to_oct(m.beta()), m.a()], // Row 2: [c*, b, a]
+                2 => [m.b().conj(), m.a().conj(), to_oct(m.gamma())], // Row 3: [b*, a*, g]
+                _ => panic!("Invalid row")
+            }
+        };
+
+        // Dot product of vector of octonions
+        let dot = |r: [Octonion<M>; 3], c: [Octonion<M>; 3]| -> Octonion<M> {
+            (r[0] * c[0]) + (r[1] * c[1]) + (r[2] * c[2])
+        };
+
+        let x = self;
+        let y = other;
+
+        // Calculate Diagonal 1 (Alpha)
+        // (XY)_11 + (YX)_11
+        // (XY)_11 = Row1(X) . Col1(Y). Note Col1(Y) is Row1(Y)* (Conjugate transpose)
+        // Since Albert elements are Hermitian, Col(i) is Row(i) conjugated.
+        // let row_x_0 = get_row(x, 0);
+        // let row_y_0 = get_row(y, 0);
+        
+        // Helper to get column j from element m
+        let get_col = |m: &AlbertElement<M>, j: usize| -> [Octonion<M>; 3] {
+            let r = get_row(m, j);
+            [r[0].conj(), r[1].conj(), r[2].conj()]
+        };
+
+        // Diagonals (Real part of Octonion result)
+        let d1 = dot(get_row(x, 0), get_col(y, 0)) + dot(get_row(y, 0), get_col(x, 0));
+        let d2 = dot(get_row(x, 1), get_col(y, 1)) + dot(get_row(y, 1), get_col(x, 1));
+        let d3 = dot(get_row(x, 2), get_col(y, 2)) + dot(get_row(y, 2), get_col(x, 2));
+
+        // Off-Diagonals
+        // (XY)_12 + (YX)_12
+        let od_c = dot(get_row(x, 0), get_col(y, 1)) + dot(get_row(y, 0), get_col(x, 1)); // (1,2) -> c
+        let od_b = dot(get_row(x, 0), get_col(y, 2)) + dot(get_row(y, 0), get_col(x, 2)); // (1,3) -> b
+        let od_a = dot(get_row(x, 1), get_col(y, 2)) + dot(get_row(y, 1), get_col(x, 2)); // (2,3) -> a
+
+        // Real parts of the diagonal
+        AlbertElement::from_parts(d1.coeffs[0].0, d2.coeffs[0].0, d3.coeffs[0].0, od_a, od_b, od_c)
+    }
+
+    // --- JORDAN INVARIANTS ---
+    // Read as the Hermitian matrix jordan_product works with:
+    //       [ alpha  c      b     ]
+    //   X = [ c*     beta   a     ]
+    //       [ b*     a*     gamma ]
+    // Everything stays in Z_M; no 1/2 or 1/3 is ever needed. The formulas
+    // are written once, over any backend, in ring.rs (Albert<Zq<M>> here).
+
+    pub fn one() -> Self {
+        let mut e = Self::zero();
+        e.coeffs[..3].fill(1 % M);
+        e
+    }
+
+    // T(X) = alpha + beta + gamma
+    pub fn trace(&self) -> Scalar {
+        self.to_ring().trace().0
+    }
+
+    // The cubic norm form
+    //   N(X) = alpha beta gamma - alpha n(a) - beta n(b) - gamma n(c) + 2 Re((c a) b*)
+    // with N(sX) = s^3 N(X), X# # = N(X) X and X o X# = 2 N(X) I.
+    pub fn det(&self) -> Scalar {
+        self.to_ring().det().0
+    }
+
+    // The adjoint X# = X^2 - T(X) X + S(X) I, entry by entry (the matrix
+    // square, not the doubled Jordan square)
+    pub fn sharp(&self) -> Self {
+        Self::from_ring(&self.to_ring().sharp())
+    }
+
+    // The Freudenthal cross product X x Y = (X + Y)# - X# - Y#: symmetric,
+    // bilinear, and X x X = 2 X#
+    pub fn cross(&self, other: &Self) -> Self {
+        (*self + *other).sharp() - self.sharp() - other.sharp()
+    }
+
+    // The trace form T(X, Y) = T(X . Y) for the halved product X . Y, i.e.
+    // the diagonal products plus twice each off-diagonal inner product
+    pub fn trace_form(&self, other: &Self) -> Scalar {
+        self.to_ring().trace_form(&other.to_ring()).0
+    }
+
+    // The quadratic representation U_X(Y) = 2 X.(X.Y) - (X.X).Y, in the
+    // halved product X.Y = (X o Y) / 2. Written that way it needs a 1/4,
+    // which Z_(2^15) does not have, so it is computed through the cubic
+    // structure instead: U_X(Y) = T(X, Y) X - X# x Y (McCrimmon). Then
+    //   4 U_X(Y) = 2 X o (X o Y) - (X o X) o Y
+    //   U_X(1) = X^2, and U_(U_X Y) = U_X U_Y U_X (the fundamental identity)
+    pub fn quadratic_rep(&self, y: &Self) -> Self {
+        self.scale(self.trace_form(y)) - self.sharp().cross(y)
+    }
+
+    // Rank over a field: 0 for zero, 1 when X# = 0, 2 when only N(X) = 0,
+    // else 3. Over Z_(2^15) rank 3 still allows an even, non-invertible N(X).
+    pub fn rank(&self) -> usize {
+        if *self == Self::zero() {
+            0
+        } else if self.sharp() == Self::zero() {
+            1
+        } else if self.det() == 0 {
+            2
+        } else {
+            3
+        }
+    }
+
+    pub fn is_invertible(&self) -> bool {
+        inverse_mod::<M>(self.det()).is_some()
+    }
+
+    // X^-1 = X# / N(X) (the adjugate over the cubic norm), None unless N(X)
+    // is a unit mod M. Then X^-1 o X = 2 I, but over Z_(2^15) that does not
+    // pin X^-1 down; the defining identity is U_X(X^-1) = X, and with it
+    // N(X^-1) = N(X)^-1 and (X^-1)^-1 = X.
+    pub fn inverse(&self) -> Option<Self> {
+        Some(self.sharp().scale(inverse_mod::<M>(self.det())?))
+    }
+
+    // --- IDEMPOTENTS AND PEIRCE DECOMPOSITION ---
+    // The frame E1, E2, E3 (diagonal units) splits J3(O) into the Peirce
+    // spaces J_ii = F E_i and J_ij = the (i, j) octonion slot. Relative to one
+    // E_i an element splits into J_1 + J_1/2 + J_0, where the halved product
+    // acts as E_i . X = lambda X, i.e. E_i o X = 2 lambda X here.
+
+    // E1, E2, E3 for i = 0, 1, 2
+    pub fn idempotent(i: usize) -> Self {
+        assert!(i < 3, "idempotent: the frame has E1, E2, E3 (0..3)");
+        let mut e = Self::zero();
+        e.coeffs[i] = 1 % M;
+        e
+    }
+
+    // The matrix square X^2 = X# + T(X) X - S(X) I, with S(X) = T(X#), so
+    // X o X = 2 X^2 without dividing by 2
+    pub fn square(&self) -> Self {
+        let sharp = self.sharp();
+        let s = sharp.trace();
+        sharp + self.scale(self.trace()) - Self::one().scale(s)
+    }
+
+    // X^2 = X (testing X o X = 2X would accept more elements when 2 is not
+    // invertible, as in Z_(2^15))
+    pub fn is_idempotent(&self) -> bool {
+        self.square() == *self
+    }
+
+    // The frame decomposition, summing back to self (Peirce::sum)
+    pub fn peirce(&self) -> Peirce<M> {
+        Peirce {
+            diagonal: [
+                Self::idempotent(0).scale(self.alpha()),
+                Self::idempotent(1).scale(self.beta()),
+                Self::idempotent(2).scale(self.gamma()),
+            ],
+            off_diagonal: [A, B, C].map(|at| {
+                let mut e = Self::zero();
+                e.coeffs[at..at + 8].copy_from_slice(&self.coeffs[at..at + 8]);
+                e
+            }),
+        }
+    }
+
+    // [J_1, J_1/2, J_0] relative to E_(i+1)
+    pub fn peirce_spaces(&self, i: usize) -> [Self; 3] {
+        let p = self.peirce();
+        let [diag, [a, b, c]] = [p.diagonal, p.off_diagonal];
+        // The two off-diagonal slots in row / column i, and the one that is not
+        let (half, zero_off) = match i {
+            0 => (b + c, a),
+            1 => (a + c, b),
+            2 => (a + b, c),
+            _ => panic!("peirce_spaces: the frame has E1, E2, E3 (0..3)"),
+        };
+        let zero_diag = diag.iter().enumerate().filter(|&(j, _)| j != i).fold(Self::zero(), |acc, (_, d)| acc + *d);
+        [diag[i], half, zero_diag + zero_off]
+    }
+
+    // --- CENTERED COEFFICIENTS ---
+    // Residues r in [0, M) stand for the integers in (-M/2, M/2]: Q - 1 is
+    // -1, a small value, not a huge one. Norms are measured there.
+
+    // alpha, beta, gamma, then a, b, c coefficient by coefficient
+    pub fn to_centered(&self) -> [i64; 27] {
+        self.coeffs.map(centered::<M>)
+    }
+
+    // Any integers, reduced into [0, M)
+    pub fn from_centered(v: &[i64; 27]) -> Self {
+        AlbertElement { coeffs: v.map(reduce::<M>) }
+    }
+
+    // Largest |coefficient| in the centered representation
+    pub fn norm_inf(&self) -> u64 {
+        self.to_centered().iter().map(|x| x.unsigned_abs()).max().unwrap_or(0)
+    }
+
+    // Sum of squared centered coefficients
+    pub fn norm_l2_sq(&self) -> u128 {
+        self.to_centered().iter().map(|&x| (x as i128 * x as i128) as u128).sum()
+    }
+
+    // Check bounds (centered L-infinity norm) for rejection sampling
+    pub fn exceeds_bound(&self, bound: Scalar) -> bool {
+        self.norm_inf() > bound
+    }
+}
+
+// Components of X in the Peirce spaces of the frame E1, E2, E3
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Peirce<const M: u64 = Q> {
+    pub diagonal: [AlbertElement<M>; 3],     // J_11, J_22, J_33: alpha E1, beta E2, gamma E3
+    pub off_diagonal: [AlbertElement<M>; 3], // J_23 (a), J_13 (b), J_12 (c)
+}
+
+impl<const M: u64> Peirce<M> {
+    pub fn sum(&self) -> AlbertElement<M> {
+        self.diagonal.iter().chain(&self.off_diagonal).fold(AlbertElement::zero(), |acc, x| acc + *x)
+    }
+}
+
+// --- NOISE ---
+// Both samplers touch the same memory and run the same instructions
+// whatever they return, so a secret drawn from them leaks nothing through
+// timing.
+
+// B(eta): eta fair bits minus eta more, from one u64 (eta <= 32)
+pub fn cbd<R: Rng + ?Sized>(rng: &mut R, eta: u32) -> i64 {
+    assert!(eta <= 32, "cbd: eta must be at most 32");
+    let bits = rng.next_u64();
+    let mask = ((1u128 << eta) - 1) as u64;
+    (bits & mask).count_ones() as i64 - ((bits >> eta) & mask).count_ones() as i64
+}
+
+// The table covers [-TAIL * sigma, TAIL * sigma]; the mass beyond is < 2^-100
+const TAIL: f64 = 12.0;
+
+// D_sigma over the integers by a full cumulative-table scan: a uniform
+// 63-bit u is compared against every entry of one table spanning the whole
+// width, and the count of entries it passes is the sample. A sample costs
+// 2 TAIL sigma comparisons - about 50k for the aggregate masks at Q - and
+// the table is built once per DiscreteGaussian.
+#[derive(Clone, Debug)]
+pub struct DiscreteGaussian {
+    pub sigma: f64,
+    tail: i64,
+    cdt: Vec<u64>, // cdt[i] = 2^63 P(X <= i - tail), for i in 0..2 tail
+}
+
+impl DiscreteGaussian {
+    pub fn new(sigma: f64) -> Self {
+        assert!(sigma > 0.0, "DiscreteGaussian: sigma must be positive");
+        let tail = (TAIL * sigma).ceil() as i64;
+        let rho: Vec<f64> = (-tail..=tail).map(|x| (-((x * x) as f64) / (2.0 * sigma * sigma)).exp()).collect();
+        let total: f64 = rho.iter().sum();
+        let mut acc = 0.0;
+        let cdt = rho[..rho.len() - 1]
+            .iter()
+            .map(|r| {
+                acc += r;
+                ((acc / total) * (1u64 << 63) as f64) as u64
+            })
+            .collect();
+        DiscreteGaussian { sigma, tail, cdt }
+    }
+
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> i64 {
+        let u = rng.next_u64() >> 1;
+        // u >= entry exactly when entry - u - 1 is negative (both < 2^63)
+        let passed: u64 = self.cdt.iter().map(|&c| c.wrapping_sub(u).wrapping_sub(1) >> 63).sum();
+        passed as i64 - self.tail
+    }
+}
+
+// x^-1 mod M by extended Euclid, None unless gcd(x, M) = 1
+pub fn inverse_mod<const M: u64>(x: Scalar) -> Option<Scalar> {
+    let (mut r0, mut r1) = (M as i128, (x % M) as i128);
+    let (mut t0, mut t1) = (0i128, 1i128);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (t0, t1) = (t1, t0 - q * t1);
+    }
+    (r0 == 1).then(|| t0.rem_euclid(M as i128) as Scalar)
+}
+
+// r in [0, M) as the representative in (-M/2, M/2]
+pub fn centered<const M: u64>(r: Scalar) -> i64 {
+    let r = r % M;
+    if r > M / 2 { (r as i128 - M as i128) as i64 } else { r as i64 }
+}
+
+// Any integer into [0, M)
+pub fn reduce<const M: u64>(x: i64) -> Scalar {
+    (x as i128).rem_euclid(M as i128) as Scalar
+}
+
+// --- ALBERT ARITHMETIC ---
+
+impl<const M: u64> Add for AlbertElement<M> {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        AlbertElement { coeffs: std::array::from_fn(|i| (self.coeffs[i] + other.coeffs[i]) % M) }
+    }
+}
+
+impl<const M: u64> Sub for AlbertElement<M> {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        AlbertElement { coeffs: std::array::from_fn(|i| (self.coeffs[i] + M - other.coeffs[i]) % M) }
+    }
+}
+
+// Secrets are vectors of these (redact.rs)
+impl<const M: u64> Zeroize for AlbertElement<M> {
+    fn zeroize(&mut self) {
+        self.coeffs.zeroize();
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hashing_to_elements_is_uniform_and_separated() {
+        let x = AlbertElement::<Q>::hash_to_element(b"msg", b"domain");
+        assert_eq!(x, AlbertElement::hash_to_element(b"msg", b"domain"));
+        assert_ne!(x, AlbertElement::hash_to_element(b"msg", b"domain2"));
+        // The domain is length-prefixed, so bytes cannot move across
+        assert_ne!(x, AlbertElement::hash_to_element(b"nmsg", b"domai"));
+
+        // Prime modulus: rejection keeps every coefficient reduced, and the
+        // top bit of [0, M) is set about (M - 2^22) / M of the time
+        let mut top = 0;
+        for i in 0..100u32 {
+            let y = AlbertElement::<Q_DILITHIUM>::hash_to_element(&i.to_le_bytes(), b"uniform");
+            assert!(y.coeffs.iter().all(|&c| c < Q_DILITHIUM));
+            top += y.coeffs.iter().filter(|&&c| c >= 1 << 22).count();
+        }
+        let expected = 2700.0 * (Q_DILITHIUM - (1 << 22)) as f64 / Q_DILITHIUM as f64;
+        assert!((top as f64 - expected).abs() < 150.0, "{top} vs {expected}");
+
+        let a = crate::jordan_sig::JordanSchnorr::<Q>::derive_generator(b"horizon/nike");
+        assert!(a.is_invertible());
+        assert_eq!(a, crate::jordan_sig::JordanSchnorr::<Q>::derive_generator(b"horizon/nike"));
+    }
+
+    #[test]
+    fn parameter_sets_share_one_implementation() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let (x, y): (AlbertElement, AlbertElement) = (AlbertElement::sample_gaussian(&mut rng, 50.0), AlbertElement::sample_gaussian(&mut rng, 50.0));
+        assert_eq!(x.jordan_product(&y), y.jordan_product(&x));
+
+        // The same small values read mod 12289 agree with mod Q until products wrap
+        let lift = |e: &AlbertElement| AlbertElement::<12289> { coeffs: e.coeffs.map(|x| x % 12289) };
+        let (u, v) = (lift(&x), lift(&y));
+        assert_eq!(u.jordan_product(&v), v.jordan_product(&u));
+        assert_eq!((u + v) - v, u);
+        assert_eq!(Zq::<12289>::MODULUS, 12289);
+        let third = Zq::<12289>(12288);
+        assert_eq!(crate::algebra::Scalar::mul(third, third), Zq(1));
+    }
+
+    #[test]
+    fn cubic_norm_adjoint_and_cross_product() {
+        let mut rng = StdRng::seed_from_u64(5);
+        for _ in 0..8 {
+            let (x, y): (AlbertElement, AlbertElement) = (AlbertElement::sample_gaussian(&mut rng, 50.0), AlbertElement::sample_gaussian(&mut rng, 50.0));
+            let n = x.det();
+            assert_eq!(x.sharp().sharp(), x.scale(n));
+            assert_eq!(x.jordan_product(&x.sharp()), AlbertElement::one().scale(2 * n));
+            assert_eq!(x.scale(3).det(), n * 27 % Q);
+            assert_eq!(x.cross(&y), y.cross(&x));
+            assert_eq!(x.cross(&x), x.sharp().scale(2));
+            assert_eq!(x.cross(&(y + y)), x.cross(&y).scale(2));
+            assert_eq!((x + y).trace(), (x.trace() + y.trace()) % Q);
+        }
+        assert_eq!(AlbertElement::<12289>::one().det(), 1);
+        assert_eq!(AlbertElement::<12289>::one().trace(), 3);
+        // diag(2, 3, 5): N = 30 and X# = diag(15, 10, 6)
+        let zero = Octonion::<12289>::zero();
+        let d = AlbertElement::<12289>::from_parts(2, 3, 5, zero, zero, zero);
+        assert_eq!(d.det(), 30);
+        assert_eq!(d.sharp().coeffs[..3], [15, 10, 6]);
+    }
+
+    #[test]
+    fn quadratic_representation_fundamental_identity() {
+        let mut rng = StdRng::seed_from_u64(9);
+        let mut draw = || {
+            let e: AlbertElement = AlbertElement::sample_gaussian(&mut rng, 50.0);
+            AlbertElement::<12289> { coeffs: e.coeffs.map(|x| x % 12289) }
+        };
+        for _ in 0..4 {
+            let (x, y, z) = (draw(), draw(), draw());
+            let u = |x: &AlbertElement<12289>, y: &AlbertElement<12289>| x.quadratic_rep(y);
+            // Agrees with the textbook definition (4 is invertible mod 12289)
+            assert_eq!(u(&x, &y).scale(4), x.jordan_product(&x.jordan_product(&y)).scale(2) - x.jordan_product(&x).jordan_product(&y));
+            assert_eq!(u(&x, &AlbertElement::one()).scale(2), x.jordan_product(&x));
+            assert_eq!(u(&u(&x, &y), &z), u(&x, &u(&y, &u(&x, &z))));
+        }
+        // The power-of-two protocol modulus gets the same identity
+        let (x, y): (AlbertElement, AlbertElement) = (AlbertElement::sample_gaussian(&mut rng, 50.0), AlbertElement::sample_gaussian(&mut rng, 50.0));
+        assert_eq!(x.quadratic_rep(&y).quadratic_rep(&x), x.quadratic_rep(&y.quadratic_rep(&x.quadratic_rep(&x))));
+    }
+
+    #[test]
+    fn noise_samplers_have_the_right_moments() {
+        let mut rng = StdRng::seed_from_u64(17);
+        let moments = |xs: &[i64]| {
+            let n = xs.len() as f64;
+            let mean = xs.iter().sum::<i64>() as f64 / n;
+            (mean, xs.iter().map(|&x| (x as f64 - mean).powi(2)).sum::<f64>() / n)
+        };
+
+        let b: Vec<i64> = (0..20_000).map(|_| cbd(&mut rng, 3)).collect();
+        assert!(b.iter().all(|x| x.abs() <= 3));
+        let (mean, var) = moments(&b);
+        assert!(mean.abs() < 0.05 && (var - 1.5).abs() < 0.1, "B(3): {} {}", mean, var);
+
+        for sigma in [3.0, 600.0] {
+            let d = DiscreteGaussian::new(sigma);
+            let g: Vec<i64> = (0..20_000).map(|_| d.sample(&mut rng)).collect();
+            let (mean, var) = moments(&g);
+            assert!(mean.abs() < sigma * 0.05, "sigma {}: mean {}", sigma, mean);
+            assert!((var.sqrt() / sigma - 1.0).abs() < 0.05, "sigma {}: sd {}", sigma, var.sqrt());
+        }
+
+        let e: AlbertElement = AlbertElement::sample_cbd(&mut rng, 2);
+        assert!(e.norm_inf() <= 2);
+        assert!(AlbertElement::<12289>::random(&mut rng).to_centered().iter().all(|x| x.abs() <= 6144));
+    }
+
+    #[test]
+    fn wide_gaussians_are_smooth_at_every_residue() {
+        // sigma = 4000 was once x1 + 250 x2 with x1 of width 16: every
+        // sample sat within a few dozen of a multiple of 250
+        let mut rng = StdRng::seed_from_u64(4296);
+        let sigma = 4000.0;
+        let d = DiscreteGaussian::new(sigma);
+        let n = 4_000;
+        let g: Vec<i64> = (0..n).map(|_| d.sample(&mut rng)).collect();
+
+        let mut residues = [0u32; 250];
+        for x in &g {
+            residues[x.rem_euclid(250) as usize] += 1;
+        }
+        // 16 expected each, standard deviation 4
+        assert!(residues.iter().all(|&c| (2..=40).contains(&c)), "{:?}", residues);
+
+        // Unit-sigma bins on [-3 sigma, 3 sigma] against Phi
+        let expected = [0.0214, 0.1359, 0.3413, 0.3413, 0.1359, 0.0214];
+        let mut bins = [0u32; 6];
+        for &x in &g {
+            let bin = (x as f64 / sigma + 3.0).floor();
+            if (0.0..6.0).contains(&bin) {
+                bins[bin as usize] += 1;
+            }
+        }
+        for (got, want) in bins.iter().zip(expected) {
+            assert!((*got as f64 / n as f64 - want).abs() < 0.03, "{:?}", bins);
+        }
+    }
+
+    #[test]
+    fn lazy_jordan_product_matches_the_matrix_form() {
+        fn check<const M: u64>(rng: &mut StdRng) {
+            for _ in 0..16 {
+                let (x, y) = (AlbertElement::<M>::random(rng), AlbertElement::<M>::random(rng));
+                assert_eq!(x.jordan_product(&y), x.jordan_product_matrix(&y), "modulus {}", M);
+            }
+        }
+        let mut rng = StdRng::seed_from_u64(21);
+        check::<Q>(&mut rng);
+        check::<12289>(&mut rng);
+        check::<Q_DILITHIUM>(&mut rng);
+        check::<{ 1 << 32 }>(&mut rng);
+        check::<{ (1 << 61) - 1 }>(&mut rng);
+        check::<{ 1 << 63 }>(&mut rng);
+    }
+
+    #[test]
+    fn frame_idempotents_and_peirce_spaces() {
+        let e: [AlbertElement; 3] = std::array::from_fn(AlbertElement::idempotent);
+        assert_eq!(e[0] + e[1] + e[2], AlbertElement::one());
+        for i in 0..3 {
+            assert!(e[i].is_idempotent());
+            assert_eq!(e[i].det(), 0);
+            for j in (0..3).filter(|&j| j != i) {
+                assert_eq!(e[i].jordan_product(&e[j]), AlbertElement::zero());
+            }
+        }
+
+        let mut rng = StdRng::seed_from_u64(31);
+        let x: AlbertElement = AlbertElement::random(&mut rng);
+        assert!(!x.is_idempotent());
+        assert_eq!(x.square().scale(2), x.jordan_product(&x));
+        assert_eq!(x.peirce().sum(), x);
+        for (i, e_i) in e.iter().enumerate() {
+            // E o X = 2 lambda X on J_lambda(E)
+            let [one, half, zero] = x.peirce_spaces(i);
+            assert_eq!(one + half + zero, x);
+            assert_eq!(e_i.jordan_product(&one), one.scale(2));
+            assert_eq!(e_i.jordan_product(&half), half);
+            assert_eq!(e_i.jordan_product(&zero), AlbertElement::zero());
+        }
+        // E1 + E2 is idempotent too, and so is the rank-one projector onto (1, 0, 1) / 2 mod 12289
+        assert!((e[0] + e[1]).is_idempotent());
+        let half = 6145; // 1/2 mod 12289
+        let zero = Octonion::<12289>::zero();
+        let p = AlbertElement::<12289>::from_parts(half, 0, half, zero, Octonion::<12289>::from_real(Zq(half)), zero);
+        assert!(p.is_idempotent());
+    }
+
+    #[test]
+    fn invertible_sampling_rejects_degenerate_elements() {
+        let e: [AlbertElement; 3] = std::array::from_fn(AlbertElement::idempotent);
+        assert_eq!([AlbertElement::<Q>::zero().rank(), e[0].rank(), (e[0] + e[1]).rank(), AlbertElement::<Q>::one().rank()], [0, 1, 2, 3]);
+        assert!(!(e[0] + e[1]).is_invertible());
+        // diag(2, 1, 1) has rank 3 but N = 2 is not a unit mod 2^15
+        let even = e[0].scale(2) + e[1] + e[2];
+        assert_eq!(even.rank(), 3);
+        assert!(!even.is_invertible() && even.inverse().is_none());
+
+        let mut rng = StdRng::seed_from_u64(41);
+        for _ in 0..8 {
+            let x: AlbertElement = AlbertElement::sample_invertible(&mut rng);
+            assert_eq!(x.det() % 2, 1);
+            assert_eq!(x.jordan_product(&x.inverse().unwrap()), AlbertElement::one().scale(2));
+            let y = AlbertElement::<Q_DILITHIUM>::sample_invertible(&mut rng);
+            assert_eq!(y.inverse().unwrap().inverse(), Some(y));
+        }
+        assert_eq!(inverse_mod::<12289>(2), Some(6145));
+        assert_eq!(inverse_mod::<Q>(4), None);
+    }
+
+    #[test]
+    fn inverse_is_the_jordan_inverse() {
+        let mut rng = StdRng::seed_from_u64(47);
+        for _ in 0..8 {
+            let x: AlbertElement = AlbertElement::sample_invertible(&mut rng);
+            let inv = x.inverse().unwrap();
+            assert_eq!(x.quadratic_rep(&inv), x);
+            assert_eq!(inv.det(), inverse_mod::<Q>(x.det()).unwrap());
+            assert_eq!(inv.inverse(), Some(x));
+            // Blinding a generator by a unit scalar s inverts to s^-1 X^-1
+            assert_eq!(x.scale(3).inverse(), Some(inv.scale(inverse_mod::<Q>(3).unwrap())));
+        }
+        let zero = Octonion::<Q>::zero();
+        assert_eq!(AlbertElement::<Q>::from_parts(3, 5, 7, zero, zero, zero).inverse().map(|i| i.det()), inverse_mod::<Q>(105));
+        assert_eq!(AlbertElement::<Q>::zero().inverse(), None);
+    }
+
+    #[test]
+    fn flat_layout_backs_the_fieldwise_views() {
+        let mut rng = StdRng::seed_from_u64(51);
+        let x: AlbertElement = AlbertElement::random(&mut rng);
+        let (alpha, beta, gamma, a, b, c) = x.parts();
+        assert_eq!(AlbertElement::from_parts(alpha, beta, gamma, a, b, c), x);
+        assert_eq!(x.coeffs[B..C], b.coeffs.map(|z| z.0));
+
+        let mut y = x;
+        y.set_c(Octonion::one());
+        assert_eq!((y.coeffs[C], y.c(), y.a()), (1, Octonion::one(), a));
+        y.set_beta(7);
+        assert_eq!(y.coeffs[BETA], 7);
+        assert!(x.ct_eq(&x) && !x.ct_eq(&y));
+
+        // The in-place views write the same slots the setters do
+        let mut z = x;
+        *z.gamma_mut() = 5;
+        z.b_mut()[2] = 9;
+        assert_eq!((z.gamma(), z.b().coeffs[2], z.coeffs[B + 2]), (5, Zq(9), 9));
+        assert_eq!((z.alpha(), z.a(), z.c()), (alpha, a, c));
+    }
+
+    #[test]
+    fn large_moduli_do_not_wrap() {
+        use crate::algebra::Scalar as _;
+        const M61: u64 = (1 << 61) - 1;
+        // (-1)^2 = 1 needs the full 122-bit product
+        let minus_one = Zq::<M61>(M61 - 1);
+        assert_eq!(minus_one.mul(minus_one), Zq(1));
+        assert_eq!(mul_mod::<M61>(1 << 40, 1 << 40), 1 << 19); // 2^80 = 2^19 * (2^61)
+        assert_eq!(Zq::<{ 1 << 63 }>(1 << 62).mul(Zq(2)), Zq(0));
+
+        let big = |s: u64| AlbertElement::<M61>::from_parts(
+            M61 - s,
+            s,
+            M61 / 3,
+            Octonion::<M61>::new(std::array::from_fn(|i| Zq(M61 - 1 - i as u64 * s))),
+            Octonion::<M61>::new(std::array::from_fn(|i| Zq((i as u64 + 1) << 57))),
+            Octonion::<M61>::one(),
+        );
+        let (x, y) = (big(3), big(5));
+        assert_eq!(x.jordan_product(&y), y.jordan_product(&x));
+        assert_eq!(x.scale(M61 - 1) + x, AlbertElement::zero());
+        assert_eq!(x.jordan_product(&y.scale(2)), x.jordan_product(&y) + x.jordan_product(&y));
+    }
+}
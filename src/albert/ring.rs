@@ -0,0 +1,237 @@
+// src/albert/ring.rs
+// GENERIC COEFFICIENTS: J3(O) over any scalar backend.
+//
+// AlbertElement<M> keeps u64 residues and its tuned product. The algebra
+// itself - trace, cubic norm, adjoint, cross product, trace form - is
+// written once here over an AlbertScalar, so the same code runs over:
+//   Zq<M>    - u64 residues; a constant M reduces by multiply-shift
+//              (Barrett), never a divide. AlbertElement delegates here.
+//   Zq32<M>  - u32 residues for embedded targets, products widened to u64
+//   vdf::Fp  - the Goldilocks field, the STARK's native field, for
+//              verifying Albert arithmetic inside a trace
+// An AlbertScalar is an algebra::Scalar (add / sub / mul / neg) that can
+// also reduce a u64 into itself and read its residue back.
+
+use super::{AlbertElement, Zq};
+use crate::algebra::{Octonion, Scalar};
+use crate::vdf::Fp;
+use std::ops::{Add, Sub};
+
+pub trait AlbertScalar: Scalar {
+    fn reduce(x: u64) -> Self;
+    // The canonical residue
+    fn residue(self) -> u64;
+}
+
+impl<const M: u64> AlbertScalar for Zq<M> {
+    fn reduce(x: u64) -> Self { Zq(x % M) }
+    fn residue(self) -> u64 { self.0 % M }
+}
+
+impl AlbertScalar for Fp {
+    fn reduce(x: u64) -> Self { Fp::new(x) }
+    fn residue(self) -> u64 { self.0 }
+}
+
+// --- U32 RESIDUES ---
+// Sums of two residues fit in a u32, so M <= 2^31
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct Zq32<const M: u32>(pub u32);
+
+impl<const M: u32> Zq32<M> {
+    const VALID: () = assert!(M >= 2 && M <= 1 << 31, "modulus must be in [2, 2^31]");
+}
+
+impl<const M: u32> Scalar for Zq32<M> {
+    fn zero() -> Self { Zq32(0) }
+    fn one() -> Self { Zq32(1 % M) }
+    fn add(self, rhs: Self) -> Self { Zq32((self.0 + rhs.0) % M) }
+    fn sub(self, rhs: Self) -> Self { Zq32((self.0 + M - rhs.0) % M) }
+    fn mul(self, rhs: Self) -> Self {
+        let () = Self::VALID;
+        Zq32((self.0 as u64 * rhs.0 as u64 % M as u64) as u32)
+    }
+    fn neg(self) -> Self { Zq32((M - self.0) % M) }
+}
+
+impl<const M: u32> AlbertScalar for Zq32<M> {
+    fn reduce(x: u64) -> Self { Zq32((x % M as u64) as u32) }
+    fn residue(self) -> u64 { (self.0 % M) as u64 }
+}
+
+// --- ALBERT<S> ---
+// (alpha, beta, gamma, a, b, c)
+pub type Parts<S> = (S, S, S, Octonion<S>, Octonion<S>, Octonion<S>);
+
+// Same layout as AlbertElement: alpha, beta, gamma, then a, b, c
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Albert<S> {
+    pub coeffs: [S; 27],
+}
+
+impl<S: AlbertScalar> Albert<S> {
+    pub fn zero() -> Self {
+        Albert { coeffs: [S::zero(); 27] }
+    }
+
+    pub fn one() -> Self {
+        let mut e = Self::zero();
+        e.coeffs[..3].fill(S::one());
+        e
+    }
+
+    pub fn from_parts(alpha: S, beta: S, gamma: S, a: Octonion<S>, b: Octonion<S>, c: Octonion<S>) -> Self {
+        let mut coeffs = [S::zero(); 27];
+        coeffs[..3].copy_from_slice(&[alpha, beta, gamma]);
+        for (slot, o) in coeffs[3..].chunks_mut(8).zip([a, b, c]) {
+            slot.copy_from_slice(&o.coeffs);
+        }
+        Albert { coeffs }
+    }
+
+    pub fn parts(&self) -> Parts<S> {
+        let oct = |at: usize| Octonion::new(std::array::from_fn(|i| self.coeffs[at + i]));
+        (self.coeffs[0], self.coeffs[1], self.coeffs[2], oct(3), oct(11), oct(19))
+    }
+
+    // Any u64 coefficients, reduced into the ring
+    pub fn from_u64(v: &[u64; 27]) -> Self {
+        Albert { coeffs: v.map(S::reduce) }
+    }
+
+    pub fn residues(&self) -> [u64; 27] {
+        self.coeffs.map(S::residue)
+    }
+
+    pub fn scale(&self, s: S) -> Self {
+        Albert { coeffs: self.coeffs.map(|x| x.mul(s)) }
+    }
+
+    // X o Y = XY + YX, in the expanded form of AlbertElement::jordan_product
+    pub fn jordan_product(&self, other: &Self) -> Self {
+        let (x1, x2, x3, xa, xb, xc) = self.parts();
+        let (y1, y2, y3, ya, yb, yc) = other.parts();
+        let dot = |u: &Octonion<S>, v: &Octonion<S>| u.coeffs.iter().zip(&v.coeffs).fold(S::zero(), |acc, (&p, &q)| acc.add(p.mul(q)));
+        let (ab, bb, cb) = (dot(&xa, &ya), dot(&xb, &yb), dot(&xc, &yc));
+        let diag = |s: S, t: S, u: S, v: S| {
+            let d = s.mul(t).add(u).add(v);
+            d.add(d)
+        };
+        Albert::from_parts(
+            diag(x1, y1, bb, cb),
+            diag(x2, y2, cb, ab),
+            diag(x3, y3, ab, bb),
+            yc.conj() * xb + xc.conj() * yb + ya.scale(x2.add(x3)) + xa.scale(y2.add(y3)),
+            xc * ya + yc * xa + yb.scale(x1.add(x3)) + xb.scale(y1.add(y3)),
+            xb * ya.conj() + yb * xa.conj() + yc.scale(x1.add(x2)) + xc.scale(y1.add(y2)),
+        )
+    }
+
+    // --- JORDAN INVARIANTS ---
+    // As documented on AlbertElement
+
+    pub fn trace(&self) -> S {
+        self.coeffs[0].add(self.coeffs[1]).add(self.coeffs[2])
+    }
+
+    pub fn det(&self) -> S {
+        let (alpha, beta, gamma, a, b, c) = self.parts();
+        let re = (c * a * b.conj()).coeffs[0];
+        alpha.mul(beta).mul(gamma)
+            .sub(alpha.mul(a.norm_sq()))
+            .sub(beta.mul(b.norm_sq()))
+            .sub(gamma.mul(c.norm_sq()))
+            .add(re.add(re))
+    }
+
+    pub fn sharp(&self) -> Self {
+        let (alpha, beta, gamma, a, b, c) = self.parts();
+        Albert::from_parts(
+            beta.mul(gamma).sub(a.norm_sq()),
+            gamma.mul(alpha).sub(b.norm_sq()),
+            alpha.mul(beta).sub(c.norm_sq()),
+            c.conj() * b - a.scale(alpha),
+            c * a - b.scale(beta),
+            b * a.conj() - c.scale(gamma),
+        )
+    }
+
+    pub fn cross(&self, other: &Self) -> Self {
+        (*self + *other).sharp() - self.sharp() - other.sharp()
+    }
+
+    pub fn trace_form(&self, other: &Self) -> S {
+        let term = |(&s, &t): (&S, &S)| s.mul(t);
+        let pairs = self.coeffs.iter().zip(&other.coeffs);
+        let diag = pairs.clone().take(3).map(term).fold(S::zero(), S::add);
+        let off = pairs.skip(3).map(term).fold(S::zero(), S::add);
+        diag.add(off).add(off)
+    }
+
+    pub fn quadratic_rep(&self, y: &Self) -> Self {
+        self.scale(self.trace_form(y)) - self.sharp().cross(y)
+    }
+
+    // The matrix square X# + T(X) X - S(X) I
+    pub fn square(&self) -> Self {
+        let sharp = self.sharp();
+        let s = sharp.trace();
+        sharp + self.scale(self.trace()) - Self::one().scale(s)
+    }
+}
+
+impl<S: AlbertScalar> Add for Albert<S> {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Albert { coeffs: std::array::from_fn(|i| self.coeffs[i].add(other.coeffs[i])) }
+    }
+}
+
+impl<S: AlbertScalar> Sub for Albert<S> {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        Albert { coeffs: std::array::from_fn(|i| self.coeffs[i].sub(other.coeffs[i])) }
+    }
+}
+
+// --- ALBERTELEMENT <-> ALBERT<Zq<M>> ---
+impl<const M: u64> AlbertElement<M> {
+    pub fn to_ring(&self) -> Albert<Zq<M>> {
+        Albert::from_u64(&self.coeffs)
+    }
+
+    pub fn from_ring(x: &Albert<Zq<M>>) -> Self {
+        AlbertElement { coeffs: x.residues() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::albert::Q;
+    use rand::prelude::*;
+
+    #[test]
+    fn one_implementation_over_every_backend() {
+        let mut rng = StdRng::seed_from_u64(61);
+        for _ in 0..8 {
+            let (x, y): (AlbertElement, AlbertElement) = (AlbertElement::random(&mut rng), AlbertElement::random(&mut rng));
+            // u64 residues agree with the tuned AlbertElement product
+            let (u, v) = (x.to_ring(), y.to_ring());
+            assert_eq!(AlbertElement::from_ring(&u.jordan_product(&v)), x.jordan_product(&y));
+            // ...and u32 residues agree with u64 ones
+            let (s, t) = (Albert::<Zq32<{ Q as u32 }>>::from_u64(&x.coeffs), Albert::<Zq32<{ Q as u32 }>>::from_u64(&y.coeffs));
+            assert_eq!(s.jordan_product(&t).residues(), x.jordan_product(&y).coeffs);
+            assert_eq!((s.det().residue(), s.sharp().residues()), (x.det(), x.sharp().coeffs));
+            assert_eq!(s.quadratic_rep(&t).residues(), x.quadratic_rep(&y).coeffs);
+
+            // Over the Goldilocks field the cubic identities hold too
+            let (f, g) = (Albert::<Fp>::from_u64(&x.coeffs), Albert::<Fp>::from_u64(&y.coeffs));
+            let n = f.det();
+            assert_eq!(f.sharp().sharp(), f.scale(n));
+            assert_eq!(f.jordan_product(&f.sharp()), Albert::one().scale(n + n));
+            assert_eq!(f.square().scale(Fp::new(2)), f.jordan_product(&f));
+            assert_eq!(f.cross(&g), g.cross(&f));
+        }
+    }
+}
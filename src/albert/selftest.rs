@@ -0,0 +1,121 @@
+// src/albert/selftest.rs
+// SELF TEST: The identities any correct Jordan product on J3(O) satisfies.
+//
+// jordan_product is written out entry by entry, and a swapped row / column
+// convention or a dropped conjugate still gives an answer, just a wrong
+// one. This checks it against what every Jordan algebra must obey, over
+// seeded random elements:
+//   Commutative  x o y = y o x
+//   Additive     x o (y + z) = x o y + x o z        (with commutativity, bilinear)
+//   Homogeneous  x o (s y) = s (x o y)               (degree one in each slot)
+//   Jordan       (x o x) o (x o y) = x o ((x o x) o y)
+//   Reference    x o y = jordan_product_matrix(x, y)
+// Every failure is reported with the seed that rebuilds its point, so a
+// broken edit can be replayed (sample(seed)) rather than rediscovered.
+
+use super::{AlbertElement, Scalar};
+use rand::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Identity {
+    Commutative,
+    Additive,
+    Homogeneous,
+    Jordan,
+    Reference,
+}
+
+impl Identity {
+    pub const ALL: [Identity; 5] = [
+        Identity::Commutative,
+        Identity::Additive,
+        Identity::Homogeneous,
+        Identity::Jordan,
+        Identity::Reference,
+    ];
+}
+
+// One sample point: three elements and a scalar, all from one seed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Point<const M: u64> {
+    pub seed: u64,
+    pub x: AlbertElement<M>,
+    pub y: AlbertElement<M>,
+    pub z: AlbertElement<M>,
+    pub s: Scalar,
+}
+
+pub fn sample<const M: u64>(seed: u64) -> Point<M> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let (x, y, z) = (AlbertElement::random(&mut rng), AlbertElement::random(&mut rng), AlbertElement::random(&mut rng));
+    Point { seed, x, y, z, s: rng.gen_range(0..M) }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Failure<const M: u64> {
+    pub identity: Identity,
+    pub point: Point<M>,
+}
+
+// Which identities `product` breaks at one point
+pub fn check<const M: u64>(point: &Point<M>, product: impl Fn(&AlbertElement<M>, &AlbertElement<M>) -> AlbertElement<M>) -> Vec<Identity> {
+    let Point { x, y, z, s, .. } = point;
+    let xx = product(x, x);
+    let holds = |identity: Identity| match identity {
+        Identity::Commutative => product(x, y) == product(y, x),
+        Identity::Additive => product(x, &(*y + *z)) == product(x, y) + product(x, z),
+        Identity::Homogeneous => product(x, &y.scale(*s)) == product(x, y).scale(*s),
+        Identity::Jordan => product(&xx, &product(x, y)) == product(x, &product(&xx, y)),
+        Identity::Reference => product(x, y) == x.jordan_product_matrix(y),
+    };
+    Identity::ALL.into_iter().filter(|&identity| !holds(identity)).collect()
+}
+
+// Points seed, seed + 1, .., seed + trials - 1 against `product`
+pub fn run_with<const M: u64>(
+    trials: usize,
+    seed: u64,
+    product: impl Fn(&AlbertElement<M>, &AlbertElement<M>) -> AlbertElement<M>,
+) -> Vec<Failure<M>> {
+    (seed..seed + trials as u64)
+        .flat_map(|seed| {
+            let point = sample::<M>(seed);
+            check(&point, &product).into_iter().map(move |identity| Failure { identity, point })
+        })
+        .collect()
+}
+
+// The shipped jordan_product; empty when it is a Jordan product
+pub fn run<const M: u64>(trials: usize, seed: u64) -> Vec<Failure<M>> {
+    run_with(trials, seed, AlbertElement::jordan_product)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::albert::{Q, Q_DILITHIUM};
+
+    #[test]
+    fn shipped_product_passes_and_broken_ones_are_caught() {
+        assert!(run::<Q>(16, 0).is_empty());
+        assert!(run::<Q_DILITHIUM>(8, 100).is_empty());
+
+        // The Freudenthal cross product is symmetric and bilinear, but not Jordan
+        let failures = run_with::<Q>(4, 7, AlbertElement::cross);
+        let failed = |identity| failures.iter().any(|f| f.identity == identity);
+        assert!(failed(Identity::Reference) && failed(Identity::Jordan));
+        assert!(!failed(Identity::Commutative) && !failed(Identity::Additive) && !failed(Identity::Homogeneous));
+
+        // A transposed convention (a conjugated) is caught at the point it fails
+        let swapped = |x: &AlbertElement, y: &AlbertElement| {
+            let mut x = *x;
+            x.set_a(x.a().conj());
+            x.jordan_product(y)
+        };
+        let failures = run_with::<Q>(4, 7, swapped);
+        assert!(failures.iter().any(|f| f.identity == Identity::Commutative));
+        let first = failures[0];
+        assert_eq!(first.point, sample(first.point.seed));
+        assert!(check(&first.point, swapped).contains(&first.identity));
+    }
+}
@@ -0,0 +1,26 @@
+// src/algebra.rs
+// CORE ALGEBRA: Shared hypercomplex constructions.
+//
+// Every octonion and sedenion in the crate (VDF over Goldilocks, GSH over
+// Z_2^64 / Z_2^32, Flutter and the HD wallet over Z_2^16, Albert over Z_2^15)
+// is the canonical Octonion<S> below, multiplied through one Cayley-Dickson
+// tower.
+
+pub mod backends;
+pub mod cayley_dickson;
+pub mod ct;
+pub mod encoding;
+pub mod fano;
+// Arbitrary impls: public with `--features arbitrary`, always in tests
+#[cfg(any(test, feature = "arbitrary"))]
+pub mod generators;
+pub mod laws;
+pub mod nonassociative;
+pub mod octonion;
+pub mod poly;
+
+pub use cayley_dickson::{Algebra, CayleyDickson, Field, Scalar};
+pub use encoding::{DecodeError, ScalarBytes};
+pub use fano::FanoTable;
+pub use nonassociative::NonAssociativeAlgebra;
+pub use octonion::Octonion;
@@ -0,0 +1,109 @@
+// src/algebra/backends.rs
+// WIDE BACKENDS: The same algebras over bigger rings.
+//
+// The wrapping u16/u32/u64 rings (Z_2^N) are fast but structurally weak:
+// every even element is a zero divisor, and the low k bits of a product
+// depend only on the low k bits of its factors. The backend is a type
+// parameter, so a wider or prime ring is chosen at compile time:
+//   u128      - Z_2^128, wrapping (cayley_dickson.rs); same structure,
+//               twice the width, no extra dependency
+//   Fp25519   - the prime field mod 2^255 - 19 as a crypto-bigint
+//               Montgomery residue (--features bigint); every non-zero
+//               coefficient is a unit
+// Octonion<S>, cd::Sedenion<S> and everything written against Scalar or
+// NonAssociativeAlgebra take either. The tests below run the same checks
+// over every backend.
+
+#[cfg(feature = "bigint")]
+pub use self::bigint::{Fp25519, Modulus25519};
+
+#[cfg(feature = "bigint")]
+mod bigint {
+    use super::super::cayley_dickson::{Field, Scalar};
+    use crypto_bigint::modular::constant_mod::{Residue, ResidueParams};
+    use crypto_bigint::{impl_modulus, Invert, U256};
+
+    impl_modulus!(Modulus25519, U256, "7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed");
+
+    pub type Fp25519 = Residue<Modulus25519, { U256::LIMBS }>;
+
+    // Any crypto-bigint constant-modulus residue is a scalar
+    impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize> Scalar for Residue<MOD, LIMBS> {
+        fn zero() -> Self { Self::ZERO }
+        fn one() -> Self { Self::ONE }
+        fn add(self, rhs: Self) -> Self { Residue::add(&self, &rhs) }
+        fn sub(self, rhs: Self) -> Self { Residue::sub(&self, &rhs) }
+        fn mul(self, rhs: Self) -> Self { Residue::mul(&self, &rhs) }
+        fn neg(self) -> Self { Residue::neg(&self) }
+    }
+
+    // A field when MOD is prime, as for Modulus25519
+    impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize> Field for Residue<MOD, LIMBS> {
+        fn inverse(self) -> Option<Self> { Invert::invert(&self).into() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::cayley_dickson::{self as cd, Algebra, Scalar};
+    use super::super::laws::{self, Law};
+    use super::super::Octonion;
+
+    // Small distinct coefficients from a seed, lifted into any backend
+    fn lift<S: Scalar>(seed: u64) -> [S; 8] {
+        std::array::from_fn(|i| {
+            let k = (seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) >> (8 * i)) & 0xFF;
+            (0..k).fold(S::zero(), |acc, _| acc.add(S::one()))
+        })
+    }
+
+    // Shared checks: octonions compose, alternate and do not associate;
+    // sedenions do not even alternate
+    fn backend_laws<S: Scalar>() {
+        let o = |seed: u64| Octonion::new(lift::<S>(seed));
+        for seed in 0..8 {
+            let (x, y) = (o(2 * seed), o(2 * seed + 1));
+            assert_eq!((x * y).norm_sq(), x.norm_sq().mul(y.norm_sq()), "seed {}", seed);
+            assert_eq!((x * y).conj(), y.conj() * x.conj(), "seed {}", seed);
+        }
+        let survey = laws::survey(8, o);
+        assert_eq!(survey.satisfied(), &Law::ALL[1..]);
+        assert!(survey.non_associativity() > 0.5);
+
+        let s = |seed: u64| cd::Sedenion::<S>::new(o(seed).to_cd(), o(seed + 1000).to_cd());
+        assert!(!laws::survey(8, s).satisfied().contains(&Law::LeftAlternative));
+        assert_eq!(cd::Sedenion::<S>::DIM, 16);
+    }
+
+    #[test]
+    fn every_backend_satisfies_the_same_laws() {
+        backend_laws::<u16>();
+        backend_laws::<u32>();
+        backend_laws::<u64>();
+        backend_laws::<u128>();
+        backend_laws::<crate::vdf::Fp>();
+        backend_laws::<crate::albert::Zq>();
+        #[cfg(feature = "bigint")]
+        backend_laws::<super::Fp25519>();
+
+        // u128 keeps the carries u64 loses
+        let big = Octonion::<u128>::from_real(1u128 << 64);
+        assert_eq!((big * big).coeffs[0], 0);
+        assert_ne!(Octonion::<u128>::from_real(u64::MAX as u128 + 1), Octonion::<u128>::zero());
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn prime_field_backend_has_inverses() {
+        use crypto_bigint::U256;
+        let x = Octonion::new(lift::<super::Fp25519>(3));
+        // x * conj(x) = N(x) is real, and non-zero coefficients are units
+        let n = x.norm_sq();
+        let (inv, ok) = n.invert();
+        assert!(bool::from(ok));
+        assert_eq!(Scalar::mul(n, inv), super::Fp25519::ONE);
+        assert_eq!((x * x.conj()).coeffs[0], n);
+        let two = super::Fp25519::new(&U256::from(2u8));
+        assert_eq!(-two + two, super::Fp25519::ZERO);
+    }
+}
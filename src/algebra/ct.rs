@@ -0,0 +1,214 @@
+// src/algebra/ct.rs
+// CONSTANT TIME: Selection, comparison and the ConstantTime marker.
+//
+// The subtle-crate pattern, in-tree: a secret boolean is a Choice (0 or 1,
+// hidden from the optimizer), values are picked with conditional_select
+// instead of `if`, and compared with ct_eq instead of `==` (whose derived
+// impls stop at the first differing coefficient).
+//
+// ConstantTime marks a type whose ring operations (add, sub, mul, neg) run
+// in time independent of the values:
+//   - u16 / u32 / u64 (wrapping) and Zq (Q = 2^15, reductions are masks)
+//   - Fp only with `--features ct` (vdf.rs swaps in branch-free reduction)
+//   - Octonion<S> / CayleyDickson<A> / Sedenion when their scalars are
+//     (the tower is a fixed sequence of scalar ops, no branches)
+// Modules handling secrets pin it with `const _: () = assert_constant_time::<T>();`
+// so a backend change that loses the guarantee fails to compile.
+// Inversion, division and the zero-divisor test return Option/Result and
+// are variable-time by nature; keep secrets out of them.
+
+use super::cayley_dickson::{CayleyDickson, Scalar};
+use super::octonion::Octonion;
+use crate::albert::Zq;
+use crate::sedenion::Sedenion;
+use crate::vdf::Fp;
+use std::ops::{BitAnd, BitOr, Not};
+
+#[derive(Clone, Copy, Debug)]
+pub struct Choice(u8);
+
+impl Choice {
+    // Only the low bit is used
+    pub fn from_bit(bit: u8) -> Self {
+        Choice(std::hint::black_box(bit & 1))
+    }
+
+    pub fn unwrap_u8(self) -> u8 {
+        self.0
+    }
+
+    // All ones if set, zero otherwise
+    fn mask(self) -> u64 {
+        (std::hint::black_box(self.0) as u64).wrapping_neg()
+    }
+}
+
+impl From<Choice> for bool {
+    fn from(c: Choice) -> bool {
+        c.0 == 1
+    }
+}
+
+impl BitAnd for Choice {
+    type Output = Choice;
+    fn bitand(self, rhs: Choice) -> Choice {
+        Choice(self.0 & rhs.0)
+    }
+}
+
+impl BitOr for Choice {
+    type Output = Choice;
+    fn bitor(self, rhs: Choice) -> Choice {
+        Choice(self.0 | rhs.0)
+    }
+}
+
+impl Not for Choice {
+    type Output = Choice;
+    fn not(self) -> Choice {
+        Choice(self.0 ^ 1)
+    }
+}
+
+pub trait ConditionallySelectable: Copy {
+    // `a` if choice is 0, `b` if it is 1
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self;
+
+    fn conditional_swap(a: &mut Self, b: &mut Self, choice: Choice) {
+        let (x, y) = (*a, *b);
+        *a = Self::conditional_select(&x, &y, choice);
+        *b = Self::conditional_select(&y, &x, choice);
+    }
+}
+
+pub trait ConstantTimeEq {
+    fn ct_eq(&self, other: &Self) -> Choice;
+}
+
+pub trait ConstantTime {}
+
+// Compile-time check for modules that rely on the guarantee
+pub const fn assert_constant_time<T: ConstantTime>() {}
+
+// --- SCALARS ---
+macro_rules! ct_word {
+    ($($t:ty),*) => {$(
+        impl ConditionallySelectable for $t {
+            fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+                let mask = choice.mask() as $t;
+                a ^ (mask & (a ^ b))
+            }
+        }
+
+        impl ConstantTimeEq for $t {
+            fn ct_eq(&self, other: &Self) -> Choice {
+                let x = (self ^ other) as u64;
+                // Top bit of x | -x is set iff x != 0
+                Choice(((x | x.wrapping_neg()) >> 63) as u8 ^ 1)
+            }
+        }
+
+        impl ConstantTime for $t {}
+    )*};
+}
+ct_word!(u16, u32, u64);
+
+impl ConditionallySelectable for Fp {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Fp(u64::conditional_select(&a.0, &b.0, choice))
+    }
+}
+
+impl ConstantTimeEq for Fp {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+#[cfg(feature = "ct")]
+impl ConstantTime for Fp {}
+
+impl ConditionallySelectable for Zq {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Zq(u64::conditional_select(&a.0, &b.0, choice))
+    }
+}
+
+impl ConstantTimeEq for Zq {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+impl ConstantTime for Zq {}
+
+// --- HYPERCOMPLEX TYPES ---
+fn select_coeffs<S: ConditionallySelectable, const N: usize>(a: &[S; N], b: &[S; N], choice: Choice) -> [S; N] {
+    std::array::from_fn(|i| S::conditional_select(&a[i], &b[i], choice))
+}
+
+// Fold every coefficient in; no early exit
+fn eq_coeffs<S: ConstantTimeEq>(a: &[S], b: &[S]) -> Choice {
+    a.iter().zip(b).fold(Choice(1), |acc, (x, y)| acc & x.ct_eq(y))
+}
+
+impl<S: Scalar + ConditionallySelectable> ConditionallySelectable for Octonion<S> {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Octonion::new(select_coeffs(&a.coeffs, &b.coeffs, choice))
+    }
+}
+
+impl<S: ConstantTimeEq> ConstantTimeEq for Octonion<S> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        eq_coeffs(&self.coeffs, &other.coeffs)
+    }
+}
+
+impl<S: ConstantTime> ConstantTime for Octonion<S> {}
+impl<A: ConstantTime> ConstantTime for CayleyDickson<A> {}
+
+impl ConditionallySelectable for Sedenion {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Sedenion::new(
+            Octonion::conditional_select(&a.low, &b.low, choice),
+            Octonion::conditional_select(&a.high, &b.high, choice),
+        )
+    }
+}
+
+impl ConstantTimeEq for Sedenion {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.low.ct_eq(&other.low) & self.high.ct_eq(&other.high)
+    }
+}
+
+impl ConstantTime for Sedenion {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn select_and_compare_without_branches() {
+        let (x, y) = (crate::sedenion::Octonion::from_seed(1), crate::sedenion::Octonion::from_seed(2));
+        assert_eq!(Octonion::conditional_select(&x, &y, Choice::from_bit(0)), x);
+        assert_eq!(Octonion::conditional_select(&x, &y, Choice::from_bit(1)), y);
+        assert!(bool::from(x.ct_eq(&x)));
+        assert!(!bool::from(x.ct_eq(&y)));
+
+        // A single differing low bit in the last coefficient is still seen
+        let mut z = x;
+        z.coeffs[7] ^= 1;
+        assert!(!bool::from(x.ct_eq(&z)));
+        assert!(bool::from(!x.ct_eq(&z) & Choice::from_bit(1)));
+
+        let (mut a, mut b) = (Fp(3), Fp(5));
+        Fp::conditional_swap(&mut a, &mut b, Choice::from_bit(1));
+        assert_eq!((a, b), (Fp(5), Fp(3)));
+        assert_eq!(u16::conditional_select(&0x1234, &0xFFFF, Choice::from_bit(1)), 0xFFFF);
+        assert!(bool::from(0u16.ct_eq(&0)) && !bool::from(0x8000u16.ct_eq(&0)));
+
+        let s = Sedenion::new(x, y);
+        assert!(bool::from(s.ct_eq(&s)) && !bool::from(s.ct_eq(&Sedenion::new(y, x))));
+    }
+}
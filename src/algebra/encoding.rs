@@ -0,0 +1,553 @@
+// src/algebra/encoding.rs
+// CANONICAL BYTES: Fixed little-endian layout for the algebra types.
+//
+// An element is its coefficients in order (e_0 first), each written at the
+// fixed width of its scalar:
+//   u16, Zq        - 2 bytes        Octonion = 16 bytes
+//   u32            - 4 bytes        Octonion = 32 bytes
+//   u64, Fp        - 8 bytes        Octonion = 64 bytes, Sedenion = 128
+//   u128           - 16 bytes       Octonion = 128 bytes
+// There are no length prefixes or tags, so the encoding of an Octonion<Fp>
+// is exactly what wire.rs puts in a header. Decoding is strict: a wrong
+// length or an unreduced coefficient (x and x + p would alias) is an error,
+// so every element has exactly one encoding and bytes can be hashed or
+// compared directly.
+//
+// AlbertElement<M> packs instead: its 27 coefficients (alpha, beta, gamma,
+// then a, b, c) at ceil(log2 M) bits each, little-endian bit order, zero
+// padding to a whole byte. 51 bytes at Q = 2^15 against wire.rs's 54, and
+// still one encoding per element: padding and out-of-range values are
+// rejected.
+//
+// With `--features serde` the same bytes back Serialize / Deserialize:
+// a hex string in human-readable formats (JSON, TOML), raw bytes otherwise.
+// Display / LowerHex print that hex string too (`{:#x}` adds "0x") and
+// FromStr parses it back with the same strictness, for CLIs and logs.
+
+use super::cayley_dickson::Scalar;
+use super::octonion::Octonion;
+use crate::albert::{self, AlbertElement, Zq};
+use crate::sedenion::Sedenion;
+use crate::vdf::Fp;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    Length { expected: usize, got: usize },
+    // Coefficient `index` is not the reduced representative
+    NonCanonical { index: usize },
+    // Text form only: odd length or a non-hex digit
+    InvalidHex,
+    // Bech32 text only: bad characters, mixed case, wrong human-readable
+    // part or checksum
+    InvalidBech32,
+    // Packed forms only: a set bit after the last coefficient
+    Padding,
+    // Versioned forms only: the prefix byte names another version or
+    // parameter set
+    ParameterSet { expected: u8, got: u8 },
+}
+
+pub trait ScalarBytes: Scalar {
+    const WIDTH: usize;
+
+    fn write_le(self, out: &mut Vec<u8>);
+
+    // `bytes` is exactly WIDTH long; None if not reduced
+    fn read_le(bytes: &[u8]) -> Option<Self>;
+}
+
+macro_rules! scalar_bytes_word {
+    ($($t:ty),*) => {$(
+        impl ScalarBytes for $t {
+            const WIDTH: usize = std::mem::size_of::<$t>();
+
+            fn write_le(self, out: &mut Vec<u8>) {
+                out.extend_from_slice(&self.to_le_bytes());
+            }
+
+            fn read_le(bytes: &[u8]) -> Option<Self> {
+                Some(<$t>::from_le_bytes(bytes.try_into().ok()?))
+            }
+        }
+    )*};
+}
+scalar_bytes_word!(u16, u32, u64, u128);
+
+impl ScalarBytes for Fp {
+    const WIDTH: usize = 8;
+
+    fn write_le(self, out: &mut Vec<u8>) {
+        self.0.write_le(out);
+    }
+
+    fn read_le(bytes: &[u8]) -> Option<Self> {
+        Fp::canonical(u64::read_le(bytes)?)
+    }
+}
+
+impl ScalarBytes for Zq {
+    const WIDTH: usize = 2;
+
+    fn write_le(self, out: &mut Vec<u8>) {
+        (self.0 as u16).write_le(out);
+    }
+
+    fn read_le(bytes: &[u8]) -> Option<Self> {
+        let raw = u16::read_le(bytes)? as u64;
+        (raw < albert::Q).then_some(Zq(raw))
+    }
+}
+
+// Shared by every fixed-width type: check the length, then read each
+// coefficient, reporting the first one that is out of range.
+pub(crate) fn read_coeffs<T, const N: usize>(
+    bytes: &[u8],
+    width: usize,
+    read: impl Fn(&[u8]) -> Option<T>,
+) -> Result<[T; N], DecodeError> {
+    if bytes.len() != N * width {
+        return Err(DecodeError::Length { expected: N * width, got: bytes.len() });
+    }
+    let mut out = Vec::with_capacity(N);
+    for (index, chunk) in bytes.chunks_exact(width).enumerate() {
+        out.push(read(chunk).ok_or(DecodeError::NonCanonical { index })?);
+    }
+    Ok(out.try_into().unwrap_or_else(|_| unreachable!()))
+}
+
+// --- OCTONIONS ---
+impl<S: ScalarBytes> Octonion<S> {
+    pub const BYTES: usize = 8 * S::WIDTH;
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(Self::BYTES);
+        for c in self.coeffs {
+            c.write_le(&mut out);
+        }
+        out
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        read_coeffs(bytes, S::WIDTH, S::read_le).map(Octonion::new)
+    }
+}
+
+// --- SEDENIONS (Z_2^64) ---
+impl Sedenion {
+    pub const BYTES: usize = 2 * Octonion::<u64>::BYTES;
+
+    // Low half (coefficients 0-7) first
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = self.low.to_bytes();
+        out.extend(self.high.to_bytes());
+        out
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let c: [u64; 16] = read_coeffs(bytes, 8, u64::read_le)?;
+        let (low, high) = c.split_at(8);
+        Ok(Sedenion::new(
+            Octonion::new(low.try_into().unwrap()),
+            Octonion::new(high.try_into().unwrap()),
+        ))
+    }
+}
+
+// --- ALBERT ELEMENTS (packed) ---
+impl<const M: u64> AlbertElement<M> {
+    // ceil(log2 M): enough bits for every residue in [0, M)
+    pub const COEFF_BITS: u32 = u64::BITS - (M - 1).leading_zeros();
+    pub const PACKED_BYTES: usize = (27 * Self::COEFF_BITS as usize).div_ceil(8);
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(Self::PACKED_BYTES);
+        let (mut acc, mut filled) = (0u128, 0);
+        for c in self.coeffs {
+            acc |= ((c % M) as u128) << filled;
+            filled += Self::COEFF_BITS;
+            while filled >= 8 {
+                out.push(acc as u8);
+                acc >>= 8;
+                filled -= 8;
+            }
+        }
+        if filled > 0 {
+            out.push(acc as u8);
+        }
+        out
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        if bytes.len() != Self::PACKED_BYTES {
+            return Err(DecodeError::Length { expected: Self::PACKED_BYTES, got: bytes.len() });
+        }
+        let mask = (1u128 << Self::COEFF_BITS) - 1;
+        let mut c = [0u64; 27];
+        let (mut acc, mut filled, mut next) = (0u128, 0, bytes.iter());
+        for (index, slot) in c.iter_mut().enumerate() {
+            while filled < Self::COEFF_BITS {
+                acc |= (*next.next().unwrap() as u128) << filled;
+                filled += 8;
+            }
+            let x = (acc & mask) as u64;
+            if x >= M {
+                return Err(DecodeError::NonCanonical { index });
+            }
+            *slot = x;
+            acc >>= Self::COEFF_BITS;
+            filled -= Self::COEFF_BITS;
+        }
+        if acc != 0 {
+            return Err(DecodeError::Padding);
+        }
+        Ok(AlbertElement { coeffs: c })
+    }
+}
+
+// --- HEX ---
+pub fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub fn unhex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..s.len()).step_by(2).map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok()).collect()
+}
+
+// --- BECH32 ---
+// BIP-173 text for short binary strings meant to be read and typed: a
+// human-readable part, '1', the data in a 32-letter alphabet, and a six-
+// letter BCH checksum that catches any four substitutions. Bech32m (BIP-350)
+// differs only in the checksum constant.
+const BECH32_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bech32Variant {
+    Bech32,
+    Bech32m,
+}
+
+impl Bech32Variant {
+    const fn constant(self) -> u32 {
+        match self {
+            Bech32Variant::Bech32 => 1,
+            Bech32Variant::Bech32m => 0x2bc8_30a3,
+        }
+    }
+}
+
+fn bech32_polymod(values: impl Iterator<Item = u8>) -> u32 {
+    const GEN: [u32; 5] = [0x3b6a_57b2, 0x2650_8e6d, 0x1ea1_19fa, 0x3d42_33dd, 0x2a14_62b3];
+    values.fold(1, |chk, v| {
+        let top = chk >> 25;
+        let chk = ((chk & 0x01ff_ffff) << 5) ^ v as u32;
+        (0..5).filter(|i| (top >> i) & 1 == 1).fold(chk, |chk, i| chk ^ GEN[i])
+    })
+}
+
+fn bech32_hrp_expand(hrp: &str) -> impl Iterator<Item = u8> + '_ {
+    hrp.bytes().map(|c| c >> 5).chain([0]).chain(hrp.bytes().map(|c| c & 31))
+}
+
+// Regroups bits; None when decoding leaves a partial group that is not
+// zero padding
+fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Option<Vec<u8>> {
+    let (mut acc, mut bits, mut out) = (0u32, 0u32, Vec::new());
+    for &v in data {
+        acc = (acc << from) | v as u32;
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            out.push(((acc >> bits) & ((1 << to) - 1)) as u8);
+        }
+    }
+    if pad && bits > 0 {
+        out.push(((acc << (to - bits)) & ((1 << to) - 1)) as u8);
+    } else if !pad && (bits >= from || (acc << (to - bits)) & ((1 << to) - 1) != 0) {
+        return None;
+    }
+    Some(out)
+}
+
+/// `hrp` is lowercase ASCII; the result is all lowercase
+pub fn bech32_encode(hrp: &str, data: &[u8], variant: Bech32Variant) -> String {
+    let data = convert_bits(data, 8, 5, true).unwrap_or_else(|| unreachable!());
+    let poly = bech32_polymod(bech32_hrp_expand(hrp).chain(data.iter().copied()).chain([0; 6])) ^ variant.constant();
+    let checksum = (0..6).map(|i| ((poly >> (5 * (5 - i))) & 31) as u8);
+    let letters: String = data.iter().copied().chain(checksum).map(|v| BECH32_CHARSET[v as usize] as char).collect();
+    format!("{}1{}", hrp, letters)
+}
+
+/// The data under `hrp`, from either all-lowercase or all-uppercase text
+pub fn bech32_decode(hrp: &str, s: &str, variant: Bech32Variant) -> Result<Vec<u8>, DecodeError> {
+    if s.bytes().any(|b| b.is_ascii_lowercase()) && s.bytes().any(|b| b.is_ascii_uppercase()) {
+        return Err(DecodeError::InvalidBech32);
+    }
+    let s = s.to_ascii_lowercase();
+    let (head, letters) = s.rsplit_once('1').ok_or(DecodeError::InvalidBech32)?;
+    if head != hrp || letters.len() < 6 {
+        return Err(DecodeError::InvalidBech32);
+    }
+    let values = letters
+        .bytes()
+        .map(|c| BECH32_CHARSET.iter().position(|&x| x == c).map(|v| v as u8))
+        .collect::<Option<Vec<u8>>>()
+        .ok_or(DecodeError::InvalidBech32)?;
+    if bech32_polymod(bech32_hrp_expand(hrp).chain(values.iter().copied())) != variant.constant() {
+        return Err(DecodeError::InvalidBech32);
+    }
+    convert_bits(&values[..values.len() - 6], 5, 8, false).ok_or(DecodeError::InvalidBech32)
+}
+
+// --- TEXT ---
+// Lowercase hex of the canonical bytes; parsing takes either case and an
+// optional "0x" prefix.
+fn parse_hex(s: &str) -> Result<Vec<u8>, DecodeError> {
+    let digits = s.strip_prefix("0x").unwrap_or(s);
+    // from_str_radix would also take a sign
+    if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(DecodeError::InvalidHex);
+    }
+    unhex(digits).ok_or(DecodeError::InvalidHex)
+}
+
+impl<S: ScalarBytes> fmt::LowerHex for Octonion<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let prefix = if f.alternate() { "0x" } else { "" };
+        write!(f, "{}{}", prefix, hex(&self.to_bytes()))
+    }
+}
+
+impl<S: ScalarBytes> fmt::Display for Octonion<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(self, f)
+    }
+}
+
+impl<S: ScalarBytes> FromStr for Octonion<S> {
+    type Err = DecodeError;
+
+    fn from_str(s: &str) -> Result<Self, DecodeError> {
+        Octonion::from_bytes(&parse_hex(s)?)
+    }
+}
+
+impl fmt::LowerHex for Sedenion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let prefix = if f.alternate() { "0x" } else { "" };
+        write!(f, "{}{}", prefix, hex(&self.to_bytes()))
+    }
+}
+
+impl fmt::Display for Sedenion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(self, f)
+    }
+}
+
+impl FromStr for Sedenion {
+    type Err = DecodeError;
+
+    fn from_str(s: &str) -> Result<Self, DecodeError> {
+        Sedenion::from_bytes(&parse_hex(s)?)
+    }
+}
+
+// --- SERDE ---
+#[cfg(feature = "serde")]
+pub(crate) mod serde_bytes {
+    use super::{hex, unhex};
+    use serde::de::{self, Visitor};
+    use serde::{Deserializer, Serializer};
+    use std::fmt;
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&hex(bytes))
+        } else {
+            serializer.serialize_bytes(bytes)
+        }
+    }
+
+    struct BytesVisitor;
+
+    impl<'de> Visitor<'de> for BytesVisitor {
+        type Value = Vec<u8>;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "canonical bytes or their hex string")
+        }
+
+        fn visit_str<E: de::Error>(self, s: &str) -> Result<Vec<u8>, E> {
+            unhex(s).ok_or_else(|| E::custom("invalid hex"))
+        }
+
+        fn visit_bytes<E: de::Error>(self, b: &[u8]) -> Result<Vec<u8>, E> {
+            Ok(b.to_vec())
+        }
+
+        // Formats without a native byte type hand them over as a sequence
+        fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+            let mut out = Vec::new();
+            while let Some(b) = seq.next_element()? {
+                out.push(b);
+            }
+            Ok(out)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(BytesVisitor)
+        } else {
+            deserializer.deserialize_bytes(BytesVisitor)
+        }
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Length { expected, got } => write!(f, "expected {} bytes, got {}", expected, got),
+            DecodeError::NonCanonical { index } => write!(f, "coefficient {} is not reduced", index),
+            DecodeError::InvalidHex => write!(f, "invalid hex"),
+            DecodeError::InvalidBech32 => write!(f, "invalid bech32"),
+            DecodeError::Padding => write!(f, "non-zero padding bits"),
+            DecodeError::ParameterSet { expected, got } => write!(f, "parameter set {:#04x}, expected {:#04x}", got, expected),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<S: ScalarBytes> serde::Serialize for Octonion<S> {
+    fn serialize<Z: serde::Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> {
+        serde_bytes::serialize(&self.to_bytes(), serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, S: ScalarBytes> serde::Deserialize<'de> for Octonion<S> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = serde_bytes::deserialize(deserializer)?;
+        Octonion::from_bytes(&bytes).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Sedenion {
+    fn serialize<Z: serde::Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> {
+        serde_bytes::serialize(&self.to_bytes(), serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Sedenion {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = serde_bytes::deserialize(deserializer)?;
+        Sedenion::from_bytes(&bytes).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_layout_round_trips_and_rejects_aliases() {
+        let x = crate::vdf::Octonion::from_seed(7);
+        let bytes = x.to_bytes();
+        assert_eq!(bytes.len(), 64);
+        assert_eq!(&bytes[..8], &x.coeffs[0].0.to_le_bytes());
+        assert_eq!(crate::vdf::Octonion::from_bytes(&bytes), Ok(x));
+
+        // p aliases 0: only the reduced form decodes
+        let mut alias = bytes.clone();
+        alias[16..24].copy_from_slice(&crate::vdf::P.to_le_bytes());
+        assert_eq!(crate::vdf::Octonion::from_bytes(&alias), Err(DecodeError::NonCanonical { index: 2 }));
+        assert_eq!(crate::vdf::Octonion::from_bytes(&bytes[1..]), Err(DecodeError::Length { expected: 64, got: 63 }));
+
+        let z = albert::Octonion::new(std::array::from_fn(|i| Zq(i as u64 * 4000)));
+        assert_eq!(z.to_bytes().len(), 16);
+        assert_eq!(albert::Octonion::from_bytes(&z.to_bytes()), Ok(z));
+        assert_eq!(albert::Octonion::from_bytes(&[0xFF; 16]), Err(DecodeError::NonCanonical { index: 0 }));
+
+        let s = Sedenion::new(crate::sedenion::Octonion::from_seed(1), crate::sedenion::Octonion::from_seed(2));
+        assert_eq!(s.to_bytes().len(), Sedenion::BYTES);
+        assert_eq!(Sedenion::from_bytes(&s.to_bytes()), Ok(s));
+        let w = Octonion::<u16>::from_bytes(&unhex("0100020003000400050006000700ffff").unwrap()).unwrap();
+        assert_eq!(w.coeffs, [1, 2, 3, 4, 5, 6, 7, 0xFFFF]);
+    }
+
+    #[test]
+    fn hex_text_round_trips() {
+        let x = crate::vdf::Octonion::from_seed(5);
+        let text = x.to_string();
+        assert_eq!(text, hex(&x.to_bytes()));
+        assert_eq!(format!("{:#x}", x), format!("0x{}", text));
+        assert_eq!(text.parse(), Ok(x));
+        assert_eq!(format!("{:#x}", x).to_uppercase().replace("0X", "0x").parse(), Ok(x));
+
+        let s = Sedenion::new(crate::sedenion::Octonion::from_seed(1), crate::sedenion::Octonion::from_seed(2));
+        assert_eq!(s.to_string().parse(), Ok(s));
+        assert_eq!(s.to_string()[2..].parse::<Sedenion>(), Err(DecodeError::Length { expected: 128, got: 127 }));
+        assert_eq!(s.to_string()[1..].parse::<Sedenion>(), Err(DecodeError::InvalidHex));
+        assert_eq!("+1".repeat(16).parse::<Octonion<u16>>(), Err(DecodeError::InvalidHex));
+        assert_eq!("ff".repeat(64).parse::<crate::vdf::Octonion>(), Err(DecodeError::NonCanonical { index: 0 }));
+    }
+
+    #[test]
+    fn bech32_matches_the_bip_vectors() {
+        // BIP-173 / BIP-350: an empty payload under "a"
+        assert_eq!(bech32_encode("a", &[], Bech32Variant::Bech32), "a12uel5l");
+        assert_eq!(bech32_encode("a", &[], Bech32Variant::Bech32m), "a1lqfn3a");
+        assert_eq!(bech32_decode("a", "A12UEL5L", Bech32Variant::Bech32), Ok(vec![]));
+        assert_eq!(bech32_decode("a", "a12uel5l", Bech32Variant::Bech32m), Err(DecodeError::InvalidBech32));
+
+        let text = bech32_encode("jdk", &[0xde, 0xad, 0xbe, 0xef, 0x01], Bech32Variant::Bech32);
+        assert_eq!(bech32_decode("jdk", &text, Bech32Variant::Bech32), Ok(vec![0xde, 0xad, 0xbe, 0xef, 0x01]));
+        assert_eq!(bech32_decode("jdx", &text, Bech32Variant::Bech32), Err(DecodeError::InvalidBech32));
+        let mut typo = text.into_bytes();
+        typo[6] = if typo[6] == b'q' { b'p' } else { b'q' };
+        assert_eq!(bech32_decode("jdk", std::str::from_utf8(&typo).unwrap(), Bech32Variant::Bech32), Err(DecodeError::InvalidBech32));
+    }
+
+    #[test]
+    fn albert_elements_pack_tightly() {
+        let mut rng = <rand::rngs::StdRng as rand::SeedableRng>::seed_from_u64(4);
+        let x: AlbertElement = AlbertElement::random(&mut rng);
+        let bytes = x.to_bytes();
+        assert_eq!((AlbertElement::<{ albert::Q }>::COEFF_BITS, bytes.len()), (15, 51));
+        assert_eq!(AlbertElement::from_bytes(&bytes), Ok(x));
+
+        // 12289 needs 14 bits, and 2^14 - 1 is out of range
+        let y = AlbertElement::<12289>::random(&mut rng);
+        assert_eq!(AlbertElement::<12289>::from_bytes(&y.to_bytes()), Ok(y));
+        let mut bad = y.to_bytes();
+        bad[0] = 0xFF;
+        bad[1] |= 0x3F;
+        assert_eq!(AlbertElement::<12289>::from_bytes(&bad), Err(DecodeError::NonCanonical { index: 0 }));
+
+        let mut padded = bytes.clone();
+        padded[50] |= 0x80; // 27 * 15 = 405 bits: the top 3 of the last byte are padding
+        assert_eq!(AlbertElement::<{ albert::Q }>::from_bytes(&padded), Err(DecodeError::Padding));
+        assert_eq!(AlbertElement::<{ albert::Q }>::from_bytes(&bytes[1..]), Err(DecodeError::Length { expected: 51, got: 50 }));
+        let big = AlbertElement::<{ albert::Q_DILITHIUM }>::random(&mut rng);
+        assert_eq!(AlbertElement::<{ albert::Q_DILITHIUM }>::from_bytes(&big.to_bytes()), Ok(big));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_goes_through_the_canonical_bytes() {
+        let x = crate::vdf::Octonion::from_seed(3);
+        let json = serde_json::to_string(&x).unwrap();
+        assert_eq!(json, format!("\"{}\"", hex(&x.to_bytes())));
+        assert_eq!(serde_json::from_str::<crate::vdf::Octonion>(&json).unwrap(), x);
+
+        let unreduced = format!("\"{}\"", "ff".repeat(64));
+        assert!(serde_json::from_str::<crate::vdf::Octonion>(&unreduced).is_err());
+    }
+}
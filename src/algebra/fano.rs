@@ -0,0 +1,233 @@
+// src/algebra/fano.rs
+// FANO TABLES: Octonion structure constants as data.
+//
+// An octonion multiplication is fixed by orienting the seven lines of the
+// Fano plane: a line (i, j, k) means e_i e_j = e_k, hence also
+// e_j e_k = e_i and e_k e_i = e_j, and swapping the factors flips the sign.
+// Relabelling the seven imaginary units and flipping their signs moves
+// between the 480 distinct (and mutually isomorphic) tables, so two modules
+// that both say "octonion" can still disagree. A protocol pins its choice by
+// naming a FanoTable; its Display form (the seven lines) is what to write in
+// a spec.
+//
+// CANONICAL is the orientation of the Cayley-Dickson tower, i.e. of every
+// Octonion<S> in the crate and of the hand-written STARK table.
+
+use super::cayley_dickson::Scalar;
+use super::octonion::Octonion;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FanoTable {
+    lines: [[u8; 3]; 7],
+    // e_i e_j = sign[i][j] * e_index[i][j]
+    index: [[u8; 8]; 8],
+    sign: [[i8; 8]; 8],
+}
+
+pub const CANONICAL_LINES: [[u8; 3]; 7] = [[1, 2, 3], [1, 4, 5], [1, 7, 6], [2, 4, 6], [2, 5, 7], [3, 4, 7], [3, 6, 5]];
+
+pub const CANONICAL: FanoTable = match FanoTable::from_lines(CANONICAL_LINES) {
+    Some(t) => t,
+    None => panic!("canonical Fano lines are not a Fano plane"),
+};
+
+impl FanoTable {
+    // None unless every pair of distinct imaginary units lies on exactly one
+    // line (units are 1..=7; 0 is the real unit) and the orientations make
+    // an octonion algebra: of the 128 ways to orient a Fano plane's lines
+    // only 16 do, the rest are not alternative. The test is the one that
+    // decides it, (e_i e_j) e_k = -e_i (e_j e_k) for every triple of units
+    // not on a common line.
+    pub const fn from_lines(lines: [[u8; 3]; 7]) -> Option<Self> {
+        let mut index = [[0u8; 8]; 8];
+        let mut sign = [[0i8; 8]; 8];
+        let mut i = 0;
+        while i < 8 {
+            // Real unit: e_0 e_i = e_i e_0 = e_i; e_i e_i = -1
+            index[0][i] = i as u8;
+            sign[0][i] = 1;
+            index[i][0] = i as u8;
+            sign[i][0] = 1;
+            sign[i][i] = if i == 0 { 1 } else { -1 };
+            i += 1;
+        }
+        let mut l = 0;
+        while l < 7 {
+            let [a, b, c] = lines[l];
+            if a == 0 || b == 0 || c == 0 || a > 7 || b > 7 || c > 7 || a == b || b == c || a == c {
+                return None;
+            }
+            let cyclic = [[a, b, c], [b, c, a], [c, a, b]];
+            let mut r = 0;
+            while r < 3 {
+                let [x, y, z] = cyclic[r];
+                let (x, y) = (x as usize, y as usize);
+                if sign[x][y] != 0 {
+                    return None; // Pair already on another line
+                }
+                index[x][y] = z;
+                sign[x][y] = 1;
+                index[y][x] = z;
+                sign[y][x] = -1;
+                r += 1;
+            }
+            l += 1;
+        }
+        let mut i = 1;
+        while i < 8 {
+            let mut j = 1;
+            while j < 8 {
+                let mut k = 1;
+                while k < 8 {
+                    if i != j && j != k && i != k && index[i][j] as usize != k {
+                        let ij = index[i][j] as usize;
+                        let jk = index[j][k] as usize;
+                        let left = sign[i][j] * sign[ij][k];
+                        let right = sign[j][k] * sign[i][jk];
+                        if index[ij][k] != index[i][jk] || left != -right {
+                            return None;
+                        }
+                    }
+                    k += 1;
+                }
+                j += 1;
+            }
+            i += 1;
+        }
+        Some(FanoTable { lines, index, sign })
+    }
+
+    // The table of the relabelled basis f_a = signs[a-1] * e_perm[a-1] for
+    // a = 1..=7 (perm a permutation of 1..=7, signs +-1). None on bad input.
+    pub fn relabel(&self, perm: [u8; 7], signs: [i8; 7]) -> Option<Self> {
+        let mut inverse = [0u8; 8];
+        for (a, &p) in perm.iter().enumerate() {
+            if p == 0 || p > 7 || inverse[p as usize] != 0 || signs[a].abs() != 1 {
+                return None;
+            }
+            inverse[p as usize] = a as u8 + 1;
+        }
+        let s = |f: u8| signs[f as usize - 1];
+        let lines = self.lines.map(|[i, j, _]| {
+            // f_a f_b = s_a s_b e_pa e_pb = s_a s_b sigma e_m = (s_a s_b sigma s_c) f_c
+            let (a, b) = (inverse[i as usize], inverse[j as usize]);
+            let (pa, pb) = (perm[a as usize - 1] as usize, perm[b as usize - 1] as usize);
+            let c = inverse[self.index[pa][pb] as usize];
+            if s(a) * s(b) * self.sign[pa][pb] * s(c) > 0 { [a, b, c] } else { [b, a, c] }
+        });
+        FanoTable::from_lines(lines)
+    }
+
+    // Convenience: relabel the canonical orientation
+    pub fn from_permutation(perm: [u8; 7], signs: [i8; 7]) -> Option<Self> {
+        CANONICAL.relabel(perm, signs)
+    }
+
+    // e_i e_j = sign * e_index
+    pub fn product(&self, i: usize, j: usize) -> (usize, i8) {
+        (self.index[i][j] as usize, self.sign[i][j])
+    }
+
+    // Lines rotated to start at their smallest unit, then sorted: equal
+    // tables have equal normal forms.
+    pub fn lines(&self) -> [[u8; 3]; 7] {
+        let mut lines = self.lines.map(|[a, b, c]| {
+            if a < b && a < c { [a, b, c] } else if b < c { [b, c, a] } else { [c, a, b] }
+        });
+        lines.sort_unstable();
+        lines
+    }
+
+    pub fn mul<S: Scalar>(&self, a: &Octonion<S>, b: &Octonion<S>) -> Octonion<S> {
+        let mut out = [S::zero(); 8];
+        for i in 0..8 {
+            for j in 0..8 {
+                let (k, sign) = self.product(i, j);
+                let term = a.coeffs[i].mul(b.coeffs[j]);
+                out[k] = if sign > 0 { out[k].add(term) } else { out[k].sub(term) };
+            }
+        }
+        Octonion::new(out)
+    }
+}
+
+impl Default for FanoTable {
+    fn default() -> Self {
+        CANONICAL
+    }
+}
+
+impl fmt::Display for FanoTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines: Vec<String> = self.lines().iter().map(|[a, b, c]| format!("{}{}{}", a, b, c)).collect();
+        write!(f, "{}", lines.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn seeded(seed: u64) -> Octonion<u64> {
+        Octonion::new(std::array::from_fn(|i| seed.wrapping_mul(0x9E37_79B9_7F4A_7C15).rotate_left(8 * i as u32)))
+    }
+
+    #[test]
+    fn canonical_table_is_the_tower() {
+        assert_eq!(CANONICAL.to_string(), "123 145 176 246 257 347 365");
+        for seed in 1..16 {
+            let (x, y) = (seeded(seed), seeded(seed + 100));
+            assert_eq!(CANONICAL.mul(&x, &y), x * y);
+        }
+        // Relabelling is an isomorphism: phi(x) phi(y) = phi(xy) in the new table
+        let (perm, signs) = ([3, 1, 7, 2, 6, 4, 5], [1, -1, 1, 1, -1, -1, 1]);
+        let table = FanoTable::from_permutation(perm, signs).unwrap();
+        let phi = |x: &Octonion<u64>| {
+            // Coordinates of x in the basis f_a = s_a e_perm(a)
+            let mut c = [x.coeffs[0]; 8];
+            for a in 1..8 {
+                let v = x.coeffs[perm[a - 1] as usize];
+                c[a] = if signs[a - 1] > 0 { v } else { v.wrapping_neg() };
+            }
+            Octonion::new(c)
+        };
+        let (x, y) = (seeded(7), seeded(8));
+        assert_eq!(table.mul(&phi(&x), &phi(&y)), phi(&(x * y)));
+        assert_eq!(CANONICAL.relabel([1, 2, 3, 4, 5, 6, 7], [1; 7]), Some(CANONICAL));
+    }
+
+    #[test]
+    fn there_are_480_orientations() {
+        assert_eq!(FanoTable::from_lines([[1, 2, 3], [1, 4, 5], [1, 6, 7], [2, 4, 6], [2, 5, 7], [3, 4, 7], [3, 4, 6]]), None);
+        assert_eq!(FanoTable::from_permutation([1, 1, 3, 4, 5, 6, 7], [1; 7]), None);
+
+        let mut seen = BTreeSet::new();
+        let mut perm = [1u8, 2, 3, 4, 5, 6, 7];
+        loop {
+            for flips in 0..128u8 {
+                let signs = std::array::from_fn(|a| if flips >> a & 1 == 1 { -1 } else { 1 });
+                seen.insert(FanoTable::from_permutation(perm, signs).unwrap().lines());
+            }
+            // Next permutation (lexicographic)
+            let Some(i) = (0..6).rev().find(|&i| perm[i] < perm[i + 1]) else { break };
+            let j = (i + 1..7).rev().find(|&j| perm[j] > perm[i]).unwrap();
+            perm.swap(i, j);
+            perm[i + 1..].reverse();
+        }
+        assert_eq!(seen.len(), 480);
+
+        // 16 of the 128 orientations of one plane's lines are octonions
+        let valid = (0..128u8)
+            .filter(|flips| {
+                let lines = std::array::from_fn(|l| {
+                    let [a, b, c] = CANONICAL_LINES[l];
+                    if flips >> l & 1 == 1 { [b, a, c] } else { [a, b, c] }
+                });
+                FanoTable::from_lines(lines).is_some()
+            })
+            .count();
+        assert_eq!(valid, 16);
+    }
+}
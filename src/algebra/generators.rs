@@ -0,0 +1,109 @@
+// src/algebra/generators.rs
+// GENERATORS: arbitrary::Arbitrary for the algebra types.
+//
+// With `--features arbitrary`, Octonion<S> (for every scalar backend),
+// Sedenion and AlbertElement can be drawn from raw bytes, so downstream
+// fuzz targets (cargo-fuzz hands out an Unstructured) and property tests
+// need no hand-written generators. Scalars are always produced reduced:
+// Fp in [0, p), Zq in [0, Q).
+//
+// The crate's own tests use the same impls (they are compiled for
+// `cfg(test)` too) through `sample(seed)`, which feeds seeded StdRng bytes
+// into an Unstructured: reproducible, and any failure names its seed.
+
+use super::octonion::Octonion;
+use crate::albert::{AlbertElement, Zq, Q};
+use crate::sedenion::Sedenion;
+use crate::vdf::Fp;
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+impl<'a> Arbitrary<'a> for Fp {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Fp::new(u64::arbitrary(u)?))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        u64::size_hint(depth)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Zq {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Zq(u.int_in_range(0..=Q - 1)?))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        u16::size_hint(depth)
+    }
+}
+
+impl<'a, S: Arbitrary<'a>> Arbitrary<'a> for Octonion<S> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Octonion { coeffs: <[S; 8]>::arbitrary(u)? })
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <[S; 8]>::size_hint(depth)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Sedenion {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Sedenion::new(u.arbitrary()?, u.arbitrary()?))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <[u64; 16]>::size_hint(depth)
+    }
+}
+
+// Hermitian 3x3: three Z_Q diagonal entries, three octonions off it
+impl<'a> Arbitrary<'a> for AlbertElement {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(AlbertElement { coeffs: <[Zq; 27]>::arbitrary(u)?.map(|z| z.0) })
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <[u16; 27]>::size_hint(depth)
+    }
+}
+
+// A value drawn from `seed`'s byte stream (enough bytes for any type above)
+pub fn sample<T: for<'a> Arbitrary<'a>>(seed: u64) -> T {
+    use rand::{RngCore, SeedableRng};
+    let mut bytes = [0u8; 512];
+    rand::rngs::StdRng::seed_from_u64(seed).fill_bytes(&mut bytes);
+    T::arbitrary(&mut Unstructured::new(&bytes)).expect("512 bytes cover every algebra type")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vdf;
+
+    #[test]
+    fn composition_algebra_identities() {
+        for seed in 0..64 {
+            let (x, y, z): (vdf::Octonion, vdf::Octonion, vdf::Octonion) = (sample(3 * seed), sample(3 * seed + 1), sample(3 * seed + 2));
+            assert_eq!(x * (y + z), x * y + x * z, "left distributivity, seed {}", seed);
+            assert_eq!((x + y) * z, x * z + y * z, "right distributivity, seed {}", seed);
+            assert_eq!((x * y).conj(), y.conj() * x.conj(), "conjugation, seed {}", seed);
+            assert_eq!((x * y).norm(), x.norm() * y.norm(), "norm multiplicativity, seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn sedenion_and_albert_identities() {
+        for seed in 0..32 {
+            let (x, y, z): (Sedenion, Sedenion, Sedenion) = (sample(seed), sample(seed + 100), sample(seed + 200));
+            assert_eq!(x * (y + z), x * y + x * z, "sedenion distributivity, seed {}", seed);
+            assert_eq!((x * y).conjugate(), y.conjugate() * x.conjugate(), "sedenion conjugation, seed {}", seed);
+
+            let (a, b, c): (AlbertElement, AlbertElement, AlbertElement) = (sample(seed), sample(seed + 1), sample(seed + 2));
+            assert_eq!(a.jordan_product(&b), b.jordan_product(&a), "Jordan commutativity, seed {}", seed);
+            assert_eq!(a.jordan_product(&(b + c)), a.jordan_product(&b) + a.jordan_product(&c), "Jordan bilinearity, seed {}", seed);
+        }
+        let e: AlbertElement = sample(9);
+        assert!(e.coeffs.iter().all(|&d| d < Q));
+    }
+}
@@ -0,0 +1,134 @@
+// src/algebra/laws.rs
+// LAWS: Which weakenings of associativity an algebra still satisfies.
+//
+// Octonions are alternative (hence flexible and Moufang) but not
+// associative; sedenions keep only flexibility. A new multiplication table
+// or step function should land where it claims to, and this measures it:
+//   check(x, y, z)          - the laws one triple satisfies
+//   survey(trials, draw)    - how often each law fails over seeded triples
+// `draw(seed)` builds an element from a seed (e.g. Octonion::from_seed), so
+// a failing triple can be rebuilt from the seeds in the report.
+
+use super::nonassociative::NonAssociativeAlgebra;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Law {
+    Associative,      // (xy)z = x(yz)
+    LeftAlternative,  // (xx)y = x(xy)
+    RightAlternative, // (yx)x = y(xx)
+    Flexible,         // (xy)x = x(yx)
+    MoufangLeft,      // z(x(zy)) = ((zx)z)y
+    MoufangRight,     // x(z(yz)) = ((xz)y)z
+    MoufangMiddle,    // (zx)(yz) = (z(xy))z
+}
+
+impl Law {
+    pub const ALL: [Law; 7] = [
+        Law::Associative,
+        Law::LeftAlternative,
+        Law::RightAlternative,
+        Law::Flexible,
+        Law::MoufangLeft,
+        Law::MoufangRight,
+        Law::MoufangMiddle,
+    ];
+
+    pub fn holds<A: NonAssociativeAlgebra>(self, x: A, y: A, z: A) -> bool {
+        match self {
+            Law::Associative => A::associator(x, y, z) == A::zero(),
+            Law::LeftAlternative => (x * x) * y == x * (x * y),
+            Law::RightAlternative => (y * x) * x == y * (x * x),
+            Law::Flexible => (x * y) * x == x * (y * x),
+            Law::MoufangLeft => z * (x * (z * y)) == ((z * x) * z) * y,
+            Law::MoufangRight => x * (z * (y * z)) == ((x * z) * y) * z,
+            Law::MoufangMiddle => (z * x) * (y * z) == (z * (x * y)) * z,
+        }
+    }
+}
+
+// --- ONE TRIPLE ---
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TripleReport {
+    pub violated: Vec<Law>,
+}
+
+impl TripleReport {
+    pub fn holds(&self, law: Law) -> bool {
+        !self.violated.contains(&law)
+    }
+}
+
+pub fn check<A: NonAssociativeAlgebra>(x: A, y: A, z: A) -> TripleReport {
+    TripleReport { violated: Law::ALL.into_iter().filter(|law| !law.holds(x, y, z)).collect() }
+}
+
+// --- RANDOMIZED SAMPLE ---
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LawFailures {
+    pub law: Law,
+    pub count: usize,
+    pub first_seed: Option<u64>, // Trial i uses seeds 3i, 3i+1, 3i+2
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SurveyReport {
+    pub trials: usize,
+    pub laws: Vec<LawFailures>,
+}
+
+impl SurveyReport {
+    pub fn failures(&self, law: Law) -> usize {
+        self.laws.iter().find(|f| f.law == law).map_or(0, |f| f.count)
+    }
+
+    // Fraction of triples whose associator is non-zero
+    pub fn non_associativity(&self) -> f64 {
+        if self.trials == 0 {
+            return 0.0;
+        }
+        self.failures(Law::Associative) as f64 / self.trials as f64
+    }
+
+    pub fn satisfied(&self) -> Vec<Law> {
+        self.laws.iter().filter(|f| f.count == 0).map(|f| f.law).collect()
+    }
+}
+
+pub fn survey<A: NonAssociativeAlgebra>(trials: usize, mut draw: impl FnMut(u64) -> A) -> SurveyReport {
+    let mut laws: Vec<LawFailures> = Law::ALL.into_iter().map(|law| LawFailures { law, count: 0, first_seed: None }).collect();
+    for trial in 0..trials as u64 {
+        let seed = 3 * trial;
+        let report = check(draw(seed), draw(seed + 1), draw(seed + 2));
+        for failure in laws.iter_mut().filter(|f| !report.holds(f.law)) {
+            failure.count += 1;
+            failure.first_seed.get_or_insert(seed);
+        }
+    }
+    SurveyReport { trials, laws }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra::cayley_dickson as cd;
+    use crate::sedenion::{self, Sedenion};
+    use crate::vdf;
+
+    #[test]
+    fn octonions_are_alternative_sedenions_only_flexible() {
+        let octonions = survey(32, vdf::Octonion::from_seed);
+        assert_eq!(octonions.satisfied(), &Law::ALL[1..]);
+        assert_eq!(octonions.non_associativity(), 1.0);
+
+        let draw = |s: u64| Sedenion::new(sedenion::Octonion::from_seed(s), sedenion::Octonion::from_seed(s ^ 1 << 63));
+        let sedenions = survey(32, draw);
+        assert_eq!(sedenions.satisfied(), vec![Law::Flexible]);
+        // The reported seed rebuilds a failing triple
+        let seed = sedenions.laws[1].first_seed.unwrap();
+        assert!(!check(draw(seed), draw(seed + 1), draw(seed + 2)).holds(Law::LeftAlternative));
+
+        // Quaternions satisfy everything
+        let q = |s: u64| cd::Quaternion::<u64>::from_array([s, s * s + 1, 3 * s, s ^ 5]);
+        assert_eq!(survey(16, q).satisfied(), Law::ALL);
+    }
+}
@@ -0,0 +1,118 @@
+// src/algebra/nonassociative.rs
+// NON-ASSOCIATIVE ALGEBRAS: What the protocol code actually relies on.
+//
+// The VDF, GSH and Flutter only ever add, multiply and take associators;
+// they never care which table or tower sits underneath. Writing them
+// against this trait lets one iteration serve every backend:
+//   Octonion<S>         - any scalar (Fp, u64, u32, u16, Zq)
+//   Sedenion            - the GSH-256 ring (Z_2^64)
+//   Trigintaduonion     - its 32-dim doubling
+//   CayleyDickson<A>    - any level of the tower
+// `+` and `*` come from the operator traits; the associator defaults to
+// (XY)Z - X(YZ) and vanishes for associative instances (e.g. quaternions).
+
+use super::cayley_dickson::{Algebra, CayleyDickson, Scalar};
+use super::octonion::Octonion;
+use crate::sedenion::Sedenion;
+use crate::trigintaduonion::Trigintaduonion;
+use std::ops::{Add, Mul, Sub};
+
+pub trait NonAssociativeAlgebra: Copy + PartialEq + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> {
+    fn zero() -> Self;
+    fn one() -> Self;
+
+    // [X, Y, Z] = (XY)Z - X(YZ)
+    fn associator(x: Self, y: Self, z: Self) -> Self {
+        (x * y) * z - x * (y * z)
+    }
+}
+
+// One step of the quadratic map shared by the VDF and Flutter:
+// returns (Z^2 + C, [Z, C, H]); callers weight and add the second term.
+pub fn quadratic_map<A: NonAssociativeAlgebra>(z: A, c: A, h: A) -> (A, A) {
+    (z * z + c, A::associator(z, c, h))
+}
+
+impl<S: Scalar> NonAssociativeAlgebra for Octonion<S> {
+    fn zero() -> Self {
+        Octonion::zero()
+    }
+
+    fn one() -> Self {
+        Octonion::one()
+    }
+}
+
+impl<A: Algebra> NonAssociativeAlgebra for CayleyDickson<A> {
+    fn zero() -> Self {
+        Algebra::zero()
+    }
+
+    fn one() -> Self {
+        Self::from_real(<A::Scalar as Scalar>::one())
+    }
+}
+
+impl NonAssociativeAlgebra for Sedenion {
+    fn zero() -> Self {
+        Sedenion::zero()
+    }
+
+    fn one() -> Self {
+        Sedenion::one()
+    }
+}
+
+impl NonAssociativeAlgebra for Trigintaduonion {
+    fn zero() -> Self {
+        Trigintaduonion::zero()
+    }
+
+    fn one() -> Self {
+        Trigintaduonion::one()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra::cayley_dickson as cd;
+    use rand::prelude::*;
+
+    // Generic over the trait, exercised on three unrelated backends
+    fn unit_laws<A: NonAssociativeAlgebra + std::fmt::Debug>(x: A, y: A, h: A) {
+        assert!(x != A::zero() && y != A::zero() && h != A::zero());
+        assert_eq!(x * A::one(), x);
+        assert_eq!(A::one() * x, x);
+        assert_eq!(x + A::zero(), x);
+        let (square, hazard) = quadratic_map(x, y, h);
+        assert_eq!(square, x * x + y);
+        assert_eq!(hazard, (x * y) * h - x * (y * h));
+    }
+
+    #[test]
+    fn one_iteration_serves_every_backend() {
+        let mut rng = StdRng::seed_from_u64(4281);
+        let mut fp = || crate::vdf::Octonion::from_seed(rng.gen());
+        let (a, b, c) = (fp(), fp(), fp());
+        unit_laws(a, b, c);
+        // Alternative law: [x, x, y] = 0 for octonions, but not a plain identity
+        assert_eq!(NonAssociativeAlgebra::associator(a, a, b), crate::vdf::Octonion::zero());
+        assert_ne!(NonAssociativeAlgebra::associator(a, b, c), crate::vdf::Octonion::zero());
+
+        let mut ring = || crate::sedenion::Octonion::from_seed(rng.gen());
+        let (x, y, z, w) = (ring(), ring(), ring(), ring());
+        unit_laws(x, y, z);
+        assert_eq!(NonAssociativeAlgebra::associator(x, x, y), crate::sedenion::Octonion::zero());
+        // Sedenions are not alternative
+        let (s, t) = (Sedenion::new(x, y), Sedenion::new(z, w));
+        unit_laws(s, t, Sedenion::new(w, x));
+        assert_ne!(NonAssociativeAlgebra::associator(s, s, t), Sedenion::zero());
+
+        let mut quat = || cd::Quaternion::<u64>::from_array([rng.gen(), rng.gen(), rng.gen(), rng.gen()]);
+        let (q, r, t) = (quat(), quat(), quat());
+        unit_laws(q, r, t);
+        // Quaternions associate; octonions do not
+        assert_eq!(NonAssociativeAlgebra::associator(q, r, t), NonAssociativeAlgebra::zero());
+    }
+}
@@ -0,0 +1,216 @@
+// src/algebra/octonion.rs
+// THE OCTONION: One flat 8-coefficient type for every module.
+//
+// The scalar backend picks the semantics:
+//   Octonion<Fp>  - VDF / STARK (Goldilocks field)
+//   Octonion<u64> - GSH-256 sedenion halves (Z_2^64)
+//   Octonion<u32> - GSH-128 (Z_2^32)
+//   Octonion<u16> - Flutter cipher, HD wallet (Z_2^16)
+//   Octonion<Zq>  - Albert / Jordan lattice (Z_2^15)
+// Multiplication is level 3 of the Cayley-Dickson tower, so a fix to the
+// table lands in all of them at once. Module-specific helpers (seeding,
+// rotations) live next to their users as inherent impls on the concrete type.
+//
+// mul_batch / mul_batch_in_place spread independent products (the STARK
+// prover's trace-constraint check) over the rayon pool. Cargo.toml turns on
+// p3-maybe-rayon's `parallel` unconditionally, so they are always parallel.
+
+use super::cayley_dickson::{self as cd, Field, Scalar};
+use p3_maybe_rayon::prelude::*;
+use std::ops::{Add, Mul, Neg, Sub};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct Octonion<S> {
+    pub coeffs: [S; 8],
+}
+
+impl<S: Scalar> Octonion<S> {
+    pub fn new(coeffs: [S; 8]) -> Self {
+        Octonion { coeffs }
+    }
+
+    pub fn zero() -> Self {
+        Octonion { coeffs: [S::zero(); 8] }
+    }
+
+    pub fn one() -> Self {
+        Self::from_real(S::one())
+    }
+
+    pub fn from_real(s: S) -> Self {
+        let mut coeffs = [S::zero(); 8];
+        coeffs[0] = s;
+        Octonion { coeffs }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.iter().all(|&x| x == S::zero())
+    }
+
+    // Real part kept, imaginary parts negated
+    pub fn conj(&self) -> Self {
+        let mut coeffs = self.coeffs.map(S::neg);
+        coeffs[0] = self.coeffs[0];
+        Octonion { coeffs }
+    }
+
+    // Sum of squared coefficients, x * conj(x) = norm_sq as a real
+    pub fn norm_sq(&self) -> S {
+        self.coeffs.iter().fold(S::zero(), |acc, &x| acc.add(x.mul(x)))
+    }
+
+    pub fn scale(&self, s: S) -> Self {
+        Octonion { coeffs: self.coeffs.map(|x| x.mul(s)) }
+    }
+
+    pub fn to_cd(self) -> cd::Octonion<S> {
+        cd::Octonion::from_array(self.coeffs)
+    }
+
+    pub fn from_cd(x: cd::Octonion<S>) -> Self {
+        Octonion { coeffs: x.to_array() }
+    }
+}
+
+// --- THE NORM FORM ---
+// Over a field N(x) = x * conj(x) is a nondegenerate quadratic form with
+// N(xy) = N(x) N(y), so protocol code may lean on it: N(x) = 0 exactly when
+// x has no inverse. Over F_p the form is isotropic (every finite-field
+// octonion algebra is split), so such x != 0 exist and must be screened out.
+impl<S: Field> Octonion<S> {
+    pub fn norm(&self) -> S {
+        self.norm_sq()
+    }
+
+    // The polar form: N(x + y) = N(x) + N(y) + 2 <x, y>
+    pub fn bilinear(&self, other: &Self) -> S {
+        self.coeffs.iter().zip(&other.coeffs).fold(S::zero(), |acc, (&x, &y)| acc.add(x.mul(y)))
+    }
+
+    // Zero, or a zero divisor: no inverse, and multiplying by it loses information
+    pub fn is_singular(&self) -> bool {
+        self.norm() == S::zero()
+    }
+
+    // conj(x) / N(x), None when singular
+    pub fn try_inverse(&self) -> Option<Self> {
+        self.norm().inverse().map(|n_inv| self.conj().scale(n_inv))
+    }
+}
+
+impl<S: Scalar> Add for Octonion<S> {
+    type Output = Self;
+    #[inline(always)]
+    fn add(self, other: Self) -> Self {
+        Octonion { coeffs: core::array::from_fn(|i| self.coeffs[i].add(other.coeffs[i])) }
+    }
+}
+
+impl<S: Scalar> Sub for Octonion<S> {
+    type Output = Self;
+    #[inline(always)]
+    fn sub(self, other: Self) -> Self {
+        Octonion { coeffs: core::array::from_fn(|i| self.coeffs[i].sub(other.coeffs[i])) }
+    }
+}
+
+impl<S: Scalar> Neg for Octonion<S> {
+    type Output = Self;
+    #[inline(always)]
+    fn neg(self) -> Self {
+        Octonion { coeffs: self.coeffs.map(S::neg) }
+    }
+}
+
+// Full non-associative Fano-plane product (via the Cayley-Dickson tower)
+impl<S: Scalar> Mul for Octonion<S> {
+    type Output = Self;
+    #[inline(always)]
+    fn mul(self, other: Self) -> Self {
+        Octonion::from_cd(self.to_cd() * other.to_cd())
+    }
+}
+
+// The Associator: [X, Y, Z] = (XY)Z - X(YZ)
+pub fn associator<S: Scalar>(x: Octonion<S>, y: Octonion<S>, z: Octonion<S>) -> Octonion<S> {
+    super::NonAssociativeAlgebra::associator(x, y, z)
+}
+
+// --- BATCHES ---
+// xs[i] * ys[i] for every i; the slices must be the same length
+pub fn mul_batch<S: Scalar + Send + Sync>(xs: &[Octonion<S>], ys: &[Octonion<S>]) -> Vec<Octonion<S>> {
+    assert_eq!(xs.len(), ys.len(), "mul_batch: operand lengths differ");
+    xs.par_iter().zip(ys.par_iter()).map(|(&x, &y)| x * y).collect()
+}
+
+// xs[i] = xs[i] * ys[i], reusing the left operands' buffer
+pub fn mul_batch_in_place<S: Scalar + Send + Sync>(xs: &mut [Octonion<S>], ys: &[Octonion<S>]) {
+    assert_eq!(xs.len(), ys.len(), "mul_batch_in_place: operand lengths differ");
+    xs.par_iter_mut().zip(ys.par_iter()).for_each(|(x, &y)| *x = *x * y);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backends_share_one_table() {
+        // e1 * e2 = e3 and e2 * e1 = -e3 on every backend
+        fn check<S: Scalar>() {
+            let e = |i: usize| {
+                let mut c = [S::zero(); 8];
+                c[i] = S::one();
+                Octonion::new(c)
+            };
+            assert_eq!(e(1) * e(2), e(3));
+            assert_eq!(e(2) * e(1), -e(3));
+            assert_eq!(e(4) * e(4), -Octonion::one());
+        }
+        check::<u16>();
+        check::<u64>();
+        check::<crate::vdf::Fp>();
+        check::<crate::albert::Zq>();
+
+        let x = Octonion::new([3u64, 1, 4, 1, 5, 9, 2, 6]);
+        assert_eq!((x * x.conj()).coeffs[0], x.norm_sq());
+        assert_eq!(associator(x, x, x.scale(7)), Octonion::zero());
+    }
+
+    #[test]
+    fn batches_match_one_at_a_time() {
+        let xs: Vec<crate::vdf::Octonion> = (0..1000).map(crate::vdf::Octonion::from_seed).collect();
+        let ys: Vec<crate::vdf::Octonion> = (1000..2000).map(crate::vdf::Octonion::from_seed).collect();
+        let expected: Vec<_> = xs.iter().zip(&ys).map(|(&x, &y)| x * y).collect();
+        assert_eq!(mul_batch(&xs, &ys), expected);
+
+        let mut in_place = xs.clone();
+        mul_batch_in_place(&mut in_place, &ys);
+        assert_eq!(in_place, expected);
+        assert!(mul_batch::<u16>(&[], &[]).is_empty());
+    }
+
+    #[test]
+    fn field_norm_composes() {
+        fn check<S: Field>(lift: impl Fn(u64) -> S) {
+            let o = |seed: u64| Octonion::new(std::array::from_fn(|i| lift(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) >> (4 * i))));
+            for seed in 0..16 {
+                let (x, y) = (o(2 * seed + 1), o(2 * seed + 2));
+                assert_eq!((x * y).norm(), x.norm().mul(y.norm()), "seed {}", seed);
+                let two_xy = x.bilinear(&y).add(x.bilinear(&y));
+                assert_eq!((x + y).norm(), x.norm().add(y.norm()).add(two_xy), "seed {}", seed);
+                assert_eq!(x * x.try_inverse().unwrap(), Octonion::one(), "seed {}", seed);
+            }
+            assert!(Octonion::<S>::zero().is_singular());
+        }
+        check(crate::vdf::Fp::new);
+        #[cfg(feature = "bigint")]
+        check(|v| super::super::backends::Fp25519::new(&crypto_bigint::U256::from(v)));
+
+        // Isotropic: e0 + i e1 with i^2 = -1 is nonzero but singular
+        let i = crate::vdf::Fp::new(1 << 48);
+        let mut iso = Octonion::<crate::vdf::Fp>::one();
+        iso.coeffs[1] = i;
+        assert!(iso.is_singular() && !iso.is_zero());
+        assert_eq!(iso.try_inverse(), None);
+    }
+}
@@ -0,0 +1,188 @@
+// src/algebra/poly.rs
+// OCTONION POLYNOMIALS: Oct[x] and its quotients by x^n - 1 and x^n + 1.
+//
+// The indeterminate is central and associative, so (a x^i)(b x^j) = (ab)
+// x^(i+j) and the product is the usual convolution with every coefficient
+// product kept in order (a_i * b_j, never b_j * a_i). Module-lattice style
+// constructions live in the quotients:
+//   Reduction::Cyclic      - x^n = 1
+//   Reduction::Negacyclic  - x^n = -1 (the Ring-LWE shape)
+// over any scalar backend, e.g. albert::Octonion (Z_2^15) for the Jordan
+// signature work. Evaluation puts coefficients on the left, sum a_i t^i;
+// octonions are power-associative, so t^i is well defined.
+
+use super::cayley_dickson::Scalar;
+use super::octonion::Octonion;
+use std::ops::{Add, Mul, Neg, Sub};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Reduction {
+    Cyclic,
+    Negacyclic,
+}
+
+// coeffs[i] multiplies x^i; never ends in a zero coefficient, so the zero
+// polynomial is empty and equal polynomials compare equal
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Poly<S> {
+    pub coeffs: Vec<Octonion<S>>,
+}
+
+impl<S: Scalar> Poly<S> {
+    pub fn new(mut coeffs: Vec<Octonion<S>>) -> Self {
+        while coeffs.last().is_some_and(Octonion::is_zero) {
+            coeffs.pop();
+        }
+        Poly { coeffs }
+    }
+
+    pub fn zero() -> Self {
+        Poly { coeffs: Vec::new() }
+    }
+
+    pub fn constant(c: Octonion<S>) -> Self {
+        Poly::new(vec![c])
+    }
+
+    // c x^k
+    pub fn monomial(c: Octonion<S>, k: usize) -> Self {
+        let mut coeffs = vec![Octonion::zero(); k + 1];
+        coeffs[k] = c;
+        Poly::new(coeffs)
+    }
+
+    // None for the zero polynomial
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs.len().checked_sub(1)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    pub fn coeff(&self, i: usize) -> Octonion<S> {
+        self.coeffs.get(i).copied().unwrap_or_else(Octonion::zero)
+    }
+
+    pub fn scale(&self, s: S) -> Self {
+        Poly::new(self.coeffs.iter().map(|c| c.scale(s)).collect())
+    }
+
+    // Fold x^(n + k) onto x^k, negated for Negacyclic; degree < n after
+    pub fn reduce(&self, n: usize, reduction: Reduction) -> Self {
+        assert!(n > 0, "reduce: x^0 +- 1 is not a modulus");
+        let mut out = vec![Octonion::zero(); n];
+        for (i, &c) in self.coeffs.iter().enumerate() {
+            let wraps = i / n;
+            let c = if reduction == Reduction::Negacyclic && wraps % 2 == 1 { -c } else { c };
+            out[i % n] = out[i % n] + c;
+        }
+        Poly::new(out)
+    }
+
+    // self * other in Oct[x] / (x^n -+ 1), without the full-length product
+    pub fn mul_mod(&self, other: &Self, n: usize, reduction: Reduction) -> Self {
+        assert!(n > 0, "mul_mod: x^0 +- 1 is not a modulus");
+        let mut out = vec![Octonion::zero(); n];
+        for (i, &a) in self.coeffs.iter().enumerate() {
+            for (j, &b) in other.coeffs.iter().enumerate() {
+                let k = i + j;
+                let p = a * b;
+                let p = if reduction == Reduction::Negacyclic && (k / n) % 2 == 1 { -p } else { p };
+                out[k % n] = out[k % n] + p;
+            }
+        }
+        Poly::new(out)
+    }
+
+    // sum a_i t^i, by Horner: (..(a_d t + a_(d-1)) t + ..) t + a_0. Right
+    // multiplication by t keeps every a_i to the left of its power, and
+    // (a t^k) t = a t^(k+1) because a and t generate an associative
+    // subalgebra (Artin's theorem).
+    pub fn eval(&self, t: Octonion<S>) -> Octonion<S> {
+        self.coeffs.iter().rev().fold(Octonion::zero(), |acc, &a| acc * t + a)
+    }
+}
+
+impl<S: Scalar> Add for &Poly<S> {
+    type Output = Poly<S>;
+    fn add(self, other: Self) -> Poly<S> {
+        let len = self.coeffs.len().max(other.coeffs.len());
+        Poly::new((0..len).map(|i| self.coeff(i) + other.coeff(i)).collect())
+    }
+}
+
+impl<S: Scalar> Sub for &Poly<S> {
+    type Output = Poly<S>;
+    fn sub(self, other: Self) -> Poly<S> {
+        let len = self.coeffs.len().max(other.coeffs.len());
+        Poly::new((0..len).map(|i| self.coeff(i) - other.coeff(i)).collect())
+    }
+}
+
+impl<S: Scalar> Neg for &Poly<S> {
+    type Output = Poly<S>;
+    fn neg(self) -> Poly<S> {
+        Poly { coeffs: self.coeffs.iter().map(|&c| -c).collect() }
+    }
+}
+
+// Full product in Oct[x], degree deg(self) + deg(other)
+impl<S: Scalar> Mul for &Poly<S> {
+    type Output = Poly<S>;
+    fn mul(self, other: Self) -> Poly<S> {
+        if self.is_zero() || other.is_zero() {
+            return Poly::zero();
+        }
+        let mut out = vec![Octonion::zero(); self.coeffs.len() + other.coeffs.len() - 1];
+        for (i, &a) in self.coeffs.iter().enumerate() {
+            for (j, &b) in other.coeffs.iter().enumerate() {
+                out[i + j] = out[i + j] + a * b;
+            }
+        }
+        Poly::new(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::albert::{self, Zq};
+    use crate::vdf;
+
+    fn poly(seed: u64, len: usize) -> Poly<vdf::Fp> {
+        Poly::new((0..len as u64).map(|i| vdf::Octonion::from_seed(seed * 100 + i)).collect())
+    }
+
+    #[test]
+    fn quotient_rings_and_evaluation() {
+        let (p, q) = (poly(1, 5), poly(2, 4));
+        assert_eq!((&p * &q).degree(), Some(7));
+        // Coefficient order is kept: x and octonions commute, octonions do not
+        assert_ne!(&p * &q, &q * &p);
+        assert!((&(&p + &q) - &q) == p && (&p + &(-&p)).is_zero());
+
+        for reduction in [Reduction::Cyclic, Reduction::Negacyclic] {
+            assert_eq!(p.mul_mod(&q, 4, reduction), (&p * &q).reduce(4, reduction));
+        }
+        // x^(n-1) * x = -1 in the negacyclic ring, 1 in the cyclic one
+        let one = vdf::Octonion::one();
+        let (top, x) = (Poly::monomial(one, 3), Poly::monomial(one, 1));
+        assert_eq!(top.mul_mod(&x, 4, Reduction::Negacyclic), Poly::constant(-one));
+        assert_eq!(top.mul_mod(&x, 4, Reduction::Cyclic), Poly::constant(one));
+
+        // Evaluation at a real point is a ring homomorphism
+        let t = vdf::Octonion::from_real(vdf::Fp::new(7));
+        assert_eq!((&p * &q).eval(t), p.eval(t) * q.eval(t));
+        assert_eq!((&p + &q).eval(t), p.eval(t) + q.eval(t));
+        // ...and at any point it is sum a_i t^i
+        let t = vdf::Octonion::from_seed(9);
+        let powers = std::iter::successors(Some(one), |&tk| Some(tk * t));
+        assert_eq!(p.eval(t), p.coeffs.iter().zip(powers).fold(vdf::Octonion::zero(), |acc, (&a, tk)| acc + a * tk));
+        assert_eq!(Poly::monomial(one, 3).eval(t), t * t * t);
+
+        // Same ring over the Albert scalars
+        let a: Poly<Zq> = Poly::new(vec![albert::Octonion::new(std::array::from_fn(|i| Zq(i as u64 + 1))); 8]);
+        assert_eq!(a.mul_mod(&Poly::constant(albert::Octonion::one()), 8, Reduction::Negacyclic), a);
+    }
+}
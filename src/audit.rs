@@ -0,0 +1,244 @@
+// src/audit.rs
+// STORAGE AUDIT: Bridge nodes prove, over time, that they still hold the Bulk.
+//
+// Each round the auditor takes a fresh beacon (the VDF output of a recent
+// block), derives challenge indices from it and the current Horizon root, and
+// the bridge must answer every challenge with the leaf and its witness before
+// the deadline. Indices are unpredictable until the beacon exists, so they
+// cannot be precomputed, and the deadline is kept below the time needed to
+// re-derive the answers (re-fetch the Bulk and rebuild the tree): a bridge
+// that dropped the data cannot pass by recomputing on demand.
+//
+// Verdicts feed horizon_net::PeerScores. Time is passed in explicitly (ms),
+// so the protocol runs identically under the soak harness and replay. The
+// deadline is judged on the auditor's clock, at the moment the response
+// arrives: a response carries no timestamp, since the bridge could write
+// any time it liked there.
+
+use crate::gsh::GSH256;
+use crate::horizon::{HorizonAccumulator, Witness};
+use crate::horizon_net::PeerScores;
+use crate::merkle::{self, Gsh32};
+use crate::vdf::Octonion;
+
+const DOMAIN: &[u8] = b"OLC-STORAGE-AUDIT-v1";
+
+// --- CONFIGURATION ---
+#[derive(Clone, Debug)]
+pub struct AuditConfig {
+    pub challenges: usize, // Leaves opened per round
+    pub deadline_ms: u64,  // Time the bridge has to answer
+    pub rederive_ms: u64,  // Estimated time to rebuild the answers without the data
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        AuditConfig { challenges: 16, deadline_ms: 2_000, rederive_ms: 30_000 }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AuditError {
+    NoChallenges,
+    DeadlineTooLong { deadline_ms: u64, rederive_ms: u64 },
+    EmptyBulk,
+}
+
+impl AuditConfig {
+    pub fn validate(&self) -> Result<(), AuditError> {
+        if self.challenges == 0 {
+            return Err(AuditError::NoChallenges);
+        }
+        if self.deadline_ms >= self.rederive_ms {
+            return Err(AuditError::DeadlineTooLong { deadline_ms: self.deadline_ms, rederive_ms: self.rederive_ms });
+        }
+        Ok(())
+    }
+}
+
+// --- CHALLENGE / RESPONSE ---
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Challenge {
+    pub round: u64,
+    pub horizon_root: String,
+    pub indices: Vec<u64>,
+    pub issued_at_ms: u64,
+    pub deadline_ms: u64, // Absolute
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Opening {
+    pub leaf: [u8; 32],
+    pub witness: Witness,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuditResponse {
+    pub round: u64,
+    pub openings: Vec<Opening>, // Same order as Challenge::indices
+}
+
+// Challenge positions in 0..leaf_count, bound to the beacon and the root.
+pub fn challenge_indices(beacon: &Octonion, horizon_root: &str, leaf_count: u64, count: usize) -> Vec<u64> {
+    let beacon = beacon.to_bytes();
+    (0..count as u64)
+        .map(|i| {
+            let h = GSH256::hash_slices(&[DOMAIN, &beacon, horizon_root.as_bytes(), &i.to_le_bytes()]);
+            u64::from_le_bytes(h[..8].try_into().unwrap()) % leaf_count
+        })
+        .collect()
+}
+
+// Bridge side: open every challenged leaf from the locally held Bulk.
+pub fn respond(bulk: &HorizonAccumulator, challenge: &Challenge) -> AuditResponse {
+    let openings = challenge
+        .indices
+        .iter()
+        .map(|&index| Opening { leaf: bulk.leaf(index), witness: bulk.generate_witness(index) })
+        .collect();
+    AuditResponse { round: challenge.round, openings }
+}
+
+// --- VERDICTS ---
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AuditVerdict {
+    Passed,
+    Late { by_ms: u64 },
+    WrongRound { expected: u64, got: u64 },
+    WrongCount { expected: usize, got: usize },
+    BadOpening { index: u64 },
+}
+
+impl AuditVerdict {
+    // Score change fed to PeerScores. A bad opening is proof the data is
+    // gone (or being forged), so it costs far more than a late answer.
+    pub fn score_delta(&self) -> i64 {
+        match self {
+            AuditVerdict::Passed => 2,
+            AuditVerdict::Late { .. } => -20,
+            AuditVerdict::WrongRound { .. } | AuditVerdict::WrongCount { .. } => -30,
+            AuditVerdict::BadOpening { .. } => -60,
+        }
+    }
+}
+
+// --- THE AUDITOR ---
+pub struct Auditor {
+    pub config: AuditConfig,
+    round: u64,
+}
+
+impl Auditor {
+    pub fn new(config: AuditConfig) -> Result<Self, AuditError> {
+        config.validate()?;
+        Ok(Auditor { config, round: 0 })
+    }
+
+    // Start a round against the bridge's claimed Bulk of `leaf_count` leaves.
+    pub fn issue(&mut self, beacon: &Octonion, horizon_root: &str, leaf_count: u64, now_ms: u64) -> Result<Challenge, AuditError> {
+        if leaf_count == 0 {
+            return Err(AuditError::EmptyBulk);
+        }
+        self.round += 1;
+        Ok(Challenge {
+            round: self.round,
+            horizon_root: horizon_root.to_string(),
+            indices: challenge_indices(beacon, horizon_root, leaf_count, self.config.challenges),
+            issued_at_ms: now_ms,
+            deadline_ms: now_ms + self.config.deadline_ms,
+        })
+    }
+
+    // `received_at_ms` is the auditor's own clock when the response arrived
+    pub fn check(&self, challenge: &Challenge, response: &AuditResponse, received_at_ms: u64) -> AuditVerdict {
+        if response.round != challenge.round {
+            return AuditVerdict::WrongRound { expected: challenge.round, got: response.round };
+        }
+        if received_at_ms > challenge.deadline_ms {
+            return AuditVerdict::Late { by_ms: received_at_ms - challenge.deadline_ms };
+        }
+        if response.openings.len() != challenge.indices.len() {
+            return AuditVerdict::WrongCount { expected: challenge.indices.len(), got: response.openings.len() };
+        }
+        for (&index, opening) in challenge.indices.iter().zip(&response.openings) {
+            let root = merkle::root_from_path::<Gsh32>(&opening.leaf, index, &opening.witness.siblings);
+            if opening.witness.index != index || Gsh32::to_hex(&root) != challenge.horizon_root {
+                return AuditVerdict::BadOpening { index };
+            }
+        }
+        AuditVerdict::Passed
+    }
+
+    // Check and feed the result into the peer scores.
+    pub fn audit(&self, peer: &str, challenge: &Challenge, response: &AuditResponse, received_at_ms: u64, scores: &mut PeerScores) -> AuditVerdict {
+        let verdict = self.check(challenge, response, received_at_ms);
+        scores.record(peer, verdict.score_delta());
+        verdict
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::horizon::Utxo;
+    use crate::horizon_net::{MAX_SCORE, MIN_SCORE};
+    use crate::jordan_sig::JordanSchnorr;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn bulk(leaves: u64) -> HorizonAccumulator {
+        let mut rng = StdRng::seed_from_u64(9);
+        let owner = JordanSchnorr::keygen(&mut rng).pub_key;
+        let mut acc = HorizonAccumulator::new();
+        for i in 0..leaves {
+            acc.add_utxo(&Utxo::new([i as u8; 32], owner.address(), i), i);
+        }
+        acc
+    }
+
+    #[test]
+    fn honest_bridge_passes_and_late_answers_are_penalised() {
+        let acc = bulk(8);
+        let mut auditor = Auditor::new(AuditConfig { challenges: 4, ..AuditConfig::default() }).unwrap();
+        let mut scores = PeerScores::new();
+
+        let challenge = auditor.issue(&Octonion::from_seed(77), &acc.root, 8, 1_000).unwrap();
+        assert!(challenge.indices.iter().all(|&i| i < 8));
+        let response = respond(&acc, &challenge);
+        assert_eq!(auditor.audit("bridge", &challenge, &response, 1_500, &mut scores), AuditVerdict::Passed);
+        // The same answer arriving after the deadline is late, whatever the bridge says
+        assert_eq!(auditor.check(&challenge, &response, 4_000), AuditVerdict::Late { by_ms: 1_000 });
+
+        // A different beacon gives a fresh, unpredictable challenge set
+        let next = auditor.issue(&Octonion::from_seed(78), &acc.root, 8, 5_000).unwrap();
+        assert_eq!(next.round, 2);
+        assert_ne!(next.indices, challenge.indices);
+        assert_eq!(scores.score("bridge"), 2);
+
+        assert_eq!(
+            AuditConfig { deadline_ms: 5_000, rederive_ms: 5_000, ..AuditConfig::default() }.validate(),
+            Err(AuditError::DeadlineTooLong { deadline_ms: 5_000, rederive_ms: 5_000 })
+        );
+    }
+
+    #[test]
+    fn bridge_without_the_data_is_banned() {
+        let acc = bulk(8);
+        let mut auditor = Auditor::new(AuditConfig { challenges: 8, ..AuditConfig::default() }).unwrap();
+        let mut scores = PeerScores::new();
+
+        // The bridge kept the root but lost the leaves: its openings come
+        // from an empty tree and cannot reproduce the committed root.
+        let lost = HorizonAccumulator::new();
+        for round in 0..2 {
+            let challenge = auditor.issue(&Octonion::from_seed(round), &acc.root, 8, 0).unwrap();
+            let response = respond(&lost, &challenge);
+            let verdict = auditor.audit("lazy", &challenge, &response, 10, &mut scores);
+            assert!(matches!(verdict, AuditVerdict::BadOpening { .. }));
+        }
+        assert!(scores.is_banned("lazy"));
+        // The score bottoms out rather than running away
+        assert_eq!(scores.record("lazy", i64::MIN), MIN_SCORE);
+        assert_eq!(scores.record("lazy", i64::MAX), MAX_SCORE);
+    }
+}
@@ -0,0 +1,208 @@
+// src/compress.rs
+// COMPRESSION: Size-bounded framing for peer payloads.
+//
+// Header batches (hex hash strings, repeated roots) and packages shrink
+// well, so large payloads travel compressed. A frame is
+//   [codec u8][decoded length varint][body]
+// and is opened only after the declared length has been checked against the
+// caller's limit; the decoder then refuses to produce a byte past it. A
+// small frame can therefore never inflate into a large allocation (no
+// decompression bombs), whatever the body says.
+//
+// Codecs:
+//   0x00 RAW  - body is the payload
+//   0x01      - retired (an in-tree LZ77); refused like any unknown tag
+//   0x02 ZSTD - body is one zstd frame (the zstd crate, level ZSTD_LEVEL)
+// Peers exchange a Hello before anything else; a frame may only use a codec
+// both sides advertised, and payloads under the larger of the two
+// thresholds go RAW (compression overhead is not worth it on small
+// messages).
+
+use crate::wire::{write_varint, Message, Reader, Wire, WireError, MAX_MESSAGE_BYTES};
+use std::io::Read;
+
+pub const CODEC_RAW: u8 = 0x00;
+pub const CODEC_ZSTD: u8 = 0x02;
+
+pub const DEFAULT_THRESHOLD: usize = 1024;
+// zstd's own default: most of the ratio for a fraction of the time
+const ZSTD_LEVEL: i32 = 3;
+
+// --- HANDSHAKE ---
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Hello {
+    // Bit c set = codec c understood (RAW is always understood)
+    pub codecs: u8,
+    pub threshold: u32,
+}
+
+impl Default for Hello {
+    fn default() -> Self {
+        Hello { codecs: 1 << CODEC_RAW | 1 << CODEC_ZSTD, threshold: DEFAULT_THRESHOLD as u32 }
+    }
+}
+
+impl Wire for Hello {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.push(self.codecs);
+        write_varint(out, self.threshold as u64);
+    }
+
+    fn decode(r: &mut Reader) -> Result<Self, WireError> {
+        let codecs = r.read_u8()?;
+        let threshold = r.read_varint()?;
+        if threshold > MAX_MESSAGE_BYTES as u64 {
+            return Err(WireError::TooLarge { what: "threshold", len: threshold as usize, max: MAX_MESSAGE_BYTES });
+        }
+        Ok(Hello { codecs, threshold: threshold as u32 })
+    }
+}
+
+// The agreed settings for one peer connection
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Link {
+    pub codec: u8,
+    pub threshold: usize,
+    pub max_decoded: usize,
+}
+
+impl Link {
+    pub fn negotiate(ours: &Hello, theirs: &Hello) -> Self {
+        let common = ours.codecs & theirs.codecs;
+        let codec = if common & (1 << CODEC_ZSTD) != 0 { CODEC_ZSTD } else { CODEC_RAW };
+        Link { codec, threshold: ours.threshold.max(theirs.threshold) as usize, max_decoded: MAX_MESSAGE_BYTES }
+    }
+
+    pub fn seal(&self, payload: &[u8]) -> Vec<u8> {
+        let codec = if payload.len() < self.threshold { CODEC_RAW } else { self.codec };
+        encode_frame(payload, codec)
+    }
+
+    pub fn open(&self, frame: &[u8]) -> Result<Vec<u8>, WireError> {
+        if let Some(&tag) = frame.first() {
+            if tag != CODEC_RAW && tag != self.codec {
+                return Err(WireError::UnknownTag(tag));
+            }
+        }
+        decode_frame(frame, self.max_decoded)
+    }
+
+    pub fn seal_message(&self, msg: &Message) -> Vec<u8> {
+        self.seal(&msg.to_bytes())
+    }
+
+    pub fn open_message(&self, frame: &[u8]) -> Result<Message, WireError> {
+        Message::from_bytes(&self.open(frame)?)
+    }
+}
+
+// --- FRAMES ---
+// Falls back to RAW when compression does not help
+pub fn encode_frame(payload: &[u8], codec: u8) -> Vec<u8> {
+    let mut out = Vec::with_capacity(payload.len() + 6);
+    if codec == CODEC_ZSTD {
+        if let Ok(body) = zstd::bulk::compress(payload, ZSTD_LEVEL) {
+            if body.len() < payload.len() {
+                out.push(CODEC_ZSTD);
+                write_varint(&mut out, payload.len() as u64);
+                out.extend_from_slice(&body);
+                return out;
+            }
+        }
+    }
+    out.push(CODEC_RAW);
+    write_varint(&mut out, payload.len() as u64);
+    out.extend_from_slice(payload);
+    out
+}
+
+pub fn decode_frame(frame: &[u8], max_decoded: usize) -> Result<Vec<u8>, WireError> {
+    let mut r = Reader::new(frame);
+    let codec = r.read_u8()?;
+    let declared = r.read_varint()?;
+    if declared > max_decoded as u64 {
+        return Err(WireError::TooLarge { what: "decoded frame", len: declared as usize, max: max_decoded });
+    }
+    let declared = declared as usize;
+    match codec {
+        CODEC_RAW => {
+            let body = r.read_bytes(declared)?.to_vec();
+            r.finish()?;
+            Ok(body)
+        }
+        CODEC_ZSTD => zstd_decompress(r.read_bytes(r.remaining())?, declared),
+        other => Err(WireError::UnknownTag(other)),
+    }
+}
+
+// Streams at most declared + 1 bytes out of the decoder: the buffer grows
+// with what the body really produces, never with what it claims
+fn zstd_decompress(body: &[u8], declared: usize) -> Result<Vec<u8>, WireError> {
+    let bad = || WireError::NonCanonical("bad zstd body");
+    let decoder = zstd::stream::Decoder::with_buffer(body).map_err(|_| bad())?;
+    let mut out = Vec::new();
+    decoder.take(declared as u64 + 1).read_to_end(&mut out).map_err(|_| bad())?;
+    match out.len().cmp(&declared) {
+        std::cmp::Ordering::Equal => Ok(out),
+        std::cmp::Ordering::Greater => Err(WireError::NonCanonical("frame exceeds its declared length")),
+        std::cmp::Ordering::Less => Err(WireError::NonCanonical("frame falls short of its declared length")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wire::golden_messages;
+
+    fn zstd_frame(declared: usize, payload: &[u8]) -> Vec<u8> {
+        let mut frame = vec![CODEC_ZSTD];
+        write_varint(&mut frame, declared as u64);
+        frame.extend(zstd::bulk::compress(payload, ZSTD_LEVEL).unwrap());
+        frame
+    }
+
+    #[test]
+    fn frames_round_trip_and_shrink_header_batches() {
+        let link = Link::negotiate(&Hello::default(), &Hello::default());
+        assert_eq!(link.codec, CODEC_ZSTD);
+        for (name, msg) in golden_messages() {
+            assert_eq!(link.open_message(&link.seal_message(&msg)), Ok(msg), "{}", name);
+        }
+
+        let Message::Headers(headers) = golden_messages()[0].1.clone() else { unreachable!() };
+        let batch = Message::Headers(headers.iter().cycle().take(200).cloned().collect());
+        let (plain, sealed) = (batch.to_bytes(), link.seal_message(&batch));
+        assert_eq!(sealed[0], CODEC_ZSTD);
+        assert!(sealed.len() * 10 < plain.len(), "{} -> {}", plain.len(), sealed.len());
+        assert_eq!(link.open_message(&sealed), Ok(batch));
+
+        // Below the threshold, or with a peer that only speaks RAW: uncompressed
+        assert_eq!(link.seal(b"small")[0], CODEC_RAW);
+        let raw_only = Link::negotiate(&Hello::default(), &Hello { codecs: 1, threshold: 0 });
+        assert_eq!(raw_only.seal(&plain)[0], CODEC_RAW);
+        assert_eq!(raw_only.open(&sealed), Err(WireError::UnknownTag(CODEC_ZSTD)));
+        assert_eq!(Hello::from_bytes(&Hello::default().to_bytes()), Ok(Hello::default()));
+        // The retired LZ tag is no codec at all
+        assert_eq!(decode_frame(&[0x01, 0], 64), Err(WireError::UnknownTag(0x01)));
+    }
+
+    #[test]
+    fn decompression_bombs_are_refused() {
+        // A 5-byte frame claiming 1 GiB is rejected before any allocation
+        let mut bomb = vec![CODEC_ZSTD];
+        write_varint(&mut bomb, 1 << 30);
+        bomb.extend([1, 0]);
+        assert!(matches!(decode_frame(&bomb, MAX_MESSAGE_BYTES), Err(WireError::TooLarge { what: "decoded frame", .. })));
+
+        // A body that inflates past its declared length stops at the limit
+        let zeros = vec![0u8; 1 << 20];
+        let overrun = zstd_frame(16, &zeros);
+        assert!(overrun.len() < 100);
+        assert_eq!(decode_frame(&overrun, MAX_MESSAGE_BYTES), Err(WireError::NonCanonical("frame exceeds its declared length")));
+        assert_eq!(decode_frame(&zstd_frame(17, &[0; 16]), 64), Err(WireError::NonCanonical("frame falls short of its declared length")));
+
+        // Garbage is an error, not a panic
+        let bad = [CODEC_ZSTD, 8, 0xAA, 0xBB, 0xCC];
+        assert_eq!(decode_frame(&bad, 64), Err(WireError::NonCanonical("bad zstd body")));
+    }
+}
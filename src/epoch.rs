@@ -0,0 +1,327 @@
+// src/epoch.rs
+// EPOCH SUMMARIES: Periodic super-headers for light sync.
+//
+// Every `epoch_len` blocks the chain is summarized by an EpochSummary that
+// commits (through the header MMR) to every header up to the end of the
+// epoch, the cumulative VDF weight, and the Horizon root at the epoch end.
+// Any individual header can be drilled into with an MMR inclusion proof
+// against the summary's root.
+//
+// Summaries are signed. A summary chain can be made internally consistent
+// with any weight at all, and nothing short of the headers shows the weight
+// was worked for, so a light client only takes summaries signed under a
+// checkpoint key it already trusts (SUMMARY_CONTEXT).
+//
+// A light client walks the summary chain epoch-by-epoch and only fully
+// verifies the headers produced after the last completed epoch: linkage and
+// the VDF output of each, and of the anchor they hang from.
+
+use crate::gsh::GSH256;
+use crate::horizon_net::BlockHeader;
+use crate::jordan_sig::{JordanSchnorr, PublicKey, SecretKey, SignError, Signature};
+use crate::mmr::{Mmr, MmrProof};
+use rand::Rng;
+
+const GENESIS_SUMMARY: &str = "EPOCH_GENESIS";
+pub const SUMMARY_CONTEXT: &[u8] = b"olc/epoch/summary/v1";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EpochSummary {
+    pub epoch: u64,
+    pub start_height: u64,    // First header in the epoch
+    pub end_height: u64,      // Last header in the epoch (inclusive)
+    pub mmr_root: String,     // MMR root over header ids [0, end_height]
+    pub epoch_weight: u64,    // VDF iterations inside this epoch
+    pub total_weight: u64,    // Cumulative VDF iterations [0, end_height]
+    pub end_root: String,     // horizon_root of the last header
+    pub prev_summary: String, // id() of the previous summary
+}
+
+impl EpochSummary {
+    pub fn id(&self) -> String {
+        let raw = format!("{}|{}|{}|{}|{}|{}|{}|{}",
+            self.epoch, self.start_height, self.end_height, self.mmr_root,
+            self.epoch_weight, self.total_weight, self.end_root, self.prev_summary);
+        GSH256::hash_bytes(raw.as_bytes())
+    }
+
+    // Summarize every completed epoch of `chain` (genesis is height 0).
+    pub fn summarize(chain: &[BlockHeader], epoch_len: usize) -> Vec<EpochSummary> {
+        assert!(epoch_len > 0, "epoch length must be positive");
+
+        let mut mmr = Mmr::new();
+        let mut summaries: Vec<EpochSummary> = Vec::new();
+        let mut total_weight = 0u64;
+        let mut epoch_weight = 0u64;
+
+        for (height, header) in chain.iter().enumerate() {
+            mmr.append(header.id());
+            total_weight += header.vdf_iterations;
+            epoch_weight += header.vdf_iterations;
+
+            if (height + 1) % epoch_len == 0 {
+                let prev_summary = summaries.last()
+                    .map_or(GENESIS_SUMMARY.to_string(), |s| s.id());
+                summaries.push(EpochSummary {
+                    epoch: summaries.len() as u64,
+                    start_height: (height + 1 - epoch_len) as u64,
+                    end_height: height as u64,
+                    mmr_root: mmr.root(),
+                    epoch_weight,
+                    total_weight,
+                    end_root: header.horizon_root.clone(),
+                    prev_summary,
+                });
+                epoch_weight = 0;
+            }
+        }
+        summaries
+    }
+
+    // Vouch for this summary under the checkpoint key
+    pub fn sign<R: Rng + ?Sized>(self, key: &SecretKey, rng: &mut R) -> Result<SignedSummary, SignError> {
+        let signature = JordanSchnorr::sign(key, SUMMARY_CONTEXT, self.id().as_bytes(), rng)?;
+        Ok(SignedSummary { summary: self, signature })
+    }
+
+    // Drill-down: prove that `header` sits at `height` under this summary.
+    pub fn verify_header(&self, header: &BlockHeader, proof: &MmrProof) -> bool {
+        proof.leaf_count == self.end_height + 1 && proof.verify(&header.id(), &self.mmr_root)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignedSummary {
+    pub summary: EpochSummary,
+    pub signature: Signature,
+}
+
+impl SignedSummary {
+    pub fn verify(&self, checkpoint_key: &PublicKey) -> bool {
+        JordanSchnorr::verify(checkpoint_key, SUMMARY_CONTEXT, self.summary.id().as_bytes(), &self.signature).is_ok()
+    }
+}
+
+// Builds drill-down proofs for a summary (served by full nodes).
+pub fn prove_header(chain: &[BlockHeader], summary: &EpochSummary, height: u64) -> Option<MmrProof> {
+    if height > summary.end_height || summary.end_height as usize >= chain.len() {
+        return None;
+    }
+    let mut mmr = Mmr::new();
+    for header in &chain[..=summary.end_height as usize] {
+        mmr.append(header.id());
+    }
+    mmr.prove(height)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EpochSyncError {
+    BrokenSummaryChain { epoch: u64 },
+    InconsistentWeight { epoch: u64 },
+    // Not signed under the checkpoint key
+    BadSignature { epoch: u64 },
+    BadAnchorProof,
+    AnchorRootMismatch,
+    BrokenRecentLinkage { offset: usize },
+    // The header's VDF output does not recompute
+    InvalidVdf { height: u64 },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EpochSyncResult {
+    pub tip_root: String,
+    pub total_weight: u64,
+    pub epochs: u64,
+    pub recent_headers: usize,
+}
+
+pub struct EpochSync;
+
+impl EpochSync {
+    // Light sync from summaries plus the headers after the last epoch.
+    // Every summary must be signed under `checkpoint_key`. `anchor` is the
+    // last header of the final summarized epoch, proven via MMR; `recent`
+    // are the headers that follow it, verified one by one.
+    pub fn sync(
+        checkpoint_key: &PublicKey,
+        summaries: &[SignedSummary],
+        anchor: &BlockHeader,
+        anchor_proof: &MmrProof,
+        recent: &[BlockHeader],
+    ) -> Result<EpochSyncResult, EpochSyncError> {
+        // 1. Walk the summary chain
+        let mut prev_id = GENESIS_SUMMARY.to_string();
+        let mut prev_total = 0u64;
+        let mut next_start = 0u64;
+        for signed in summaries {
+            let s = &signed.summary;
+            if s.prev_summary != prev_id || s.start_height != next_start || s.end_height < s.start_height {
+                return Err(EpochSyncError::BrokenSummaryChain { epoch: s.epoch });
+            }
+            if s.total_weight != prev_total + s.epoch_weight {
+                return Err(EpochSyncError::InconsistentWeight { epoch: s.epoch });
+            }
+            if !signed.verify(checkpoint_key) {
+                return Err(EpochSyncError::BadSignature { epoch: s.epoch });
+            }
+            prev_id = s.id();
+            prev_total = s.total_weight;
+            next_start = s.end_height + 1;
+        }
+
+        let last = match summaries.last() {
+            Some(s) => &s.summary,
+            None => return Err(EpochSyncError::BrokenSummaryChain { epoch: 0 }),
+        };
+
+        // 2. Anchor the recent headers to the last summary
+        if anchor_proof.leaf_index != last.end_height || !last.verify_header(anchor, anchor_proof) {
+            return Err(EpochSyncError::BadAnchorProof);
+        }
+        if anchor.horizon_root != last.end_root {
+            return Err(EpochSyncError::AnchorRootMismatch);
+        }
+        if !anchor.verify_vdf() {
+            return Err(EpochSyncError::InvalidVdf { height: last.end_height });
+        }
+
+        // 3. Verify only the recent headers in full
+        let mut prev = anchor;
+        let mut total_weight = last.total_weight;
+        for (offset, header) in recent.iter().enumerate() {
+            if header.prev_hash != prev.id() {
+                return Err(EpochSyncError::BrokenRecentLinkage { offset });
+            }
+            if !header.verify_vdf() {
+                return Err(EpochSyncError::InvalidVdf { height: last.end_height + 1 + offset as u64 });
+            }
+            total_weight += header.vdf_iterations;
+            prev = header;
+        }
+
+        Ok(EpochSyncResult {
+            tip_root: prev.horizon_root.clone(),
+            total_weight,
+            epochs: summaries.len() as u64,
+            recent_headers: recent.len(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::horizon_net::HorizonPeer;
+    use crate::vdf::Octonion;
+    use rand::prelude::*;
+
+    fn chain(blocks: usize) -> Vec<BlockHeader> {
+        let mut peer = HorizonPeer::new("GENESIS".to_string());
+        for i in 0..blocks {
+            peer.mine_next_block(format!("ROOT_{}", i), 100 + i as u64);
+        }
+        peer.chain
+    }
+
+    fn key(seed: u64) -> SecretKey {
+        JordanSchnorr::keygen(&mut StdRng::seed_from_u64(seed))
+    }
+
+    fn signed(summaries: Vec<EpochSummary>, key: &SecretKey) -> Vec<SignedSummary> {
+        let mut rng = StdRng::seed_from_u64(4264);
+        summaries.into_iter().map(|s| s.sign(key, &mut rng).unwrap()).collect()
+    }
+
+    #[test]
+    fn light_sync_matches_full_chain() {
+        let checkpoint = key(1);
+        let headers = chain(10); // 11 headers incl. genesis
+        let summaries = EpochSummary::summarize(&headers, 4);
+        assert_eq!(summaries.len(), 2);
+
+        let last = summaries.last().unwrap().clone();
+        let anchor = &headers[last.end_height as usize];
+        let proof = prove_header(&headers, &last, last.end_height).unwrap();
+        let recent = &headers[last.end_height as usize + 1..];
+
+        let summaries = signed(summaries, &checkpoint);
+        let result = EpochSync::sync(&checkpoint.pub_key, &summaries, anchor, &proof, recent).unwrap();
+        let full_weight: u64 = headers.iter().map(|h| h.vdf_iterations).sum();
+        assert_eq!(result.total_weight, full_weight);
+        assert_eq!(result.tip_root, headers.last().unwrap().horizon_root);
+        assert_eq!(result.recent_headers, 3);
+
+        // Drill into an older header of the first epoch
+        let old = prove_header(&headers, &last, 2).unwrap();
+        assert!(last.verify_header(&headers[2], &old));
+        assert!(!last.verify_header(&headers[3], &old));
+    }
+
+    #[test]
+    fn tampered_summary_is_rejected() {
+        let checkpoint = key(1);
+        let headers = chain(9);
+        let summaries = EpochSummary::summarize(&headers, 3);
+        let last = summaries.last().unwrap().clone();
+        let anchor = &headers[last.end_height as usize];
+        let proof = prove_header(&headers, &last, last.end_height).unwrap();
+
+        let mut summaries = signed(summaries, &checkpoint);
+        summaries[0].summary.epoch_weight += 1;
+        assert_eq!(
+            EpochSync::sync(&checkpoint.pub_key, &summaries, anchor, &proof, &[]),
+            Err(EpochSyncError::InconsistentWeight { epoch: 0 })
+        );
+    }
+
+    #[test]
+    fn consistent_but_inflated_summaries_are_rejected() {
+        let (checkpoint, attacker) = (key(1), key(2));
+        let headers = chain(9);
+        let honest = EpochSummary::summarize(&headers, 3);
+        let last = honest.last().unwrap().clone();
+        let anchor = &headers[last.end_height as usize];
+        let proof = prove_header(&headers, &last, last.end_height).unwrap();
+
+        // Every epoch claims a million extra iterations; links and running
+        // totals are rebuilt so the chain is self-consistent
+        let mut forged = honest.clone();
+        let (mut prev_id, mut total) = (GENESIS_SUMMARY.to_string(), 0);
+        for s in &mut forged {
+            s.epoch_weight += 1_000_000;
+            total += s.epoch_weight;
+            s.total_weight = total;
+            s.prev_summary = prev_id;
+            prev_id = s.id();
+        }
+        let forged = signed(forged, &attacker);
+        let inflated = EpochSync::sync(&attacker.pub_key, &forged, anchor, &proof, &[]).unwrap();
+        assert_eq!(inflated.total_weight, last.total_weight + 3_000_000);
+        assert_eq!(
+            EpochSync::sync(&checkpoint.pub_key, &forged, anchor, &proof, &[]),
+            Err(EpochSyncError::BadSignature { epoch: 0 })
+        );
+
+        let honest = signed(honest, &checkpoint);
+        assert_eq!(EpochSync::sync(&checkpoint.pub_key, &honest, anchor, &proof, &[]).unwrap().total_weight, last.total_weight);
+    }
+
+    #[test]
+    fn forged_vdf_output_is_rejected() {
+        let checkpoint = key(1);
+        let mut headers = chain(10);
+        let summaries = EpochSummary::summarize(&headers, 4);
+        let last = summaries.last().unwrap().clone();
+        let proof = prove_header(&headers, &last, last.end_height).unwrap();
+        let summaries = signed(summaries, &checkpoint);
+
+        // The tip claims a VDF output nobody computed
+        headers.last_mut().unwrap().vdf_proof = Octonion::from_seed(1);
+        let anchor = &headers[last.end_height as usize];
+        let recent = &headers[last.end_height as usize + 1..];
+        assert_eq!(
+            EpochSync::sync(&checkpoint.pub_key, &summaries, anchor, &proof, recent),
+            Err(EpochSyncError::InvalidVdf { height: 10 })
+        );
+    }
+}
@@ -0,0 +1,375 @@
+// src/faultnet.rs
+// FAULT INJECTION: A hostile network for resilience testing.
+//
+// Real links drop, duplicate, delay, reorder and corrupt messages, and
+// split into partitions. Nodes talk through the Transport trait; Loopback
+// delivers every frame at once and in order, Faulty<T> wraps any transport
+// and injects, per frame and from a seeded RNG:
+//   * drop       - the frame never arrives
+//   * duplicate  - it arrives twice, each copy with its own delay
+//   * corrupt    - one bit is flipped
+//   * delay      - uniform in [min, max] ms; differing delays reorder
+//   * partitions - during [start_ms, end_ms) nodes on `side` and the rest
+//                  cannot reach each other (frames are lost, not queued)
+// Frames carry a 4-byte SHA-256 tag (seal / open), as a link-layer CRC
+// would, so a corrupted frame is discarded by the receiver instead of being
+// parsed into a different header chain.
+//
+// run_convergence drives N HorizonPeers over a faulty full mesh: random
+// nodes mine, every node periodically announces its chain, receivers apply
+// the usual fork choice. Once mining stops, the report says how long it
+// took every node to settle on the heaviest chain (None if it never did
+// within the deadline). Time is simulated, in ms, as in audit.rs.
+
+use crate::horizon_net::{HorizonPeer, NetworkBootstrapper};
+use crate::wire::{Message, Wire};
+use rand::prelude::*;
+use sha2::{Digest, Sha256};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap, VecDeque};
+
+pub type NodeId = usize;
+
+// --- TRANSPORT ---
+pub trait Transport {
+    fn send(&mut self, from: NodeId, to: NodeId, frame: Vec<u8>, now_ms: u64);
+    // Frames for `to` that have arrived by `now_ms`, oldest first
+    fn poll(&mut self, to: NodeId, now_ms: u64) -> Vec<(NodeId, Vec<u8>)>;
+}
+
+#[derive(Default)]
+pub struct Loopback {
+    queues: BTreeMap<NodeId, VecDeque<(NodeId, Vec<u8>)>>,
+}
+
+impl Transport for Loopback {
+    fn send(&mut self, from: NodeId, to: NodeId, frame: Vec<u8>, _now_ms: u64) {
+        self.queues.entry(to).or_default().push_back((from, frame));
+    }
+
+    fn poll(&mut self, to: NodeId, _now_ms: u64) -> Vec<(NodeId, Vec<u8>)> {
+        self.queues.get_mut(&to).map(|q| q.drain(..).collect()).unwrap_or_default()
+    }
+}
+
+// --- ENVELOPE ---
+const TAG_BYTES: usize = 4;
+
+pub fn seal(msg: &Message) -> Vec<u8> {
+    let mut frame = msg.to_bytes();
+    let tag = Sha256::digest(&frame);
+    frame.extend_from_slice(&tag[..TAG_BYTES]);
+    frame
+}
+
+// None if the frame was damaged in transit or does not parse
+pub fn open(frame: &[u8]) -> Option<Message> {
+    let (body, tag) = frame.split_at_checked(frame.len().checked_sub(TAG_BYTES)?)?;
+    if Sha256::digest(body)[..TAG_BYTES] != *tag {
+        return None;
+    }
+    Message::from_bytes(body).ok()
+}
+
+// --- FAULTS ---
+#[derive(Clone, Debug)]
+pub struct Partition {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub side: Vec<NodeId>,
+}
+
+impl Partition {
+    fn separates(&self, a: NodeId, b: NodeId, now_ms: u64) -> bool {
+        (self.start_ms..self.end_ms).contains(&now_ms) && self.side.contains(&a) != self.side.contains(&b)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FaultConfig {
+    pub drop: f64,
+    pub duplicate: f64,
+    pub corrupt: f64,
+    pub delay_ms: (u64, u64), // Uniform in [min, max]
+    pub partitions: Vec<Partition>,
+    pub seed: u64,
+}
+
+impl Default for FaultConfig {
+    fn default() -> Self {
+        FaultConfig { drop: 0.0, duplicate: 0.0, corrupt: 0.0, delay_ms: (0, 0), partitions: Vec::new(), seed: 0 }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConfigError {
+    NoNodes,
+    // An interval of 0 ms would divide by zero or never advance time
+    ZeroInterval(&'static str),
+    BadProbability { name: &'static str, value: f64 },
+}
+
+impl FaultConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        for (name, value) in [("drop", self.drop), ("duplicate", self.duplicate), ("corrupt", self.corrupt)] {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(ConfigError::BadProbability { name, value });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FaultStats {
+    pub sent: u64,
+    pub dropped: u64,
+    pub partitioned: u64,
+    pub duplicated: u64,
+    pub corrupted: u64,
+    pub delivered: u64,
+}
+
+// (due, seq, from, to, frame), earliest due first in the heap
+type InFlight = Reverse<(u64, u64, NodeId, NodeId, Vec<u8>)>;
+
+pub struct Faulty<T: Transport> {
+    pub inner: T,
+    pub config: FaultConfig,
+    pub stats: FaultStats,
+    rng: StdRng,
+    seq: u64, // Keeps equal-time frames in send order
+    in_flight: BinaryHeap<InFlight>,
+}
+
+impl<T: Transport> Faulty<T> {
+    pub fn new(inner: T, config: FaultConfig) -> Self {
+        let rng = StdRng::seed_from_u64(config.seed);
+        Faulty { inner, config, stats: FaultStats::default(), rng, seq: 0, in_flight: BinaryHeap::new() }
+    }
+
+    fn schedule(&mut self, from: NodeId, to: NodeId, mut frame: Vec<u8>, now_ms: u64) {
+        if !frame.is_empty() && self.rng.gen_bool(self.config.corrupt) {
+            let bit = self.rng.gen_range(0..frame.len() * 8);
+            frame[bit / 8] ^= 1 << (bit % 8);
+            self.stats.corrupted += 1;
+        }
+        let (min, max) = self.config.delay_ms;
+        let due = now_ms + self.rng.gen_range(min..=max.max(min));
+        self.seq += 1;
+        self.in_flight.push(Reverse((due, self.seq, from, to, frame)));
+    }
+}
+
+impl<T: Transport> Transport for Faulty<T> {
+    fn send(&mut self, from: NodeId, to: NodeId, frame: Vec<u8>, now_ms: u64) {
+        self.stats.sent += 1;
+        if self.config.partitions.iter().any(|p| p.separates(from, to, now_ms)) {
+            self.stats.partitioned += 1;
+            return;
+        }
+        if self.rng.gen_bool(self.config.drop) {
+            self.stats.dropped += 1;
+            return;
+        }
+        if self.rng.gen_bool(self.config.duplicate) {
+            self.stats.duplicated += 1;
+            self.schedule(from, to, frame.clone(), now_ms);
+        }
+        self.schedule(from, to, frame, now_ms);
+    }
+
+    fn poll(&mut self, to: NodeId, now_ms: u64) -> Vec<(NodeId, Vec<u8>)> {
+        while self.in_flight.peek().is_some_and(|Reverse((due, ..))| *due <= now_ms) {
+            let Reverse((due, _, from, dest, frame)) = self.in_flight.pop().unwrap();
+            self.inner.send(from, dest, frame, due);
+        }
+        let frames = self.inner.poll(to, now_ms);
+        self.stats.delivered += frames.len() as u64;
+        frames
+    }
+}
+
+// --- CONVERGENCE ---
+#[derive(Clone, Debug)]
+pub struct ConvergenceConfig {
+    pub nodes: usize,
+    pub mining_ms: u64,   // Blocks are found during [0, mining_ms)
+    pub deadline_ms: u64, // Allowed settling time after mining stops
+    pub tick_ms: u64,
+    pub mine_chance: f64, // Per tick, that some node finds a block
+    pub announce_ms: u64, // Every node re-announces its chain this often
+    pub faults: FaultConfig,
+    pub seed: u64,
+}
+
+impl Default for ConvergenceConfig {
+    fn default() -> Self {
+        ConvergenceConfig {
+            nodes: 5,
+            mining_ms: 60_000,
+            deadline_ms: 30_000,
+            tick_ms: 100,
+            mine_chance: 0.02,
+            announce_ms: 2_000,
+            faults: FaultConfig {
+                drop: 0.2,
+                duplicate: 0.1,
+                corrupt: 0.05,
+                delay_ms: (20, 1_500),
+                partitions: vec![Partition { start_ms: 10_000, end_ms: 30_000, side: vec![0, 1] }],
+                seed: 0,
+            },
+            seed: 0,
+        }
+    }
+}
+
+impl ConvergenceConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.nodes == 0 {
+            return Err(ConfigError::NoNodes);
+        }
+        if self.tick_ms == 0 {
+            return Err(ConfigError::ZeroInterval("tick_ms"));
+        }
+        if self.announce_ms == 0 {
+            return Err(ConfigError::ZeroInterval("announce_ms"));
+        }
+        if !(0.0..=1.0).contains(&self.mine_chance) {
+            return Err(ConfigError::BadProbability { name: "mine_chance", value: self.mine_chance });
+        }
+        self.faults.validate()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ConvergenceReport {
+    pub blocks_mined: u64,
+    pub converged_after_ms: Option<u64>, // Since mining stopped
+    pub tip: Option<String>,             // Common tip id, once converged
+    pub rejected_frames: u64,            // Failed the envelope check
+    pub stats: FaultStats,
+}
+
+pub fn run_convergence(config: &ConvergenceConfig) -> Result<ConvergenceReport, ConfigError> {
+    config.validate()?;
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let genesis_root = "00".repeat(32);
+    let mut nodes: Vec<HorizonPeer> = (0..config.nodes).map(|_| HorizonPeer::new(genesis_root.clone())).collect();
+    let mut net = Faulty::new(Loopback::default(), config.faults.clone());
+    let (mut blocks_mined, mut rejected_frames) = (0, 0);
+
+    let broadcast = |net: &mut Faulty<Loopback>, nodes: &[HorizonPeer], from: NodeId, now_ms: u64| {
+        let frame = seal(&Message::Headers(nodes[from].chain.clone()));
+        for to in (0..nodes.len()).filter(|&to| to != from) {
+            net.send(from, to, frame.clone(), now_ms);
+        }
+    };
+
+    let end_ms = config.mining_ms + config.deadline_ms;
+    let mut now_ms = 0;
+    while now_ms <= end_ms {
+        // 1. Mining; difficulties vary so forks rarely tie on weight
+        if now_ms < config.mining_ms && rng.gen_bool(config.mine_chance) {
+            let miner = rng.gen_range(0..nodes.len());
+            let root = nodes[miner].current_horizon.clone();
+            nodes[miner].mine_next_block(root, rng.gen_range(1_000..2_000));
+            blocks_mined += 1;
+            broadcast(&mut net, &nodes, miner, now_ms);
+        }
+        if now_ms % config.announce_ms == 0 {
+            for from in 0..nodes.len() {
+                broadcast(&mut net, &nodes, from, now_ms);
+            }
+        }
+
+        // 2. Delivery and fork choice
+        for (to, node) in nodes.iter_mut().enumerate() {
+            for (_, frame) in net.poll(to, now_ms) {
+                match open(&frame) {
+                    Some(Message::Headers(chain)) if !chain.is_empty() => {
+                        NetworkBootstrapper::try_sync(node, &chain);
+                    }
+                    Some(_) => {}
+                    None => rejected_frames += 1,
+                }
+            }
+        }
+
+        // 3. Settled: one tip everywhere, and it is the heaviest chain known
+        if now_ms >= config.mining_ms {
+            let tip = nodes[0].chain.last().unwrap().id();
+            if nodes.iter().all(|n| n.chain.last().unwrap().id() == tip) {
+                return Ok(ConvergenceReport {
+                    blocks_mined,
+                    converged_after_ms: Some(now_ms - config.mining_ms),
+                    tip: Some(tip),
+                    rejected_frames,
+                    stats: net.stats,
+                });
+            }
+        }
+        now_ms += config.tick_ms;
+    }
+    Ok(ConvergenceReport { blocks_mined, converged_after_ms: None, tip: None, rejected_frames, stats: net.stats })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn faults_follow_the_configuration() {
+        let msg = Message::GetHeaders { from_id: "tip".to_string(), max: 10 };
+        let frame = seal(&msg);
+
+        // Every frame doubled; a partition loses frames only while it lasts
+        let partition = Partition { start_ms: 100, end_ms: 200, side: vec![1] };
+        let config = FaultConfig { delay_ms: (0, 50), duplicate: 1.0, partitions: vec![partition], ..FaultConfig::default() };
+        let mut net = Faulty::new(Loopback::default(), config);
+        net.send(0, 1, frame.clone(), 0);
+        net.send(0, 1, frame.clone(), 150);
+        net.send(0, 2, frame.clone(), 150);
+        assert!(net.poll(2, 149).is_empty()); // Not due yet
+        assert_eq!(net.poll(1, 1_000).len(), 2);
+        assert_eq!(net.poll(2, 1_000).len(), 2);
+        assert_eq!(net.stats.partitioned, 1);
+        assert_eq!(net.stats.duplicated, 2);
+
+        // Corruption is caught by the envelope; a clean frame opens
+        let mut lossy = Faulty::new(Loopback::default(), FaultConfig { corrupt: 1.0, ..FaultConfig::default() });
+        lossy.send(0, 1, frame.clone(), 0);
+        let received = lossy.poll(1, 0);
+        assert_eq!(open(&received[0].1), None);
+        assert_eq!(open(&frame), Some(msg));
+
+        let mut dead = Faulty::new(Loopback::default(), FaultConfig { drop: 1.0, ..FaultConfig::default() });
+        dead.send(0, 1, frame, 0);
+        assert!(dead.poll(1, u64::MAX).is_empty());
+    }
+
+    #[test]
+    fn consensus_converges_under_the_fault_envelope() {
+        let config = ConvergenceConfig { mining_ms: 40_000, ..ConvergenceConfig::default() };
+        let report = run_convergence(&config).unwrap();
+        assert!(report.blocks_mined > 5, "{:?}", report);
+        let settled = report.converged_after_ms.expect("nodes did not converge");
+        assert!(settled <= config.deadline_ms);
+        // Every kind of fault actually happened
+        let s = &report.stats;
+        assert!(s.dropped > 0 && s.duplicated > 0 && s.corrupted > 0 && s.partitioned > 0, "{:?}", s);
+        assert!(report.rejected_frames > 0);
+    }
+
+    #[test]
+    fn unusable_configs_are_refused_up_front() {
+        let zero = ConvergenceConfig { announce_ms: 0, ..ConvergenceConfig::default() };
+        assert_eq!(run_convergence(&zero).unwrap_err(), ConfigError::ZeroInterval("announce_ms"));
+        let stalled = ConvergenceConfig { tick_ms: 0, ..ConvergenceConfig::default() };
+        assert_eq!(stalled.validate(), Err(ConfigError::ZeroInterval("tick_ms")));
+        let mut lossy = ConvergenceConfig::default();
+        lossy.faults.drop = 1.5;
+        assert_eq!(lossy.validate(), Err(ConfigError::BadProbability { name: "drop", value: 1.5 }));
+    }
+}
@@ -0,0 +1,346 @@
+// src/fee_market.rs
+// FEE MARKET: Block-space equilibrium under synthetic demand.
+//
+// Users arrive as a Poisson stream (arrivals_per_block on average, times the
+// spike multiplier while a spike is on). Each draws a coin value and a
+// willingness - the most it will pay per byte - then bids what a simple
+// estimator quotes, capped at that willingness:
+//   * after a full block: one above the cheapest tx that made it in
+//   * after a block with room to spare: the relay floor
+// A user whose coin would end up below the dust limit after paying the fee
+// does not transact at all (priced out). Users never re-bid; an underpaying
+// tx waits in the pool until demand falls or it is trimmed.
+// The miner takes Mempool::block_template, so selection is the real policy
+// (ancestor-package feerate, size trimming, generation expiry).
+//
+// The report gives, per phase (before / during / after the spike), the
+// equilibrium fee (mean feerate of the cheapest included tx), feerate and
+// confirmation-time percentiles and the backlog: what the block size and
+// dust defaults should be judged against. Seeded, so runs are reproducible.
+
+use crate::albert::AlbertElement;
+use crate::horizon::{Transaction, Utxo, Witness, SPEND_SIZE_BYTES, TREE_DEPTH};
+use crate::jordan_sig::{ParamSet, PublicKey, Signature, KEY_ELEMENTS, RESPONSE_ELEMENTS};
+use crate::mempool::{Mempool, MempoolConfig, TxId};
+use rand::prelude::*;
+use rand_distr::{LogNormal, Poisson};
+use std::collections::BTreeMap;
+
+// --- CONFIGURATION ---
+// Per-byte fee a user is willing to pay
+#[derive(Clone, Copy, Debug)]
+pub enum Willingness {
+    LogNormal { median: f64, sigma: f64 },
+    Uniform { low: u64, high: u64 },
+}
+
+impl Willingness {
+    fn sample(&self, rng: &mut StdRng) -> u64 {
+        match *self {
+            Willingness::LogNormal { median, sigma } => LogNormal::new(median.ln(), sigma).expect("sigma must be finite").sample(rng).round() as u64,
+            Willingness::Uniform { low, high } => rng.gen_range(low..=high),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Spike {
+    pub start: u64,      // First block of the spike
+    pub blocks: u64,     // How long it lasts
+    pub multiplier: f64, // Arrival rate during the spike, relative to normal
+}
+
+#[derive(Clone, Debug)]
+pub struct FeeMarketConfig {
+    pub blocks: u64,
+    pub block_bytes: usize,
+    pub block_interval_ms: u64,
+    pub arrivals_per_block: f64,
+    pub spike: Option<Spike>,
+    pub willingness: Willingness,
+    pub median_value: f64, // Coin values are log-normal around this
+    pub dust_limit: u64,   // Smallest output worth creating
+    pub mempool: MempoolConfig,
+    pub seed: u64,
+}
+
+impl Default for FeeMarketConfig {
+    fn default() -> Self {
+        FeeMarketConfig {
+            blocks: 300,
+            block_bytes: 256 * 1024, // ~113 spends
+            block_interval_ms: 15_000,
+            arrivals_per_block: 100.0,
+            spike: Some(Spike { start: 100, blocks: 50, multiplier: 3.0 }),
+            willingness: Willingness::LogNormal { median: 10.0, sigma: 1.0 },
+            median_value: 1_000_000.0,
+            dust_limit: 10_000,
+            mempool: MempoolConfig::default(),
+            seed: 0,
+        }
+    }
+}
+
+impl FeeMarketConfig {
+    pub fn phase(&self, height: u64) -> Phase {
+        match self.spike {
+            Some(s) if height >= s.start + s.blocks => Phase::After,
+            Some(s) if height >= s.start => Phase::Spike,
+            _ => Phase::Before,
+        }
+    }
+}
+
+// --- REPORT ---
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Phase {
+    Before,
+    Spike,
+    After,
+}
+
+#[derive(Clone, Debug)]
+pub struct BlockStats {
+    pub height: u64,
+    pub phase: Phase,
+    pub arrivals: u64,
+    pub included: usize,
+    pub fees: u64,
+    pub min_feerate: Option<u64>, // Cheapest included tx, per byte
+    pub backlog_txs: usize,       // Left in the pool after the block
+    pub backlog_bytes: usize,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Percentiles {
+    pub p50: u64,
+    pub p90: u64,
+    pub p99: u64,
+}
+
+impl Percentiles {
+    // Nearest rank; all zero for an empty sample
+    fn of(mut values: Vec<u64>) -> Self {
+        if values.is_empty() {
+            return Percentiles::default();
+        }
+        values.sort_unstable();
+        let rank = |p: usize| values[(values.len() * p).div_ceil(100).max(1) - 1];
+        Percentiles { p50: rank(50), p90: rank(90), p99: rank(99) }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PhaseSummary {
+    pub phase: Phase,
+    pub blocks: u64,
+    pub arrivals: u64,
+    pub confirmed: u64,
+    pub priced_out: u64, // Would have left dust after the fee
+    pub rejected: u64,   // Refused by the pool (below relay floor, pool full)
+    pub dropped: u64,    // Trimmed or expired while waiting
+    pub equilibrium_fee: f64,
+    pub feerate: Percentiles,             // Of txs confirmed in this phase
+    pub confirmation_blocks: Percentiles, // Blocks from arrival to inclusion
+    pub peak_backlog: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct FeeMarketReport {
+    pub blocks: Vec<BlockStats>,
+    pub phases: Vec<PhaseSummary>,
+}
+
+// --- SIMULATION ---
+struct Waiting {
+    height: u64,
+    feerate: u64,
+}
+
+// A standalone spend of a fresh coin; only fee and size matter to the pool
+fn spend(input: u64, value: u64, fee: u64) -> Transaction {
+    let mut id = [0u8; 32];
+    id[..8].copy_from_slice(&input.to_le_bytes());
+    let key = PublicKey { t: [AlbertElement::zero(); KEY_ELEMENTS], a: AlbertElement::zero(), params: ParamSet::JdLight };
+    Transaction {
+        input_utxo: Utxo::new(id, key.address(), value),
+        witness: Witness { siblings: vec![[0u8; 32]; TREE_DEPTH], index: 0 },
+        owner_key: key,
+        signature: Signature { z: [AlbertElement::zero(); RESPONSE_ELEMENTS], c: [0; 32], params: ParamSet::JdLight },
+        new_owner: key.address(),
+        new_amount: value - fee,
+    }
+}
+
+pub fn simulate(config: &FeeMarketConfig) -> FeeMarketReport {
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let values = LogNormal::new(config.median_value.ln(), 1.5).expect("median_value must be positive");
+    let mut pool = Mempool::new(config.mempool.clone());
+    let mut waiting: BTreeMap<TxId, Waiting> = BTreeMap::new();
+    let mut quote = config.mempool.min_relay_fee;
+    let mut next_input = 0u64;
+
+    let mut blocks = Vec::new();
+    let mut phases: BTreeMap<Phase, (PhaseSummary, Vec<u64>, Vec<u64>)> = BTreeMap::new();
+    for height in 0..config.blocks {
+        let phase = config.phase(height);
+        let (summary, feerates, waits) = phases.entry(phase).or_insert_with(|| {
+            let summary = PhaseSummary {
+                phase,
+                blocks: 0,
+                arrivals: 0,
+                confirmed: 0,
+                priced_out: 0,
+                rejected: 0,
+                dropped: 0,
+                equilibrium_fee: 0.0,
+                feerate: Percentiles::default(),
+                confirmation_blocks: Percentiles::default(),
+                peak_backlog: 0,
+            };
+            (summary, Vec::new(), Vec::new())
+        });
+
+        // 1. Users
+        let mean = config.arrivals_per_block * if phase == Phase::Spike { config.spike.map_or(1.0, |s| s.multiplier) } else { 1.0 };
+        let arrivals = if mean > 0.0 { Poisson::new(mean).expect("mean is positive").sample(&mut rng) as u64 } else { 0 };
+        let start_ms = height * config.block_interval_ms;
+        for k in 0..arrivals {
+            let now_ms = start_ms + k * config.block_interval_ms / arrivals;
+            let feerate = quote.min(config.willingness.sample(&mut rng));
+            let value = values.sample(&mut rng) as u64;
+            let fee = feerate * SPEND_SIZE_BYTES as u64;
+            if value < fee.saturating_add(config.dust_limit) {
+                summary.priced_out += 1;
+                continue;
+            }
+            next_input += 1;
+            match pool.accept(spend(next_input, value, fee), now_ms) {
+                Ok(accepted) => {
+                    waiting.insert(accepted.txid, Waiting { height, feerate });
+                    for id in accepted.trimmed.iter().chain(&accepted.replaced) {
+                        waiting.remove(id);
+                        summary.dropped += 1;
+                    }
+                }
+                Err(_) => summary.rejected += 1,
+            }
+        }
+
+        // 2. Miner
+        let mined_ms = start_ms + config.block_interval_ms;
+        for id in pool.expire(mined_ms) {
+            waiting.remove(&id);
+            summary.dropped += 1;
+        }
+        let template = pool.block_template(config.block_bytes);
+        let mut min_feerate: Option<u64> = None;
+        for id in &template.txids {
+            pool.remove(id);
+            let Some(w) = waiting.remove(id) else { continue };
+            min_feerate = Some(min_feerate.map_or(w.feerate, |m| m.min(w.feerate)));
+            feerates.push(w.feerate);
+            waits.push(height - w.height);
+        }
+        let full = template.total_bytes + SPEND_SIZE_BYTES > config.block_bytes;
+        quote = match min_feerate {
+            Some(m) if full => m + 1,
+            _ => config.mempool.min_relay_fee,
+        };
+
+        summary.blocks += 1;
+        summary.arrivals += arrivals;
+        summary.confirmed += template.txids.len() as u64;
+        summary.equilibrium_fee += min_feerate.unwrap_or(0) as f64;
+        summary.peak_backlog = summary.peak_backlog.max(pool.len());
+        blocks.push(BlockStats {
+            height,
+            phase,
+            arrivals,
+            included: template.txids.len(),
+            fees: template.total_fee,
+            min_feerate,
+            backlog_txs: pool.len(),
+            backlog_bytes: pool.total_bytes(),
+        });
+    }
+
+    let phases = phases
+        .into_values()
+        .map(|(mut summary, feerates, waits)| {
+            summary.equilibrium_fee /= summary.blocks as f64;
+            summary.feerate = Percentiles::of(feerates);
+            summary.confirmation_blocks = Percentiles::of(waits);
+            summary
+        })
+        .collect();
+    FeeMarketReport { blocks, phases }
+}
+
+pub fn run_simulation(config: &FeeMarketConfig) {
+    println!("\n=== Fee Market Simulation ===");
+    println!(
+        "Parameters: {} blocks of {} bytes every {}s, {:.0} arrivals/block, spike {:?}, dust limit {}",
+        config.blocks,
+        config.block_bytes,
+        config.block_interval_ms / 1000,
+        config.arrivals_per_block,
+        config.spike,
+        config.dust_limit
+    );
+    let report = simulate(config);
+    for p in &report.phases {
+        println!("{:?} ({} blocks, {} arrivals):", p.phase, p.blocks, p.arrivals);
+        println!("  Equilibrium fee: {:.2}/byte", p.equilibrium_fee);
+        println!("  Feerate paid p50/p90/p99: {}/{}/{}", p.feerate.p50, p.feerate.p90, p.feerate.p99);
+        println!(
+            "  Confirmation (blocks) p50/p90/p99: {}/{}/{}",
+            p.confirmation_blocks.p50, p.confirmation_blocks.p90, p.confirmation_blocks.p99
+        );
+        println!("  Confirmed {}, priced out {}, rejected {}, dropped {}", p.confirmed, p.priced_out, p.rejected, p.dropped);
+        println!("  Peak backlog: {} txs", p.peak_backlog);
+    }
+    if let Some(last) = report.blocks.last() {
+        println!("Final backlog: {} txs ({} bytes)", last.backlog_txs, last.backlog_bytes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn small() -> FeeMarketConfig {
+        FeeMarketConfig {
+            blocks: 60,
+            block_bytes: 10 * SPEND_SIZE_BYTES,
+            arrivals_per_block: 7.0,
+            spike: Some(Spike { start: 15, blocks: 15, multiplier: 4.0 }),
+            ..FeeMarketConfig::default()
+        }
+    }
+
+    #[test]
+    fn spike_raises_fees_and_backlog_then_drains() {
+        let report = simulate(&small());
+        let [before, spike, after] = &report.phases[..] else { panic!("three phases") };
+        assert!(spike.equilibrium_fee > before.equilibrium_fee, "{} vs {}", spike.equilibrium_fee, before.equilibrium_fee);
+        assert!(spike.peak_backlog > before.peak_backlog);
+        // Underbidders caught in the spike confirm only once it is over
+        assert!(after.confirmation_blocks.p90 > before.confirmation_blocks.p90 + 10);
+        // Blocks never exceed their size, and the backlog shrinks once demand falls
+        assert!(report.blocks.iter().all(|b| b.included * SPEND_SIZE_BYTES <= small().block_bytes));
+        assert!(report.blocks.last().unwrap().backlog_txs < after.peak_backlog);
+    }
+
+    #[test]
+    fn dust_limit_prices_out_small_coins_and_runs_are_reproducible() {
+        let strict = FeeMarketConfig { dust_limit: 1_000_000, ..small() };
+        let (loose, tight) = (simulate(&small()), simulate(&strict));
+        let priced_out = |r: &FeeMarketReport| r.phases.iter().map(|p| p.priced_out).sum::<u64>();
+        assert!(priced_out(&tight) > priced_out(&loose));
+
+        let again = simulate(&small());
+        let fees = |r: &FeeMarketReport| r.blocks.iter().map(|b| b.fees).collect::<Vec<_>>();
+        assert_eq!(fees(&again), fees(&loose));
+    }
+}
@@ -11,7 +11,11 @@
 // Key Size: 128 bits
 // ============================================================================
 
-use std::ops::{Add, Mul};
+use crate::algebra::nonassociative::quadratic_map;
+use crate::gsh::GSH256;
+use crate::redact::{self, Redacted};
+use std::fmt;
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 // Use u16 for lightweight IoT compatibility
 type Scalar = u16;
@@ -20,116 +24,12 @@ type Scalar = u16;
 // Core Structure: Discrete Octonion (Z_2^16)
 // ----------------------------------------------------------------------------
 
-// REMOVED `Copy` to allow `Drop`. Added `Clone` for explicit duplication.
-#[derive(Clone, Debug, PartialEq, Eq)]
-pub struct Octonion {
-    pub c: [Scalar; 8],
-}
-
-impl Octonion {
-    pub fn new(coeffs: [Scalar; 8]) -> Self {
-        Octonion { c: coeffs }
-    }
-
-    pub fn zero() -> Self {
-        Octonion { c: [0; 8] }
-    }
-}
-
-// Secure Zeroization: Wipes memory when the variable goes out of scope.
-impl Drop for Octonion {
-    fn drop(&mut self) {
-        // In a real no_std crate, we would use `ptr::write_volatile`.
-        // Since we are simulating in a hosted environment (for output check),
-        // we'll use a simple loop.
-        for i in 0..8 {
-            unsafe {
-                let ptr = self.c.as_mut_ptr().add(i);
-                std::ptr::write_volatile(ptr, 0);
-            }
-        }
-    }
-}
-
-// ----------------------------------------------------------------------------
-// Arithmetic Implementations (IoT Optimized)
-// ----------------------------------------------------------------------------
-
-// Using references to avoid ownership issues with non-Copy types
-impl<'a, 'b> Add<&'b Octonion> for &'a Octonion {
-    type Output = Octonion;
-    fn add(self, other: &'b Octonion) -> Octonion {
-        let mut res = [0; 8];
-        for i in 0..8 {
-            res[i] = self.c[i].wrapping_add(other.c[i]);
-        }
-        Octonion::new(res)
-    }
-}
-
-// Helper for value + reference
-impl Add<&Octonion> for Octonion {
-    type Output = Octonion;
-    fn add(self, other: &Octonion) -> Octonion {
-        let mut res = [0; 8];
-        for i in 0..8 {
-            res[i] = self.c[i].wrapping_add(other.c[i]);
-        }
-        Octonion::new(res)
-    }
-}
-
-impl<'a, 'b> Mul<&'b Octonion> for &'a Octonion {
-    type Output = Octonion;
-    fn mul(self, other: &'b Octonion) -> Octonion {
-        // Standard Cayley-Dickson doubling logic optimized for arrays
-        // x = (a, b), y = (c, d) -> xy = (ac - d*b_conj, a_conj*d + c*b)
-        
-        let a = &self.c[0..4];
-        let b = &self.c[4..8];
-        let c = &other.c[0..4];
-        let d = &other.c[4..8];
-
-        // Quaternion Multiply Helper
-        fn qmul(x: &[Scalar], y: &[Scalar]) -> [Scalar; 4] {
-            let r = x[0].wrapping_mul(y[0]).wrapping_sub(x[1].wrapping_mul(y[1]))
-                    .wrapping_sub(x[2].wrapping_mul(y[2])).wrapping_sub(x[3].wrapping_mul(y[3]));
-            let i = x[0].wrapping_mul(y[1]).wrapping_add(x[1].wrapping_mul(y[0]))
-                    .wrapping_add(x[2].wrapping_mul(y[3])).wrapping_sub(x[3].wrapping_mul(y[2]));
-            let j = x[0].wrapping_mul(y[2]).wrapping_sub(x[1].wrapping_mul(y[3]))
-                    .wrapping_add(x[2].wrapping_mul(y[0])).wrapping_add(x[3].wrapping_mul(y[1]));
-            let k = x[0].wrapping_mul(y[3]).wrapping_add(x[1].wrapping_mul(y[2]))
-                    .wrapping_sub(x[2].wrapping_mul(y[1])).wrapping_add(x[3].wrapping_mul(y[0]));
-            [r, i, j, k]
-        }
-
-        // Quaternion Conjugate Helper
-        fn qconj(x: &[Scalar]) -> [Scalar; 4] {
-            [x[0], (0 as Scalar).wrapping_sub(x[1]), 
-                   (0 as Scalar).wrapping_sub(x[2]), 
-                   (0 as Scalar).wrapping_sub(x[3])]
-        }
-
-        // First part: ac - d * b_conj
-        let ac = qmul(a, c);
-        let b_conj = qconj(b);
-        let d_b_conj = qmul(d, &b_conj);
-        let mut first = [0; 4];
-        for k in 0..4 { first[k] = ac[k].wrapping_sub(d_b_conj[k]); }
-
-        // Second part: a_conj * d + c * b
-        let a_conj = qconj(a);
-        let a_conj_d = qmul(&a_conj, d);
-        let cb = qmul(c, b);
-        let mut second = [0; 4];
-        for k in 0..4 { second[k] = a_conj_d[k].wrapping_add(cb[k]); }
-
-        let mut res = [0; 8];
-        res[0..4].copy_from_slice(&first);
-        res[4..8].copy_from_slice(&second);
-        Octonion::new(res)
-    }
-}
+// The canonical algebra::Octonion over Z_2^16. It is Copy, so every copy of
+// a secret is wiped by hand: FlutterCipher zeroizes its state and key on
+// drop, and each function wipes the locals it copied them into.
+pub type Octonion = crate::algebra::Octonion<Scalar>;
+// Key and state pass through every ring op: they must stay branch-free
+const _: () = crate::algebra::ct::assert_constant_time::<Octonion>();
 
 // ----------------------------------------------------------------------------
 // The Flutter Cipher (Vacuum Iterator)
@@ -143,20 +43,42 @@ pub struct FlutterCipher {
     kappa: Scalar, 
 }
 
+// Both the running state and the key octonion are secret: knowing the state
+// at any point yields the entire remaining keystream.
+impl fmt::Debug for FlutterCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FlutterCipher")
+            .field("state", &Redacted(&redact::u16_bytes(&self.state.coeffs)))
+            .field("key_c", &Redacted(&redact::u16_bytes(&self.key_c.coeffs)))
+            .field("kappa", &self.kappa)
+            .finish()
+    }
+}
+
+// Secure Zeroization: wipe the keystream state and key when the cipher
+// goes out of scope.
+impl Drop for FlutterCipher {
+    fn drop(&mut self) {
+        self.state.coeffs.zeroize();
+        self.key_c.coeffs.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for FlutterCipher {}
+const _: () = crate::redact::assert_zeroize_on_drop::<FlutterCipher>();
+
 impl FlutterCipher {
     /// Initialize with a 128-bit key (represented as 8 u16s)
     /// and a 128-bit nonce (IV).
-    pub fn new(key: [u16; 8], nonce: [u16; 8]) -> Self {
-        let k = Octonion::new(key);
-        let n = Octonion::new(nonce);
-        
+    pub fn new(mut key: [u16; 8], nonce: [u16; 8]) -> Self {
         let mut cipher = FlutterCipher {
-            state: n,
-            key_c: k,
+            state: Octonion::new(nonce),
+            key_c: Octonion::new(key),
             // A heuristic constant derived from the "Golden Ratio" of the octonions 
             // to ensure maximum mixing (related to 1/8 phase transition).
             kappa: 0x1910, // ~1.910 scaled (Beta from paper)
         };
+        key.zeroize();
 
         // "Warm up" the vacuum - Iterate 16 times to mix Key and IV
         // This corresponds to the "Inflationary Search Phase".
@@ -170,42 +92,30 @@ impl FlutterCipher {
     /// The "Octonionic Iterator" Step
     /// Z_{n+1} = Z_n^2 + C + Associator_Feedback
     fn clock(&mut self) {
-        let z = &self.state;
-        let c = &self.key_c;
+        let mut z = self.state;
+        let mut c = self.key_c;
 
         // 1. Primary Chaotic Map: Z^2 + C
-        let z_sq = z * z;
-        let map_res = z_sq + c; // Note: Using reference arithmetic
-
         // 2. Associator Injection (The "Hard" Part)
         // APH Physics: [Z, C, Z_conjugate]
         // This term vanishes if Z and C associate. We force non-associativity
         // by mixing in a rotated version of the state.
         
         // Simple rotation for efficiency: Swap halves
-        let z_rot_coeffs = [z.c[4], z.c[5], z.c[6], z.c[7], z.c[0], z.c[1], z.c[2], z.c[3]];
-        let z_rot = Octonion::new(z_rot_coeffs);
+        let mut zc = z.coeffs;
+        let mut z_rot = Octonion::new([zc[4], zc[5], zc[6], zc[7], zc[0], zc[1], zc[2], zc[3]]);
 
         // Calculate Associator: (Z * C) * Z_rot - Z * (C * Z_rot)
         // This is the "Topological Impedance" term.
-        let term1 = &(z * c) * &z_rot;
-        let term2 = z * &(c * &z_rot);
-        
-        // Associator Hazard
-        let mut hazard_c = [0; 8];
-        for i in 0..8 {
-            hazard_c[i] = term1.c[i].wrapping_sub(term2.c[i]);
-        }
+        let (mut map_res, mut hazard) = quadratic_map(z, c, z_rot);
 
         // Feedback: Apply stiffness
         // State += Map + Kappa * Hazard
-        let mut final_c = [0; 8];
-        for i in 0..8 {
-            let stiff = hazard_c[i].wrapping_mul(self.kappa);
-            final_c[i] = map_res.c[i].wrapping_add(stiff);
+        self.state = map_res + hazard.scale(self.kappa);
+        for o in [&mut z, &mut c, &mut z_rot, &mut map_res, &mut hazard] {
+            o.coeffs.zeroize();
         }
-
-        self.state = Octonion::new(final_c);
+        zc.zeroize();
     }
 
     /// Generate the next byte of the keystream
@@ -213,8 +123,9 @@ impl FlutterCipher {
         self.clock();
         // Extract entropy from the "Vacuum Fluctuations"
         // Mix the coefficients to get a single byte
-        let s = self.state.c;
+        let mut s = self.state.coeffs;
         let b = s[0] ^ s[1] ^ s[2] ^ s[3] ^ s[4] ^ s[5] ^ s[6] ^ s[7];
+        s.zeroize();
         (b & 0xFF) as u8
     }
 
@@ -226,3 +137,63 @@ impl FlutterCipher {
     }
 }
 
+// ----------------------------------------------------------------------------
+// Sealing: Flutter keystream + GSH-256 tag (encrypt-then-MAC)
+// ----------------------------------------------------------------------------
+// The AEAD timelock.rs builds by hand, over a 32-byte key: a stream key and
+// MAC key are derived per nonce, the body is encrypted, and the tag covers
+// the associated data and the ciphertext. Never seal twice under one
+// (key, nonce).
+
+const SEAL_DOMAIN: &[u8] = b"OLC-FLUTTER-SEAL-v1";
+pub const TAG_BYTES: usize = 32;
+
+impl FlutterCipher {
+    /// Encrypts `plaintext`; returns ciphertext || tag
+    pub fn seal(key: &[u8; 32], nonce: [u16; 8], aad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+        let (mut stream_key, mut mac_key) = seal_keys(key, nonce);
+        let mut out = plaintext.to_vec();
+        FlutterCipher::new(stream_key, nonce).process(&mut out);
+        stream_key.zeroize();
+        let tag = seal_tag(&mac_key, aad, &out);
+        mac_key.zeroize();
+        out.extend_from_slice(&tag);
+        out
+    }
+
+    /// The plaintext, or None if the key, nonce, aad or bytes are not the
+    /// ones sealed
+    pub fn open(key: &[u8; 32], nonce: [u16; 8], aad: &[u8], sealed: &[u8]) -> Option<Vec<u8>> {
+        let body_len = sealed.len().checked_sub(TAG_BYTES)?;
+        let (body, tag) = sealed.split_at(body_len);
+        let (mut stream_key, mut mac_key) = seal_keys(key, nonce);
+        let expected = seal_tag(&mac_key, aad, body);
+        mac_key.zeroize();
+        // Compare without early exit
+        if expected.iter().zip(tag).fold(0u8, |acc, (a, b)| acc | (a ^ b)) != 0 {
+            stream_key.zeroize();
+            return None;
+        }
+        let mut plain = body.to_vec();
+        FlutterCipher::new(stream_key, nonce).process(&mut plain);
+        stream_key.zeroize();
+        Some(plain)
+    }
+}
+
+fn seal_keys(key: &[u8; 32], nonce: [u16; 8]) -> ([u16; 8], [u8; 32]) {
+    let nonce: Vec<u8> = nonce.iter().flat_map(|n| n.to_le_bytes()).collect();
+    let mut okm = GSH256::hash_slices(&[SEAL_DOMAIN, key, &nonce]);
+    let stream_key = std::array::from_fn(|i| u16::from_le_bytes([okm[2 * i], okm[2 * i + 1]]));
+    let mut mac_key = [0u8; 32];
+    mac_key.copy_from_slice(&okm[16..48]);
+    okm.zeroize();
+    (stream_key, mac_key)
+}
+
+fn seal_tag(mac_key: &[u8; 32], aad: &[u8], body: &[u8]) -> [u8; TAG_BYTES] {
+    let full = GSH256::hash_slices(&[mac_key, &(aad.len() as u64).to_le_bytes(), aad, &(body.len() as u64).to_le_bytes(), body]);
+    let mut tag = [0u8; TAG_BYTES];
+    tag.copy_from_slice(&full[..TAG_BYTES]);
+    tag
+}
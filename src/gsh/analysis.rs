@@ -0,0 +1,237 @@
+// src/gsh/analysis.rs
+// Statistical analysis harness for GSH sponges.
+//
+// Strict Avalanche Criterion (SAC): flipping any single input bit should flip
+// each output bit with probability 1/2. For every sample input we flip each
+// input bit in turn and record which output bits changed, alongside a byte
+// histogram of all produced digests. The report carries raw counts plus
+// chi-square statistics so CI can gate on fixed thresholds.
+
+use super::{Compression, GshParams, GshSponge, GSH256};
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use std::marker::PhantomData;
+
+#[derive(Clone, Debug)]
+pub struct AnalysisConfig {
+    pub samples: usize,   // Random base inputs
+    pub input_len: usize, // Bytes per input (every bit gets flipped)
+    pub seed: u64,
+}
+
+impl Default for AnalysisConfig {
+    fn default() -> Self {
+        AnalysisConfig { samples: 64, input_len: 32, seed: 0x6A5 }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AnalysisReport {
+    pub trials: u64,                // samples * input bits
+    pub output_bits: usize,
+    pub flip_probability: Vec<f64>, // Per output bit, ideal 0.5
+    pub mean_flip: f64,
+    pub max_bias: f64,              // max |p - 0.5| over output bits
+    pub flip_chi_square: f64,       // Sum over output bits, dof = output_bits
+    pub byte_histogram: [u64; 256], // Over every digest byte produced
+    pub byte_chi_square: f64,       // Against uniform, dof = 255
+}
+
+// Regression gates. Defaults are loose enough for the default sample count.
+#[derive(Clone, Debug)]
+pub struct Thresholds {
+    pub max_bias: f64,
+    pub max_flip_chi_square: f64,
+    pub max_byte_chi_square: f64,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Thresholds { max_bias: 0.1, max_flip_chi_square: 2.0, max_byte_chi_square: 2.0 }
+    }
+}
+
+impl AnalysisReport {
+    // Chi-square limits are expressed per degree of freedom so the same
+    // thresholds work for GSH-128 and GSH-256.
+    pub fn violations(&self, t: &Thresholds) -> Vec<String> {
+        let mut out = Vec::new();
+        if self.max_bias > t.max_bias {
+            out.push(format!("max bias {:.4} > {:.4}", self.max_bias, t.max_bias));
+        }
+        let flip = self.flip_chi_square / self.output_bits as f64;
+        if flip > t.max_flip_chi_square {
+            out.push(format!("flip chi2/dof {:.3} > {:.3}", flip, t.max_flip_chi_square));
+        }
+        let bytes = self.byte_chi_square / 255.0;
+        if bytes > t.max_byte_chi_square {
+            out.push(format!("byte chi2/dof {:.3} > {:.3}", bytes, t.max_byte_chi_square));
+        }
+        out
+    }
+}
+
+// Input bytes -> hex digest
+type HashFn = Box<dyn Fn(&[u8]) -> String>;
+
+pub struct Analyzer<H: GshSponge = GSH256> {
+    config: AnalysisConfig,
+    hasher: HashFn,
+    _sponge: PhantomData<H>,
+}
+
+impl<H: GshSponge + 'static> Analyzer<H> {
+    pub fn new(config: AnalysisConfig) -> Self {
+        Analyzer { config, hasher: Box::new(H::hash_bytes), _sponge: PhantomData }
+    }
+
+    pub fn run(&self) -> AnalysisReport {
+        let mut rng = StdRng::seed_from_u64(self.config.seed);
+        let input_bits = self.config.input_len * 8;
+
+        let mut flips: Vec<u64> = Vec::new();
+        let mut histogram = [0u64; 256];
+        let mut trials = 0u64;
+
+        for _ in 0..self.config.samples {
+            let mut input = vec![0u8; self.config.input_len];
+            rng.fill(&mut input[..]);
+            let base = self.digest_of(&input);
+            if flips.is_empty() {
+                flips = vec![0; base.len() * 8];
+            }
+            record(&mut histogram, &base);
+
+            for bit in 0..input_bits {
+                input[bit / 8] ^= 1 << (bit % 8);
+                let flipped = self.digest_of(&input);
+                input[bit / 8] ^= 1 << (bit % 8);
+                record(&mut histogram, &flipped);
+
+                for (i, (a, b)) in base.iter().zip(&flipped).enumerate() {
+                    let diff = a ^ b;
+                    for j in 0..8 {
+                        if diff & (0x80 >> j) != 0 {
+                            flips[i * 8 + j] += 1;
+                        }
+                    }
+                }
+                trials += 1;
+            }
+        }
+
+        // SAC: each output bit is Binomial(trials, 1/2)
+        let n = trials.max(1) as f64;
+        let flip_probability: Vec<f64> = flips.iter().map(|&f| f as f64 / n).collect();
+        let flip_chi_square = flips.iter()
+            .map(|&f| (f as f64 - n / 2.0).powi(2) / (n / 4.0))
+            .sum();
+        let mean_flip = flip_probability.iter().sum::<f64>() / flip_probability.len().max(1) as f64;
+        let max_bias = flip_probability.iter().fold(0.0f64, |m, p| m.max((p - 0.5).abs()));
+
+        let total: u64 = histogram.iter().sum();
+        let expected = total as f64 / 256.0;
+        let byte_chi_square = histogram.iter()
+            .map(|&c| (c as f64 - expected).powi(2) / expected.max(f64::MIN_POSITIVE))
+            .sum();
+
+        AnalysisReport {
+            trials,
+            output_bits: flips.len(),
+            flip_probability,
+            mean_flip,
+            max_bias,
+            flip_chi_square,
+            byte_histogram: histogram,
+            byte_chi_square,
+        }
+    }
+
+    fn digest_of(&self, input: &[u8]) -> Vec<u8> {
+        let hex = (self.hasher)(input);
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+}
+
+impl Analyzer<GSH256> {
+    // GSH-256 under an explicit parameter set (sponge profile + compression)
+    pub fn with_params(config: AnalysisConfig, params: GshParams) -> Self {
+        Analyzer {
+            config,
+            hasher: Box::new(move |input| GSH256::hash_bytes_with_params(input, params)),
+            _sponge: PhantomData,
+        }
+    }
+}
+
+// Convenience entry point for the default sponge.
+pub fn analyze(config: AnalysisConfig) -> AnalysisReport {
+    Analyzer::<GSH256>::new(config).run()
+}
+
+// Same samples through both GSH-256 compression modes, for choosing the default.
+pub fn compare_compression(config: AnalysisConfig) -> Vec<(Compression, AnalysisReport)> {
+    [Compression::Associator, Compression::KeyedPermutation]
+        .into_iter()
+        .map(|compression| {
+            let params = GshParams { compression, ..GshParams::default() };
+            (compression, Analyzer::with_params(config.clone(), params).run())
+        })
+        .collect()
+}
+
+fn record(histogram: &mut [u64; 256], digest: &[u8]) {
+    for &b in digest {
+        histogram[b as usize] += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gsh::GSH128;
+
+    #[test]
+    fn report_shape_and_gates() {
+        let config = AnalysisConfig { samples: 4, input_len: 8, seed: 7 };
+        let report = analyze(config.clone());
+        assert_eq!(report.trials, 4 * 64);
+        assert_eq!(report.output_bits, 512);
+        assert_eq!(report.byte_histogram.iter().sum::<u64>(), (4 + 4 * 64) * 64);
+        assert_eq!(Analyzer::<GSH128>::new(config).run().output_bits, 128);
+
+        // Both profiles against the default gates, at enough samples for them
+        let gated = AnalysisConfig { samples: 32, input_len: 32, seed: 0x6A5 };
+        for (name, report) in [("GSH-256", analyze(gated.clone())), ("GSH-128", Analyzer::<GSH128>::new(gated).run())] {
+            let violations = report.violations(&Thresholds::default());
+            assert!(violations.is_empty(), "{}: {:?}", name, violations);
+        }
+
+        // A perfectly biased report must trip every gate
+        let broken = AnalysisReport {
+            flip_probability: vec![0.0; 512],
+            mean_flip: 0.0,
+            max_bias: 0.5,
+            flip_chi_square: 512.0 * 256.0,
+            byte_chi_square: 1e6,
+            ..report
+        };
+        assert_eq!(broken.violations(&Thresholds::default()).len(), 3);
+    }
+
+    #[test]
+    fn both_compression_modes_pass_the_gates() {
+        let config = AnalysisConfig { samples: 4, input_len: 8, seed: 7 };
+        let gates = Thresholds { max_bias: 0.15, ..Thresholds::default() };
+        for (mode, report) in compare_compression(config) {
+            assert!(report.violations(&gates).is_empty(), "{:?}: {:?}", mode, report.violations(&gates));
+        }
+
+        let keyed = GshParams { compression: Compression::KeyedPermutation, ..GshParams::default() };
+        assert_ne!(GSH256::hash_bytes_with_params(b"abc", keyed), GSH256::hash_bytes(b"abc"));
+        assert_eq!(GSH256::hash_bytes_with_params(b"abc", GshParams::default()), GSH256::hash_bytes(b"abc"));
+    }
+}
@@ -0,0 +1,179 @@
+// src/gsh/pwhash.rs
+// Memory-hard password hashing on top of GSH ("GSH-PW").
+//
+// Argon2-style construction with the sedenion associator as the mixing
+// function:
+//   1. H0 = GSH-256(domain || params || password || salt)
+//   2. Fill `memory_kib` of sedenion blocks (128 bytes each). Block i mixes
+//      the previous block with a reference block chosen by the previous
+//      block's contents (data-dependent addressing).
+//   3. Repeat for `iterations` passes, XOR-ing new values over old ones.
+//   4. Output = first 32 bytes of GSH-256(last block || H0).
+// An attacker trading memory for time has to recompute reference chains,
+// so every guess costs the full memory footprint.
+
+use super::GSH256;
+use crate::sedenion::{associator, Octonion, Sedenion};
+use zeroize::Zeroize;
+
+const DOMAIN: &[u8] = b"GSH-PWHASH-v1";
+pub const BLOCK_BYTES: usize = 128; // One sedenion (16 x u64)
+pub const MIN_SALT_LEN: usize = 8;
+pub const MIN_MEMORY_KIB: u32 = 8;
+// Upper bounds, so parameters read from an untrusted header cannot demand
+// an arbitrary allocation or run time
+pub const MAX_MEMORY_KIB: u32 = 1 << 20; // 1 GiB
+pub const MAX_ITERATIONS: u32 = 64;
+pub const OUTPUT_LEN: usize = 32;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PwHashParams {
+    pub memory_kib: u32, // Memory cost
+    pub iterations: u32, // Time cost (passes over memory)
+}
+
+impl PwHashParams {
+    pub fn new(memory_kib: u32, iterations: u32) -> Result<Self, PwHashError> {
+        let params = PwHashParams { memory_kib, iterations };
+        params.check()?;
+        Ok(params)
+    }
+
+    // The fields are public, so pwhash checks again
+    fn check(&self) -> Result<(), PwHashError> {
+        if self.memory_kib < MIN_MEMORY_KIB {
+            return Err(PwHashError::MemoryTooSmall { min_kib: MIN_MEMORY_KIB });
+        }
+        if self.memory_kib > MAX_MEMORY_KIB {
+            return Err(PwHashError::MemoryTooLarge { max_kib: MAX_MEMORY_KIB });
+        }
+        if self.iterations == 0 {
+            return Err(PwHashError::NoIterations);
+        }
+        if self.iterations > MAX_ITERATIONS {
+            return Err(PwHashError::TooManyIterations { max: MAX_ITERATIONS });
+        }
+        Ok(())
+    }
+
+    pub fn blocks(&self) -> usize {
+        self.memory_kib as usize * 1024 / BLOCK_BYTES
+    }
+}
+
+// Wallet default: 16 MiB, 3 passes
+impl Default for PwHashParams {
+    fn default() -> Self {
+        PwHashParams { memory_kib: 16 * 1024, iterations: 3 }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PwHashError {
+    MemoryTooSmall { min_kib: u32 },
+    MemoryTooLarge { max_kib: u32 },
+    NoIterations,
+    TooManyIterations { max: u32 },
+    SaltTooShort { min_len: usize },
+}
+
+pub fn pwhash(password: &[u8], salt: &[u8], params: &PwHashParams) -> Result<[u8; OUTPUT_LEN], PwHashError> {
+    params.check()?;
+    if salt.len() < MIN_SALT_LEN {
+        return Err(PwHashError::SaltTooShort { min_len: MIN_SALT_LEN });
+    }
+
+    // 1. Initial hash binds every input and parameter
+    let mut pre = Vec::with_capacity(DOMAIN.len() + 24 + password.len() + salt.len());
+    pre.extend_from_slice(DOMAIN);
+    pre.extend_from_slice(&params.memory_kib.to_le_bytes());
+    pre.extend_from_slice(&params.iterations.to_le_bytes());
+    pre.extend_from_slice(&(password.len() as u64).to_le_bytes());
+    pre.extend_from_slice(password);
+    pre.extend_from_slice(&(salt.len() as u64).to_le_bytes());
+    pre.extend_from_slice(salt);
+    let h0 = GSH256::hash_raw(&pre);
+    pre.zeroize();
+
+    let mut lanes = [0u64; 8];
+    for (i, lane) in lanes.iter_mut().enumerate() {
+        *lane = u64::from_le_bytes(h0[i * 8..i * 8 + 8].try_into().unwrap());
+    }
+    let seed = Sedenion::new(Octonion::new(lanes), Octonion::new(lanes).rotate());
+
+    // 2-3. Fill and re-mix memory
+    let n = params.blocks();
+    let mut memory = vec![Sedenion::zero(); n];
+    memory[0] = mix(seed, seed, 0, 0);
+    for pass in 0..params.iterations {
+        let start = if pass == 0 { 1 } else { 0 };
+        for i in start..n {
+            let prev = memory[if i == 0 { n - 1 } else { i - 1 }];
+            // Data-dependent reference; the first pass may only look backwards
+            let span = if pass == 0 { i } else { n };
+            let j = (prev.low.coeffs[0] % span as u64) as usize;
+            let fresh = mix(prev, memory[j], pass, i);
+            memory[i] = if pass == 0 { fresh } else { memory[i] ^ fresh };
+        }
+    }
+
+    // 4. Finalize
+    let mut tail = sedenion_bytes(&memory[n - 1]);
+    tail.extend_from_slice(&h0);
+    let digest = GSH256::hash_raw(&tail);
+    tail.zeroize();
+    for block in memory.iter_mut() {
+        block.low.coeffs.zeroize();
+        block.high.coeffs.zeroize();
+    }
+
+    let mut out = [0u8; OUTPUT_LEN];
+    out.copy_from_slice(&digest[..OUTPUT_LEN]);
+    Ok(out)
+}
+
+// G(x, y) = x ^ [x, y', swap(x)] ^ y'  with  y' = y ^ tag(pass, index)
+// Same associator twist as the GSH absorb step; the tag keeps blocks
+// distinct even when x == y.
+fn mix(x: Sedenion, y: Sedenion, pass: u32, index: usize) -> Sedenion {
+    let mut tag = [0u64; 8];
+    tag[0] = index as u64;
+    tag[1] = pass as u64;
+    let y = y ^ Sedenion::new(Octonion::new(tag), Octonion::zero());
+    let k = Sedenion::new(x.high, x.low);
+    (x ^ associator(x, y, k)) ^ y
+}
+
+fn sedenion_bytes(s: &Sedenion) -> Vec<u8> {
+    s.low.coeffs.iter().chain(s.high.coeffs.iter()).flat_map(|c| c.to_le_bytes()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FAST: PwHashParams = PwHashParams { memory_kib: 16, iterations: 2 };
+
+    #[test]
+    fn deterministic_and_input_sensitive() {
+        let salt = b"olc-salt-0001";
+        let a = pwhash(b"hunter2", salt, &FAST).unwrap();
+        assert_eq!(a, pwhash(b"hunter2", salt, &FAST).unwrap());
+        assert_ne!(a, pwhash(b"hunter3", salt, &FAST).unwrap());
+        assert_ne!(a, pwhash(b"hunter2", b"olc-salt-0002", &FAST).unwrap());
+        assert_ne!(a, pwhash(b"hunter2", salt, &PwHashParams::new(16, 3).unwrap()).unwrap());
+        assert_ne!(a, pwhash(b"hunter2", salt, &PwHashParams::new(32, 2).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn rejects_weak_parameters() {
+        assert_eq!(pwhash(b"pw", b"short", &FAST), Err(PwHashError::SaltTooShort { min_len: MIN_SALT_LEN }));
+        assert_eq!(PwHashParams::new(16, 0), Err(PwHashError::NoIterations));
+        assert_eq!(PwHashParams::new(4, 1), Err(PwHashError::MemoryTooSmall { min_kib: MIN_MEMORY_KIB }));
+        assert_eq!(PwHashParams::new(u32::MAX, 1), Err(PwHashError::MemoryTooLarge { max_kib: MAX_MEMORY_KIB }));
+        assert_eq!(PwHashParams::new(16, MAX_ITERATIONS + 1), Err(PwHashError::TooManyIterations { max: MAX_ITERATIONS }));
+        // Fields set directly are checked by pwhash itself
+        let huge = PwHashParams { memory_kib: MAX_MEMORY_KIB + 1, iterations: 1 };
+        assert_eq!(pwhash(b"pw", b"long-enough", &huge), Err(PwHashError::MemoryTooLarge { max_kib: MAX_MEMORY_KIB }));
+    }
+}
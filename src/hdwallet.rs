@@ -1,5 +1,18 @@
 use sha2::{Sha256, Digest}; // Standard hash for message digest
-
+use crate::algebra::octonion::associator;
+use crate::redact::{self, Redacted};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+use std::fmt;
+
+pub mod builder;
+pub mod mnemonic;
+pub mod path;
+pub mod psbt;
+pub mod shamir;
+pub mod store;
+pub mod watch;
+pub mod xmss;
+pub mod xpub;
 
 // --- IOT OPTIMIZATION: u16 FIELD ---
 pub type Scalar = u16;
@@ -14,62 +27,19 @@ pub struct BiOctonion {
     pub right: Octonion,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Default)]
-pub struct Octonion {
-    pub c: [Scalar; 8],
-}
+// The canonical algebra::Octonion over the Z_2^16 ring.
+pub type Octonion = crate::algebra::Octonion<Scalar>;
 
 impl Octonion {
-    pub fn new(coeffs: [Scalar; 8]) -> Self {
-        Octonion { c: coeffs }
-    }
-
-    pub fn zero() -> Self {
-        Octonion { c: [0; 8] }
-    }
-
-    // Wrapping addition for Z_2^16 ring
-    pub fn add(&self, other: &Self) -> Self {
-        let mut res = [0; 8];
-        for i in 0..8 { res[i] = self.c[i].wrapping_add(other.c[i]); }
-        Octonion::new(res)
-    }
-    
     // Mixing XOR (Cheap non-linearity for coupling)
     pub fn xor(&self, other: &Self) -> Self {
-        let mut res = [0; 8];
-        for i in 0..8 { res[i] = self.c[i] ^ other.c[i]; }
-        Octonion::new(res)
+        Octonion::new(std::array::from_fn(|i| self.coeffs[i] ^ other.coeffs[i]))
     }
 
-    // Octonion Multiplication (Non-Associative)
-    // Implementation of Cayley-Dickson over u16
-    pub fn mul(&self, other: &Self) -> Self {
-        // Simplified mixing for prototype stability
-        // In full production this is the explicit 64-mul expansion
-        let mut res = [0; 8];
-        
-        // Real part
-        let mut real = self.c[0].wrapping_mul(other.c[0]);
-        for i in 1..8 {
-            real = real.wrapping_sub(self.c[i].wrapping_mul(other.c[i]));
-        }
-        res[0] = real;
-
-        // Imaginary parts (Cross products)
-        for i in 1..8 {
-            res[i] = self.c[0].wrapping_mul(other.c[i])
-                .wrapping_add(self.c[i].wrapping_mul(other.c[0]))
-                .wrapping_add(self.c[(i+1)%8].wrapping_mul(other.c[(i+2)%8]));
-        }
-        
-        Octonion::new(res)
-    }
-    
     // Rotation for Associator Injection
     pub fn rotate(&self) -> Self {
-        let mut new_c = [0; 8];
-        for i in 0..8 { new_c[i] = self.c[(i + 1) % 8]; }
+        let mut new_c = self.coeffs;
+        new_c.rotate_left(1);
         Octonion::new(new_c)
     }
 }
@@ -106,14 +76,14 @@ impl FlutterEngine {
     /// Z_L' = Z_L^2 + C + kappa * [Z_L, Z_R, Z_rot]
     /// Z_R' = Z_R^2 + C + kappa * [Z_R, Z_L, Z_rot]
     pub fn clock(&self, state: &BiOctonion) -> BiOctonion {
-        let z_l = &state.left;
-        let z_r = &state.right;
-        let c = &self.params.c;
+        let z_l = state.left;
+        let z_r = state.right;
+        let c = self.params.c;
         let k = self.params.kappa;
 
         // 1. Primary Chaos (Independent)
-        let l_sq = z_l.mul(z_l);
-        let r_sq = z_r.mul(z_r);
+        let l_sq = z_l * z_l;
+        let r_sq = z_r * z_r;
 
         // 2. Associator Hazard (Coupling)
         // We use the *other* octonion as the "perturbation" in the associator
@@ -121,25 +91,15 @@ impl FlutterEngine {
         let z_rot = z_l.rotate().xor(&z_r.rotate());
         
         // Hazard L: [Z_L, Z_R, Z_rot]
-        let h_l_term1 = z_l.mul(z_r).mul(&z_rot);
-        let h_l_term2 = z_l.mul(&z_r.mul(&z_rot));
-        let hazard_l = h_l_term1.add(&h_l_term2.mul(&Octonion::new([65535; 8]))); // Sub approx
+        let hazard_l = associator(z_l, z_r, z_rot);
         
         // Hazard R: [Z_R, Z_L, Z_rot]
-        let h_r_term1 = z_r.mul(z_l).mul(&z_rot);
-        let h_r_term2 = z_r.mul(&z_l.mul(&z_rot));
-        let hazard_r = h_r_term1.add(&h_r_term2.mul(&Octonion::new([65535; 8])));
+        let hazard_r = associator(z_r, z_l, z_rot);
 
         // 3. Update with Stiffness
         // scale hazard by kappa
-        let apply_k = |h: Octonion| -> Octonion {
-            let mut res = [0; 8];
-            for i in 0..8 { res[i] = h.c[i].wrapping_mul(k); }
-            Octonion::new(res)
-        };
-
-        let new_l = l_sq.add(c).add(&apply_k(hazard_l));
-        let new_r = r_sq.add(c).add(&apply_k(hazard_r));
+        let new_l = l_sq + c + hazard_l.scale(k);
+        let new_r = r_sq + c + hazard_r.scale(k);
 
         BiOctonion { left: new_l, right: new_r }
     }
@@ -162,18 +122,41 @@ pub struct MasterSeed {
     pub seed_bytes: [u8; 32],
 }
 
+impl Zeroize for MasterSeed {
+    fn zeroize(&mut self) {
+        self.seed_bytes.zeroize();
+    }
+}
+
+impl Drop for MasterSeed {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for MasterSeed {}
+const _: () = crate::redact::assert_zeroize_on_drop::<MasterSeed>();
+
+impl fmt::Debug for MasterSeed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MasterSeed")
+            .field("seed_bytes", &Redacted(&self.seed_bytes))
+            .finish()
+    }
+}
+
 impl MasterSeed {
     /// Derive the KeyPair for index `i`
     /// We uses the Flutter engine itself as the KDF (Key Derivation Function).
     pub fn derive_keypair(&self, engine: &FlutterEngine, index: u32) -> FlutterKeyPair {
         // 1. Mix Master Seed + Index into Initial State
         let mut mixed_seed = [0u16; 16]; // 256 bits
-        for i in 0..16 {
+        for (i, word) in mixed_seed.iter_mut().enumerate() {
             let b1 = self.seed_bytes[i];
             let b2 = self.seed_bytes[16+i];
             // Simple mixing with index
-            mixed_seed[i] = (b1 as u16) << 8 | (b2 as u16);
-            mixed_seed[i] = mixed_seed[i].wrapping_add(index as u16);
+            *word = (b1 as u16) << 8 | (b2 as u16);
+            *word = word.wrapping_add(index as u16);
         }
         
         let z0 = BiOctonion {
@@ -199,6 +182,18 @@ pub struct FlutterKeyPair {
     pub public_key: BiOctonion,   // Z_256
 }
 
+impl fmt::Debug for FlutterKeyPair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut seed = redact::u16_bytes(&self.private_seed.left.coeffs);
+        seed.extend(redact::u16_bytes(&self.private_seed.right.coeffs));
+        f.debug_struct("FlutterKeyPair")
+            .field("index", &self.index)
+            .field("private_seed", &Redacted(&seed))
+            .field("public_key", &self.public_key)
+            .finish()
+    }
+}
+
 // --- SIGNING (Winternitz-style / "Burst" Method) ---
 
 pub struct FlutterSignature {
@@ -209,6 +204,8 @@ pub struct FlutterSignature {
 }
 
 impl FlutterKeyPair {
+    // One message per key pair: a second signature reveals chain states past
+    // the first one's. WalletStore::reserve_ots hands each key out once.
     pub fn sign(&self, engine: &FlutterEngine, message: &[u8]) -> FlutterSignature {
         // 1. Hash message to get 32 bytes of "instructions"
         let mut hasher = Sha256::new();
@@ -227,7 +224,7 @@ impl FlutterKeyPair {
         for (i, &byte_val) in digest.iter().enumerate() {
             // Permute seed for this chain index
             let mut chain_seed = self.private_seed;
-            chain_seed.left.c[0] = chain_seed.left.c[0].wrapping_add(i as u16);
+            chain_seed.left.coeffs[0] = chain_seed.left.coeffs[0].wrapping_add(i as u16);
             
             // "Burst": Run the iterator `byte_val` times
             let z_m = engine.iterate(&chain_seed, byte_val as usize);
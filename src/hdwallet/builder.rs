@@ -0,0 +1,310 @@
+// src/hdwallet/builder.rs
+// TRANSACTION BUILDER: from witnessed coins to signed Horizon spends.
+//
+// The one builder for every wallet. Coins come from a CoinSource (a
+// single-key Wallet, a WatchOnlyWallet, a plain list of witnessed coins
+// from a node's UTXO scan) and signatures from a Signer, so the keys can
+// live in a WalletStore, on another device, or in a plain SecretKey.
+//
+// Horizon's rules: one coin per transaction, one output per coin,
+// SPEND_SIZE_BYTES of fee each.
+// - Automatic selection nets the amount exactly from the offered coins;
+//   there is no change.
+// - `with_inputs` spends exactly the named coins: those that net the amount
+//   pay the recipient, the rest go whole (less fee) to the change address.
+// - The source decides what may be spent: a Wallet offers no frozen coin to
+//   selection and refuses one named as an input.
+//
+// `build` stops at UnsignedTransactions, which is what a watch-only wallet
+// needs; `sign` then asks the Signer for each owner's key and signature.
+// The Signer signs the spend message under TX_CONTEXT itself, so a signer
+// cannot be talked into signing for another context through this path.
+
+use super::store::WalletStore;
+use super::watch::WatchedCoin;
+use crate::address::Address;
+use crate::horizon::{Transaction, UnsignedTransaction, SPEND_SIZE_BYTES, TX_CONTEXT};
+use crate::jordan_sig::{JordanSchnorr, PublicKey, SecretKey, SignError, Signature};
+use crate::wallet::{exact_subset, CoinId, WalletError};
+use rand::Rng;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SignerError {
+    // The signer holds no key for this address
+    UnknownOwner(Address),
+    Signing(SignError),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BuildError {
+    Wallet(WalletError),
+    // Leftover inputs but nowhere to send them
+    NoChangeAddress,
+    Signer(SignerError),
+}
+
+/// Something that holds Jordan keys and signs Horizon spends with them
+pub trait Signer {
+    /// The key behind `owner`, if this signer holds it
+    fn public_key(&self, owner: &Address) -> Option<PublicKey>;
+
+    /// Sign `message` under TX_CONTEXT with the key behind `owner`
+    fn sign<R: Rng + ?Sized>(&mut self, owner: &Address, message: &[u8], rng: &mut R) -> Result<Signature, SignerError>;
+}
+
+impl Signer for SecretKey {
+    fn public_key(&self, owner: &Address) -> Option<PublicKey> {
+        (self.pub_key.address() == *owner).then_some(self.pub_key)
+    }
+
+    fn sign<R: Rng + ?Sized>(&mut self, owner: &Address, message: &[u8], rng: &mut R) -> Result<Signature, SignerError> {
+        if self.pub_key.address() != *owner {
+            return Err(SignerError::UnknownOwner(*owner));
+        }
+        JordanSchnorr::sign(self, TX_CONTEXT, message, rng).map_err(SignerError::Signing)
+    }
+}
+
+// Signs for every key the store has derived; the key is re-derived from its
+// seed per signature and dropped (zeroized) straight after.
+impl Signer for WalletStore {
+    fn public_key(&self, owner: &Address) -> Option<PublicKey> {
+        self.jordan_key_for(owner).map(|key| key.pub_key)
+    }
+
+    fn sign<R: Rng + ?Sized>(&mut self, owner: &Address, message: &[u8], rng: &mut R) -> Result<Signature, SignerError> {
+        let mut key = self.jordan_key_for(owner).ok_or(SignerError::UnknownOwner(*owner))?;
+        Signer::sign(&mut key, owner, message, rng)
+    }
+}
+
+impl WalletStore {
+    fn jordan_key_for(&self, owner: &Address) -> Option<SecretKey> {
+        let (id, _) = self.keys().find(|(_, record)| record.address == *owner)?;
+        self.seed(&id.seed)?.derive_jordan_keypair(&id.path).ok()
+    }
+}
+
+// --- PLANS ---
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Plan {
+    pub payment: Vec<UnsignedTransaction>, // Spent to the recipient
+    pub change: Vec<UnsignedTransaction>,  // Spent back to the change address
+    pub amount: u64,                       // What the recipient receives
+    pub change_amount: u64,
+    pub fee_per_input: u64,
+}
+
+impl Plan {
+    pub fn fee(&self) -> u64 {
+        self.fee_per_input * (self.payment.len() + self.change.len()) as u64
+    }
+
+    pub fn unsigned(&self) -> impl Iterator<Item = &UnsignedTransaction> {
+        self.payment.iter().chain(&self.change)
+    }
+
+    /// Every spend of the plan, payment first, signed through `signer`
+    pub fn sign<S: Signer, R: Rng + ?Sized>(self, signer: &mut S, rng: &mut R) -> Result<Vec<Transaction>, BuildError> {
+        self.payment
+            .into_iter()
+            .chain(self.change)
+            .map(|spend| {
+                let owner = spend.input_utxo.owner;
+                let key = signer.public_key(&owner).ok_or(BuildError::Signer(SignerError::UnknownOwner(owner)))?;
+                let signature = signer.sign(&owner, &spend.message(), rng).map_err(BuildError::Signer)?;
+                Ok(spend.complete(key, signature))
+            })
+            .collect()
+    }
+}
+
+// --- COIN SOURCES ---
+/// Where a builder's coins come from
+pub trait CoinSource {
+    /// Every coin automatic selection may spend
+    fn spendable(&self) -> Vec<WatchedCoin>;
+
+    /// The coin `with_inputs` names, if it may be spent
+    fn input(&self, id: &CoinId) -> Result<WatchedCoin, WalletError>;
+
+    /// Where leftover inputs go unless the builder is told otherwise
+    fn change_address(&self) -> Option<Address> {
+        None
+    }
+}
+
+impl CoinSource for [WatchedCoin] {
+    fn spendable(&self) -> Vec<WatchedCoin> {
+        self.to_vec()
+    }
+
+    fn input(&self, id: &CoinId) -> Result<WatchedCoin, WalletError> {
+        self.iter().find(|c| c.utxo.id == *id).cloned().ok_or(WalletError::UnknownCoin(*id))
+    }
+}
+
+// --- BUILDER ---
+pub struct TxBuilder<'s, S: CoinSource + ?Sized> {
+    source: &'s S,
+    recipient: Address,
+    amount: u64,
+    fee_rate: u64, // Per byte, as in the mempool
+    inputs: Option<Vec<CoinId>>,
+    change_to: Option<Address>,
+}
+
+impl<'s, S: CoinSource + ?Sized> TxBuilder<'s, S> {
+    pub fn new(source: &'s S, recipient: Address, amount: u64, fee_rate: u64) -> Self {
+        TxBuilder { source, recipient, amount, fee_rate, inputs: None, change_to: None }
+    }
+
+    // Spend exactly these coins (coin control); bypasses automatic selection
+    pub fn with_inputs(mut self, inputs: Vec<CoinId>) -> Self {
+        self.inputs = Some(inputs);
+        self
+    }
+
+    // Defaults to the source's change address
+    pub fn change_to(mut self, address: Address) -> Self {
+        self.change_to = Some(address);
+        self
+    }
+
+    pub fn build(&self) -> Result<Plan, BuildError> {
+        let fee = self.fee_rate.saturating_mul(SPEND_SIZE_BYTES as u64);
+        let coins: Vec<WatchedCoin> = match &self.inputs {
+            Some(ids) => {
+                let mut coins: Vec<WatchedCoin> = Vec::with_capacity(ids.len());
+                for id in ids {
+                    let coin = self.source.input(id)?;
+                    if coins.iter().any(|c| c.utxo.id == *id) {
+                        return Err(WalletError::DuplicateInput(*id).into());
+                    }
+                    if coin.utxo.amount <= fee {
+                        return Err(WalletError::Uneconomic { coin: *id, amount: coin.utxo.amount, fee }.into());
+                    }
+                    coins.push(coin);
+                }
+                coins
+            }
+            None => {
+                let mut coins: Vec<WatchedCoin> = self.source.spendable().into_iter().filter(|c| c.utxo.amount > fee).collect();
+                coins.sort_by_key(|c| (std::cmp::Reverse(c.utxo.amount), c.utxo.id));
+                coins.dedup_by_key(|c| c.utxo.id);
+                coins
+            }
+        };
+
+        let nets: Vec<u64> = coins.iter().map(|c| c.utxo.amount - fee).collect();
+        let available: u64 = nets.iter().sum();
+        if available < self.amount {
+            return Err(WalletError::InsufficientFunds { available, required: self.amount }.into());
+        }
+        let chosen = exact_subset(&nets, self.amount).ok_or(WalletError::NoExactPayment { amount: self.amount })?;
+
+        let spend = |coin: WatchedCoin, to: Address| UnsignedTransaction {
+            new_amount: coin.utxo.amount - fee,
+            input_utxo: coin.utxo,
+            witness: coin.witness,
+            owner_key: None,
+            new_owner: to,
+        };
+        let (mut payment, mut change) = (Vec::new(), Vec::new());
+        for (i, coin) in coins.into_iter().enumerate() {
+            if chosen.contains(&i) {
+                payment.push(spend(coin, self.recipient));
+            } else if self.inputs.is_some() {
+                let to = self.change_to.or_else(|| self.source.change_address()).ok_or(BuildError::NoChangeAddress)?;
+                change.push(spend(coin, to));
+            }
+        }
+        let change_amount = change.iter().map(|s| s.new_amount).sum();
+        Ok(Plan { payment, change, amount: self.amount, change_amount, fee_per_input: fee })
+    }
+
+    /// Build, then sign every spend through `signer`
+    pub fn sign<G: Signer, R: Rng + ?Sized>(&self, signer: &mut G, rng: &mut R) -> Result<Vec<Transaction>, BuildError> {
+        self.build()?.sign(signer, rng)
+    }
+}
+
+impl From<WalletError> for BuildError {
+    fn from(e: WalletError) -> Self {
+        BuildError::Wallet(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hdwallet::path::DerivationPath;
+    use crate::hdwallet::MasterSeed;
+    use crate::horizon::{HorizonAccumulator, HorizonValidator, Utxo};
+    use rand::prelude::*;
+
+    const FEE: u64 = SPEND_SIZE_BYTES as u64;
+
+    fn witnessed(utxos: &[Utxo]) -> (HorizonAccumulator, Vec<WatchedCoin>) {
+        let mut acc = HorizonAccumulator::new();
+        for (i, utxo) in utxos.iter().enumerate() {
+            acc.add_utxo(utxo, i as u64);
+        }
+        let coins = utxos.iter().enumerate().map(|(i, u)| WatchedCoin { utxo: u.clone(), witness: acc.generate_witness(i as u64) }).collect();
+        (acc, coins)
+    }
+
+    #[test]
+    fn store_keys_sign_a_built_payment() {
+        let mut rng = StdRng::seed_from_u64(4340);
+        let dir = std::env::temp_dir().join(format!("olc-builder-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut store = WalletStore::create(&dir.join("wallet.olcw"), b"pass", &mut rng).unwrap();
+        store.add_seed("main", MasterSeed { seed_bytes: [0x40; 32] }).unwrap();
+        let owners: Vec<Address> = (0..3)
+            .map(|i| store.derive("main", &DerivationPath::bip44(0, 0, 0, i).unwrap()).unwrap().jordan_key().pub_key.address())
+            .collect();
+        let bob = JordanSchnorr::keygen(&mut rng).pub_key.address();
+
+        let utxos: Vec<Utxo> = [500, 300, 200].iter().zip(&owners).enumerate().map(|(i, (&a, &o))| Utxo::new([i as u8; 32], o, FEE + a)).collect();
+        let (acc, coins) = witnessed(&utxos);
+        let builder = TxBuilder::new(&coins[..], bob, 700, 1);
+        let plan = builder.build().unwrap();
+        assert_eq!((plan.payment.len(), plan.change.len(), plan.fee()), (2, 0, 2 * FEE));
+
+        let txs = plan.sign(&mut store, &mut rng).unwrap();
+        let validator = HorizonValidator::new(acc.root.clone());
+        assert!(txs.iter().all(|tx| validator.validate_transaction(tx).is_ok() && tx.new_owner == bob));
+        assert_eq!(txs.iter().map(|tx| tx.new_amount).sum::<u64>(), 700);
+
+        // A key the signer does not hold
+        let stranger = JordanSchnorr::keygen(&mut rng);
+        let (_, coins) = witnessed(&[Utxo::new([9; 32], stranger.pub_key.address(), FEE + 100)]);
+        assert_eq!(
+            TxBuilder::new(&coins[..], bob, 100, 1).sign(&mut store, &mut rng),
+            Err(BuildError::Signer(SignerError::UnknownOwner(stranger.pub_key.address())))
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn named_inputs_send_the_rest_to_change() {
+        let mut rng = StdRng::seed_from_u64(4341);
+        let mut alice = JordanSchnorr::keygen(&mut rng);
+        let (bob, carol) = (Address([0xB0; 32]), Address([0xC0; 32]));
+        let utxos: Vec<Utxo> = (1..=3).map(|i| Utxo::new([i; 32], alice.pub_key.address(), FEE + 100 * i as u64)).collect();
+        let (acc, coins) = witnessed(&utxos);
+        let ids: Vec<CoinId> = utxos.iter().map(|u| u.id).collect();
+
+        let builder = TxBuilder::new(&coins[..], bob, 300, 1).with_inputs(ids.clone());
+        assert_eq!(builder.build(), Err(BuildError::NoChangeAddress));
+        let txs = builder.change_to(carol).sign(&mut alice, &mut rng).unwrap();
+        let to: Vec<(Address, u64)> = txs.iter().map(|tx| (tx.new_owner, tx.new_amount)).collect();
+        assert_eq!(to, vec![(bob, 100), (bob, 200), (carol, 300)]);
+        let validator = HorizonValidator::new(acc.root.clone());
+        assert!(txs.iter().all(|tx| validator.validate_transaction(tx).is_ok()));
+
+        let twice = TxBuilder::new(&coins[..], bob, 100, 1).with_inputs(vec![ids[0], ids[0]]);
+        assert_eq!(twice.build(), Err(BuildError::Wallet(WalletError::DuplicateInput(ids[0]))));
+    }
+}
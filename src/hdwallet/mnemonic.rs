@@ -0,0 +1,208 @@
+// src/hdwallet/mnemonic.rs
+// BIP39 MNEMONICS: the master seed as words a person can write down.
+//
+// Entropy of 128..256 bits (a multiple of 32) gets the first ENT / 32 bits
+// of its SHA-256 appended, and the result is read as 11-bit indices into
+// the standard English list: 12 words for 128 bits, 24 for 256. The seed is
+//   PBKDF2-HMAC-SHA512(phrase, "mnemonic" || passphrase, 2048 rounds)
+// exactly as BIP39 has it, so a phrase restores the same 64-byte seed in
+// any BIP39 tool. MasterSeed::from_mnemonic then narrows it to the Flutter
+// seed under its own HMAC key, the way BIP32 does with "Bitcoin seed".
+//
+// Words, checksum and NFKD normalization are the bip39 crate's (English
+// list only); HMAC and PBKDF2 are the RustCrypto hmac and pbkdf2 crates.
+// Mnemonic keeps only the entropy, so there is one secret to wipe.
+
+use super::MasterSeed;
+use crate::redact::Redacted;
+use bip39::Language;
+use hmac::{Hmac, Mac};
+use rand::prelude::*;
+use sha2::Sha512;
+use std::borrow::Cow;
+use std::fmt;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+const SEED_ROUNDS: u32 = 2048;
+const FLUTTER_SEED_KEY: &[u8] = b"Flutter seed";
+pub const SEED_BYTES: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MnemonicError {
+    // 12, 15, 18, 21 or 24 words
+    WordCount { got: usize },
+    // 16, 20, 24, 28 or 32 bytes
+    EntropyLength { got: usize },
+    // Not in the English list (position counts from 0)
+    UnknownWord { position: usize },
+    // A word is wrong or the order is off
+    Checksum,
+}
+
+pub struct Mnemonic {
+    entropy: Vec<u8>,
+}
+
+impl Zeroize for Mnemonic {
+    fn zeroize(&mut self) {
+        self.entropy.zeroize();
+    }
+}
+
+impl Drop for Mnemonic {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for Mnemonic {}
+const _: () = crate::redact::assert_zeroize_on_drop::<Mnemonic>();
+
+impl fmt::Debug for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mnemonic")
+            .field("words", &self.word_count())
+            .field("entropy", &Redacted(&self.entropy))
+            .finish()
+    }
+}
+
+impl Mnemonic {
+    /// NEW PHRASE
+    /// `words` is 12 (128-bit) up to 24 (256-bit), in steps of 3
+    pub fn generate<R: Rng + ?Sized>(words: usize, rng: &mut R) -> Result<Self, MnemonicError> {
+        if !(12..=24).contains(&words) || !words.is_multiple_of(3) {
+            return Err(MnemonicError::WordCount { got: words });
+        }
+        let mut entropy = vec![0u8; words / 3 * 4];
+        rng.fill_bytes(&mut entropy);
+        Ok(Mnemonic { entropy })
+    }
+
+    pub fn from_entropy(entropy: &[u8]) -> Result<Self, MnemonicError> {
+        if !(16..=32).contains(&entropy.len()) || !entropy.len().is_multiple_of(4) {
+            return Err(MnemonicError::EntropyLength { got: entropy.len() });
+        }
+        Ok(Mnemonic { entropy: entropy.to_vec() })
+    }
+
+    /// RESTORE
+    /// Words separated by any whitespace; the checksum must hold
+    pub fn parse(phrase: &str) -> Result<Self, MnemonicError> {
+        let words = bip39::Mnemonic::parse_in(Language::English, phrase).map_err(|e| match e {
+            bip39::Error::BadWordCount(got) => MnemonicError::WordCount { got },
+            bip39::Error::UnknownWord(position) => MnemonicError::UnknownWord { position },
+            bip39::Error::BadEntropyBitCount(bits) => MnemonicError::EntropyLength { got: bits / 8 },
+            bip39::Error::InvalidChecksum | bip39::Error::AmbiguousLanguages(_) => MnemonicError::Checksum,
+        })?;
+        Ok(Mnemonic { entropy: words.to_entropy() })
+    }
+
+    pub fn word_count(&self) -> usize {
+        self.entropy.len() * 3 / 4
+    }
+
+    pub fn entropy(&self) -> &[u8] {
+        &self.entropy
+    }
+
+    /// The words, space-separated. Treat the String as the secret it is.
+    pub fn phrase(&self) -> String {
+        self.words().to_string()
+    }
+
+    /// BIP39 SEED
+    /// 64 bytes; an empty passphrase is the standard "no passphrase"
+    pub fn to_seed(&self, passphrase: &str) -> [u8; SEED_BYTES] {
+        let mut passphrase = Cow::Borrowed(passphrase);
+        bip39::Mnemonic::normalize_utf8_cow(&mut passphrase);
+        let mut phrase = self.phrase().into_bytes();
+        let mut salt = [b"mnemonic".as_slice(), passphrase.as_bytes()].concat();
+        let mut seed = [0u8; SEED_BYTES];
+        pbkdf2::pbkdf2_hmac::<Sha512>(&phrase, &salt, SEED_ROUNDS, &mut seed);
+        phrase.zeroize();
+        salt.zeroize();
+        if let Cow::Owned(mut owned) = passphrase {
+            owned.zeroize();
+        }
+        seed
+    }
+
+    // Entropy is checked on the way in, so it always encodes
+    fn words(&self) -> bip39::Mnemonic {
+        bip39::Mnemonic::from_entropy_in(Language::English, &self.entropy).unwrap_or_else(|_| unreachable!())
+    }
+}
+
+impl MasterSeed {
+    /// RESTORE FROM WORDS
+    /// seed_bytes = HMAC-SHA512("Flutter seed", BIP39 seed)[..32]
+    pub fn from_mnemonic(mnemonic: &Mnemonic, passphrase: &str) -> Self {
+        let mut seed = mnemonic.to_seed(passphrase);
+        let mut digest = hmac_sha512(FLUTTER_SEED_KEY, &seed);
+        let seed_bytes = std::array::from_fn(|i| digest[i]);
+        seed.zeroize();
+        digest.zeroize();
+        MasterSeed { seed_bytes }
+    }
+}
+
+// The BIP39 English list, sorted (shamir.rs spells shares in it too)
+pub(super) fn wordlist() -> &'static [&'static str] {
+    Language::English.word_list()
+}
+
+// HMAC-SHA512 (RFC 2104)
+pub fn hmac_sha512(key: &[u8], msg: &[u8]) -> [u8; 64] {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).unwrap_or_else(|_| unreachable!());
+    mac.update(msg);
+    mac.finalize().into_bytes().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    #[test]
+    fn trezor_vectors() {
+        // From the BIP39 reference vectors (passphrase "TREZOR")
+        let m = Mnemonic::from_entropy(&[0; 16]).unwrap();
+        assert_eq!(m.phrase(), "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about");
+        assert_eq!(
+            hex(&m.to_seed("TREZOR")),
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+        );
+        let m = Mnemonic::from_entropy(&[0xff; 32]).unwrap();
+        assert_eq!(m.phrase(), [&["zoo"; 23][..], &["vote"]].concat().join(" "));
+        assert_eq!(
+            hex(&m.to_seed("TREZOR")),
+            "dd48c104698c30cfe2b6142103248622fb7bb0ff692eebb00089b32d22484e1613912f0a5b694407be899ffd31ed3992c456cdf60f5d4564b8ba3f05a69890ad"
+        );
+    }
+
+    #[test]
+    fn phrases_round_trip_and_bad_ones_are_refused() {
+        let mut rng = StdRng::seed_from_u64(4329);
+        for words in [12, 24] {
+            let m = Mnemonic::generate(words, &mut rng).unwrap();
+            let back = Mnemonic::parse(&m.phrase()).unwrap();
+            assert_eq!((back.word_count(), back.entropy()), (words, m.entropy()));
+            assert_eq!(MasterSeed::from_mnemonic(&back, "").seed_bytes, MasterSeed::from_mnemonic(&m, "").seed_bytes);
+            assert_ne!(MasterSeed::from_mnemonic(&m, "").seed_bytes, MasterSeed::from_mnemonic(&m, "extra").seed_bytes);
+        }
+        assert_eq!(Mnemonic::generate(13, &mut rng).unwrap_err(), MnemonicError::WordCount { got: 13 });
+        // Passphrases are NFKD-normalized: composed and decomposed agree
+        let m = Mnemonic::from_entropy(&[7; 16]).unwrap();
+        assert_eq!(m.to_seed("caf\u{e9}"), m.to_seed("cafe\u{301}"));
+
+        let twelve = ["abandon"; 12].join(" ");
+        assert_eq!(Mnemonic::parse(&twelve).unwrap_err(), MnemonicError::Checksum);
+        let typo = ["abandon"; 11].join(" ") + " abuot";
+        assert_eq!(Mnemonic::parse(&typo).unwrap_err(), MnemonicError::UnknownWord { position: 11 });
+        assert_eq!(Mnemonic::parse("abandon about").unwrap_err(), MnemonicError::WordCount { got: 2 });
+    }
+}
@@ -0,0 +1,361 @@
+// src/hdwallet/path.rs
+// DERIVATION PATHS: a tree of Flutter keys under one MasterSeed.
+//
+// BIP32 in shape. Every node is a 32-byte key and a 32-byte chain code; a
+// child is
+//   hardened i:  I = HMAC-SHA512(chain, 0x00 || key || i)
+//                key' = I_L
+//   normal i:    I = HMAC-SHA512(chain, 0x01 || i)
+//                key' = key + I_L (mod 2^256)
+// with chain' = I_R. A hardened child needs the parent key; a normal
+// child's tweak needs only the chain code, so whoever holds a node's chain
+// code can link its normal subtree - harden account levels, as BIP44 does:
+//   m / purpose' / coin' / account' / change / index
+//
+// BIP32 also hashes the parent public key into normal steps and
+// fingerprints. Flutter public keys cannot serve: Z_256 is the attractor,
+// and squaring in Z_2^16 forgets the seed a bit per step, so every key pair
+// of one engine ends on the same Z_256. The tree leans on chain codes only.
+//
+// Each node also holds a JordanSchnorr key (JD-Light). A hardened node
+// runs keygen on ChaCha20 seeded with H(key) - a fixed stream, where
+// StdRng's algorithm may change with any rand release and move every
+// key; a normal child adds the small tweak d = B(eta) drawn from I_L,
+// s' = s + d, so its public key t' = t + A o d follows from the
+// parent's: see xpub.rs.
+//
+// The master node is HMAC-SHA512("Flutter HD", seed_bytes).
+
+use super::{FlutterEngine, FlutterKeyPair, MasterSeed};
+use super::mnemonic::hmac_sha512;
+use super::xpub::{self, ExtendedPublicKey};
+use crate::gsh::GSH256;
+use crate::jordan_sig::{JordanSchnorr, SecretKey};
+use crate::redact::Redacted;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+use rand::prelude::*;
+use rand_chacha::ChaCha20Rng;
+use std::fmt;
+use std::str::FromStr;
+
+const MASTER_KEY: &[u8] = b"Flutter HD";
+const JORDAN_DOMAIN: &[u8] = b"flutter-hd/jordan";
+pub const HARDENED: u32 = 1 << 31;
+pub const MAX_DEPTH: usize = 255;
+pub const BIP44_PURPOSE: u32 = 44;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathError {
+    // Not m/a/b'/..
+    Format,
+    // A hardened step needs the private key
+    HardenedFromPublic,
+    // A step at or above 2^31 (the hardened bit is the ' suffix)
+    IndexTooLarge { index: u64 },
+    TooDeep { depth: usize },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ChildNumber(pub u32); // Index, with HARDENED set for hardened steps
+
+impl ChildNumber {
+    pub fn normal(index: u32) -> Result<Self, PathError> {
+        if index >= HARDENED {
+            return Err(PathError::IndexTooLarge { index: index as u64 });
+        }
+        Ok(ChildNumber(index))
+    }
+
+    pub fn hardened(index: u32) -> Result<Self, PathError> {
+        Self::normal(index).map(|c| ChildNumber(c.0 | HARDENED))
+    }
+
+    pub fn is_hardened(self) -> bool {
+        self.0 & HARDENED != 0
+    }
+
+    // Without the hardened bit
+    pub fn index(self) -> u32 {
+        self.0 & !HARDENED
+    }
+}
+
+impl fmt::Display for ChildNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.index(), if self.is_hardened() { "'" } else { "" })
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DerivationPath(pub Vec<ChildNumber>);
+
+impl DerivationPath {
+    pub fn master() -> Self {
+        DerivationPath(Vec::new())
+    }
+
+    /// m / 44' / coin' / account' / change / index
+    pub fn bip44(coin: u32, account: u32, change: u32, index: u32) -> Result<Self, PathError> {
+        Ok(DerivationPath(vec![
+            ChildNumber::hardened(BIP44_PURPOSE)?,
+            ChildNumber::hardened(coin)?,
+            ChildNumber::hardened(account)?,
+            ChildNumber::normal(change)?,
+            ChildNumber::normal(index)?,
+        ]))
+    }
+
+    pub fn child(&self, step: ChildNumber) -> Result<Self, PathError> {
+        if self.0.len() >= MAX_DEPTH {
+            return Err(PathError::TooDeep { depth: self.0.len() + 1 });
+        }
+        Ok(DerivationPath([self.0.as_slice(), &[step]].concat()))
+    }
+
+    pub fn depth(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "m")?;
+        self.0.iter().try_for_each(|step| write!(f, "/{step}"))
+    }
+}
+
+// "m", "m/44'/0'/0'/0/7"; h and H are accepted for '
+impl FromStr for DerivationPath {
+    type Err = PathError;
+
+    fn from_str(s: &str) -> Result<Self, PathError> {
+        let mut parts = s.split('/');
+        if parts.next() != Some("m") {
+            return Err(PathError::Format);
+        }
+        let steps = parts
+            .map(|part| {
+                let (digits, hardened) = match part.strip_suffix(['\'', 'h', 'H']) {
+                    Some(digits) => (digits, true),
+                    None => (part, false),
+                };
+                if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(PathError::Format);
+                }
+                let index: u64 = digits.parse().map_err(|_| PathError::IndexTooLarge { index: u64::MAX })?;
+                let index = u32::try_from(index).map_err(|_| PathError::IndexTooLarge { index })?;
+                if hardened { ChildNumber::hardened(index) } else { ChildNumber::normal(index) }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if steps.len() > MAX_DEPTH {
+            return Err(PathError::TooDeep { depth: steps.len() });
+        }
+        Ok(DerivationPath(steps))
+    }
+}
+
+// One node of the tree
+#[derive(Clone)]
+pub struct ExtendedKey {
+    pub depth: u8,
+    pub child_number: ChildNumber,
+    key: [u8; 32],
+    chain_code: [u8; 32],
+    jordan: SecretKey,
+}
+
+impl Zeroize for ExtendedKey {
+    fn zeroize(&mut self) {
+        self.key.zeroize();
+        self.chain_code.zeroize();
+        self.jordan.zeroize();
+    }
+}
+
+impl Drop for ExtendedKey {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for ExtendedKey {}
+const _: () = crate::redact::assert_zeroize_on_drop::<ExtendedKey>();
+
+// Manual Debug: key and chain code are redacted (the Jordan key redacts
+// itself)
+impl fmt::Debug for ExtendedKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtendedKey")
+            .field("depth", &self.depth)
+            .field("child_number", &self.child_number)
+            .field("key", &Redacted(&self.key))
+            .field("chain_code", &Redacted(&self.chain_code))
+            .field("jordan", &self.jordan)
+            .finish()
+    }
+}
+
+impl ExtendedKey {
+    /// The node's Flutter key pair (its index is the child number's)
+    pub fn keypair(&self, engine: &FlutterEngine) -> FlutterKeyPair {
+        let kp = MasterSeed { seed_bytes: self.key }.derive_keypair(engine, 0);
+        FlutterKeyPair { index: self.child_number.index(), ..kp }
+    }
+
+    /// The node's JordanSchnorr key
+    pub fn jordan_key(&self) -> &SecretKey {
+        &self.jordan
+    }
+
+    // The node's 32-byte key, for schemes that seed their own keys from it
+    pub(super) fn key_bytes(&self) -> &[u8; 32] {
+        &self.key
+    }
+
+    /// WATCH-ONLY NODE
+    /// Chain code and Jordan public key: enough to derive the normal subtree
+    pub fn public(&self) -> ExtendedPublicKey {
+        ExtendedPublicKey {
+            depth: self.depth,
+            child_number: self.child_number,
+            key: self.jordan.pub_key,
+            chain_code: self.chain_code,
+        }
+    }
+
+    /// CHILD DERIVATION
+    pub fn derive_child(&self, child: ChildNumber) -> Result<Self, PathError> {
+        if self.depth as usize >= MAX_DEPTH {
+            return Err(PathError::TooDeep { depth: MAX_DEPTH + 1 });
+        }
+        let mut i = if child.is_hardened() {
+            let mut data = [&[0u8][..], &self.key, &child.0.to_be_bytes()].concat();
+            let i = hmac_sha512(&self.chain_code, &data);
+            data.zeroize();
+            i
+        } else {
+            normal_step(&self.chain_code, child)
+        };
+
+        let mut key: [u8; 32] = std::array::from_fn(|j| i[j]);
+        if !child.is_hardened() {
+            // key + I_L, big-endian, wrapping
+            let mut carry = 0u16;
+            for j in (0..32).rev() {
+                let sum = self.key[j] as u16 + key[j] as u16 + carry;
+                key[j] = sum as u8;
+                carry = sum >> 8;
+            }
+        }
+        let jordan = if child.is_hardened() { node_jordan(&key) } else { xpub::tweak_secret(&self.jordan, &i) };
+        let chain_code = std::array::from_fn(|j| i[32 + j]);
+        i.zeroize();
+        Ok(ExtendedKey { depth: self.depth + 1, child_number: child, key, chain_code, jordan })
+    }
+
+    /// SUBTREE DERIVATION
+    /// `path` is read relative to this node
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<Self, PathError> {
+        path.0.iter().try_fold(self.clone(), |node, &step| node.derive_child(step))
+    }
+}
+
+// I = HMAC-SHA512(chain, 0x01 || i): a normal step needs no key
+pub(super) fn normal_step(chain_code: &[u8; 32], child: ChildNumber) -> [u8; 64] {
+    hmac_sha512(chain_code, &[&[1u8][..], &child.0.to_be_bytes()].concat())
+}
+
+fn node_jordan(key: &[u8; 32]) -> SecretKey {
+    let mut digest = GSH256::hash_slices(&[JORDAN_DOMAIN, key]);
+    let sk = JordanSchnorr::keygen(&mut ChaCha20Rng::from_seed(std::array::from_fn(|i| digest[i])));
+    digest.zeroize();
+    sk
+}
+
+impl MasterSeed {
+    pub fn master_key(&self) -> ExtendedKey {
+        let mut i = hmac_sha512(MASTER_KEY, &self.seed_bytes);
+        let key = std::array::from_fn(|j| i[j]);
+        let node = ExtendedKey {
+            depth: 0,
+            child_number: ChildNumber(0),
+            key,
+            chain_code: std::array::from_fn(|j| i[32 + j]),
+            jordan: node_jordan(&key),
+        };
+        i.zeroize();
+        node
+    }
+
+    /// The key pair at `path` (from m)
+    pub fn derive_path(&self, engine: &FlutterEngine, path: &DerivationPath) -> Result<FlutterKeyPair, PathError> {
+        Ok(self.master_key().derive_path(path)?.keypair(engine))
+    }
+
+    /// The JordanSchnorr key at `path` (from m): what Horizon spends are
+    /// signed with, so one mnemonic backs both kinds of key
+    pub fn derive_jordan_keypair(&self, path: &DerivationPath) -> Result<SecretKey, PathError> {
+        Ok(self.master_key().derive_path(path)?.jordan_key().clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn engine() -> FlutterEngine {
+        FlutterEngine::new(0x1910, [0xAB; 16])
+    }
+
+    #[test]
+    fn paths_parse_and_print() {
+        let path: DerivationPath = "m/44'/0'/1h/0/7".parse().unwrap();
+        assert_eq!(path, DerivationPath::bip44(0, 1, 0, 7).unwrap());
+        assert_eq!(path.to_string(), "m/44'/0'/1'/0/7");
+        assert_eq!("m".parse(), Ok(DerivationPath::master()));
+        assert_eq!("44'/0'".parse::<DerivationPath>(), Err(PathError::Format));
+        assert_eq!("m//1".parse::<DerivationPath>(), Err(PathError::Format));
+        assert_eq!("m/-1".parse::<DerivationPath>(), Err(PathError::Format));
+        assert_eq!("m/2147483648".parse::<DerivationPath>(), Err(PathError::IndexTooLarge { index: 1 << 31 }));
+    }
+
+    #[test]
+    fn subtrees_derive_the_same_keys_as_full_paths() {
+        let engine = engine();
+        let seed = MasterSeed { seed_bytes: [0x42; 32] };
+        let account: DerivationPath = "m/44'/0'/0'".parse().unwrap();
+        let full = DerivationPath::bip44(0, 0, 0, 3).unwrap();
+
+        let node = seed.master_key().derive_path(&account).unwrap();
+        let kp = node.derive_path(&"m/0/3".parse().unwrap()).unwrap().keypair(&engine);
+        assert_eq!(kp.private_seed, seed.derive_path(&engine, &full).unwrap().private_seed);
+        assert_eq!(kp.index, 3);
+
+        // Siblings, hardened twins and other seeds all differ
+        let sibling = seed.derive_path(&engine, &DerivationPath::bip44(0, 0, 0, 4).unwrap()).unwrap();
+        let hardened = seed.derive_path(&engine, &"m/44'/0'/0'/0/3'".parse().unwrap()).unwrap();
+        let other = MasterSeed { seed_bytes: [0x43; 32] }.derive_path(&engine, &full).unwrap();
+        for kp2 in [sibling, hardened, other] {
+            assert_ne!(kp.private_seed, kp2.private_seed);
+        }
+        assert_eq!(node.derive_child(ChildNumber::normal(0).unwrap()).unwrap().depth, 4);
+    }
+
+    #[test]
+    fn jordan_keys_follow_the_seed() {
+        let seed = MasterSeed { seed_bytes: [0x42; 32] };
+        let path = DerivationPath::bip44(0, 0, 0, 3).unwrap();
+        let sk = seed.derive_jordan_keypair(&path).unwrap();
+        assert_eq!(sk.to_versioned_bytes(), MasterSeed { seed_bytes: [0x42; 32] }.derive_jordan_keypair(&path).unwrap().to_versioned_bytes());
+        let sibling = seed.derive_jordan_keypair(&DerivationPath::bip44(0, 0, 0, 4).unwrap()).unwrap();
+        let other = MasterSeed { seed_bytes: [0x43; 32] }.derive_jordan_keypair(&path).unwrap();
+        assert_ne!(sk.pub_key, sibling.pub_key);
+        assert_ne!(sk.pub_key, other.pub_key);
+        // Pinned: a change of stream (or of rand) would move every wallet
+        assert_eq!(format!("{:016x}", sk.pub_key.fingerprint().key_id()), "fa2f0e22830d8f1c");
+
+        let mut rng = StdRng::seed_from_u64(4335);
+        let sig = JordanSchnorr::sign(&sk, b"test", b"spend", &mut rng).unwrap();
+        assert_eq!(JordanSchnorr::verify(&sk.pub_key, b"test", b"spend", &sig), Ok(()));
+    }
+}
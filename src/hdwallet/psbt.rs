@@ -0,0 +1,360 @@
+// src/hdwallet/psbt.rs
+// PARTIALLY SIGNED TRANSACTIONS: Horizon spends in transit between signers.
+//
+// A builder.rs Plan is a batch of one-coin spends, and in a cold-storage or
+// multi-party setup the keys for those coins are spread over devices. A
+// Psbt carries the batch between them: each input is an UnsignedTransaction
+// (coin, witness, output) plus what is known about its signer and, once a
+// device has it, the signature.
+//
+// - `origin` names the key a spend needs: the fingerprint of the seed's
+//   master Jordan key and the path below it, so a device can tell "mine"
+//   without seeing an address list. It is a hint; the owner address is
+//   what the signature is checked against.
+// - `sign` fills in every input the Signer has a key for and leaves the
+//   rest; `merge` takes signatures from another copy of the same batch;
+//   `finalize` hands back Transactions once every input is signed.
+// - Signatures from another copy are checked at the merge and every one
+//   again at `finalize`, so a bad copy is refused here rather than at the
+//   validator. Two valid signatures for one input differ (signing is
+//   randomized); the first one kept wins.
+// - Witnesses are not covered by the signature, so a coordinator can
+//   refresh them (`update_witness`) after signing, as the tree moves.
+//
+// Wire form: "OLCP" || version || varint count || inputs, each
+//   utxo || witness || ?owner key || new owner || varint amount ||
+//   ?(fingerprint || varint depth || u32 LE steps) || ?(key || signature)
+// with ? a 0/1 presence byte.
+
+use super::builder::{Signer, SignerError};
+use super::path::{ChildNumber, DerivationPath};
+use super::MasterSeed;
+use crate::address::Address;
+use crate::horizon::{Transaction, UnsignedTransaction, Utxo, Witness, TX_CONTEXT};
+use crate::jordan_sig::{Fingerprint, JordanSchnorr, PublicKey, Signature, FINGERPRINT_BYTES};
+use crate::wire::{write_varint, Reader, Wire, WireError};
+use rand::Rng;
+
+pub const PSBT_MAGIC: &[u8; 4] = b"OLCP";
+pub const PSBT_VERSION: u8 = 1;
+pub const MAX_PSBT_INPUTS: usize = 1000;
+const MAX_PATH_DEPTH: usize = u8::MAX as usize;
+// id, owner, amount, asset, condition and created_at at their smallest
+const MIN_INPUT_BYTES: usize = 32 + 32 + 1 + 32 + 1 + 1;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PsbtError {
+    // The other copy is of a different batch
+    Mismatch,
+    NoInput(usize),
+    BadSignature { input: usize },
+    Unsigned { input: usize },
+    // The witness does not prove the input's coin under the given root
+    BadWitness { input: usize },
+}
+
+// Where the key for an input comes from
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyOrigin {
+    pub master: Fingerprint,
+    pub path: DerivationPath,
+}
+
+impl KeyOrigin {
+    pub fn of(seed: &MasterSeed, path: DerivationPath) -> Self {
+        KeyOrigin { master: seed.master_key().jordan_key().pub_key.fingerprint(), path }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PsbtInput {
+    pub spend: UnsignedTransaction,
+    pub origin: Option<KeyOrigin>,
+    signature: Option<(PublicKey, Signature)>,
+}
+
+impl PsbtInput {
+    pub fn new(spend: UnsignedTransaction) -> Self {
+        PsbtInput { spend, origin: None, signature: None }
+    }
+
+    pub fn is_signed(&self) -> bool {
+        self.signature.is_some()
+    }
+
+    pub fn signature(&self) -> Option<&(PublicKey, Signature)> {
+        self.signature.as_ref()
+    }
+
+    // The same coin paying the same output: another copy of this batch.
+    // A signature for a different output would fail `check` anyway; this
+    // tells a foreign batch (Mismatch) from a bad signature.
+    fn same_spend(&self, other: &PsbtInput) -> bool {
+        let (a, b) = (&self.spend, &other.spend);
+        a.input_utxo == b.input_utxo && a.new_owner == b.new_owner && a.new_amount == b.new_amount
+    }
+
+    // Signed by the coin's owner over the spend message, which covers the
+    // coin, the new owner and the amount (horizon::spend_message)
+    fn check(&self, key: &PublicKey, sig: &Signature) -> bool {
+        key.address() == self.spend.input_utxo.owner
+            && JordanSchnorr::verify(key, TX_CONTEXT, &self.spend.message(), sig).is_ok()
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Psbt {
+    pub inputs: Vec<PsbtInput>,
+}
+
+impl Psbt {
+    pub fn new(spends: impl IntoIterator<Item = UnsignedTransaction>) -> Self {
+        Psbt { inputs: spends.into_iter().map(PsbtInput::new).collect() }
+    }
+
+    pub fn set_origin(&mut self, input: usize, origin: KeyOrigin) -> Result<(), PsbtError> {
+        self.inputs.get_mut(input).ok_or(PsbtError::NoInput(input))?.origin = Some(origin);
+        Ok(())
+    }
+
+    pub fn update_witness(&mut self, input: usize, witness: Witness, root: &str) -> Result<(), PsbtError> {
+        let slot = self.inputs.get_mut(input).ok_or(PsbtError::NoInput(input))?;
+        if witness.root(&slot.spend.input_utxo.leaf_hash()) != root {
+            return Err(PsbtError::BadWitness { input });
+        }
+        slot.spend.witness = witness;
+        Ok(())
+    }
+
+    /// Indices of inputs still waiting for a signature
+    pub fn pending(&self) -> impl Iterator<Item = usize> + '_ {
+        self.inputs.iter().enumerate().filter(|(_, input)| !input.is_signed()).map(|(i, _)| i)
+    }
+
+    /// The distinct seeds still needed, by master fingerprint
+    pub fn required_signers(&self) -> Vec<Fingerprint> {
+        let mut masters: Vec<Fingerprint> = self.pending().filter_map(|i| self.inputs[i].origin.as_ref().map(|o| o.master)).collect();
+        masters.sort_by_key(|f| f.0);
+        masters.dedup();
+        masters
+    }
+
+    /// Sign every pending input `signer` holds the key for; how many it signed
+    pub fn sign<S: Signer, R: Rng + ?Sized>(&mut self, signer: &mut S, rng: &mut R) -> Result<usize, SignerError> {
+        let mut signed = 0;
+        for input in self.inputs.iter_mut().filter(|input| !input.is_signed()) {
+            let owner = input.spend.input_utxo.owner;
+            let Some(key) = signer.public_key(&owner) else { continue };
+            let sig = signer.sign(&owner, &input.spend.message(), rng)?;
+            input.signature = Some((key, sig));
+            signed += 1;
+        }
+        Ok(signed)
+    }
+
+    /// Take the signatures `other` has and this copy lacks
+    pub fn merge(&mut self, other: &Psbt) -> Result<(), PsbtError> {
+        if self.inputs.len() != other.inputs.len() || self.inputs.iter().zip(&other.inputs).any(|(a, b)| !a.same_spend(b)) {
+            return Err(PsbtError::Mismatch);
+        }
+        for (input, (ours, theirs)) in self.inputs.iter().zip(&other.inputs).enumerate() {
+            if let (None, Some((key, sig))) = (&ours.signature, &theirs.signature) {
+                if !ours.check(key, sig) {
+                    return Err(PsbtError::BadSignature { input });
+                }
+            }
+        }
+        for (ours, theirs) in self.inputs.iter_mut().zip(&other.inputs) {
+            if ours.signature.is_none() {
+                ours.signature.clone_from(&theirs.signature);
+            }
+            if ours.origin.is_none() {
+                ours.origin.clone_from(&theirs.origin);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn finalize(self) -> Result<Vec<Transaction>, PsbtError> {
+        self.inputs
+            .into_iter()
+            .enumerate()
+            .map(|(input, slot)| match slot.signature {
+                Some((key, sig)) if slot.check(&key, &sig) => Ok(slot.spend.complete(key, sig)),
+                Some(_) => Err(PsbtError::BadSignature { input }),
+                None => Err(PsbtError::Unsigned { input }),
+            })
+            .collect()
+    }
+}
+
+// --- WIRE ---
+fn write_option<T>(out: &mut Vec<u8>, value: Option<&T>, encode: impl FnOnce(&T, &mut Vec<u8>)) {
+    match value {
+        None => out.push(0),
+        Some(v) => {
+            out.push(1);
+            encode(v, out);
+        }
+    }
+}
+
+fn read_option<T>(r: &mut Reader, decode: impl FnOnce(&mut Reader) -> Result<T, WireError>) -> Result<Option<T>, WireError> {
+    match r.read_u8()? {
+        0 => Ok(None),
+        1 => decode(r).map(Some),
+        _ => Err(WireError::NonCanonical("presence byte")),
+    }
+}
+
+impl Wire for KeyOrigin {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.master.0);
+        write_varint(out, self.path.0.len() as u64);
+        for step in &self.path.0 {
+            out.extend_from_slice(&step.0.to_le_bytes());
+        }
+    }
+
+    fn decode(r: &mut Reader) -> Result<Self, WireError> {
+        let master = Fingerprint(r.read_bytes(FINGERPRINT_BYTES)?.try_into().unwrap());
+        let depth = r.read_len("path depth", MAX_PATH_DEPTH, 4)?;
+        let mut steps = Vec::new();
+        for _ in 0..depth {
+            steps.push(ChildNumber(u32::from_le_bytes(r.read_bytes(4)?.try_into().unwrap())));
+        }
+        Ok(KeyOrigin { master, path: DerivationPath(steps) })
+    }
+}
+
+impl Wire for Psbt {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(PSBT_MAGIC);
+        out.push(PSBT_VERSION);
+        write_varint(out, self.inputs.len() as u64);
+        for input in &self.inputs {
+            let spend = &input.spend;
+            spend.input_utxo.encode(out);
+            spend.witness.encode(out);
+            write_option(out, spend.owner_key.as_ref(), PublicKey::encode);
+            spend.new_owner.encode(out);
+            write_varint(out, spend.new_amount);
+            write_option(out, input.origin.as_ref(), KeyOrigin::encode);
+            write_option(out, input.signature.as_ref(), |(key, sig), out| {
+                key.encode(out);
+                sig.encode(out);
+            });
+        }
+    }
+
+    fn decode(r: &mut Reader) -> Result<Self, WireError> {
+        if r.read_bytes(4)? != PSBT_MAGIC {
+            return Err(WireError::NonCanonical("psbt magic"));
+        }
+        let version = r.read_u8()?;
+        if version != PSBT_VERSION {
+            return Err(WireError::UnknownTag(version));
+        }
+        let count = r.read_len("psbt inputs", MAX_PSBT_INPUTS, MIN_INPUT_BYTES)?;
+        let mut inputs = Vec::new();
+        for _ in 0..count {
+            let spend = UnsignedTransaction {
+                input_utxo: Utxo::decode(r)?,
+                witness: Witness::decode(r)?,
+                owner_key: read_option(r, PublicKey::decode)?,
+                new_owner: Address::decode(r)?,
+                new_amount: r.read_varint()?,
+            };
+            let origin = read_option(r, KeyOrigin::decode)?;
+            let signature = read_option(r, |r| Ok((PublicKey::decode(r)?, Signature::decode(r)?)))?;
+            inputs.push(PsbtInput { spend, origin, signature });
+        }
+        Ok(Psbt { inputs })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hdwallet::builder::TxBuilder;
+    use crate::hdwallet::watch::WatchedCoin;
+    use crate::horizon::{spend_message, HorizonAccumulator, HorizonValidator, SPEND_SIZE_BYTES};
+    use rand::prelude::*;
+
+    const FEE: u64 = SPEND_SIZE_BYTES as u64;
+
+    #[test]
+    fn two_devices_sign_their_halves_and_merge() {
+        let mut rng = StdRng::seed_from_u64(4341);
+        let path = DerivationPath::bip44(0, 0, 0, 0).unwrap();
+        let seeds = [MasterSeed { seed_bytes: [0x01; 32] }, MasterSeed { seed_bytes: [0x02; 32] }];
+        let mut keys: Vec<_> = seeds.iter().map(|s| s.derive_jordan_keypair(&path).unwrap()).collect();
+        let utxos: Vec<Utxo> = keys.iter().enumerate().map(|(i, k)| Utxo::new([i as u8; 32], k.pub_key.address(), FEE + 400)).collect();
+        let mut acc = HorizonAccumulator::new();
+        utxos.iter().enumerate().for_each(|(i, u)| acc.add_utxo(u, i as u64));
+        let coins: Vec<WatchedCoin> = utxos.iter().enumerate().map(|(i, u)| WatchedCoin { utxo: u.clone(), witness: acc.generate_witness(i as u64) }).collect();
+
+        // The coordinator builds the batch and says who must sign
+        let plan = TxBuilder::new(&coins[..], Address([0xB0; 32]), 800, 1).build().unwrap();
+        let mut psbt = Psbt::new(plan.payment);
+        for i in 0..2 {
+            let seed = &seeds[if psbt.inputs[i].spend.input_utxo.owner == keys[0].pub_key.address() { 0 } else { 1 }];
+            psbt.set_origin(i, KeyOrigin::of(seed, path.clone())).unwrap();
+        }
+        assert_eq!(psbt.required_signers().len(), 2);
+
+        // Each device gets the bytes, signs what it can, sends them back
+        let mut copies = Vec::new();
+        for key in &mut keys {
+            let mut copy = Psbt::from_bytes(&psbt.to_bytes()).unwrap();
+            assert_eq!(copy.sign(key, &mut rng), Ok(1));
+            copies.push(Psbt::from_bytes(&copy.to_bytes()).unwrap());
+        }
+        assert_eq!(psbt.clone().finalize(), Err(PsbtError::Unsigned { input: 0 }));
+        psbt.merge(&copies[0]).unwrap();
+        assert_eq!(psbt.pending().count(), 1);
+        psbt.merge(&copies[1]).unwrap();
+        assert!(psbt.required_signers().is_empty());
+
+        let validator = HorizonValidator::new(acc.root.clone());
+        let txs = psbt.finalize().unwrap();
+        assert!(txs.iter().all(|tx| validator.validate_transaction(tx).is_ok()));
+    }
+
+    #[test]
+    fn foreign_batches_and_forged_signatures_are_refused() {
+        let mut rng = StdRng::seed_from_u64(4342);
+        let mut key = JordanSchnorr::keygen(&mut rng);
+        let utxo = Utxo::new([7; 32], key.pub_key.address(), 500);
+        let spend = |amount| UnsignedTransaction {
+            input_utxo: utxo.clone(),
+            witness: Witness { siblings: vec![], index: 0 },
+            owner_key: None,
+            new_owner: Address([0xB0; 32]),
+            new_amount: amount,
+        };
+        let mut ours = Psbt::new([spend(400)]);
+        let mut other = Psbt::new([spend(300)]);
+        other.sign(&mut key, &mut rng).unwrap();
+        assert_eq!(ours.merge(&other), Err(PsbtError::Mismatch));
+
+        // A signature by the right key, but over another coin
+        let coin = Utxo::new([8; 32], key.pub_key.address(), 500);
+        let elsewhere = JordanSchnorr::sign(&key, TX_CONTEXT, &spend_message(&coin, &Address([0xB0; 32]), 400), &mut rng);
+        let mut forged = Psbt::new([spend(400)]);
+        forged.inputs[0].signature = Some((key.pub_key, elsewhere.unwrap()));
+        assert_eq!(ours.merge(&forged), Err(PsbtError::BadSignature { input: 0 }));
+        assert_eq!(forged.finalize(), Err(PsbtError::BadSignature { input: 0 }));
+
+        // ...or over this coin paying another amount
+        let mut moved = Psbt::new([spend(400)]);
+        moved.inputs[0].signature = other.inputs[0].signature.clone();
+        assert_eq!(ours.merge(&moved), Err(PsbtError::BadSignature { input: 0 }));
+        assert_eq!(moved.finalize(), Err(PsbtError::BadSignature { input: 0 }));
+
+        let mut bytes = ours.to_bytes();
+        bytes[4] = 9;
+        assert_eq!(Psbt::from_bytes(&bytes), Err(WireError::UnknownTag(9)));
+        assert_eq!(Psbt::from_bytes(b"OLCP\x01\xff\xff\x01"), Err(WireError::TooLarge { what: "psbt inputs", len: 32_767, max: MAX_PSBT_INPUTS }));
+    }
+}
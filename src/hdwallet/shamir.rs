@@ -0,0 +1,306 @@
+// src/hdwallet/shamir.rs
+// SEED SHARES: a MasterSeed split t-of-n, SLIP-39 style.
+//
+// Shamir sharing byte by byte over GF(256) (x^8 + x^4 + x^3 + x + 1): the
+// seed is the value at x = 255 of a polynomial of degree t - 1, and share
+// i (i = 0..n) is its value at x = i. Any t shares interpolate it back;
+// fewer say nothing about it. As in SLIP-39, the polynomial also passes
+// through a digest point at x = 254:
+//   D || R,  R random (28 bytes),  D = HMAC-SHA512(R, seed)[..4]
+// so recovery rebuilds both points and refuses a seed whose digest does
+// not check - a wrong or altered share is caught, not turned into another
+// wallet. (SLIP-39 takes HMAC-SHA256; there is only SHA-512 HMAC in tree.)
+// With t = 1 every share is the seed itself.
+//
+// A share is written as 28 words of the BIP39 English list:
+//   identifier (u16) || threshold || index || value (32) || checksum (20 bits)
+// The identifier is random per split, so shares of two splits do not mix;
+// the checksum is the top 20 bits of SHA-256("olc-share" || payload).
+// SLIP-39 proper uses its own 1024-word list and an RS1024 checksum; these
+// shares are not compatible with SLIP-39 tools.
+//
+// Field arithmetic is shift-and-add with masks, no tables, so timing does
+// not depend on the share bytes.
+
+use super::mnemonic::{hmac_sha512, wordlist, MnemonicError};
+use super::MasterSeed;
+use crate::redact::Redacted;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+use rand::prelude::*;
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+const CHECKSUM_DOMAIN: &[u8] = b"olc-share";
+const SECRET_X: u8 = 255;
+const DIGEST_X: u8 = 254;
+const DIGEST_BYTES: usize = 4;
+pub const MAX_SHARES: u8 = 16;
+const PAYLOAD_BYTES: usize = 2 + 1 + 1 + 32;
+const CHECKSUM_BITS: usize = 20;
+pub const SHARE_WORDS: usize = (PAYLOAD_BYTES * 8 + CHECKSUM_BITS) / 11;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShareError {
+    // 1 <= threshold <= shares <= MAX_SHARES
+    Threshold { threshold: u8, shares: u8 },
+    TooFewShares { threshold: u8, got: usize },
+    // Shares of different splits
+    Mixed,
+    DuplicateIndex { index: u8 },
+    // The recovered seed fails the digest: a share is wrong
+    Digest,
+    Words(MnemonicError),
+}
+
+pub struct Share {
+    pub identifier: u16,
+    pub threshold: u8,
+    pub index: u8,
+    value: [u8; 32],
+}
+
+impl Zeroize for Share {
+    fn zeroize(&mut self) {
+        self.value.zeroize();
+    }
+}
+
+impl Drop for Share {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for Share {}
+const _: () = crate::redact::assert_zeroize_on_drop::<Share>();
+
+impl fmt::Debug for Share {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Share")
+            .field("identifier", &self.identifier)
+            .field("threshold", &self.threshold)
+            .field("index", &self.index)
+            .field("value", &Redacted(&self.value))
+            .finish()
+    }
+}
+
+impl Share {
+    /// The share as words, space-separated. As secret as the share.
+    pub fn to_mnemonic(&self) -> String {
+        let mut bits = self.payload();
+        bits.extend(&checksum(&bits).to_be_bytes()[1..]);
+        let bit = |at: usize| (bits[at / 8] >> (7 - at % 8) & 1) as usize;
+        let list = wordlist();
+        let words = (0..SHARE_WORDS).map(|k| list[(0..11).fold(0, |acc, b| acc << 1 | bit(k * 11 + b))]).collect::<Vec<_>>().join(" ");
+        bits.zeroize();
+        words
+    }
+
+    pub fn from_mnemonic(words: &str) -> Result<Self, ShareError> {
+        let list = wordlist();
+        let indices = words
+            .split_whitespace()
+            .enumerate()
+            .map(|(position, word)| list.binary_search(&word).map_err(|_| ShareError::Words(MnemonicError::UnknownWord { position })))
+            .collect::<Result<Vec<usize>, _>>()?;
+        if indices.len() != SHARE_WORDS {
+            return Err(ShareError::Words(MnemonicError::WordCount { got: indices.len() }));
+        }
+        let mut bits = vec![0u8; (SHARE_WORDS * 11).div_ceil(8)];
+        for (k, &index) in indices.iter().enumerate() {
+            for b in 0..11 {
+                if index >> (10 - b) & 1 == 1 {
+                    let at = k * 11 + b;
+                    bits[at / 8] |= 0x80 >> (at % 8);
+                }
+            }
+        }
+        let (payload, tail) = bits.split_at(PAYLOAD_BYTES);
+        let got = u32::from_be_bytes([0, tail[0], tail[1], tail[2]]) >> (24 - CHECKSUM_BITS);
+        let ok = got == checksum(payload) >> (24 - CHECKSUM_BITS);
+        let share = Share {
+            identifier: u16::from_be_bytes([payload[0], payload[1]]),
+            threshold: payload[2],
+            index: payload[3],
+            value: payload[4..].try_into().unwrap_or_else(|_| unreachable!()),
+        };
+        bits.zeroize();
+        if !ok {
+            return Err(ShareError::Words(MnemonicError::Checksum));
+        }
+        Ok(share)
+    }
+
+    fn payload(&self) -> Vec<u8> {
+        let mut out = self.identifier.to_be_bytes().to_vec();
+        out.extend([self.threshold, self.index]);
+        out.extend(self.value);
+        out
+    }
+}
+
+// Top 24 bits of SHA-256(domain || payload), right-aligned
+fn checksum(payload: &[u8]) -> u32 {
+    let digest = Sha256::new().chain_update(CHECKSUM_DOMAIN).chain_update(payload).finalize();
+    u32::from_be_bytes([0, digest[0], digest[1], digest[2]])
+}
+
+impl MasterSeed {
+    /// SPLIT
+    /// `shares` shares, any `threshold` of which recover the seed
+    pub fn split<R: Rng + ?Sized>(&self, threshold: u8, shares: u8, rng: &mut R) -> Result<Vec<Share>, ShareError> {
+        if threshold == 0 || threshold > shares || shares > MAX_SHARES {
+            return Err(ShareError::Threshold { threshold, shares });
+        }
+        let identifier = rng.gen();
+        let share = |index: u8, value: [u8; 32]| Share { identifier, threshold, index, value };
+        if threshold == 1 {
+            return Ok((0..shares).map(|index| share(index, self.seed_bytes)).collect());
+        }
+
+        // t - 2 random shares, the digest point and the seed fix the polynomial
+        let mut digest_point = [0u8; 32];
+        rng.fill_bytes(&mut digest_point[DIGEST_BYTES..]);
+        let mut digest = hmac_sha512(&digest_point[DIGEST_BYTES..], &self.seed_bytes);
+        digest_point[..DIGEST_BYTES].copy_from_slice(&digest[..DIGEST_BYTES]);
+        digest.zeroize();
+
+        let mut base: Vec<(u8, [u8; 32])> = (0..threshold - 2).map(|x| (x, rng.gen())).collect();
+        base.push((DIGEST_X, digest_point));
+        base.push((SECRET_X, self.seed_bytes));
+        let out = (0..shares)
+            .map(|x| match base.iter().find(|(bx, _)| *bx == x) {
+                Some((_, value)) => share(x, *value),
+                None => share(x, interpolate(&base, x)),
+            })
+            .collect();
+        base.iter_mut().for_each(|(_, value)| value.zeroize());
+        digest_point.zeroize();
+        Ok(out)
+    }
+
+    /// RECOVER
+    /// Any `threshold` shares of one split; extra shares are ignored
+    pub fn recover(shares: &[Share]) -> Result<MasterSeed, ShareError> {
+        let first = shares.first().ok_or(ShareError::TooFewShares { threshold: 1, got: 0 })?;
+        let (identifier, threshold) = (first.identifier, first.threshold);
+        if shares.iter().any(|s| s.identifier != identifier || s.threshold != threshold) {
+            return Err(ShareError::Mixed);
+        }
+        for (i, share) in shares.iter().enumerate() {
+            if shares[..i].iter().any(|s| s.index == share.index) {
+                return Err(ShareError::DuplicateIndex { index: share.index });
+            }
+        }
+        if threshold == 0 || shares.len() < threshold as usize {
+            return Err(ShareError::TooFewShares { threshold, got: shares.len() });
+        }
+        if threshold == 1 {
+            return Ok(MasterSeed { seed_bytes: first.value });
+        }
+
+        let mut points: Vec<(u8, [u8; 32])> = shares[..threshold as usize].iter().map(|s| (s.index, s.value)).collect();
+        let seed = MasterSeed { seed_bytes: interpolate(&points, SECRET_X) };
+        let mut digest_point = interpolate(&points, DIGEST_X);
+        points.iter_mut().for_each(|(_, value)| value.zeroize());
+        let mut digest = hmac_sha512(&digest_point[DIGEST_BYTES..], &seed.seed_bytes);
+        // Compare without early exit
+        let diff = digest[..DIGEST_BYTES].iter().zip(&digest_point[..DIGEST_BYTES]).fold(0u8, |acc, (a, b)| acc | (a ^ b));
+        digest.zeroize();
+        digest_point.zeroize();
+        match diff {
+            0 => Ok(seed),
+            _ => Err(ShareError::Digest),
+        }
+    }
+}
+
+// --- GF(256) ---
+// The value at `x` of the polynomial through `points` (distinct x)
+fn interpolate(points: &[(u8, [u8; 32])], x: u8) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    for (i, (xi, yi)) in points.iter().enumerate() {
+        // Lagrange basis at x; subtraction is xor
+        let (num, den) = points
+            .iter()
+            .enumerate()
+            .filter(|&(j, _)| j != i)
+            .fold((1u8, 1u8), |(num, den), (_, (xj, _))| (gf_mul(num, x ^ xj), gf_mul(den, xi ^ xj)));
+        let basis = gf_mul(num, gf_inv(den));
+        out.iter_mut().zip(yi).for_each(|(o, &y)| *o ^= gf_mul(basis, y));
+    }
+    out
+}
+
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut p = 0u8;
+    for _ in 0..8 {
+        p ^= a & 0u8.wrapping_sub(b & 1);
+        a = (a << 1) ^ (0x1b & 0u8.wrapping_sub(a >> 7));
+        b >>= 1;
+    }
+    p
+}
+
+// a^254 = a^-1 (and 0 for 0)
+fn gf_inv(a: u8) -> u8 {
+    let mut out = 1u8;
+    let mut base = a;
+    for bit in 0..8 {
+        let square_in = gf_mul(out, base);
+        out = if 254 >> bit & 1 == 1 { square_in } else { out };
+        base = gf_mul(base, base);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn any_three_of_five_recover_the_seed() {
+        assert_eq!(gf_mul(0x53, 0xca), 0x01); // The AES field's textbook pair
+        assert!((1..=255u8).all(|a| gf_mul(a, gf_inv(a)) == 1));
+
+        let mut rng = StdRng::seed_from_u64(4338);
+        let seed = MasterSeed { seed_bytes: std::array::from_fn(|i| i as u8 * 7) };
+        let shares = seed.split(3, 5, &mut rng).unwrap();
+        let words: Vec<String> = shares.iter().map(Share::to_mnemonic).collect();
+        assert!(words.iter().all(|w| w.split(' ').count() == SHARE_WORDS));
+
+        for pick in [[0, 1, 2], [4, 2, 0], [1, 3, 4]] {
+            let chosen: Vec<Share> = pick.iter().map(|&i| Share::from_mnemonic(&words[i]).unwrap()).collect();
+            assert_eq!(MasterSeed::recover(&chosen).unwrap().seed_bytes, seed.seed_bytes);
+        }
+        let two: Vec<Share> = words[..2].iter().map(|w| Share::from_mnemonic(w).unwrap()).collect();
+        assert_eq!(MasterSeed::recover(&two).unwrap_err(), ShareError::TooFewShares { threshold: 3, got: 2 });
+        assert_eq!(seed.split(4, 3, &mut rng).unwrap_err(), ShareError::Threshold { threshold: 4, shares: 3 });
+        let single = seed.split(1, 2, &mut rng).unwrap();
+        assert_eq!(MasterSeed::recover(&single[1..]).unwrap().seed_bytes, seed.seed_bytes);
+    }
+
+    #[test]
+    fn wrong_shares_are_caught() {
+        let mut rng = StdRng::seed_from_u64(4339);
+        let seed = MasterSeed { seed_bytes: [0x42; 32] };
+        let mut shares = seed.split(2, 3, &mut rng).unwrap();
+
+        // A changed word fails the checksum
+        let words = shares[0].to_mnemonic();
+        let (first, rest) = words.split_once(' ').unwrap();
+        let typo = format!("{} {}", if first == "zoo" { "abandon" } else { "zoo" }, rest);
+        assert_eq!(Share::from_mnemonic(&typo).unwrap_err(), ShareError::Words(MnemonicError::Checksum));
+
+        // A share altered behind the checksum fails the digest
+        shares[1].value[5] ^= 1;
+        assert_eq!(MasterSeed::recover(&shares[..2]).unwrap_err(), ShareError::Digest);
+
+        let other = seed.split(2, 3, &mut rng).unwrap();
+        let mixed = [Share::from_mnemonic(&shares[0].to_mnemonic()).unwrap(), Share::from_mnemonic(&other[1].to_mnemonic()).unwrap()];
+        assert_eq!(MasterSeed::recover(&mixed).unwrap_err(), ShareError::Mixed);
+        let twice = [Share::from_mnemonic(&words).unwrap(), Share::from_mnemonic(&words).unwrap()];
+        assert_eq!(MasterSeed::recover(&twice).unwrap_err(), ShareError::DuplicateIndex { index: 0 });
+    }
+}
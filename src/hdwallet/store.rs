@@ -0,0 +1,666 @@
+// src/hdwallet/store.rs
+// WALLET STORE: seeds and key metadata, encrypted at rest.
+//
+// One file per wallet:
+//   "OLCW" || version || memory_kib (u32 LE) || iterations (u32 LE)
+//          || salt (16) || nonce (16) || sealed contents || tag (32)
+// the envelope of the key files in jordan_sig.rs: the sealing key is GSH-PW
+// of the passphrase and salt, and FlutterCipher::seal takes the header as
+// associated data. The key is derived once, at create or open; a save
+// reseals under it with a fresh nonce, so recording a signature does not
+// pay the password cost again.
+//
+// The contents are a storage::Store (text, one namespace per kind of
+// record), so their format changes ship as storage::Migration steps:
+//   seeds   <name>                 hex seed bytes
+//   keys    <name> <path>          hex address of the node's Jordan key
+//   labels  <name> <path>          free text
+//   ots     <name> <path>          one-time signatures made (decimal)
+//   ots-next <name> <chain path>   next one-time key index on the chain,
+//                                  or next XMSS leaf of the node there
+// `open_with` runs the given Migrator over a freshly opened store, backs
+// the old file up as storage::Migrator::migrate_file does, and leaves the
+// save to the caller; a namespace this build cannot read is refused.
+//
+// One-time keys (the hash-chain signature of hdwallet.rs) may sign once:
+// a second message reveals chain states past the first one's, and with
+// enough of them anyone can sign. `reserve_ots` hands out the next unused
+// index of a chain, counts the key as used and saves the file before it
+// returns, so neither a crash nor a second thread can hand the same key
+// out again; the OtsReservation it returns signs once and is consumed.
+// An XMSS tree (xmss.rs) under a node keeps its next leaf in the same
+// ots-next counter, advanced and saved before each signature; a node is
+// either a one-time chain or an XMSS tree, never both.
+// Threads share the store as Arc<Mutex<WalletStore>> (as vdf_service does)
+// and hold the lock only to reserve, not to sign. One process per file.
+//
+// A save writes `<path>.tmp`, syncs it, and renames it over the wallet, so
+// a crash leaves either the old file or the new one, never half of each.
+
+use super::path::{ChildNumber, DerivationPath, ExtendedKey, PathError};
+use super::xmss::{XmssError, XmssKey, XmssSignature};
+use super::{BiOctonion, FlutterEngine, FlutterKeyPair, FlutterSignature, MasterSeed};
+use crate::address::Address;
+use crate::algebra::encoding::{hex, unhex};
+use crate::flt_cipher::{FlutterCipher, TAG_BYTES};
+use crate::gsh::pwhash::{pwhash, PwHashError, PwHashParams};
+use crate::redact::Redacted;
+use crate::storage::{MigrationError, MigrationReport, Migrator, Store, StoreError};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+use rand::prelude::*;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const MAGIC: &[u8; 4] = b"OLCW";
+pub const WALLET_FILE_VERSION: u8 = 1;
+const HEADER_BYTES: usize = 4 + 1 + 4 + 4 + 16 + 16;
+
+// Namespaces and the version this build reads and writes
+const SEEDS: &str = "seeds";
+const KEYS: &str = "keys";
+const LABELS: &str = "labels";
+const OTS: &str = "ots";
+const OTS_NEXT: &str = "ots-next";
+pub const NAMESPACES: [(&str, u32); 5] = [(SEEDS, 1), (KEYS, 1), (LABELS, 1), (OTS, 1), (OTS_NEXT, 1)];
+
+#[derive(Debug)]
+pub enum WalletStoreError {
+    Io(std::io::Error),
+    // Not a wallet file, or truncated
+    Format,
+    Version { got: u8 },
+    Cost(PwHashError),
+    // Or the file was modified
+    WrongPassphrase,
+    // The contents do not parse
+    Store(StoreError),
+    Corrupt { namespace: &'static str, key: String },
+    // A namespace is at a version the migrations given cannot reach
+    NamespaceVersion { namespace: String, got: u32, supported: Option<u32> },
+    Migration(MigrationError),
+    // Seed names are non-empty, without whitespace; labels are one line
+    BadName(String),
+    BadLabel,
+    DuplicateSeed(String),
+    UnknownSeed(String),
+    UnknownKey,
+    Path(PathError),
+    // The one-time key has signed before
+    OtsReused { id: KeyId, uses: u32 },
+    Xmss(XmssError),
+}
+
+// A derived key, by the seed it comes from and its path
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct KeyId {
+    pub seed: String,
+    pub path: DerivationPath,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyRecord {
+    pub address: Address,
+    pub label: Option<String>,
+    pub ots_uses: u32,
+}
+
+pub struct WalletStore {
+    path: PathBuf,
+    cost: PwHashParams,
+    salt: [u8; 16],
+    kek: [u8; 32],
+    seeds: BTreeMap<String, MasterSeed>,
+    keys: BTreeMap<KeyId, KeyRecord>,
+    ots_next: BTreeMap<KeyId, u32>, // By chain
+}
+
+impl Zeroize for WalletStore {
+    fn zeroize(&mut self) {
+        self.kek.zeroize();
+        self.seeds.values_mut().for_each(Zeroize::zeroize);
+    }
+}
+
+impl Drop for WalletStore {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for WalletStore {}
+const _: () = crate::redact::assert_zeroize_on_drop::<WalletStore>();
+
+// Manual Debug: the sealing key is redacted (the seeds redact themselves)
+impl fmt::Debug for WalletStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WalletStore")
+            .field("path", &self.path)
+            .field("cost", &self.cost)
+            .field("kek", &Redacted(&self.kek))
+            .field("seeds", &self.seeds)
+            .field("keys", &self.keys)
+            .field("ots_next", &self.ots_next)
+            .finish()
+    }
+}
+
+impl WalletStore {
+    /// NEW WALLET FILE
+    /// Written at once, empty, at the wallet default cost; an existing file
+    /// is never overwritten
+    pub fn create<R: Rng + ?Sized>(path: &Path, passphrase: &[u8], rng: &mut R) -> Result<Self, WalletStoreError> {
+        Self::create_with(path, passphrase, PwHashParams::default(), rng)
+    }
+
+    pub fn create_with<R: Rng + ?Sized>(
+        path: &Path,
+        passphrase: &[u8],
+        cost: PwHashParams,
+        rng: &mut R,
+    ) -> Result<Self, WalletStoreError> {
+        if path.exists() {
+            return Err(WalletStoreError::Io(std::io::ErrorKind::AlreadyExists.into()));
+        }
+        let salt: [u8; 16] = rng.gen();
+        let kek = pwhash(passphrase, &salt, &cost).map_err(WalletStoreError::Cost)?;
+        let store = WalletStore {
+            path: path.to_path_buf(),
+            cost,
+            salt,
+            kek,
+            seeds: BTreeMap::new(),
+            keys: BTreeMap::new(),
+            ots_next: BTreeMap::new(),
+        };
+        store.save(rng)?;
+        Ok(store)
+    }
+
+    /// OPEN
+    pub fn open(path: &Path, passphrase: &[u8]) -> Result<Self, WalletStoreError> {
+        Ok(Self::open_with(path, passphrase, &Migrator::new())?.0)
+    }
+
+    /// OPEN AND MIGRATE
+    /// The returned store is migrated in memory; `save` writes it back
+    pub fn open_with(path: &Path, passphrase: &[u8], migrator: &Migrator) -> Result<(Self, MigrationReport), WalletStoreError> {
+        let bytes = std::fs::read(path).map_err(WalletStoreError::Io)?;
+        if bytes.len() < HEADER_BYTES + TAG_BYTES || &bytes[..4] != MAGIC {
+            return Err(WalletStoreError::Format);
+        }
+        if bytes[4] != WALLET_FILE_VERSION {
+            return Err(WalletStoreError::Version { got: bytes[4] });
+        }
+        let (header, sealed) = bytes.split_at(HEADER_BYTES);
+        let word = |at: usize| u32::from_le_bytes([header[at], header[at + 1], header[at + 2], header[at + 3]]);
+        let cost = PwHashParams::new(word(5), word(9)).map_err(WalletStoreError::Cost)?;
+        let salt: [u8; 16] = header[13..29].try_into().unwrap_or_else(|_| unreachable!());
+        let nonce = std::array::from_fn(|i| u16::from_le_bytes([header[29 + 2 * i], header[30 + 2 * i]]));
+
+        let mut kek = pwhash(passphrase, &salt, &cost).map_err(WalletStoreError::Cost)?;
+        let Some(plain) = FlutterCipher::open(&kek, nonce, header, sealed) else {
+            kek.zeroize();
+            return Err(WalletStoreError::WrongPassphrase);
+        };
+        let mut wallet = WalletStore { path: path.to_path_buf(), cost, salt, kek, seeds: BTreeMap::new(), keys: BTreeMap::new(), ots_next: BTreeMap::new() };
+
+        let mut plain = String::from_utf8(plain).map_err(|e| {
+            e.into_bytes().zeroize();
+            WalletStoreError::Format
+        })?;
+        let decoded = Store::decode(&plain);
+        wipe_string(&mut plain);
+        let store = decoded.map_err(WalletStoreError::Store)?;
+
+        let (migrated, steps) = migrator.dry_run(&store).map_err(WalletStoreError::Migration)?;
+        wipe_store(store);
+        let backup = if steps.is_empty() { None } else { Some(backup(path)?) };
+        let read = wallet.read_contents(&migrated);
+        wipe_store(migrated);
+        read?;
+        Ok((wallet, MigrationReport { steps, backup }))
+    }
+
+    /// ATOMIC SAVE
+    pub fn save<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<(), WalletStoreError> {
+        self.write_contents(self.contents(), rng)
+    }
+
+    fn write_contents<R: Rng + ?Sized>(&self, store: Store, rng: &mut R) -> Result<(), WalletStoreError> {
+        let nonce: [u16; 8] = rng.gen();
+        let mut out = Vec::with_capacity(HEADER_BYTES + TAG_BYTES);
+        out.extend_from_slice(MAGIC);
+        out.push(WALLET_FILE_VERSION);
+        out.extend_from_slice(&self.cost.memory_kib.to_le_bytes());
+        out.extend_from_slice(&self.cost.iterations.to_le_bytes());
+        out.extend_from_slice(&self.salt);
+        out.extend(nonce.iter().flat_map(|n| n.to_le_bytes()));
+
+        let mut plain = store.encode().into_bytes();
+        wipe_store(store);
+        let sealed = FlutterCipher::seal(&self.kek, nonce, &out, &plain);
+        plain.zeroize();
+        out.extend(sealed);
+        write_atomic(&self.path, &out).map_err(WalletStoreError::Io)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    // --- SEEDS ---
+    pub fn add_seed(&mut self, name: &str, seed: MasterSeed) -> Result<(), WalletStoreError> {
+        if name.is_empty() || name.chars().any(|c| c.is_whitespace() || c.is_control()) {
+            return Err(WalletStoreError::BadName(name.to_string()));
+        }
+        if self.seeds.contains_key(name) {
+            return Err(WalletStoreError::DuplicateSeed(name.to_string()));
+        }
+        self.seeds.insert(name.to_string(), seed);
+        Ok(())
+    }
+
+    pub fn seed(&self, name: &str) -> Option<&MasterSeed> {
+        self.seeds.get(name)
+    }
+
+    pub fn seed_names(&self) -> impl Iterator<Item = &str> {
+        self.seeds.keys().map(String::as_str)
+    }
+
+    // --- KEYS ---
+    /// The node at `path` under seed `name`, recorded if it is new
+    pub fn derive(&mut self, name: &str, path: &DerivationPath) -> Result<ExtendedKey, WalletStoreError> {
+        let seed = self.seeds.get(name).ok_or_else(|| WalletStoreError::UnknownSeed(name.to_string()))?;
+        let node = seed.master_key().derive_path(path).map_err(WalletStoreError::Path)?;
+        let id = KeyId { seed: name.to_string(), path: path.clone() };
+        self.keys.entry(id).or_insert_with(|| KeyRecord {
+            address: node.jordan_key().pub_key.address(),
+            label: None,
+            ots_uses: 0,
+        });
+        Ok(node)
+    }
+
+    pub fn key(&self, id: &KeyId) -> Option<&KeyRecord> {
+        self.keys.get(id)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = (&KeyId, &KeyRecord)> {
+        self.keys.iter()
+    }
+
+    pub fn set_label(&mut self, id: &KeyId, label: Option<String>) -> Result<(), WalletStoreError> {
+        if label.as_deref().is_some_and(|l| l.chars().any(char::is_control)) {
+            return Err(WalletStoreError::BadLabel);
+        }
+        self.keys.get_mut(id).ok_or(WalletStoreError::UnknownKey)?.label = label;
+        Ok(())
+    }
+
+    // --- ONE-TIME KEYS ---
+    /// The next unused one-time key on `chain`; saved as used before it is
+    /// returned
+    pub fn reserve_ots<R: Rng + ?Sized>(
+        &mut self,
+        engine: &FlutterEngine,
+        seed: &str,
+        chain: &DerivationPath,
+        rng: &mut R,
+    ) -> Result<OtsReservation, WalletStoreError> {
+        let chain_id = KeyId { seed: seed.to_string(), path: chain.clone() };
+        let mut index = self.ots_next.get(&chain_id).copied().unwrap_or(0);
+        // Skip keys that signed outside this chain's bookkeeping
+        let id = loop {
+            let step = ChildNumber::normal(index).map_err(WalletStoreError::Path)?;
+            let id = KeyId { seed: seed.to_string(), path: chain.child(step).map_err(WalletStoreError::Path)? };
+            if self.keys.get(&id).is_none_or(|record| record.ots_uses == 0) {
+                break id;
+            }
+            index += 1;
+        };
+        self.ots_next.insert(chain_id, index + 1);
+        self.reserve_ots_key(engine, &id, rng)
+    }
+
+    /// The one-time key at `id`, refused if it has ever signed
+    pub fn reserve_ots_key<R: Rng + ?Sized>(
+        &mut self,
+        engine: &FlutterEngine,
+        id: &KeyId,
+        rng: &mut R,
+    ) -> Result<OtsReservation, WalletStoreError> {
+        let node = self.derive(&id.seed, &id.path)?;
+        let record = self.keys.get_mut(id).ok_or(WalletStoreError::UnknownKey)?;
+        if record.ots_uses > 0 {
+            return Err(WalletStoreError::OtsReused { id: id.clone(), uses: record.ots_uses });
+        }
+        record.ots_uses = 1;
+        if let Err(e) = self.save(rng) {
+            // Not on disk, so not handed out
+            self.keys.get_mut(id).ok_or(WalletStoreError::UnknownKey)?.ots_uses = 0;
+            return Err(e);
+        }
+        Ok(OtsReservation { id: id.clone(), keypair: node.keypair(engine) })
+    }
+
+    /// The XMSS tree under `path`, resumed at the store's next leaf
+    pub fn xmss_key(&mut self, seed: &str, path: &DerivationPath, height: u8) -> Result<XmssKey, WalletStoreError> {
+        let node = self.derive(seed, path)?;
+        let mut key = XmssKey::generate(&node, height).map_err(WalletStoreError::Xmss)?;
+        let id = KeyId { seed: seed.to_string(), path: path.clone() };
+        key.resume(self.ots_next.get(&id).copied().unwrap_or(0)).map_err(WalletStoreError::Xmss)?;
+        Ok(key)
+    }
+
+    /// Sign with the next leaf of the tree under `path`; the counter moves
+    /// past it and is saved before the signature is made
+    pub fn sign_xmss<R: Rng + ?Sized>(
+        &mut self,
+        seed: &str,
+        path: &DerivationPath,
+        key: &mut XmssKey,
+        context: &[u8],
+        message: &[u8],
+        rng: &mut R,
+    ) -> Result<XmssSignature, WalletStoreError> {
+        let id = KeyId { seed: seed.to_string(), path: path.clone() };
+        let stored = self.ots_next.get(&id).copied();
+        // Whichever is further along, the key or the store
+        let index = stored.unwrap_or(0).max(key.next_index().ok_or(WalletStoreError::Xmss(XmssError::NotResumed))?);
+        key.resume(index).map_err(WalletStoreError::Xmss)?;
+        if key.remaining() == Some(0) {
+            return Err(WalletStoreError::Xmss(XmssError::Exhausted));
+        }
+        self.ots_next.insert(id.clone(), index + 1);
+        if let Err(e) = self.save(rng) {
+            // Not on disk, so not used
+            match stored {
+                Some(next) => self.ots_next.insert(id, next),
+                None => self.ots_next.remove(&id),
+            };
+            return Err(e);
+        }
+        key.sign(context, message).map_err(WalletStoreError::Xmss)
+    }
+
+    // --- CONTENTS ---
+    fn contents(&self) -> Store {
+        let mut store = Store::new();
+        for (namespace, version) in NAMESPACES {
+            store.namespace_mut(namespace, version);
+        }
+        for (name, seed) in &self.seeds {
+            store.namespace_mut(SEEDS, 1).entries.insert(name.clone(), hex(&seed.seed_bytes));
+        }
+        for (id, record) in &self.keys {
+            let key = format!("{} {}", id.seed, id.path);
+            store.namespace_mut(KEYS, 1).entries.insert(key.clone(), hex(&record.address.0));
+            if let Some(label) = &record.label {
+                store.namespace_mut(LABELS, 1).entries.insert(key.clone(), label.clone());
+            }
+            if record.ots_uses > 0 {
+                store.namespace_mut(OTS, 1).entries.insert(key, record.ots_uses.to_string());
+            }
+        }
+        for (chain, next) in &self.ots_next {
+            store.namespace_mut(OTS_NEXT, 1).entries.insert(format!("{} {}", chain.seed, chain.path), next.to_string());
+        }
+        store
+    }
+
+    fn read_contents(&mut self, store: &Store) -> Result<(), WalletStoreError> {
+        for (name, ns) in &store.namespaces {
+            let supported = NAMESPACES.iter().find(|(n, _)| n == name).map(|&(_, v)| v);
+            if supported != Some(ns.version) {
+                return Err(WalletStoreError::NamespaceVersion { namespace: name.clone(), got: ns.version, supported });
+            }
+        }
+        let entries = |namespace: &str| store.namespace(namespace).into_iter().flat_map(|ns| &ns.entries);
+
+        for (name, value) in entries(SEEDS) {
+            let corrupt = || WalletStoreError::Corrupt { namespace: SEEDS, key: name.clone() };
+            let mut bytes = unhex(value).ok_or_else(corrupt)?;
+            let seed_bytes = bytes.as_slice().try_into().map_err(|_| corrupt());
+            bytes.zeroize();
+            self.seeds.insert(name.clone(), MasterSeed { seed_bytes: seed_bytes? });
+        }
+        for (key, value) in entries(KEYS) {
+            let corrupt = || WalletStoreError::Corrupt { namespace: KEYS, key: key.clone() };
+            let id = parse_key_id(key).filter(|id| self.seeds.contains_key(&id.seed)).ok_or_else(corrupt)?;
+            let address = unhex(value).and_then(|b| b.try_into().ok()).map(Address).ok_or_else(corrupt)?;
+            self.keys.insert(id, KeyRecord { address, label: None, ots_uses: 0 });
+        }
+        for (key, value) in entries(LABELS) {
+            let record = parse_key_id(key).and_then(|id| self.keys.get_mut(&id));
+            record.ok_or_else(|| WalletStoreError::Corrupt { namespace: LABELS, key: key.clone() })?.label = Some(value.clone());
+        }
+        for (key, value) in entries(OTS) {
+            let corrupt = || WalletStoreError::Corrupt { namespace: OTS, key: key.clone() };
+            let uses = value.parse().map_err(|_| corrupt())?;
+            parse_key_id(key).and_then(|id| self.keys.get_mut(&id)).ok_or_else(corrupt)?.ots_uses = uses;
+        }
+        for (key, value) in entries(OTS_NEXT) {
+            let corrupt = || WalletStoreError::Corrupt { namespace: OTS_NEXT, key: key.clone() };
+            let chain = parse_key_id(key).filter(|id| self.seeds.contains_key(&id.seed)).ok_or_else(corrupt)?;
+            self.ots_next.insert(chain, value.parse().map_err(|_| corrupt())?);
+        }
+        Ok(())
+    }
+}
+
+// A one-time key, counted as used; signing consumes it
+pub struct OtsReservation {
+    pub id: KeyId,
+    keypair: FlutterKeyPair,
+}
+
+impl fmt::Debug for OtsReservation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OtsReservation").field("id", &self.id).field("keypair", &self.keypair).finish()
+    }
+}
+
+impl OtsReservation {
+    pub fn public_key(&self) -> &BiOctonion {
+        &self.keypair.public_key
+    }
+
+    pub fn sign(self, engine: &FlutterEngine, message: &[u8]) -> FlutterSignature {
+        self.keypair.sign(engine, message)
+    }
+}
+
+// "<seed> <path>"
+fn parse_key_id(key: &str) -> Option<KeyId> {
+    let (seed, path) = key.split_once(' ')?;
+    Some(KeyId { seed: seed.to_string(), path: path.parse().ok()? })
+}
+
+fn write_atomic(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    let mut file = std::fs::File::create(&tmp)?;
+    file.write_all(bytes)?;
+    file.sync_all()?;
+    std::fs::rename(&tmp, path)
+}
+
+// `<path>.bak-<unix secs>`, as storage::Migrator::migrate_file names it
+fn backup(path: &Path) -> Result<PathBuf, WalletStoreError> {
+    let stamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(format!(".bak-{}", stamp));
+    let backup = PathBuf::from(backup);
+    std::fs::copy(path, &backup).map_err(WalletStoreError::Io)?;
+    Ok(backup)
+}
+
+// Plain contents hold the seeds in hex: wipe them once read or sealed
+fn wipe_string(s: &mut String) {
+    let mut bytes = std::mem::take(s).into_bytes();
+    bytes.zeroize();
+}
+
+fn wipe_store(store: Store) {
+    for mut ns in store.namespaces.into_values() {
+        ns.entries.values_mut().for_each(wipe_string);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{Migration, Namespace};
+
+    const FAST: PwHashParams = PwHashParams { memory_kib: 8, iterations: 1 };
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("olc_wallet_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir.join("wallet.olcw")
+    }
+
+    #[test]
+    fn wallets_survive_a_restart() {
+        let mut rng = StdRng::seed_from_u64(4333);
+        let path = scratch("restart");
+        let mut wallet = WalletStore::create_with(&path, b"hunter2", FAST, &mut rng).unwrap();
+        assert!(matches!(WalletStore::create_with(&path, b"hunter2", FAST, &mut rng), Err(WalletStoreError::Io(_))));
+
+        wallet.add_seed("main", MasterSeed { seed_bytes: [0x42; 32] }).unwrap();
+        let receive = DerivationPath::bip44(0, 0, 0, 3).unwrap();
+        let node = wallet.derive("main", &receive).unwrap();
+        let id = KeyId { seed: "main".to_string(), path: receive };
+        wallet.set_label(&id, Some("rent".to_string())).unwrap();
+        assert!(matches!(wallet.set_label(&id, Some("a\tb".to_string())), Err(WalletStoreError::BadLabel)));
+        let engine = FlutterEngine::new(0x1910, [0xAB; 16]);
+        wallet.reserve_ots_key(&engine, &id, &mut rng).unwrap().sign(&engine, b"once");
+        assert!(matches!(wallet.reserve_ots_key(&engine, &id, &mut rng), Err(WalletStoreError::OtsReused { uses: 1, .. })));
+        wallet.save(&mut rng).unwrap();
+        assert!(!path.with_extension("olcw.tmp").exists());
+
+        let reopened = WalletStore::open(&path, b"hunter2").unwrap();
+        assert_eq!(reopened.seed("main").unwrap().seed_bytes, [0x42; 32]);
+        assert_eq!(
+            reopened.key(&id),
+            Some(&KeyRecord { address: node.jordan_key().pub_key.address(), label: Some("rent".to_string()), ots_uses: 1 })
+        );
+        assert!(matches!(WalletStore::open(&path, b"hunter3"), Err(WalletStoreError::WrongPassphrase)));
+    }
+
+    // ots v0 -> v1: counters were hex
+    struct DecimalCounters;
+    impl Migration for DecimalCounters {
+        fn name(&self) -> &str { "decimal-counters" }
+        fn namespace(&self) -> &str { OTS }
+        fn source_version(&self) -> u32 { 0 }
+        fn migrate(&self, ns: &mut Namespace) -> Result<(), String> {
+            for v in ns.entries.values_mut() {
+                *v = u32::from_str_radix(v, 16).map_err(|_| format!("bad counter '{}'", v))?.to_string();
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn old_contents_are_migrated_on_open() {
+        let mut rng = StdRng::seed_from_u64(4334);
+        let path = scratch("migrate");
+        let mut wallet = WalletStore::create_with(&path, b"pw", FAST, &mut rng).unwrap();
+        wallet.add_seed("cold", MasterSeed { seed_bytes: [7; 32] }).unwrap();
+        let id = KeyId { seed: "cold".to_string(), path: "m/0'/1".parse().unwrap() };
+        wallet.derive("cold", &id.path).unwrap();
+        let mut old = wallet.contents();
+        old.namespaces.insert(OTS.to_string(), Namespace::new(0));
+        old.namespace_mut(OTS, 0).entries.insert("cold m/0'/1".to_string(), "1f".to_string());
+        wallet.write_contents(old, &mut rng).unwrap();
+
+        assert!(matches!(
+            WalletStore::open(&path, b"pw"),
+            Err(WalletStoreError::NamespaceVersion { got: 0, supported: Some(1), .. })
+        ));
+        let mut migrator = Migrator::new();
+        migrator.register(Box::new(DecimalCounters));
+        let (migrated, report) = WalletStore::open_with(&path, b"pw", &migrator).unwrap();
+        assert_eq!(report.steps.len(), 1);
+        assert!(report.backup.unwrap().exists());
+        assert_eq!(migrated.key(&id).unwrap().ots_uses, 31);
+        migrated.save(&mut rng).unwrap();
+        assert_eq!(WalletStore::open(&path, b"pw").unwrap().key(&id).unwrap().ots_uses, 31);
+    }
+
+    #[test]
+    fn threads_never_share_a_one_time_key() {
+        let path = scratch("ots");
+        let mut rng = StdRng::seed_from_u64(4336);
+        let mut wallet = WalletStore::create_with(&path, b"pw", FAST, &mut rng).unwrap();
+        wallet.add_seed("hot", MasterSeed { seed_bytes: [9; 32] }).unwrap();
+        let chain: DerivationPath = "m/7'".parse().unwrap();
+        // Signed with by hand before the wallet tracked this chain
+        let id = |index| KeyId { seed: "hot".to_string(), path: chain.child(ChildNumber::normal(index).unwrap()).unwrap() };
+        let engine = FlutterEngine::new(0x1910, [0xAB; 16]);
+        wallet.reserve_ots_key(&engine, &id(2), &mut rng).unwrap();
+
+        let shared = std::sync::Arc::new(std::sync::Mutex::new(wallet));
+        let workers: Vec<_> = (0..4)
+            .map(|t| {
+                let (shared, chain) = (shared.clone(), chain.clone());
+                std::thread::spawn(move || {
+                    let engine = FlutterEngine::new(0x1910, [0xAB; 16]);
+                    let mut rng = StdRng::seed_from_u64(t);
+                    (0..3)
+                        .map(|_| {
+                            let reservation = shared.lock().unwrap().reserve_ots(&engine, "hot", &chain, &mut rng).unwrap();
+                            let id = reservation.id.clone();
+                            reservation.sign(&engine, b"spend");
+                            id
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        let mut ids: Vec<KeyId> = workers.into_iter().flat_map(|w| w.join().unwrap()).collect();
+        ids.sort();
+        assert_eq!(ids, (0..13).filter(|&i| i != 2).map(id).collect::<Vec<_>>());
+
+        // The counters were on disk before each key was handed out
+        drop(shared);
+        let mut reopened = WalletStore::open(&path, b"pw").unwrap();
+        assert!(ids.iter().all(|id| reopened.key(id).unwrap().ots_uses == 1));
+        assert_eq!(reopened.reserve_ots(&engine, "hot", &chain, &mut rng).unwrap().id, id(13));
+    }
+
+    #[test]
+    fn xmss_leaves_survive_a_restart() {
+        let path = scratch("xmss");
+        let mut rng = StdRng::seed_from_u64(4337);
+        let mut wallet = WalletStore::create_with(&path, b"pw", FAST, &mut rng).unwrap();
+        wallet.add_seed("hot", MasterSeed { seed_bytes: [0x37; 32] }).unwrap();
+        let node: DerivationPath = "m/44'/0'/0'/2'".parse().unwrap();
+        let mut key = wallet.xmss_key("hot", &node, 2).unwrap();
+        let pk = *key.public_key();
+        let first = wallet.sign_xmss("hot", &node, &mut key, b"ctx", b"one", &mut rng).unwrap();
+        assert_eq!((first.index, pk.verify(b"ctx", b"one", &first)), (0, Ok(())));
+
+        // A key regenerated after a restart picks up at the next leaf
+        drop(wallet);
+        let mut reopened = WalletStore::open(&path, b"pw").unwrap();
+        let mut again = reopened.xmss_key("hot", &node, 2).unwrap();
+        assert_eq!(again.next_index(), Some(1));
+        assert_eq!(reopened.sign_xmss("hot", &node, &mut again, b"ctx", b"two", &mut rng).unwrap().index, 1);
+        // The stale key in hand is moved past what the store has used
+        assert_eq!(reopened.sign_xmss("hot", &node, &mut key, b"ctx", b"three", &mut rng).unwrap().index, 2);
+        reopened.sign_xmss("hot", &node, &mut again, b"ctx", b"four", &mut rng).unwrap();
+        assert!(matches!(
+            reopened.sign_xmss("hot", &node, &mut again, b"ctx", b"five", &mut rng),
+            Err(WalletStoreError::Xmss(XmssError::Exhausted))
+        ));
+    }
+}
@@ -0,0 +1,327 @@
+// src/hdwallet/watch.rs
+// WATCH-ONLY WALLET: account xpubs and bare addresses, no secrets.
+//
+// The online half of a cold-storage setup. It holds account-level xpubs
+// (xpub.rs) and addresses imported one by one, and nothing that can sign:
+// there is no secret key field to fill and no sign method to call. What it
+// produces is UnsignedTransactions, carried to the offline signer and back.
+//
+// Each account has a receive chain (xpub/0/i) and a change chain
+// (xpub/1/i). The wallet watches LOOKAHEAD addresses past the last one used
+// on each chain, so a payment to an address handed out but not yet seen
+// is still recognized; a coin on a watched address moves the window.
+//
+// Accounts and addresses can also come in as wallet.rs descriptors:
+// `import` takes either form and `descriptors` exports the accounts.
+//
+// Coins are tracked with their witnesses: `receive` only takes a coin
+// whose witness proves it under the given Horizon root, and the witness is
+// kept so the unsigned spend can carry it. Witnesses go stale as the tree
+// changes; `update_witness` swaps in a fresh one.
+//
+// Spends go through builder.rs: one transaction per coin, each paying
+// SPEND_SIZE_BYTES at the fee rate, coins chosen to net the amount exactly.
+
+use super::builder::{BuildError, CoinSource, TxBuilder};
+use super::path::{ChildNumber, DerivationPath, PathError};
+use super::xpub::ExtendedPublicKey;
+use crate::address::Address;
+use crate::horizon::{UnsignedTransaction, Utxo, Witness};
+use crate::jordan_sig::PublicKey;
+use crate::wallet::{CoinId, Descriptor, WalletError};
+use std::collections::BTreeMap;
+
+pub const LOOKAHEAD: u32 = 20;
+pub const RECEIVE_CHAIN: u32 = 0;
+pub const CHANGE_CHAIN: u32 = 1;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WatchError {
+    Path(PathError),
+    UnknownAccount(usize),
+    UnknownChain(u32),
+    UnknownCoin(CoinId),
+    // The witness does not prove the coin under the root it was given with
+    BadWitness { expected: String, got: String },
+}
+
+// Where a watched address comes from
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    Derived { account: usize, chain: u32, index: u32 },
+    Imported,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WatchedCoin {
+    pub utxo: Utxo,
+    pub witness: Witness,
+}
+
+#[derive(Clone, Debug)]
+struct Account {
+    xpub: ExtendedPublicKey,
+    next: [u32; 2],    // First unused index, per chain
+    watched: [u32; 2], // Addresses derived so far, per chain
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct WatchOnlyWallet {
+    accounts: Vec<Account>,
+    addresses: BTreeMap<Address, Source>,
+    coins: BTreeMap<CoinId, WatchedCoin>,
+}
+
+impl WatchOnlyWallet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_xpubs(xpubs: impl IntoIterator<Item = ExtendedPublicKey>) -> Result<Self, WatchError> {
+        let mut wallet = Self::new();
+        for xpub in xpubs {
+            wallet.add_xpub(xpub)?;
+        }
+        Ok(wallet)
+    }
+
+    /// Watch an account; its index in this wallet
+    pub fn add_xpub(&mut self, xpub: ExtendedPublicKey) -> Result<usize, WatchError> {
+        let account = self.accounts.len();
+        self.accounts.push(Account { xpub, next: [0; 2], watched: [0; 2] });
+        for chain in [RECEIVE_CHAIN, CHANGE_CHAIN] {
+            self.watch_to(account, chain, LOOKAHEAD)?;
+        }
+        Ok(account)
+    }
+
+    /// Watch what a descriptor describes: an account's chains, or one key
+    pub fn import(&mut self, descriptor: &Descriptor) -> Result<(), WatchError> {
+        match descriptor {
+            Descriptor::Key(key) => self.add_address(key.address()),
+            Descriptor::Account { xpub, path } => {
+                self.add_xpub(xpub.derive_path(path).map_err(WatchError::Path)?)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// One account descriptor per watched xpub, in account order
+    pub fn descriptors(&self) -> Vec<Descriptor> {
+        self.accounts.iter().map(|a| Descriptor::Account { xpub: a.xpub.clone(), path: DerivationPath::master() }).collect()
+    }
+
+    pub fn add_address(&mut self, address: Address) {
+        self.addresses.entry(address).or_insert(Source::Imported);
+    }
+
+    pub fn source(&self, address: &Address) -> Option<&Source> {
+        self.addresses.get(address)
+    }
+
+    // --- ADDRESSES ---
+    pub fn address_at(&self, account: usize, chain: u32, index: u32) -> Result<(Address, PublicKey), WatchError> {
+        let xpub = &self.accounts.get(account).ok_or(WatchError::UnknownAccount(account))?.xpub;
+        let path = DerivationPath(vec![
+            ChildNumber::normal(chain).map_err(WatchError::Path)?,
+            ChildNumber::normal(index).map_err(WatchError::Path)?,
+        ]);
+        let key = xpub.derive_path(&path).map_err(WatchError::Path)?.key;
+        Ok((key.address(), key))
+    }
+
+    /// The first address of `chain` not yet handed out, marked used
+    pub fn next_address(&mut self, account: usize, chain: u32) -> Result<Address, WatchError> {
+        let slot = chain_slot(chain)?;
+        let index = self.accounts.get(account).ok_or(WatchError::UnknownAccount(account))?.next[slot];
+        let (address, _) = self.address_at(account, chain, index)?;
+        self.mark_used(account, chain, index)?;
+        Ok(address)
+    }
+
+    pub fn receive_address(&mut self, account: usize) -> Result<Address, WatchError> {
+        self.next_address(account, RECEIVE_CHAIN)
+    }
+
+    pub fn change_address(&mut self, account: usize) -> Result<Address, WatchError> {
+        self.next_address(account, CHANGE_CHAIN)
+    }
+
+    fn mark_used(&mut self, account: usize, chain: u32, index: u32) -> Result<(), WatchError> {
+        let slot = chain_slot(chain)?;
+        let next = &mut self.accounts[account].next[slot];
+        *next = (*next).max(index.saturating_add(1));
+        let upto = next.saturating_add(LOOKAHEAD);
+        self.watch_to(account, chain, upto)
+    }
+
+    fn watch_to(&mut self, account: usize, chain: u32, upto: u32) -> Result<(), WatchError> {
+        let slot = chain_slot(chain)?;
+        for index in self.accounts[account].watched[slot]..upto {
+            let (address, _) = self.address_at(account, chain, index)?;
+            self.addresses.insert(address, Source::Derived { account, chain, index });
+            self.accounts[account].watched[slot] = index + 1;
+        }
+        Ok(())
+    }
+
+    // --- COINS ---
+    /// Track a coin proven under `root`; false if it is not ours
+    pub fn receive(&mut self, utxo: Utxo, witness: Witness, root: &str) -> Result<bool, WatchError> {
+        let Some(source) = self.addresses.get(&utxo.owner).cloned() else { return Ok(false) };
+        check_witness(&utxo, &witness, root)?;
+        if let Source::Derived { account, chain, index } = source {
+            self.mark_used(account, chain, index)?;
+        }
+        self.coins.insert(utxo.id, WatchedCoin { utxo, witness });
+        Ok(true)
+    }
+
+    pub fn update_witness(&mut self, id: &CoinId, witness: Witness, root: &str) -> Result<(), WatchError> {
+        let coin = self.coins.get_mut(id).ok_or(WatchError::UnknownCoin(*id))?;
+        check_witness(&coin.utxo, &witness, root)?;
+        coin.witness = witness;
+        Ok(())
+    }
+
+    pub fn spent(&mut self, id: &CoinId) -> Option<WatchedCoin> {
+        self.coins.remove(id)
+    }
+
+    pub fn coins(&self) -> impl Iterator<Item = &WatchedCoin> {
+        self.coins.values()
+    }
+
+    pub fn balance(&self) -> u64 {
+        self.coins.values().map(|c| c.utxo.amount).sum()
+    }
+
+    // --- SPENDS ---
+    /// Unsigned spends netting `recipient` exactly `amount`
+    pub fn build_unsigned(&self, recipient: Address, amount: u64, fee_rate: u64) -> Result<Vec<UnsignedTransaction>, BuildError> {
+        let plan = TxBuilder::new(self, recipient, amount, fee_rate).build()?;
+        // Derived keys are re-derived rather than kept per address
+        let owner_key = |owner: &Address| match self.addresses.get(owner) {
+            Some(&Source::Derived { account, chain, index }) => self.address_at(account, chain, index).ok().map(|(_, key)| key),
+            _ => None,
+        };
+        Ok(plan
+            .payment
+            .into_iter()
+            .map(|spend| UnsignedTransaction { owner_key: owner_key(&spend.input_utxo.owner), ..spend })
+            .collect())
+    }
+}
+
+// Every watched coin is spendable; there is no change chain default, so a
+// spend naming extra inputs must say where the change goes.
+impl CoinSource for WatchOnlyWallet {
+    fn spendable(&self) -> Vec<WatchedCoin> {
+        self.coins.values().cloned().collect()
+    }
+
+    fn input(&self, id: &CoinId) -> Result<WatchedCoin, WalletError> {
+        self.coins.get(id).cloned().ok_or(WalletError::UnknownCoin(*id))
+    }
+}
+
+fn chain_slot(chain: u32) -> Result<usize, WatchError> {
+    match chain {
+        RECEIVE_CHAIN => Ok(0),
+        CHANGE_CHAIN => Ok(1),
+        _ => Err(WatchError::UnknownChain(chain)),
+    }
+}
+
+fn check_witness(utxo: &Utxo, witness: &Witness, root: &str) -> Result<(), WatchError> {
+    let got = witness.root(&utxo.leaf_hash());
+    if got != root {
+        return Err(WatchError::BadWitness { expected: root.to_string(), got });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra::encoding::hex;
+    use crate::hdwallet::MasterSeed;
+    use crate::horizon::{HorizonAccumulator, HorizonValidator, SPEND_SIZE_BYTES, TX_CONTEXT};
+    use crate::jordan_sig::JordanSchnorr;
+    use crate::wallet::DescriptorError;
+    use rand::prelude::*;
+    use sha2::{Digest, Sha256};
+
+    const FEE: u64 = SPEND_SIZE_BYTES as u64;
+
+    #[test]
+    fn account_descriptors_round_trip_and_derive_the_full_wallets_addresses() {
+        let account = MasterSeed { seed_bytes: [0x43; 32] }.master_key().derive_path(&"m/44'/0'/0'".parse().unwrap()).unwrap();
+        let descriptor = Descriptor::account(account.public(), "m/3".parse().unwrap()).unwrap();
+        let exported = descriptor.to_string();
+        assert!(exported.starts_with("jordan-schnorr(fxpub1") && exported.contains("/3/<0;1>/*)#"));
+        assert_eq!(Descriptor::parse(&exported), Ok(descriptor.clone()));
+
+        let mut watcher = WatchOnlyWallet::new();
+        watcher.import(&descriptor).unwrap();
+        for (chain, index) in [(RECEIVE_CHAIN, 0), (CHANGE_CHAIN, 2)] {
+            let full = account.derive_path(&format!("m/3/{chain}/{index}").parse().unwrap()).unwrap();
+            assert_eq!(watcher.address_at(0, chain, index).unwrap().0, full.jordan_key().pub_key.address());
+        }
+        // Exported back at the node it watches, it still names the same chains
+        let again = WatchOnlyWallet::from_xpubs(watcher.descriptors().iter().map(|d| match d {
+            Descriptor::Account { xpub, .. } => xpub.clone(),
+            Descriptor::Key(_) => unreachable!(),
+        }))
+        .unwrap();
+        assert_eq!(again.address_at(0, RECEIVE_CHAIN, 5), watcher.address_at(0, RECEIVE_CHAIN, 5));
+
+        assert_eq!(Descriptor::account(account.public(), "m/3'".parse().unwrap()), Err(DescriptorError::Path(PathError::HardenedFromPublic)));
+        let hardened = exported.split('#').next().unwrap().replace("/3/", "/3'/");
+        let hardened = format!("{hardened}#{}", hex(&Sha256::digest(hardened.as_bytes())[..4]));
+        assert_eq!(Descriptor::parse(&hardened), Err(DescriptorError::Path(PathError::HardenedFromPublic)));
+    }
+
+    #[test]
+    fn cold_account_is_watched_and_spent_offline() {
+        let mut rng = StdRng::seed_from_u64(4334);
+        // Offline: the seed, and the account xpub handed to the watcher
+        let account = MasterSeed { seed_bytes: [0x42; 32] }.master_key().derive_path(&"m/44'/0'/0'".parse().unwrap()).unwrap();
+        let mut watcher = WatchOnlyWallet::from_xpubs([account.public().to_string().parse().unwrap()]).unwrap();
+
+        let first = watcher.receive_address(0).unwrap();
+        let node = account.derive_path(&"m/0/0".parse().unwrap()).unwrap();
+        assert_eq!(first, node.jordan_key().pub_key.address());
+
+        // A payer uses an address further along than any handed out yet
+        let later = watcher.address_at(0, RECEIVE_CHAIN, 7).unwrap().0;
+        let stranger = JordanSchnorr::keygen(&mut rng).pub_key.address();
+        let coins = [
+            Utxo::new([1; 32], first, FEE + 500),
+            Utxo::new([2; 32], later, FEE + 700),
+            Utxo::new([3; 32], stranger, FEE + 900),
+        ];
+        let mut acc = HorizonAccumulator::new();
+        for (i, utxo) in coins.iter().enumerate() {
+            acc.add_utxo(utxo, i as u64);
+        }
+        let stale = acc.generate_witness(1);
+        for (i, utxo) in coins.iter().enumerate() {
+            let ours = watcher.receive(utxo.clone(), acc.generate_witness(i as u64), &acc.root).unwrap();
+            assert_eq!(ours, i < 2);
+        }
+        assert!(matches!(watcher.update_witness(&coins[0].id, stale, &acc.root), Err(WatchError::BadWitness { .. })));
+        assert_eq!(watcher.balance(), 2 * FEE + 1_200);
+        assert_eq!(watcher.receive_address(0).unwrap(), watcher.address_at(0, RECEIVE_CHAIN, 8).unwrap().0);
+
+        // Online builds, offline signs, the validator accepts
+        let spends = watcher.build_unsigned(stranger, 700, 1).unwrap();
+        assert_eq!(spends.len(), 1);
+        let spend = spends.into_iter().next().unwrap();
+        let key = account.derive_path(&"m/0/7".parse().unwrap()).unwrap();
+        assert_eq!(spend.owner_key, Some(key.jordan_key().pub_key));
+        let sig = JordanSchnorr::sign(key.jordan_key(), TX_CONTEXT, &spend.message(), &mut rng).unwrap();
+        let tx = spend.complete(key.jordan_key().pub_key, sig);
+        assert!(HorizonValidator::new(acc.root.clone()).validate_transaction(&tx).is_ok());
+    }
+}
@@ -0,0 +1,374 @@
+// src/hdwallet/xmss.rs
+// XMSS: many signatures under one published key.
+//
+// A Merkle tree over GSH-256 whose 2^h leaves are one-time public keys
+// derived from one HD node; the root is the long-term key. A signature is
+// the leaf index, the one-time signature, and the h sibling hashes from
+// that leaf to the root, so the verifier rebuilds the root without ever
+// having seen the leaf. Each leaf signs once; the key keeps the index of
+// the next unused leaf and refuses to run out or to go back.
+//
+// The leaves are WOTS+ keys (w = 16) over GSH-256 chains, not the Flutter
+// chains of hdwallet.rs: those end on one attractor for every seed
+// (path.rs), so their public keys cannot tell two leaves apart. Per leaf:
+//   67 chains = 64 base-16 digits of the message digest + 3 of checksum
+//   sk[j]     = H(secret, sk_seed, leaf, j)
+//   chain     = F(pub_seed, leaf, j, step, x), 15 steps to the end
+//   leaf      = H(leaf, pub_seed, leaf, ends[0..67])
+//   node      = H(node, pub_seed, level, index, left, right)
+// All hashes are GSH-256 cut to 32 bytes under their own domain, and take
+// the public seed and position, so no two calls anywhere hash the same
+// input. sk_seed || pub_seed is GSH("flutter-hd/xmss" || node key).
+//
+// Key generation hashes every chain of every leaf (about 1000 GSH calls per
+// leaf) and keeps the whole tree, 2^(h+1) hashes, for signing. Heights up
+// to MAX_HEIGHT; h = 10 gives 1024 signatures.
+//
+// The next index is state that must outlive the process: signing twice at
+// one index breaks that leaf exactly as reusing a Flutter key does. A key
+// fresh from `generate` has no index and refuses to sign until `resume`
+// gives it the persisted one; WalletStore::xmss_key does that from the
+// store's one-time counters, and WalletStore::sign_xmss saves the advanced
+// counter before each signature is made. Horizon outputs
+// are locked to Jordan keys; spending one with an XMSS key needs a spend
+// condition of its own.
+
+use super::path::ExtendedKey;
+use crate::algebra::DecodeError;
+use crate::gsh::GSH256;
+use crate::redact::Redacted;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+use std::fmt;
+
+const XMSS_DOMAIN: &[u8] = b"flutter-hd/xmss";
+const SECRET_DOMAIN: &[u8] = b"xmss/secret";
+const CHAIN_DOMAIN: &[u8] = b"xmss/chain";
+const LEAF_DOMAIN: &[u8] = b"xmss/leaf";
+const NODE_DOMAIN: &[u8] = b"xmss/node";
+const MESSAGE_DOMAIN: &[u8] = b"xmss/message";
+
+pub const MAX_HEIGHT: u8 = 16;
+const W: u8 = 16;
+const DIGITS: usize = 64; // 256 bits in base 16
+const CHECKSUM_DIGITS: usize = 3; // Up to 64 * 15 = 960 < 16^3
+pub const CHAINS: usize = DIGITS + CHECKSUM_DIGITS;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XmssError {
+    TooTall { height: u8, max: u8 },
+    // Every leaf has signed
+    Exhausted,
+    // `resume` may only move forward
+    Rewind { next: u32, got: u32 },
+    // No persisted index has been restored with `resume`
+    NotResumed,
+    // Index, path length or height do not fit the key
+    Malformed,
+    // The rebuilt root is not the key's
+    BadSignature,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct XmssPublicKey {
+    pub height: u8,
+    pub root: [u8; 32],
+    pub pub_seed: [u8; 32],
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XmssSignature {
+    pub index: u32,
+    pub wots: Vec<[u8; 32]>,      // CHAINS chain states
+    pub auth_path: Vec<[u8; 32]>, // Siblings, leaf level first
+}
+
+pub struct XmssKey {
+    sk_seed: [u8; 32],
+    public: XmssPublicKey,
+    next: Option<u32>, // None until `resume`
+    tree: Vec<Vec<[u8; 32]>>, // tree[level][index]; tree[height] is [root]
+}
+
+impl Zeroize for XmssKey {
+    fn zeroize(&mut self) {
+        self.sk_seed.zeroize();
+    }
+}
+
+impl Drop for XmssKey {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for XmssKey {}
+const _: () = crate::redact::assert_zeroize_on_drop::<XmssKey>();
+
+impl fmt::Debug for XmssKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("XmssKey")
+            .field("sk_seed", &Redacted(&self.sk_seed))
+            .field("public", &self.public)
+            .field("next", &self.next)
+            .finish()
+    }
+}
+
+impl XmssKey {
+    /// KEY GENERATION
+    /// The tree of 2^height leaves under `node`; `resume` before signing
+    pub fn generate(node: &ExtendedKey, height: u8) -> Result<Self, XmssError> {
+        if height > MAX_HEIGHT {
+            return Err(XmssError::TooTall { height, max: MAX_HEIGHT });
+        }
+        let mut seeds = GSH256::hash_slices(&[XMSS_DOMAIN, node.key_bytes()]);
+        let sk_seed = std::array::from_fn(|i| seeds[i]);
+        let pub_seed = std::array::from_fn(|i| seeds[32 + i]);
+        seeds.zeroize();
+
+        let leaves: Vec<[u8; 32]> = (0..1u32 << height)
+            .map(|index| {
+                let ends: Vec<[u8; 32]> = (0..CHAINS).map(|j| chain(&pub_seed, index, j, secret(&sk_seed, index, j), 0, W - 1)).collect();
+                leaf(&pub_seed, index, &ends)
+            })
+            .collect();
+        let mut tree = vec![leaves];
+        for level in 0..height {
+            let below = &tree[level as usize];
+            let above = below.chunks(2).enumerate().map(|(i, pair)| node_hash(&pub_seed, level + 1, i as u32, &pair[0], &pair[1])).collect();
+            tree.push(above);
+        }
+        let root = tree[height as usize][0];
+        Ok(XmssKey { sk_seed, public: XmssPublicKey { height, root, pub_seed }, next: None, tree })
+    }
+
+    pub fn public_key(&self) -> &XmssPublicKey {
+        &self.public
+    }
+
+    /// The next leaf to sign with: the state to persist; None until resumed
+    pub fn next_index(&self) -> Option<u32> {
+        self.next
+    }
+
+    pub fn remaining(&self) -> Option<u64> {
+        self.next.map(|next| (1u64 << self.public.height) - next as u64)
+    }
+
+    /// Restore a persisted index; never moves back
+    pub fn resume(&mut self, next: u32) -> Result<(), XmssError> {
+        if let Some(current) = self.next.filter(|&current| next < current) {
+            return Err(XmssError::Rewind { next: current, got: next });
+        }
+        if next as u64 > 1u64 << self.public.height {
+            return Err(XmssError::Malformed);
+        }
+        self.next = Some(next);
+        Ok(())
+    }
+
+    /// SIGN
+    /// Uses up the next leaf
+    pub fn sign(&mut self, context: &[u8], message: &[u8]) -> Result<XmssSignature, XmssError> {
+        let index = self.next.ok_or(XmssError::NotResumed)?;
+        if index as u64 >= 1u64 << self.public.height {
+            return Err(XmssError::Exhausted);
+        }
+        self.next = Some(index + 1);
+
+        let digits = digits(&message_digest(&self.public, index, context, message));
+        let wots = digits
+            .iter()
+            .enumerate()
+            .map(|(j, &d)| {
+                let mut start = secret(&self.sk_seed, index, j);
+                let state = chain(&self.public.pub_seed, index, j, start, 0, d);
+                start.zeroize();
+                state
+            })
+            .collect();
+        let auth_path = (0..self.public.height as usize).map(|level| self.tree[level][(index >> level) as usize ^ 1]).collect();
+        Ok(XmssSignature { index, wots, auth_path })
+    }
+}
+
+impl XmssPublicKey {
+    pub const BYTES: usize = 1 + 32 + 32;
+
+    /// VERIFY
+    pub fn verify(&self, context: &[u8], message: &[u8], sig: &XmssSignature) -> Result<(), XmssError> {
+        if self.height > MAX_HEIGHT
+            || sig.index as u64 >= 1u64 << self.height
+            || sig.wots.len() != CHAINS
+            || sig.auth_path.len() != self.height as usize
+        {
+            return Err(XmssError::Malformed);
+        }
+        // Finish each chain, rebuild the leaf, climb to the root
+        let digits = digits(&message_digest(self, sig.index, context, message));
+        let ends: Vec<[u8; 32]> = digits
+            .iter()
+            .zip(&sig.wots)
+            .enumerate()
+            .map(|(j, (&d, &state))| chain(&self.pub_seed, sig.index, j, state, d, W - 1 - d))
+            .collect();
+        let mut node = leaf(&self.pub_seed, sig.index, &ends);
+        for (level, sibling) in sig.auth_path.iter().enumerate() {
+            let index = sig.index >> (level + 1);
+            node = if sig.index >> level & 1 == 0 {
+                node_hash(&self.pub_seed, level as u8 + 1, index, &node, sibling)
+            } else {
+                node_hash(&self.pub_seed, level as u8 + 1, index, sibling, &node)
+            };
+        }
+        // Compare without early exit
+        match node.iter().zip(&self.root).fold(0u8, |acc, (a, b)| acc | (a ^ b)) {
+            0 => Ok(()),
+            _ => Err(XmssError::BadSignature),
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        [&[self.height][..], &self.root, &self.pub_seed].concat()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        if bytes.len() != Self::BYTES {
+            return Err(DecodeError::Length { expected: Self::BYTES, got: bytes.len() });
+        }
+        Ok(XmssPublicKey {
+            height: bytes[0],
+            root: bytes[1..33].try_into().unwrap_or_else(|_| unreachable!()),
+            pub_seed: bytes[33..].try_into().unwrap_or_else(|_| unreachable!()),
+        })
+    }
+}
+
+// --- SERIALIZATION ---
+//   index (u32 BE) || CHAINS states || auth path
+// The path length follows from the frame, and must match the key's height.
+impl XmssSignature {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = self.index.to_be_bytes().to_vec();
+        self.wots.iter().chain(&self.auth_path).for_each(|h| out.extend(h));
+        out
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let min = 4 + 32 * CHAINS;
+        let levels = bytes.len().saturating_sub(min) / 32;
+        if bytes.len() < min || bytes.len() != min + 32 * levels || levels > MAX_HEIGHT as usize {
+            return Err(DecodeError::Length { expected: min, got: bytes.len() });
+        }
+        let mut hashes = bytes[4..].chunks_exact(32).map(|h| h.try_into().unwrap_or_else(|_| unreachable!()));
+        Ok(XmssSignature {
+            index: u32::from_be_bytes(bytes[..4].try_into().unwrap_or_else(|_| unreachable!())),
+            wots: hashes.by_ref().take(CHAINS).collect(),
+            auth_path: hashes.collect(),
+        })
+    }
+}
+
+fn truncate(digest: [u8; 64]) -> [u8; 32] {
+    std::array::from_fn(|i| digest[i])
+}
+
+fn secret(sk_seed: &[u8; 32], index: u32, j: usize) -> [u8; 32] {
+    let mut digest = GSH256::hash_slices(&[SECRET_DOMAIN, sk_seed, &index.to_be_bytes(), &[j as u8]]);
+    let out = truncate(digest);
+    digest.zeroize();
+    out
+}
+
+// `steps` applications of F to `x`, which sits at position `from`
+fn chain(pub_seed: &[u8; 32], index: u32, j: usize, x: [u8; 32], from: u8, steps: u8) -> [u8; 32] {
+    (from..from + steps).fold(x, |x, step| truncate(GSH256::hash_slices(&[CHAIN_DOMAIN, pub_seed, &index.to_be_bytes(), &[j as u8, step], &x])))
+}
+
+fn leaf(pub_seed: &[u8; 32], index: u32, ends: &[[u8; 32]]) -> [u8; 32] {
+    let index = index.to_be_bytes();
+    let mut parts: Vec<&[u8]> = vec![LEAF_DOMAIN, pub_seed, &index];
+    parts.extend(ends.iter().map(|e| e.as_slice()));
+    truncate(GSH256::hash_slices(&parts))
+}
+
+fn node_hash(pub_seed: &[u8; 32], level: u8, index: u32, left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    truncate(GSH256::hash_slices(&[NODE_DOMAIN, pub_seed, &[level], &index.to_be_bytes(), left, right]))
+}
+
+fn message_digest(pk: &XmssPublicKey, index: u32, context: &[u8], message: &[u8]) -> [u8; 32] {
+    truncate(GSH256::hash_slices(&[
+        MESSAGE_DOMAIN,
+        &pk.pub_seed,
+        &pk.root,
+        &index.to_be_bytes(),
+        &(context.len() as u64).to_le_bytes(),
+        context,
+        message,
+    ]))
+}
+
+// 64 base-16 digits, high nibble first, then the checksum sum(15 - d)
+fn digits(digest: &[u8; 32]) -> [u8; CHAINS] {
+    let mut out = [0u8; CHAINS];
+    for (i, b) in digest.iter().enumerate() {
+        out[2 * i] = b >> 4;
+        out[2 * i + 1] = b & 0x0F;
+    }
+    let checksum: u32 = out[..DIGITS].iter().map(|&d| (W - 1 - d) as u32).sum();
+    for k in 0..CHECKSUM_DIGITS {
+        out[DIGITS + k] = (checksum >> (4 * (CHECKSUM_DIGITS - 1 - k)) & 0x0F) as u8;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hdwallet::MasterSeed;
+    use crate::horizon::TX_CONTEXT;
+
+    #[test]
+    fn every_leaf_signs_once_under_one_root() {
+        let node = MasterSeed { seed_bytes: [0x42; 32] }.master_key().derive_path(&"m/44'/0'/0'/2'".parse().unwrap()).unwrap();
+        let mut key = XmssKey::generate(&node, 2).unwrap();
+        let pk = XmssPublicKey::from_bytes(&key.public_key().to_bytes()).unwrap();
+        assert_eq!(pk.root, XmssKey::generate(&node, 2).unwrap().public_key().root);
+        // A fresh key does not know which leaves have signed
+        assert_eq!(key.sign(TX_CONTEXT, b"too soon"), Err(XmssError::NotResumed));
+        key.resume(0).unwrap();
+
+        for i in 0..4u32 {
+            let msg = format!("spend {i}");
+            let sig = key.sign(TX_CONTEXT, msg.as_bytes()).unwrap();
+            assert_eq!(sig.index, i);
+            let sig = XmssSignature::from_bytes(&sig.to_bytes()).unwrap();
+            assert_eq!(pk.verify(TX_CONTEXT, msg.as_bytes(), &sig), Ok(()));
+            assert_eq!(pk.verify(TX_CONTEXT, b"other", &sig), Err(XmssError::BadSignature));
+        }
+        assert_eq!(key.sign(TX_CONTEXT, b"fifth"), Err(XmssError::Exhausted));
+        assert_eq!(key.resume(1), Err(XmssError::Rewind { next: 4, got: 1 }));
+    }
+
+    #[test]
+    fn bent_signatures_are_refused() {
+        let node = MasterSeed { seed_bytes: [7; 32] }.master_key();
+        let mut key = XmssKey::generate(&node, 1).unwrap();
+        key.resume(1).unwrap();
+        let pk = *key.public_key();
+        let sig = key.sign(b"ctx", b"msg").unwrap();
+        assert_eq!(sig.index, 1);
+
+        let mut moved = sig.clone();
+        moved.index = 0;
+        assert_eq!(pk.verify(b"ctx", b"msg", &moved), Err(XmssError::BadSignature));
+        let mut bent = sig.clone();
+        bent.auth_path[0][0] ^= 1;
+        assert_eq!(pk.verify(b"ctx", b"msg", &bent), Err(XmssError::BadSignature));
+        let mut short = sig.clone();
+        short.auth_path.clear();
+        assert_eq!(pk.verify(b"ctx", b"msg", &short), Err(XmssError::Malformed));
+        assert_eq!(pk.verify(b"other ctx", b"msg", &sig), Err(XmssError::BadSignature));
+        assert_eq!(XmssKey::generate(&node, MAX_HEIGHT + 1).unwrap_err(), XmssError::TooTall { height: MAX_HEIGHT + 1, max: MAX_HEIGHT });
+    }
+}
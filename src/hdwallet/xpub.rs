@@ -0,0 +1,167 @@
+// src/hdwallet/xpub.rs
+// EXTENDED PUBLIC KEYS: receive keys without the seed.
+//
+// A Flutter key has no public derivation: Z_256 is a hash-chain end with no
+// algebra to tweak it, and (path.rs) the same for every seed anyway. The
+// Jordan product does have the algebra - it is linear in the secret:
+//   A o (s_k + d_k) = A o s_k + A o d_k
+// So an xpub is a node's chain code and JordanSchnorr public key, and its
+// normal child i is
+//   I = HMAC-SHA512(chain, 0x01 || i),   d_k = B(eta) drawn from I_L
+//   t'_k = t_k + A o d_k,   chain' = I_R
+// which is the public half of the private step s' = s + d. A merchant or
+// explorer holding the account xpub derives every receive key; it cannot
+// sign, and hardened steps stay out of its reach.
+//
+// As in BIP32, an xpub together with any one normal child's secret gives
+// the parent secret (s = s' - d). Only hand out xpubs below a hardened
+// account level. Each normal level widens s by one B(eta) draw, and `sign`
+// only hides secrets up to ParamSet::secret_bound: two normal levels, as in
+// m/../change/index. Keys from deeper normal paths still verify, but
+// signing with them fails with SignError::SecretTooWide.
+//
+// Text form: bech32m under "fxpub" of
+//   depth || child number (u32 BE) || chain code || PublicKey bytes
+
+use super::path::{normal_step, ChildNumber, DerivationPath, PathError};
+use crate::albert::{AlbertElement, Q};
+use crate::algebra::encoding::{bech32_decode, bech32_encode, Bech32Variant};
+use crate::algebra::DecodeError;
+use crate::gsh::GSH256;
+use crate::jordan_sig::{KeyVector, PublicKey, SecretKey};
+use zeroize::Zeroize;
+use rand::prelude::*;
+use rand_chacha::ChaCha20Rng;
+use std::fmt;
+use std::str::FromStr;
+
+const TWEAK_DOMAIN: &[u8] = b"flutter-hd/tweak";
+pub const XPUB_HRP: &str = "fxpub";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtendedPublicKey {
+    pub depth: u8,
+    pub child_number: ChildNumber,
+    pub key: PublicKey,
+    pub chain_code: [u8; 32],
+}
+
+impl ExtendedPublicKey {
+    pub const BYTES: usize = 1 + 4 + 32 + PublicKey::<Q>::BYTES;
+
+    /// NORMAL CHILD
+    pub fn derive_child(&self, child: ChildNumber) -> Result<Self, PathError> {
+        if child.is_hardened() {
+            return Err(PathError::HardenedFromPublic);
+        }
+        if self.depth == u8::MAX {
+            return Err(PathError::TooDeep { depth: u8::MAX as usize + 1 });
+        }
+        let i = normal_step(&self.chain_code, child);
+        let d = tweak(&self.key, &i);
+        Ok(ExtendedPublicKey {
+            depth: self.depth + 1,
+            child_number: child,
+            key: shifted(&self.key, &d),
+            chain_code: std::array::from_fn(|j| i[32 + j]),
+        })
+    }
+
+    /// `path` is read relative to this node, and must be all normal steps
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<Self, PathError> {
+        path.0.iter().try_fold(self.clone(), |node, &step| node.derive_child(step))
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = vec![self.depth];
+        out.extend(self.child_number.0.to_be_bytes());
+        out.extend(self.chain_code);
+        out.extend(self.key.to_versioned_bytes());
+        out
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        if bytes.len() != Self::BYTES {
+            return Err(DecodeError::Length { expected: Self::BYTES, got: bytes.len() });
+        }
+        Ok(ExtendedPublicKey {
+            depth: bytes[0],
+            child_number: ChildNumber(u32::from_be_bytes(bytes[1..5].try_into().unwrap_or_else(|_| unreachable!()))),
+            chain_code: bytes[5..37].try_into().unwrap_or_else(|_| unreachable!()),
+            key: PublicKey::from_versioned_bytes(&bytes[37..])?,
+        })
+    }
+}
+
+impl fmt::Display for ExtendedPublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&bech32_encode(XPUB_HRP, &self.to_bytes(), Bech32Variant::Bech32m))
+    }
+}
+
+impl FromStr for ExtendedPublicKey {
+    type Err = DecodeError;
+
+    fn from_str(s: &str) -> Result<Self, DecodeError> {
+        Self::from_bytes(&bech32_decode(XPUB_HRP, s.trim(), Bech32Variant::Bech32m)?)
+    }
+}
+
+// d_k = B(eta) from ChaCha20 seeded by I_L; the set's eta, so d is as
+// small as s
+fn tweak(key: &PublicKey, i: &[u8; 64]) -> KeyVector {
+    let mut seed = GSH256::hash_slices(&[TWEAK_DOMAIN, &i[..32]]);
+    let mut rng = ChaCha20Rng::from_seed(std::array::from_fn(|j| seed[j]));
+    let d = std::array::from_fn(|_| AlbertElement::sample_cbd(&mut rng, key.params.secret_eta()));
+    seed.zeroize();
+    d
+}
+
+// t'_k = t_k + A o d_k
+fn shifted(key: &PublicKey, d: &KeyVector) -> PublicKey {
+    PublicKey { t: std::array::from_fn(|k| key.t[k] + key.a.jordan_product(&d[k])), ..*key }
+}
+
+// s' = s + d, t' = t + A o d: the private half of derive_child
+pub(super) fn tweak_secret(sk: &SecretKey, i: &[u8; 64]) -> SecretKey {
+    let mut d = tweak(&sk.pub_key, i);
+    let child = SecretKey { s: std::array::from_fn(|k| sk.s[k] + d[k]), pub_key: shifted(&sk.pub_key, &d) };
+    d.zeroize();
+    child
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hdwallet::MasterSeed;
+    use crate::horizon::TX_CONTEXT;
+    use crate::jordan_sig::JordanSchnorr;
+
+    #[test]
+    fn xpub_children_match_private_children() {
+        let seed = MasterSeed { seed_bytes: [0x42; 32] };
+        let account = seed.master_key().derive_path(&"m/44'/0'/0'".parse().unwrap()).unwrap();
+        let xpub: ExtendedPublicKey = account.public().to_string().parse().unwrap();
+        assert_eq!(xpub, account.public());
+
+        let mut rng = StdRng::seed_from_u64(4331);
+        for index in 0..3 {
+            let path: DerivationPath = format!("m/0/{index}").parse().unwrap();
+            let watched = xpub.derive_path(&path).unwrap();
+            let node = account.derive_path(&path).unwrap();
+            assert_eq!(watched, node.public());
+            // The watched key is the one the wallet signs with
+            let sig = JordanSchnorr::sign(node.jordan_key(), TX_CONTEXT, b"receive", &mut rng).unwrap();
+            assert_eq!(JordanSchnorr::verify(&watched.key, TX_CONTEXT, b"receive", &sig), Ok(()));
+        }
+    }
+
+    #[test]
+    fn hardened_steps_need_the_seed() {
+        let xpub = MasterSeed { seed_bytes: [0x42; 32] }.master_key().public();
+        assert_eq!(xpub.derive_path(&"m/0/1'".parse().unwrap()), Err(PathError::HardenedFromPublic));
+        let text = xpub.to_string();
+        let bent = text.replacen('q', "p", 1);
+        assert_eq!(bent.parse::<ExtendedPublicKey>(), Err(DecodeError::InvalidBech32));
+    }
+}
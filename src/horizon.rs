@@ -5,135 +5,459 @@
 // Concept: The "Horizon" (State Root) encodes the entropy of the 
 // entire "Bulk" (UTxO Set), following the Holographic Principle.
 
+use crate::address::Address;
 use crate::gsh::GSH256;
-use crate::jordan_sig::{JordanSchnorr, PublicKey, Signature};
-use std::collections::HashMap;
+use crate::jordan_sig::{JordanSchnorr, PublicKey, Signature, VerifyError};
+use crate::merkle::{self, Gsh32, SparseMerkleTree};
+use crate::shadow;
+use crate::storage::Store;
+use std::cell::Cell;
+use std::path::{Path, PathBuf};
 
 // --- CONFIGURATION ---
 // Depth of the Sparse Merkle Tree (2^64 address space)
-const TREE_DEPTH: usize = 64; 
+pub const TREE_DEPTH: usize = 64; 
 // Empty leaf hash (computed once)
-const EMPTY_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+const EMPTY_LEAF: [u8; 32] = [0u8; 32];
+// Signature context for spends (jordan_sig binds it into the challenge), so
+// a spend signature is never valid as any other kind of signature. A
+// separate network signs under its own context.
+pub const TX_CONTEXT: &[u8] = b"olc/horizon/tx/v1";
 
 // --- DATA STRUCTURES ---
 
-#[derive(Clone, Debug)]
+pub type AssetId = [u8; 32];
+// The base coin
+pub const NATIVE_ASSET: AssetId = [0u8; 32];
+
+// How an output may be spent. Only the owner's signature exists today; the
+// condition is committed in the leaf so that adding one later does not
+// change the leaves of existing outputs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SpendCondition {
+    #[default]
+    OwnerSignature,
+}
+
+impl SpendCondition {
+    pub fn tag(&self) -> u8 {
+        match self {
+            SpendCondition::OwnerSignature => 0x00,
+        }
+    }
+
+    pub fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            0x00 => Some(SpendCondition::OwnerSignature),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Utxo {
     pub id: [u8; 32],      // Unique ID (Hash of tx input)
-    pub owner: PublicKey,  // Jordan-Dilithium Public Key
+    pub owner: Address,    // Hash of the owner's Jordan-Dilithium key
     pub amount: u64,       // Value
+    pub asset: AssetId,
+    pub condition: SpendCondition,
+    pub created_at: u64,   // Height of the block that created it
+}
+
+// --- LEAF ENCODING ---
+// Every UTXO field is committed, in this order, at fixed width:
+//   domain | version u8 | id [32] | owner hash [32] | amount u64 LE |
+//   asset [32] | condition tag u8 | created_at u64 LE
+// The owner enters as its Address: the Gsh32 digest of the key's
+// canonical PublicKey wire bytes (the KEY_ELEMENTS t_k, then A), so no
+// part of the key is left unbound. The version sits in the domain tag and
+// again in the payload; a new schema gets a new domain, so leaves of
+// different versions can never collide. The same bytes open the message the owner signs (below).
+//   v1: current
+pub const LEAF_VERSION: u8 = 1;
+const LEAF_DOMAIN: &[u8] = b"OLC-UTXO-LEAF-v1";
+pub const LEAF_ENCODING_BYTES: usize = 16 + 1 + 32 + 32 + 8 + 32 + 1 + 8;
+
+// --- SPEND MESSAGE ---
+// What the owner signs under TX_CONTEXT: GSH-256 (hex) over
+//   domain | input leaf encoding | new owner hash [32] | new amount u64 LE
+// The coin alone is not enough: anyone relaying a spend could otherwise
+// point it at their own address, or change the amount, and keep the
+// signature.
+const SPEND_DOMAIN: &[u8] = b"OLC-SPEND-v1";
+
+pub fn spend_message(input: &Utxo, new_owner: &Address, new_amount: u64) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(SPEND_DOMAIN.len() + LEAF_ENCODING_BYTES + 32 + 8);
+    bytes.extend_from_slice(SPEND_DOMAIN);
+    bytes.extend_from_slice(&input.leaf_encoding());
+    bytes.extend_from_slice(&new_owner.0);
+    bytes.extend_from_slice(&new_amount.to_le_bytes());
+    GSH256::hash_bytes(&bytes).into_bytes()
 }
 
 impl Utxo {
-    pub fn hash(&self) -> String {
-        // Serialize and Hash via GSH (Geometric Stiffness Hash)
-        let mut bytes = Vec::new();
+    // A native-asset output spendable by `owner`, created at genesis
+    pub fn new(id: [u8; 32], owner: Address, amount: u64) -> Self {
+        Utxo { id, owner, amount, asset: NATIVE_ASSET, condition: SpendCondition::OwnerSignature, created_at: 0 }
+    }
+
+    pub fn owner_hash(&self) -> [u8; 32] {
+        self.owner.0
+    }
+
+    pub fn leaf_encoding(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(LEAF_ENCODING_BYTES);
+        bytes.extend_from_slice(LEAF_DOMAIN);
+        bytes.push(LEAF_VERSION);
         bytes.extend_from_slice(&self.id);
+        bytes.extend_from_slice(&self.owner_hash());
         bytes.extend_from_slice(&self.amount.to_le_bytes());
-        // Serialize Owner (Albert Element - simplified for demo)
-        bytes.extend_from_slice(&self.owner.t.alpha.to_le_bytes()); 
-        GSH256::hash_bytes(&bytes)
+        bytes.extend_from_slice(&self.asset);
+        bytes.push(self.condition.tag());
+        bytes.extend_from_slice(&self.created_at.to_le_bytes());
+        bytes
+    }
+
+    // GSH-256 of the leaf encoding
+    pub fn hash(&self) -> String {
+        GSH256::hash_bytes(&self.leaf_encoding())
+    }
+
+    // 32-byte leaf committed in the Horizon tree
+    pub fn leaf_hash(&self) -> [u8; 32] {
+        Gsh32::leaf(&self.leaf_encoding())
     }
 }
 
 // THE STATELESS WITNESS (Holographic Projection)
 // This is what the user must provide. Validators do NOT store the Bulk.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Witness {
-    pub siblings: Vec<String>, // Merkle Branch (Hashes)
+    pub siblings: Vec<[u8; 32]>, // Merkle Branch (Hashes)
     pub index: u64,            // Position in the tree
 }
 
+impl Witness {
+    // The root this branch proves `leaf_hash` under
+    pub fn root(&self, leaf_hash: &[u8; 32]) -> String {
+        Gsh32::to_hex(&merkle::root_from_path::<Gsh32>(leaf_hash, self.index, &self.siblings))
+    }
+}
+
 // THE TRANSACTION
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Transaction {
     pub input_utxo: Utxo,
     pub witness: Witness,        // Proof input exists in current Horizon
+    pub owner_key: PublicKey,    // The key behind input_utxo.owner, revealed to spend
     pub signature: Signature,    // Proof owner authorizes spend
-    pub new_owner: PublicKey,    // Recipient
+    pub new_owner: Address,      // Recipient
     pub new_amount: u64,
 }
 
+// Policy size of a transaction: input UTXO, index, signature, output and
+// one 32-byte hash per witness level. An estimate until transactions get a
+// wire encoding; fee rates (mempool) are measured against it.
+const TX_BASE_BYTES: usize = 256;
+// Size of a spend carrying a full-depth Horizon witness (what wallets pay for)
+pub const SPEND_SIZE_BYTES: usize = TX_BASE_BYTES + 32 * TREE_DEPTH;
+
+impl Transaction {
+    // Id of the UTXO this transaction creates; a child spends it by using
+    // it as `input_utxo.id`.
+    pub fn id(&self) -> [u8; 32] {
+        let mut bytes = Vec::with_capacity(72);
+        bytes.extend_from_slice(&self.input_utxo.id);
+        bytes.extend_from_slice(&self.new_owner.0);
+        bytes.extend_from_slice(&self.new_amount.to_le_bytes());
+        Gsh32::leaf(&bytes)
+    }
+
+    // What the owner signed (spend_message)
+    pub fn message(&self) -> Vec<u8> {
+        spend_message(&self.input_utxo, &self.new_owner, self.new_amount)
+    }
+
+    // Input value not carried to the output; None if it spends more than it has
+    pub fn fee(&self) -> Option<u64> {
+        self.input_utxo.amount.checked_sub(self.new_amount)
+    }
+
+    pub fn size_bytes(&self) -> usize {
+        TX_BASE_BYTES + 32 * self.witness.siblings.len()
+    }
+}
+
+// A spend with everything but the authorization: what a watch-only wallet
+// builds and a signer completes. The key is known when the watcher derived
+// the address itself (xpub); for a bare address the signer supplies it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnsignedTransaction {
+    pub input_utxo: Utxo,
+    pub witness: Witness,
+    pub owner_key: Option<PublicKey>,
+    pub new_owner: Address,
+    pub new_amount: u64,
+}
+
+impl UnsignedTransaction {
+    // What the owner signs (spend_message, under TX_CONTEXT)
+    pub fn message(&self) -> Vec<u8> {
+        spend_message(&self.input_utxo, &self.new_owner, self.new_amount)
+    }
+
+    pub fn complete(self, owner_key: PublicKey, signature: Signature) -> Transaction {
+        Transaction {
+            input_utxo: self.input_utxo,
+            witness: self.witness,
+            owner_key,
+            signature,
+            new_owner: self.new_owner,
+            new_amount: self.new_amount,
+        }
+    }
+}
+
 // --- THE HORIZON ACCUMULATOR (Sparse Merkle Tree) ---
 pub struct HorizonAccumulator {
     // In a full node, we might cache nodes, but logically we only need the root
     // to verify if the witness is provided.
     // For this simulation, we act as a "Bridge Node" that holds the data 
     // to generate witnesses for the user.
-    nodes: HashMap<(usize, u64), String>, // (Level, Index) -> Hash
+    tree: SparseMerkleTree<Gsh32>,
     pub root: String,
 }
 
-impl HorizonAccumulator {
-    pub fn new() -> Self {
-        HorizonAccumulator {
-            nodes: HashMap::new(),
-            root: Self::compute_empty_root(TREE_DEPTH),
-        }
-    }
-
-    // Precompute empty roots for sparse tree
-    fn compute_empty_root(height: usize) -> String {
-        if height == 0 { return EMPTY_HASH.to_string(); }
-        let child = Self::compute_empty_root(height - 1);
-        GSH256::hash_bytes(&(child.clone() + &child).into_bytes())
+impl Default for HorizonAccumulator {
+    fn default() -> Self {
+        Self::new()
     }
+}
 
-    // Get Node Hash (or default empty)
-    fn get_node(&self, level: usize, index: u64) -> String {
-        self.nodes.get(&(level, index)).cloned()
-             .unwrap_or_else(|| Self::compute_empty_root(level))
+impl HorizonAccumulator {
+    pub fn new() -> Self {
+        let tree = SparseMerkleTree::new(TREE_DEPTH);
+        let root = Gsh32::to_hex(&tree.root());
+        HorizonAccumulator { tree, root }
     }
 
     // INSERT UTXO (Minting)
     pub fn add_utxo(&mut self, utxo: &Utxo, index: u64) {
-        let leaf_hash = utxo.hash();
-        self.update_leaf(index, leaf_hash);
+        self.update_leaf(index, utxo.leaf_hash());
     }
 
     // SPEND UTXO (Remove from state)
     // In SMT, we replace the leaf with Empty Hash
     pub fn remove_utxo(&mut self, index: u64) {
-        self.update_leaf(index, EMPTY_HASH.to_string());
+        self.update_leaf(index, EMPTY_LEAF);
     }
 
-    fn update_leaf(&mut self, index: u64, hash: String) {
-        let mut curr_idx = index;
-        let mut curr_hash = hash;
-
-        // Store Leaf
-        self.nodes.insert((0, curr_idx), curr_hash.clone());
-
-        // Bubble up
-        for level in 0..TREE_DEPTH {
-            let sibling_idx = curr_idx ^ 1; // Flip last bit
-            let sibling_hash = self.get_node(level, sibling_idx);
+    // 64 calls to gsh::hash_two; no per-level string building
+    fn update_leaf(&mut self, index: u64, hash: [u8; 32]) {
+        self.tree.update(index, hash);
+        self.root = Gsh32::to_hex(&self.tree.root());
+    }
 
-            let (left, right) = if curr_idx % 2 == 0 {
-                (curr_hash, sibling_hash)
-            } else {
-                (sibling_hash, curr_hash)
-            };
+    // Current leaf at `index` (EMPTY_LEAF if unoccupied)
+    pub fn leaf(&self, index: u64) -> [u8; 32] {
+        self.tree.leaf(index)
+    }
 
-            // Hash Parent using GSH (Sedenion Sponge)
-            curr_hash = GSH256::hash_bytes(&(left + &right).into_bytes());
-            curr_idx /= 2;
-            
-            self.nodes.insert((level + 1, curr_idx), curr_hash.clone());
+    // Rebuild from the UTXO set (how a node moves to a new leaf schema)
+    pub fn from_utxos<'a>(utxos: impl IntoIterator<Item = (u64, &'a Utxo)>) -> Self {
+        let mut acc = HorizonAccumulator::new();
+        for (index, utxo) in utxos {
+            acc.tree.update(index, utxo.leaf_hash());
         }
-        self.root = curr_hash;
+        acc.root = Gsh32::to_hex(&acc.tree.root());
+        acc
     }
 
     // GENERATE WITNESS (User needs this to create a Tx)
     pub fn generate_witness(&self, index: u64) -> Witness {
-        let mut siblings = Vec::new();
-        let mut curr_idx = index;
-        for level in 0..TREE_DEPTH {
-            let sibling_idx = curr_idx ^ 1;
-            siblings.push(self.get_node(level, sibling_idx));
-            curr_idx /= 2;
+        let proof = self.tree.prove(index);
+        Witness { siblings: proof.siblings, index }
+    }
+}
+
+// --- STATE DIFFS ---
+// Per-block leaf changes. A producer records every insert/spend of a block
+// here; replaying the diffs from genesis rebuilds the Bulk without trusting
+// whoever served them, since each result is checked against the header root
+// (see mirror.rs).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StateDiff {
+    pub height: u64,
+    pub changes: Vec<(u64, [u8; 32])>, // (index, new leaf); EMPTY_LEAF = spent
+}
+
+impl StateDiff {
+    pub fn new(height: u64) -> Self {
+        StateDiff { height, changes: Vec::new() }
+    }
+
+    pub fn insert(&mut self, utxo: &Utxo, index: u64) -> &mut Self {
+        self.changes.push((index, utxo.leaf_hash()));
+        self
+    }
+
+    pub fn remove(&mut self, index: u64) -> &mut Self {
+        self.changes.push((index, EMPTY_LEAF));
+        self
+    }
+}
+
+impl HorizonAccumulator {
+    // Apply a diff in order; returns the diff that undoes it.
+    pub fn apply_diff(&mut self, diff: &StateDiff) -> StateDiff {
+        let mut undo = StateDiff::new(diff.height);
+        for &(index, leaf) in &diff.changes {
+            undo.changes.push((index, self.leaf(index)));
+            self.update_leaf(index, leaf);
+        }
+        undo.changes.reverse();
+        undo
+    }
+}
+
+// --- PERSISTENCE ---
+// Only leaves are stored (key = decimal index, value = hex leaf); interior
+// nodes are rebuilt on load. Bump the schema version and register a
+// storage::Migration when the leaf encoding or the hash changes.
+//   v1: 128-hex-char GSH-256 leaves
+//   v2: 32-byte leaves (Gsh32). Not migratable - v1 leaves are digests of
+//       UTXOs the store does not hold; rebuild from the UTXO set.
+//   v3: versioned UTXO leaves (LEAF_VERSION 1, all fields committed). Not
+//       migratable for the same reason; rebuild with `from_utxos`.
+pub const HORIZON_NAMESPACE: &str = "horizon";
+pub const HORIZON_SCHEMA_VERSION: u32 = 3;
+
+impl HorizonAccumulator {
+    pub fn export_leaves(&self, store: &mut Store) {
+        let ns = store.namespace_mut(HORIZON_NAMESPACE, HORIZON_SCHEMA_VERSION);
+        ns.version = HORIZON_SCHEMA_VERSION;
+        ns.entries.clear();
+        for (index, hash) in self.tree.leaves() {
+            ns.entries.insert(index.to_string(), Gsh32::to_hex(hash));
         }
-        Witness { siblings, index }
+    }
+
+    pub fn import_leaves(store: &Store) -> Result<Self, String> {
+        let mut acc = HorizonAccumulator::new();
+        let ns = match store.namespace(HORIZON_NAMESPACE) {
+            Some(ns) => ns,
+            None => return Ok(acc),
+        };
+        if ns.version != HORIZON_SCHEMA_VERSION {
+            return Err(format!("horizon schema v{} (expected v{}); migrate first",
+                ns.version, HORIZON_SCHEMA_VERSION));
+        }
+        for (key, hash) in &ns.entries {
+            let index = key.parse().map_err(|_| format!("bad leaf index '{}'", key))?;
+            let leaf = Gsh32::from_hex(hash).ok_or_else(|| format!("bad leaf hash at {}", key))?;
+            acc.update_leaf(index, leaf);
+        }
+        Ok(acc)
+    }
+}
+
+// --- VALIDATION HOOKS ---
+// Application-specific rules (e.g. per-asset policies for a sidechain) plug
+// into the validator without forking it. Each hook is consulted at fixed
+// points of the pipeline and may veto the transaction.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HookPoint {
+    PreSignature, // Before the Jordan-Dilithium check (cheap policy filters)
+    PostWitness,  // Signature and Merkle inclusion both verified
+    PreCommit,    // New root computed, transition not yet returned
+}
+
+// A veto raised by a hook, tagged with where and by whom it was raised.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HookVeto {
+    pub hook: String,
+    pub point: HookPoint,
+    pub reason: String,
+}
+
+pub trait ValidationHook {
+    // Stable identifier used in veto reports
+    fn name(&self) -> &str;
+
+    fn pre_signature(&self, _tx: &Transaction) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn post_witness(&self, _tx: &Transaction) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn pre_commit(&self, _tx: &Transaction, _new_root: &str) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+// Why the validator refused a transaction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TxRejection {
+    // The revealed key does not hash to the input's address
+    OwnerMismatch { expected: Address, got: Address },
+    InvalidSignature(VerifyError),
+    InvalidWitness { expected: String, got: String },
+    Vetoed(HookVeto),
+}
+
+// --- NODE CONFIGURATION ---
+// Hooks run in registration order. The first veto short-circuits the
+// remaining hooks (and the remaining validation stages) and is propagated
+// unchanged to the caller.
+// With shadow validation on, every verdict is re-derived by shadow.rs; a
+// disagreement writes a forensic dump to the given directory and halts.
+#[derive(Default)]
+pub struct NodeConfig {
+    hooks: Vec<Box<dyn ValidationHook>>,
+    shadow: Option<PathBuf>,
+}
+
+impl NodeConfig {
+    pub fn new() -> Self {
+        NodeConfig { hooks: Vec::new(), shadow: None }
+    }
+
+    pub fn enable_shadow(&mut self, dump_dir: PathBuf) -> &mut Self {
+        self.shadow = Some(dump_dir);
+        self
+    }
+
+    pub fn shadow_dir(&self) -> Option<&Path> {
+        self.shadow.as_deref()
+    }
+
+    pub fn register_hook(&mut self, hook: Box<dyn ValidationHook>) -> &mut Self {
+        self.hooks.push(hook);
+        self
+    }
+
+    pub fn hooks(&self) -> &[Box<dyn ValidationHook>] {
+        &self.hooks
+    }
+
+    fn run_hooks(
+        &self,
+        point: HookPoint,
+        check: impl Fn(&dyn ValidationHook) -> Result<(), String>,
+    ) -> Result<(), TxRejection> {
+        for hook in &self.hooks {
+            if let Err(reason) = check(hook.as_ref()) {
+                return Err(TxRejection::Vetoed(HookVeto {
+                    hook: hook.name().to_string(),
+                    point,
+                    reason,
+                }));
+            }
+        }
+        Ok(())
     }
 }
 
@@ -141,62 +465,270 @@ impl HorizonAccumulator {
 // This struct holds NO UTXO data, only the Root Hash.
 pub struct HorizonValidator {
     pub state_root: String,
+    config: NodeConfig,
+    // Transactions judged so far, numbering them in divergence dumps
+    judged: Cell<usize>,
 }
 
 impl HorizonValidator {
     pub fn new(root: String) -> Self {
-        HorizonValidator { state_root: root }
+        Self::with_config(root, NodeConfig::new())
+    }
+
+    pub fn with_config(root: String, config: NodeConfig) -> Self {
+        HorizonValidator { state_root: root, config, judged: Cell::new(0) }
+    }
+
+    pub fn config(&self) -> &NodeConfig {
+        &self.config
     }
 
     // VERIFY AND TRANSITION
     // Returns the NEW Root if valid, or None if invalid.
     pub fn process_transaction(&self, tx: &Transaction) -> Option<String> {
+        match self.validate_transaction(tx) {
+            Ok(new_root) => Some(new_root),
+            Err(TxRejection::OwnerMismatch { expected, got }) => {
+                println!("   [Horizon] Key for {} spends an output of {}", got, expected);
+                None
+            }
+            Err(TxRejection::InvalidSignature(reason)) => {
+                println!("   [Horizon] Invalid Signature by {}: {:?}", tx.owner_key.fingerprint(), reason);
+                None
+            }
+            Err(TxRejection::InvalidWitness { expected, got }) => {
+                println!("   [Horizon] Invalid Witness (State Mismatch)");
+                println!("      Expected: {}", expected);
+                println!("      Got:      {}", got);
+                None
+            }
+            Err(TxRejection::Vetoed(veto)) => {
+                println!("   [Horizon] Vetoed by hook '{}' at {:?}: {}", veto.hook, veto.point, veto.reason);
+                None
+            }
+        }
+    }
+
+    // Same pipeline as `process_transaction`, but reports the reason for rejection.
+    pub fn validate_transaction(&self, tx: &Transaction) -> Result<String, TxRejection> {
+        let verdict = self.judge(tx);
+        let tx_index = self.judged.replace(self.judged.get() + 1);
+        if let Some(dir) = &self.config.shadow {
+            // The validator sees no block heights; the dump numbers by tx
+            if let Err(divergence) = shadow::shadow_check(0, tx_index, self, tx, &verdict) {
+                match divergence.write_dump(dir) {
+                    Ok(path) => panic!("consensus divergence on tx {}; dump at {}", tx_index, path.display()),
+                    Err(e) => panic!("consensus divergence on tx {} (dump failed: {})\n{}", tx_index, e, divergence.dump()),
+                }
+            }
+        }
+        verdict
+    }
+
+    fn judge(&self, tx: &Transaction) -> Result<String, TxRejection> {
+        self.config.run_hooks(HookPoint::PreSignature, |h| h.pre_signature(tx))?;
+
         // 1. Verify Cryptographic Signature (Jordan-Dilithium)
-        // Check that tx.signature matches tx.input_utxo.owner
-        let msg = tx.input_utxo.hash().into_bytes();
-        
-        let sig_valid = JordanSchnorr::verify(&tx.input_utxo.owner, &msg, &tx.signature);
-        if !sig_valid {
-            println!("   [Horizon] Invalid Signature");
-            return None;
+        // The revealed key must be the one the input is locked to, and
+        // tx.signature must verify under it
+        let got = Address::of(&tx.owner_key);
+        if got != tx.input_utxo.owner {
+            return Err(TxRejection::OwnerMismatch { expected: tx.input_utxo.owner, got });
         }
+        JordanSchnorr::verify(&tx.owner_key, TX_CONTEXT, &tx.message(), &tx.signature).map_err(TxRejection::InvalidSignature)?;
 
         // 2. Verify Witness (Merkle Inclusion Proof)
         // Does this UTXO actually exist in the current Horizon?
-        let calculated_root = self.calculate_root(&tx.input_utxo.hash(), &tx.witness);
+        let calculated_root = self.calculate_root(&tx.input_utxo.leaf_hash(), &tx.witness);
         
         if calculated_root != self.state_root {
-            println!("   [Horizon] Invalid Witness (State Mismatch)");
-            println!("      Expected: {}", self.state_root);
-            println!("      Got:      {}", calculated_root);
-            return None;
+            return Err(TxRejection::InvalidWitness {
+                expected: self.state_root.clone(),
+                got: calculated_root,
+            });
         }
 
+        self.config.run_hooks(HookPoint::PostWitness, |h| h.post_witness(tx))?;
+
         // 3. Compute New State Root
         // Stateless update: If valid, we calculate what the root WOULD be
         // if we removed the old UTXO.
         
         // Remove Old (Replace leaf with Empty)
-        let root_after_removal = self.calculate_root(&EMPTY_HASH.to_string(), &tx.witness);
+        let root_after_removal = self.calculate_root(&EMPTY_LEAF, &tx.witness);
+
+        self.config.run_hooks(HookPoint::PreCommit, |h| h.pre_commit(tx, &root_after_removal))?;
         
-        Some(root_after_removal)
+        Ok(root_after_removal)
     }
 
     // Merkle Root calculation from leaf + branch
-    fn calculate_root(&self, leaf_hash: &String, witness: &Witness) -> String {
-        let mut curr_hash = leaf_hash.clone();
-        let mut curr_idx = witness.index;
-
-        for sibling in &witness.siblings {
-            let (left, right) = if curr_idx % 2 == 0 {
-                (curr_hash, sibling.clone())
-            } else {
-                (sibling.clone(), curr_hash)
-            };
-            
-            curr_hash = GSH256::hash_bytes(&(left + &right).into_bytes());
-            curr_idx /= 2;
-        }
-        curr_hash
-    }
-}
\ No newline at end of file
+    fn calculate_root(&self, leaf_hash: &[u8; 32], witness: &Witness) -> String {
+        witness.root(leaf_hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    // Records every invocation and optionally vetoes at one point.
+    struct Probe {
+        name: String,
+        log: Rc<RefCell<Vec<(String, HookPoint)>>>,
+        veto_at: Option<HookPoint>,
+    }
+
+    impl Probe {
+        fn visit(&self, point: HookPoint) -> Result<(), String> {
+            self.log.borrow_mut().push((self.name.clone(), point));
+            if self.veto_at == Some(point) {
+                return Err(format!("{} refuses", self.name));
+            }
+            Ok(())
+        }
+    }
+
+    impl ValidationHook for Probe {
+        fn name(&self) -> &str { &self.name }
+        fn pre_signature(&self, _tx: &Transaction) -> Result<(), String> { self.visit(HookPoint::PreSignature) }
+        fn post_witness(&self, _tx: &Transaction) -> Result<(), String> { self.visit(HookPoint::PostWitness) }
+        fn pre_commit(&self, _tx: &Transaction, _root: &str) -> Result<(), String> { self.visit(HookPoint::PreCommit) }
+    }
+
+    fn probe(name: &str, log: &Rc<RefCell<Vec<(String, HookPoint)>>>, veto_at: Option<HookPoint>) -> Box<dyn ValidationHook> {
+        Box::new(Probe { name: name.to_string(), log: log.clone(), veto_at })
+    }
+
+    fn setup() -> (String, Transaction) {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut acc = HorizonAccumulator::new();
+        let alice = JordanSchnorr::keygen(&mut rng);
+        let bob = JordanSchnorr::keygen(&mut rng);
+        let utxo = Utxo::new([0x11; 32], alice.pub_key.address(), 5);
+        acc.add_utxo(&utxo, 3);
+        let msg = spend_message(&utxo, &bob.pub_key.address(), 5);
+        let tx = Transaction {
+            witness: acc.generate_witness(3),
+            owner_key: alice.pub_key,
+            signature: JordanSchnorr::sign(&alice, TX_CONTEXT, &msg, &mut rng).unwrap(),
+            input_utxo: utxo,
+            new_owner: bob.pub_key.address(),
+            new_amount: 5,
+        };
+        (acc.root.clone(), tx)
+    }
+
+    #[test]
+    fn hooks_run_in_registration_order() {
+        let (root, tx) = setup();
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut config = NodeConfig::new();
+        config.register_hook(probe("first", &log, None)).register_hook(probe("second", &log, None));
+
+        let validator = HorizonValidator::with_config(root, config);
+        assert!(validator.validate_transaction(&tx).is_ok());
+
+        assert_eq!(*log.borrow(), vec![
+            ("first".to_string(), HookPoint::PreSignature),
+            ("second".to_string(), HookPoint::PreSignature),
+            ("first".to_string(), HookPoint::PostWitness),
+            ("second".to_string(), HookPoint::PostWitness),
+            ("first".to_string(), HookPoint::PreCommit),
+            ("second".to_string(), HookPoint::PreCommit),
+        ]);
+    }
+
+    #[test]
+    fn first_veto_short_circuits_and_propagates() {
+        let (root, tx) = setup();
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut config = NodeConfig::new();
+        config
+            .register_hook(probe("policy", &log, Some(HookPoint::PostWitness)))
+            .register_hook(probe("audit", &log, None));
+
+        let validator = HorizonValidator::with_config(root, config);
+        let err = validator.validate_transaction(&tx).unwrap_err();
+        assert_eq!(err, TxRejection::Vetoed(HookVeto {
+            hook: "policy".to_string(),
+            point: HookPoint::PostWitness,
+            reason: "policy refuses".to_string(),
+        }));
+
+        // "audit" never sees PostWitness, and PreCommit is never reached.
+        let last = log.borrow().last().cloned().unwrap();
+        assert_eq!(last, ("policy".to_string(), HookPoint::PostWitness));
+        assert_eq!(log.borrow().len(), 3);
+        assert!(validator.process_transaction(&tx).is_none());
+    }
+
+    #[test]
+    fn core_rejections_take_priority_over_later_hooks() {
+        let (_, tx) = setup();
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut config = NodeConfig::new();
+        config.register_hook(probe("policy", &log, Some(HookPoint::PreCommit)));
+
+        let validator = HorizonValidator::with_config(Gsh32::to_hex(&EMPTY_LEAF), config);
+        assert!(matches!(validator.validate_transaction(&tx), Err(TxRejection::InvalidWitness { .. })));
+        assert_eq!(*log.borrow(), vec![("policy".to_string(), HookPoint::PreSignature)]);
+    }
+
+    #[test]
+    fn shadowed_validator_agrees_on_accepts_and_rejects() {
+        let (root, tx) = setup();
+        let mut config = NodeConfig::new();
+        config.enable_shadow(std::env::temp_dir());
+        let validator = HorizonValidator::with_config(root, config);
+        assert!(validator.validate_transaction(&tx).is_ok());
+        let forged = Transaction { new_amount: 4, ..tx.clone() };
+        assert!(matches!(validator.validate_transaction(&forged), Err(TxRejection::InvalidSignature(_))));
+        assert_eq!(validator.judged.get(), 2);
+    }
+
+    #[test]
+    fn signature_covers_the_output() {
+        let (root, tx) = setup();
+        let validator = HorizonValidator::new(root);
+        assert!(validator.validate_transaction(&tx).is_ok());
+
+        // A relay cannot redirect the coin or change what arrives
+        let thief = Address([0x66; 32]);
+        for forged in [Transaction { new_owner: thief, ..tx.clone() }, Transaction { new_amount: 4, ..tx.clone() }] {
+            assert!(matches!(validator.validate_transaction(&forged), Err(TxRejection::InvalidSignature(_))));
+        }
+    }
+
+    #[test]
+    fn leaf_commits_every_field() {
+        let (_, tx) = setup();
+        let utxo = tx.input_utxo;
+        assert_eq!(utxo.leaf_encoding().len(), LEAF_ENCODING_BYTES);
+
+        // The owner hash covers the key's whole wire encoding, down to its
+        // trailing element A (once left unbound)
+        let mut other_key = tx.owner_key;
+        other_key.a.coeffs[crate::albert::B + 3] ^= 1;
+        let variants = [
+            Utxo { owner: other_key.address(), ..utxo.clone() },
+            Utxo { amount: utxo.amount + 1, ..utxo.clone() },
+            Utxo { asset: [0x42; 32], ..utxo.clone() },
+            Utxo { created_at: 9, ..utxo.clone() },
+        ];
+        for v in &variants {
+            assert_ne!(v.leaf_hash(), utxo.leaf_hash());
+            assert_ne!(v.hash(), utxo.hash());
+        }
+
+        // Rebuilding from the UTXO set matches incremental insertion
+        let mut acc = HorizonAccumulator::new();
+        acc.add_utxo(&utxo, 3);
+        acc.add_utxo(&variants[2], 40);
+        assert_eq!(HorizonAccumulator::from_utxos([(3, &utxo), (40, &variants[2])]).root, acc.root);
+    }
+}
@@ -0,0 +1,215 @@
+// src/jordan_forward.rs
+// FORWARD-SECURE SIGNING: one public key, a secret that only moves forward.
+//
+// A JordanSchnorr secret cannot change under a fixed public key: t = A o s
+// pins s. So the long-term key is a Merkle root (Gsh32) over one
+// JordanSchnorr key per epoch, and the secret is a hash chain of seeds:
+//   seed_(e+1) = H(CHAIN_DOMAIN || seed_e),   key_e = keygen(H(KEY_DOMAIN || seed_e))
+// with keygen drawing from ChaCha20 seeded by that hash: a fixed stream,
+// so a rand release that changes StdRng cannot move the epoch keys.
+// The holder keeps only the current seed (plus the public tree). evolve
+// hashes forward and wipes the old seed, so a node compromised at epoch e
+// can sign for e and later epochs, but H is one-way: keys for earlier
+// epochs - and signatures on past Horizon blocks - stay out of reach.
+//
+// A signature carries its epoch, that epoch's public key, the key's
+// authentication path and an ordinary Signature. Key generation derives
+// every epoch key once, and the secret stores the leaf tree (32 bytes per
+// epoch): sized for thousands of epochs, not millions.
+
+use crate::albert::Q;
+use crate::gsh::GSH256;
+use crate::jordan_sig::{JordanSchnorr, PublicKey, SecretKey, SignError, Signature, VerifyError};
+use crate::merkle::{root_from_path, Gsh32, MerkleTree};
+use crate::redact::Redacted;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+use rand::prelude::*;
+use rand_chacha::ChaCha20Rng;
+use std::fmt;
+
+const CHAIN_DOMAIN: &[u8] = b"jordan-forward/chain";
+const KEY_DOMAIN: &[u8] = b"jordan-forward/key";
+const LEAF_DOMAIN: &[u8] = b"jordan-forward/leaf";
+pub const MAX_EPOCHS: u32 = 1 << 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForwardError {
+    // evolve only moves forward
+    EpochPassed { current: u32, requested: u32 },
+    EpochOutOfRange { epoch: u32, epochs: u32 },
+    // The epoch key is not the one the root commits to for that epoch
+    NotInTree,
+    Signing(SignError),
+    Verify(VerifyError),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ForwardPublicKey {
+    pub root: [u8; 32],
+    pub epochs: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForwardSignature<const M: u64 = Q> {
+    pub epoch: u32,
+    pub key: PublicKey<M>,   // The epoch's JordanSchnorr key
+    pub path: Vec<[u8; 32]>, // Its authentication path, bottom-up
+    pub signature: Signature<M>,
+}
+
+pub struct ForwardSecretKey<const M: u64 = Q> {
+    epoch: u32,
+    seed: [u8; 32], // seed_epoch; every earlier seed is gone
+    current: SecretKey<M>,
+    tree: MerkleTree<Gsh32>,
+    pub public: ForwardPublicKey,
+}
+
+impl<const M: u64> Zeroize for ForwardSecretKey<M> {
+    fn zeroize(&mut self) {
+        self.seed.zeroize();
+        self.current.zeroize();
+    }
+}
+
+impl<const M: u64> Drop for ForwardSecretKey<M> {
+    fn drop(&mut self) {
+        self.seed.zeroize();
+    }
+}
+
+impl<const M: u64> ZeroizeOnDrop for ForwardSecretKey<M> {}
+const _: () = crate::redact::assert_zeroize_on_drop::<ForwardSecretKey>();
+
+// Manual Debug: the seed is redacted (the epoch key redacts itself)
+impl<const M: u64> fmt::Debug for ForwardSecretKey<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ForwardSecretKey")
+            .field("epoch", &self.epoch)
+            .field("seed", &Redacted(&self.seed))
+            .field("current", &self.current)
+            .field("public", &self.public)
+            .finish()
+    }
+}
+
+impl<const M: u64> ForwardSecretKey<M> {
+    /// KEY GENERATION
+    /// A key valid for epochs 0 .. epochs, starting at epoch 0
+    pub fn generate<R: Rng + ?Sized>(epochs: u32, rng: &mut R) -> Self {
+        assert!((1..=MAX_EPOCHS).contains(&epochs), "generate: need 1 <= epochs <= {MAX_EPOCHS}");
+        let first: [u8; 32] = rng.gen();
+        let mut seed = first;
+        let mut leaves = Vec::with_capacity(epochs as usize);
+        for epoch in 0..epochs {
+            leaves.push(leaf(epoch, &epoch_key::<M>(&seed).pub_key));
+            let mut old = seed;
+            seed = next_seed(&old);
+            old.zeroize();
+        }
+        seed.zeroize();
+
+        let tree = MerkleTree::<Gsh32>::new(leaves);
+        let public = ForwardPublicKey { root: tree.root(), epochs };
+        ForwardSecretKey { epoch: 0, seed: first, current: epoch_key(&first), tree, public }
+    }
+
+    pub fn epoch(&self) -> u32 {
+        self.epoch
+    }
+
+    /// EVOLVE
+    /// Moves the key forward to `epoch`, irreversibly: the seeds of every
+    /// skipped epoch are wiped on the way
+    pub fn evolve(&mut self, epoch: u32) -> Result<(), ForwardError> {
+        if epoch < self.epoch {
+            return Err(ForwardError::EpochPassed { current: self.epoch, requested: epoch });
+        }
+        if epoch >= self.public.epochs {
+            return Err(ForwardError::EpochOutOfRange { epoch, epochs: self.public.epochs });
+        }
+        while self.epoch < epoch {
+            let next = next_seed(&self.seed);
+            self.seed.zeroize();
+            self.seed = next;
+            self.epoch += 1;
+        }
+        self.current = epoch_key(&self.seed);
+        Ok(())
+    }
+
+    /// SIGN IN THE CURRENT EPOCH
+    pub fn sign<R: Rng + ?Sized>(&self, context: &[u8], msg: &[u8], rng: &mut R) -> Result<ForwardSignature<M>, ForwardError> {
+        let signature = JordanSchnorr::sign(&self.current, context, msg, rng).map_err(ForwardError::Signing)?;
+        let path = self.tree.prove(self.epoch as usize).map(|proof| proof.siblings).unwrap_or_else(|| unreachable!());
+        Ok(ForwardSignature { epoch: self.epoch, key: self.current.pub_key, path, signature })
+    }
+}
+
+pub struct JordanForward<const M: u64 = Q>;
+
+impl<const M: u64> JordanForward<M> {
+    /// VERIFY
+    /// The epoch key against the root, then its signature
+    pub fn verify(pk: &ForwardPublicKey, context: &[u8], msg: &[u8], sig: &ForwardSignature<M>) -> Result<(), ForwardError> {
+        if sig.epoch >= pk.epochs {
+            return Err(ForwardError::EpochOutOfRange { epoch: sig.epoch, epochs: pk.epochs });
+        }
+        let depth = pk.epochs.next_power_of_two().trailing_zeros() as usize;
+        if sig.path.len() != depth || root_from_path::<Gsh32>(&leaf(sig.epoch, &sig.key), sig.epoch as u64, &sig.path) != pk.root {
+            return Err(ForwardError::NotInTree);
+        }
+        JordanSchnorr::verify(&sig.key, context, msg, &sig.signature).map_err(ForwardError::Verify)
+    }
+}
+
+fn next_seed(seed: &[u8; 32]) -> [u8; 32] {
+    let digest = GSH256::hash_slices(&[CHAIN_DOMAIN, seed]);
+    std::array::from_fn(|i| digest[i])
+}
+
+fn epoch_key<const M: u64>(seed: &[u8; 32]) -> SecretKey<M> {
+    let mut digest = GSH256::hash_slices(&[KEY_DOMAIN, seed]);
+    let sk = JordanSchnorr::keygen(&mut ChaCha20Rng::from_seed(std::array::from_fn(|i| digest[i])));
+    digest.zeroize();
+    sk
+}
+
+// The epoch is hashed in as well as placed by the path index
+fn leaf<const M: u64>(epoch: u32, key: &PublicKey<M>) -> [u8; 32] {
+    Gsh32::leaf(&[LEAF_DOMAIN, &epoch.to_le_bytes(), &key.to_versioned_bytes()].concat())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::horizon::TX_CONTEXT;
+
+    #[test]
+    fn signatures_verify_under_one_key_across_epochs() {
+        let mut rng = StdRng::seed_from_u64(4327);
+        let mut sk = ForwardSecretKey::<Q>::generate(6, &mut rng);
+        let pk = sk.public;
+        let early = sk.sign(TX_CONTEXT, b"block 0", &mut rng).unwrap();
+        assert_eq!(JordanForward::verify(&pk, TX_CONTEXT, b"block 0", &early), Ok(()));
+
+        sk.evolve(4).unwrap();
+        let late = sk.sign(TX_CONTEXT, b"block 4", &mut rng).unwrap();
+        assert_eq!((late.epoch, sk.public), (4, pk));
+        assert_eq!(JordanForward::verify(&pk, TX_CONTEXT, b"block 4", &late), Ok(()));
+        assert_eq!(JordanForward::verify(&pk, TX_CONTEXT, b"block 5", &late), Err(ForwardError::Verify(VerifyError::ChallengeMismatch)));
+        assert_ne!(late.key, early.key);
+
+        // No way back, and no claiming an earlier epoch with a later key
+        assert_eq!(sk.evolve(1), Err(ForwardError::EpochPassed { current: 4, requested: 1 }));
+        let backdated = ForwardSignature { epoch: 0, ..late.clone() };
+        assert_eq!(JordanForward::verify(&pk, TX_CONTEXT, b"block 4", &backdated), Err(ForwardError::NotInTree));
+        assert_eq!(sk.evolve(6), Err(ForwardError::EpochOutOfRange { epoch: 6, epochs: 6 }));
+        sk.evolve(5).unwrap();
+        assert_eq!(JordanForward::verify(&pk, TX_CONTEXT, b"block 0", &early), Ok(()));
+
+        // Pinned: the epoch keys of a seed must not move between builds
+        let key = epoch_key::<Q>(&[0x27; 32]).pub_key;
+        assert_eq!(format!("{:016x}", key.fingerprint().key_id()), "f4eb40bf2a3e48f4");
+    }
+}
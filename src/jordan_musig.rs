@@ -0,0 +1,372 @@
+// src/jordan_musig.rs
+// AGGREGATE SIGNATURES: n Jordan-Schnorr signers, one signature, one key.
+//
+// Every signer keeps its own secret s_i, but all keys share one generator A
+// (JordanSchnorr::keygen_with_generator). Then t = sum t_i = A o (sum s_i),
+// element by element, and a response z = sum z_i under the summed
+// commitment w = sum w_i is an
+// ordinary Signature: JordanSchnorr::verify accepts it against the
+// aggregate key (t, A), and nothing on the verifying side knows there were
+// n signers.
+//
+// Rogue keys: a signer who announces t_r = t' - sum t_i could sign alone for
+// the aggregate. Every member therefore proves possession of its key
+// (JordanSchnorr::prove_possession) and AggregateKey::new refuses any member
+// whose proof fails.
+//
+// Nonces: a signer who sees the others' w_i before choosing its own can
+// steer the aggregate commitment (the Wagner / ROS attacks on two-round
+// Schnorr). So the protocol commits first:
+//   Round 1  each signer samples y_i, w_i = A o y_i, broadcasts H(w_i)
+//   Round 2  once every commitment is in, each broadcasts w_i; everyone
+//            checks them against round 1 and sums w = sum w_i
+//   Respond  each computes z_i = y_i + C s_i, C expanded from
+//            H(context, msg, w), and releases it only if it passes its own
+//            rejection step
+//   Combine  z = sum z_i, checked against the bound `sign` uses
+// Either step can abort (Err(MusigError::Abort)); everyone restarts from
+// round 1.
+// Each y_i is a discrete Gaussian of width sigma = ParamSet::mask_sigma /
+// sqrt(n), so the sum y has width mask_sigma whatever n is. A partial is
+// kept with probability D_sigma(z_i) / (K D_sigma,v(z_i)), v = C s_i
+// (Lyubashevsky's rejection for Gaussian masks), which makes every released
+// z_i a plain D_sigma sample whatever s_i is: a partial seen by the combiner,
+// or by anyone after an abort, says nothing about its signer's secret.
+// K = exp(12 / a + 1 / (2 a^2)) with a = sigma / T, where T bounds |C s_i|
+// for every challenge; T depends on the key alone, so the abort rate shows
+// at most how large s_i is.
+
+use crate::albert::{AlbertElement, DiscreteGaussian, Q};
+use crate::jordan_sig::{
+    Challenge, ChallengeSeed, JordanSchnorr, KeyVector, PublicKey, ResponseVector, SecretKey, Signature, CHALLENGE_WEIGHT,
+    RESPONSE_ELEMENTS,
+};
+use crate::redact::{self, Redacted};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+use rand::prelude::*;
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+const NONCE_DOMAIN: &[u8] = b"jordan-musig/nonce";
+
+// Round 1 message: H(w_i), over all of w_i's elements
+pub type Commitment = [u8; 32];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MusigError {
+    // No members
+    Empty,
+    // A member's key does not use the first member's generator
+    GeneratorMismatch { index: usize },
+    // A member's proof of possession does not verify
+    InvalidProof { index: usize },
+    // A round carried the wrong number of messages
+    CountMismatch { expected: usize, got: usize },
+    // A revealed w_i does not open its round-1 commitment
+    CommitmentMismatch { index: usize },
+    // A signer rejected its partial response, or the aggregate is too
+    // large; restart from round 1
+    Abort,
+}
+
+// The members, in signing order, and the key they sign for together
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AggregateKey<const M: u64 = Q> {
+    pub members: Vec<PublicKey<M>>,
+    pub key: PublicKey<M>,
+}
+
+impl<const M: u64> AggregateKey<M> {
+    // Each member with its proof of possession (JordanSchnorr::prove_possession)
+    pub fn new(members: &[(PublicKey<M>, Signature<M>)]) -> Result<Self, MusigError> {
+        let a = members.first().ok_or(MusigError::Empty)?.0.a;
+        let mut t: KeyVector<M> = std::array::from_fn(|_| AlbertElement::zero());
+        for (index, (pk, proof)) in members.iter().enumerate() {
+            if pk.a != a {
+                return Err(MusigError::GeneratorMismatch { index });
+            }
+            if JordanSchnorr::verify_possession(pk, proof).is_err() {
+                return Err(MusigError::InvalidProof { index });
+            }
+            t = std::array::from_fn(|k| t[k] + pk.t[k]);
+        }
+        Ok(AggregateKey {
+            members: members.iter().map(|(pk, _)| *pk).collect(),
+            key: PublicKey { t, a, params: members[0].0.params },
+        })
+    }
+}
+
+// A signer's round-1 secret. Not Clone: respond consumes it, so a mask is
+// never used for two challenges.
+pub struct Nonce<const M: u64 = Q> {
+    y: ResponseVector<M>,
+    sigma: f64,               // The width y was drawn with
+    pub w: ResponseVector<M>, // Revealed in round 2
+}
+
+// y is wiped when the nonce is dropped, which respond does
+impl<const M: u64> Drop for Nonce<M> {
+    fn drop(&mut self) {
+        self.y.zeroize();
+    }
+}
+
+impl<const M: u64> ZeroizeOnDrop for Nonce<M> {}
+const _: () = crate::redact::assert_zeroize_on_drop::<Nonce>();
+
+// Manual Debug: the mask y is as sensitive as the secret it hides
+impl<const M: u64> fmt::Debug for Nonce<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Nonce")
+            .field("y", &Redacted(&self.y.iter().flat_map(|y| redact::u64_bytes(&y.coeffs)).collect::<Vec<u8>>()))
+            .field("sigma", &self.sigma)
+            .field("w", &self.w)
+            .finish()
+    }
+}
+
+pub struct JordanMusig<const M: u64 = Q>;
+
+impl<const M: u64> JordanMusig<M> {
+    /// ROUND 1
+    /// A fresh mask for one attempt at signing for `agg`, and the commitment
+    /// to broadcast. Keep the Nonce; reveal nonce.w only in round 2.
+    pub fn commit<R: Rng + ?Sized>(agg: &AggregateKey<M>, rng: &mut R) -> (Nonce<M>, Commitment) {
+        Self::commit_under(&agg.key, agg.members.len(), rng)
+    }
+
+    // One of `signers` masks for `key` (also jordan_threshold.rs)
+    pub(crate) fn commit_under<R: Rng + ?Sized>(key: &PublicKey<M>, signers: usize, rng: &mut R) -> (Nonce<M>, Commitment) {
+        let sigma = key.params.mask_sigma() / (signers as f64).sqrt();
+        let mask = DiscreteGaussian::new(sigma);
+        let y: ResponseVector<M> = std::array::from_fn(|_| AlbertElement::from_centered(&std::array::from_fn(|_| mask.sample(rng))));
+        let w = std::array::from_fn(|l| key.a.jordan_product(&y[l]));
+        let commitment = Self::commitment(&w);
+        (Nonce { y, sigma, w }, commitment)
+    }
+
+    /// ROUND 2
+    /// Every member's reveal against its commitment, in member order; the
+    /// aggregate commitment w on success.
+    pub fn open(
+        agg: &AggregateKey<M>,
+        commitments: &[Commitment],
+        reveals: &[ResponseVector<M>],
+    ) -> Result<ResponseVector<M>, MusigError> {
+        Self::open_n(agg.members.len(), commitments, reveals)
+    }
+
+    pub(crate) fn open_n(n: usize, commitments: &[Commitment], reveals: &[ResponseVector<M>]) -> Result<ResponseVector<M>, MusigError> {
+        for got in [commitments.len(), reveals.len()] {
+            if got != n {
+                return Err(MusigError::CountMismatch { expected: n, got });
+            }
+        }
+        let mut w: ResponseVector<M> = std::array::from_fn(|_| AlbertElement::zero());
+        for (index, (commitment, reveal)) in commitments.iter().zip(reveals).enumerate() {
+            if Self::commitment(reveal) != *commitment {
+                return Err(MusigError::CommitmentMismatch { index });
+            }
+            w = std::array::from_fn(|l| w[l] + reveal[l]);
+        }
+        Ok(w)
+    }
+
+    /// RESPOND
+    /// z_i = y_i + C s_i for the aggregate commitment w from `open`, or
+    /// Abort if this signer's rejection step refuses it: z_i is wiped and
+    /// never leaves the device
+    pub fn respond<R: Rng + ?Sized>(
+        sk: &SecretKey<M>,
+        nonce: Nonce<M>,
+        context: &[u8],
+        msg: &[u8],
+        w: &ResponseVector<M>,
+        rng: &mut R,
+    ) -> Result<ResponseVector<M>, MusigError> {
+        let c = JordanSchnorr::<M>::challenge(sk.pub_key.params, context, msg, w);
+        Self::respond_rejecting(&sk.s, nonce, &c, rng)
+    }
+
+    // y + C s for any s, released only past the rejection step; consumes the
+    // nonce (also jordan_threshold.rs, with s a share)
+    pub(crate) fn respond_rejecting<R: Rng + ?Sized>(
+        s: &KeyVector<M>,
+        nonce: Nonce<M>,
+        c: &ChallengeSeed,
+        rng: &mut R,
+    ) -> Result<ResponseVector<M>, MusigError> {
+        let mut cs = Challenge::expand(c).apply(s);
+        let mut z: ResponseVector<M> = std::array::from_fn(|l| nonce.y[l] + cs[l]);
+        let keep = Self::keeps(&z, &cs, nonce.sigma, Self::shift_bound(s), rng);
+        cs.zeroize();
+        if !keep {
+            z.zeroize();
+            return Err(MusigError::Abort);
+        }
+        Ok(z)
+    }
+
+    // T = CHALLENGE_WEIGHT sqrt(RESPONSE_ELEMENTS) max_k |s_k|: each row of
+    // C s sums CHALLENGE_WEIGHT of the s_k, up to sign
+    fn shift_bound(s: &KeyVector<M>) -> f64 {
+        let widest = s.iter().map(|s| (s.norm_l2_sq() as f64).sqrt()).fold(0.0, f64::max);
+        CHALLENGE_WEIGHT as f64 * (RESPONSE_ELEMENTS as f64).sqrt() * widest
+    }
+
+    // Keep z = y + v with probability exp((-2 <z, v> + |v|^2) / (2 sigma^2)) / K
+    fn keeps<R: Rng + ?Sized>(z: &ResponseVector<M>, v: &ResponseVector<M>, sigma: f64, bound: f64, rng: &mut R) -> bool {
+        let (mut zv, mut vv) = (0i64, 0i64);
+        for (z, v) in z.iter().zip(v) {
+            for (a, b) in z.to_centered().iter().zip(v.to_centered()) {
+                zv += a * b;
+                vv += b * b;
+            }
+        }
+        let a = sigma / bound.max(1.0);
+        let log_k = 12.0 / a + 1.0 / (2.0 * a * a);
+        let log_p = (vv as f64 - 2.0 * zv as f64) / (2.0 * sigma * sigma) - log_k;
+        rng.gen::<f64>() < log_p.exp()
+    }
+
+    /// COMBINE
+    /// The aggregate signature, or Abort if the summed response would leak
+    /// (the same bound `sign` rejects on)
+    pub fn combine(
+        agg: &AggregateKey<M>,
+        context: &[u8],
+        msg: &[u8],
+        w: &ResponseVector<M>,
+        partials: &[ResponseVector<M>],
+    ) -> Result<Signature<M>, MusigError> {
+        Self::combine_n(&agg.key, agg.members.len(), context, msg, w, partials)
+    }
+
+    pub(crate) fn combine_n(
+        key: &PublicKey<M>,
+        n: usize,
+        context: &[u8],
+        msg: &[u8],
+        w: &ResponseVector<M>,
+        partials: &[ResponseVector<M>],
+    ) -> Result<Signature<M>, MusigError> {
+        if partials.len() != n {
+            return Err(MusigError::CountMismatch { expected: n, got: partials.len() });
+        }
+        let z: ResponseVector<M> = std::array::from_fn(|l| partials.iter().fold(AlbertElement::zero(), |acc, z| acc + z[l]));
+        let params = key.params;
+        if z.iter().any(|z| z.exceeds_bound(params.gamma2())) {
+            return Err(MusigError::Abort);
+        }
+        Ok(Signature { z, c: JordanSchnorr::<M>::challenge(params, context, msg, w), params })
+    }
+
+    fn commitment(w: &ResponseVector<M>) -> Commitment {
+        let mut hasher = Sha256::new();
+        hasher.update(NONCE_DOMAIN);
+        hasher.update(M.to_le_bytes());
+        for w in w {
+            hasher.update(w.to_bytes());
+        }
+        hasher.finalize().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jordan_sig::VerifyError;
+
+    const CTX: &[u8] = b"test";
+
+    fn signers(rng: &mut StdRng, n: usize) -> (Vec<SecretKey>, AggregateKey) {
+        let a = AlbertElement::sample_invertible(rng);
+        let sks: Vec<SecretKey> = (0..n).map(|_| JordanSchnorr::keygen_with_generator(a, rng)).collect();
+        let members: Vec<_> = sks.iter().map(|sk| (sk.pub_key, JordanSchnorr::prove_possession(sk, rng).unwrap())).collect();
+        (sks, AggregateKey::new(&members).unwrap())
+    }
+
+    // The whole protocol, restarting on Abort
+    fn sign_together(rng: &mut StdRng, sks: &[SecretKey], agg: &AggregateKey, msg: &[u8]) -> Signature {
+        loop {
+            let (nonces, commitments): (Vec<_>, Vec<_>) = sks.iter().map(|_| JordanMusig::commit(agg, rng)).unzip();
+            let reveals: Vec<_> = nonces.iter().map(|nonce| nonce.w).collect();
+            let w = JordanMusig::open(agg, &commitments, &reveals).unwrap();
+            let partials: Result<Vec<_>, _> =
+                sks.iter().zip(nonces).map(|(sk, nonce)| JordanMusig::respond(sk, nonce, CTX, msg, &w, rng)).collect();
+            if let Ok(sig) = partials.and_then(|partials| JordanMusig::combine(agg, CTX, msg, &w, &partials)) {
+                return sig;
+            }
+        }
+    }
+
+    #[test]
+    fn aggregate_signature_verifies_as_an_ordinary_one() {
+        let mut rng = StdRng::seed_from_u64(4312);
+        let (sks, agg) = signers(&mut rng, 3);
+        let sig = sign_together(&mut rng, &sks, &agg, b"spend");
+        assert_eq!(JordanSchnorr::verify(&agg.key, CTX, b"spend", &sig), Ok(()));
+        assert_eq!(JordanSchnorr::verify(&agg.key, CTX, b"other", &sig), Err(VerifyError::ChallengeMismatch));
+        // ...and under no single member's key
+        assert!(sks.iter().all(|sk| JordanSchnorr::verify(&sk.pub_key, CTX, b"spend", &sig).is_err()));
+    }
+
+    #[test]
+    fn rogue_keys_and_swapped_nonces_are_refused() {
+        let mut rng = StdRng::seed_from_u64(4313);
+        let (sks, agg) = signers(&mut rng, 2);
+
+        // A rogue key cancelling the honest one has no secret to prove with
+        let rogue = PublicKey { t: sks[0].pub_key.t.map(|t| AlbertElement::zero() - t), ..agg.key };
+        let forged = JordanSchnorr::prove_possession(&sks[1], &mut rng).unwrap();
+        let members = [(sks[0].pub_key, JordanSchnorr::prove_possession(&sks[0], &mut rng).unwrap()), (rogue, forged)];
+        assert_eq!(AggregateKey::new(&members), Err(MusigError::InvalidProof { index: 1 }));
+        // ...nor can a key under another generator join
+        let other = JordanSchnorr::keygen(&mut rng);
+        let members = [members[0].clone(), (other.pub_key, JordanSchnorr::prove_possession(&other, &mut rng).unwrap())];
+        assert_eq!(AggregateKey::new(&members), Err(MusigError::GeneratorMismatch { index: 1 }));
+
+        // A nonce changed after round 1 does not open its commitment
+        let (first, c0) = JordanMusig::commit(&agg, &mut rng);
+        let (second, c1) = JordanMusig::commit(&agg, &mut rng);
+        let (late, _) = JordanMusig::commit(&agg, &mut rng);
+        assert!(JordanMusig::open(&agg, &[c0, c1], &[first.w, second.w]).is_ok());
+        assert_eq!(JordanMusig::open(&agg, &[c0, c1], &[first.w, late.w]), Err(MusigError::CommitmentMismatch { index: 1 }));
+        assert_eq!(JordanMusig::open(&agg, &[c0], &[first.w]), Err(MusigError::CountMismatch { expected: 2, got: 1 }));
+    }
+
+    #[test]
+    fn partials_are_rejection_sampled_before_release() {
+        let mut rng = StdRng::seed_from_u64(4314);
+        let (sks, agg) = signers(&mut rng, 3);
+        let s = &sks[0].s;
+        let v: ResponseVector = Challenge::expand(&[7; 32]).apply(s);
+        let (sigma, bound) = (agg.key.params.mask_sigma() / 3f64.sqrt(), JordanMusig::<Q>::shift_bound(s));
+        assert!(v.iter().map(|v| v.norm_l2_sq() as f64).sum::<f64>().sqrt() <= bound);
+
+        // A response far out along C s is rarely released, one far out
+        // against it always is: the shift C s no longer shows
+        let push = |sign: i64| -> ResponseVector {
+            std::array::from_fn(|l| AlbertElement::from_centered(&v[l].to_centered().map(|x| sign * 6000 * x.signum())))
+        };
+        let kept = |z: &ResponseVector, rng: &mut StdRng| (0..100).filter(|_| JordanMusig::<Q>::keeps(z, &v, sigma, bound, rng)).count();
+        assert!(kept(&push(1), &mut rng) < 30);
+        assert_eq!(kept(&push(-1), &mut rng), 100);
+
+        // Through the protocol: some partials abort, none of them escape
+        let mut aborts = 0;
+        for _ in 0..20 {
+            let (nonce, _) = JordanMusig::commit(&agg, &mut rng);
+            let w = nonce.w;
+            match JordanMusig::respond(&sks[0], nonce, CTX, b"spend", &w, &mut rng) {
+                Ok(z) => assert!(z.iter().all(|z| !z.exceeds_bound(agg.key.params.gamma2()))),
+                Err(e) => {
+                    assert_eq!(e, MusigError::Abort);
+                    aborts += 1;
+                }
+            }
+        }
+        assert!((1..20).contains(&aborts), "{aborts} of 20 partials aborted");
+    }
+}
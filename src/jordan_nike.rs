@@ -0,0 +1,97 @@
+// src/jordan_nike.rs
+// NON-INTERACTIVE KEY EXCHANGE: a shared secret from two JordanSchnorr keys.
+//
+// The DH analog: Alice holds s_a and sees t_b = A o s_b, Bob holds s_b and
+// sees t_a = A o s_a, and each evaluates a form both can reach. The trace
+// form T is associative (T(x o y, z) = T(x, y o z)), and multiplication by
+// A commutes with multiplication by any power of A (power associativity),
+// so for P in {1, A, A^2}
+//   T(s_a, P o t_b) = T(s_a, P o (A o s_b)) = T(s_b, P o (A o s_a)) = T(s_b, P o t_a)
+// Keys hold KEY_ELEMENTS secrets each, and the forms add up over them:
+// F_P = sum_k T(s_a[k], P o t_b[k]). Three such sums - higher powers of A
+// are combinations of these (A is a root of its cubic) - run through
+// GSH-256 with both public keys give the session secret.
+//
+// Both keys must share the generator A (JordanSchnorr::keygen_with_generator).
+// Research only (`--features research`): the products carry 3 log2(M) bits
+// (45 at Q), and keys without an error term are one linear solve from s.
+// Nothing in the node, wallet or P2P layer keys a channel from it.
+
+use crate::albert::{AlbertElement, Q};
+use crate::gsh::GSH256;
+use crate::jordan_sig::{JordanSchnorr, PublicKey, SecretKey};
+use rand::Rng;
+
+const NIKE_DOMAIN: &[u8] = b"jordan-nike/v1";
+
+pub type SharedSecret = [u8; 32];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NikeError {
+    // The peer's generator is not ours
+    GeneratorMismatch,
+    // The peer's key is not reduced, or its A is not invertible
+    MalformedKey,
+}
+
+pub struct JordanNike<const M: u64 = Q>;
+
+impl<const M: u64> JordanNike<M> {
+    /// The secret sk shares with peer's holder; both sides compute the same
+    /// bytes
+    pub fn shared_secret(sk: &SecretKey<M>, peer: &PublicKey<M>) -> Result<SharedSecret, NikeError> {
+        if peer.a != sk.pub_key.a {
+            return Err(NikeError::GeneratorMismatch);
+        }
+        JordanSchnorr::check_key(peer).map_err(|_| NikeError::MalformedKey)?;
+
+        let a = peer.a;
+        let a2 = a.square();
+        let mut forms = [0; 3];
+        for (s, t) in sk.s.iter().zip(&peer.t) {
+            let products = [*t, a.jordan_product(t), a2.jordan_product(t)];
+            for (form, p) in forms.iter_mut().zip(&products) {
+                *form = (*form + s.trace_form(p)) % M;
+            }
+        }
+
+        // Both keys, in byte order, so either side lists them the same way
+        let (mine, theirs) = (sk.pub_key.to_versioned_bytes(), peer.to_versioned_bytes());
+        let (low, high) = if mine <= theirs { (mine, theirs) } else { (theirs, mine) };
+        let forms: Vec<u8> = forms.iter().flat_map(|f| f.to_le_bytes()).collect();
+        let digest = GSH256::hash_slices(&[NIKE_DOMAIN, &M.to_le_bytes(), &low, &high, &forms]);
+        let mut out = [0u8; 32];
+        out.copy_from_slice(&digest[..32]);
+        Ok(out)
+    }
+
+    /// A fresh generator for a set of peers to key under; a published one
+    /// comes from JordanSchnorr::derive_generator instead
+    pub fn generator<R: Rng + ?Sized>(rng: &mut R) -> AlbertElement<M> {
+        AlbertElement::sample_invertible(rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::prelude::*;
+
+    #[test]
+    fn both_sides_derive_one_secret() {
+        let mut rng = StdRng::seed_from_u64(4318);
+        let a = JordanNike::<Q>::generator(&mut rng);
+        let (alice, bob, carol) = (
+            JordanSchnorr::keygen_with_generator(a, &mut rng),
+            JordanSchnorr::keygen_with_generator(a, &mut rng),
+            JordanSchnorr::keygen_with_generator(a, &mut rng),
+        );
+        let ab = JordanNike::shared_secret(&alice, &bob.pub_key).unwrap();
+        assert_eq!(JordanNike::shared_secret(&bob, &alice.pub_key), Ok(ab));
+        assert_ne!(JordanNike::shared_secret(&alice, &carol.pub_key), Ok(ab));
+
+        // Keys under separate generators have no common form
+        let stranger = JordanSchnorr::keygen(&mut rng);
+        assert_eq!(JordanNike::shared_secret(&alice, &stranger.pub_key), Err(NikeError::GeneratorMismatch));
+    }
+}
@@ -0,0 +1,247 @@
+// src/jordan_recovery.rs
+// MESSAGE RECOVERY: part of the message travels inside the signature.
+//
+// Pintsov-Vanstone over JordanSchnorr keys. The signer splits the message
+// into a clear part, sent as usual, and a short recoverable part m_r:
+//   1. w = A o y, k = KDF(w)
+//   2. r = Flutter_k(m_r || tag),  tag = H(k, m_r) cut to TAG_BYTES
+//   3. c = H(context, clear, r),   z = y + C s  (rejection sampled as `sign`)
+// and sends (z, r) with no challenge. The verifier derives c from r, gets
+// w = A o z - C t back, and decrypts r under KDF(w); only the signer's w
+// gives a tag that checks. r binds w, so c still depends on the commitment.
+//
+// A forger who picks z and r freely decrypts r under an unrelated key and
+// passes the tag with probability 2^-(8 TAG_BYTES): TAG_BYTES covers
+// CHALLENGE_BITS, so the scheme is no weaker than guessing a challenge of
+// JordanSchnorr itself. The tag replaces the 32-byte challenge seed of
+// Signature, which saves 32 - TAG_BYTES bytes per packet: meant for IoT
+// frames already sealed with the Flutter cipher, where every byte of a
+// short payload counts.
+
+use crate::albert::{AlbertElement, Q};
+use crate::algebra::DecodeError;
+use crate::flt_cipher::FlutterCipher;
+use crate::gsh::GSH256;
+use crate::jordan_sig::{
+    self, Challenge, ChallengeSeed, JordanSchnorr, ParamSet, PublicKey, ResponseVector, SecretKey, SignError, VerifyError,
+    CHALLENGE_BITS, MAX_ABORTS,
+};
+use zeroize::Zeroize;
+use rand::prelude::*;
+use sha2::{Digest, Sha256};
+
+const CHALLENGE_DOMAIN: &[u8] = b"jordan-recovery/challenge";
+const KEY_DOMAIN: &[u8] = b"jordan-recovery/key";
+const TAG_DOMAIN: &[u8] = b"jordan-recovery/tag";
+pub const MAX_RECOVERABLE: usize = 64;
+pub const TAG_BYTES: usize = CHALLENGE_BITS.div_ceil(8) as usize;
+const _: () = assert!(TAG_BYTES >= 16);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecoverableSignature<const M: u64 = Q> {
+    pub z: ResponseVector<M>,
+    pub r: Vec<u8>, // The recoverable part and its tag, encrypted under KDF(w)
+    pub params: ParamSet,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecoveryError {
+    TooLong { len: usize, max: usize },
+    Signing(SignError),
+    // Key, set or bound checks, as in JordanSchnorr::verify
+    Verify(VerifyError),
+    // The recovered part does not carry its tag: forged, altered, or
+    // checked against another key, context or clear part
+    TagMismatch,
+}
+
+pub struct JordanRecovery<const M: u64 = Q>;
+
+impl<const M: u64> JordanRecovery<M> {
+    /// SIGN, EMBEDDING `recoverable`
+    /// Only `clear` is sent alongside; verify hands `recoverable` back
+    pub fn sign<R: Rng + ?Sized>(
+        sk: &SecretKey<M>,
+        context: &[u8],
+        clear: &[u8],
+        recoverable: &[u8],
+        rng: &mut R,
+    ) -> Result<RecoverableSignature<M>, RecoveryError> {
+        if recoverable.len() > MAX_RECOVERABLE {
+            return Err(RecoveryError::TooLong { len: recoverable.len(), max: MAX_RECOVERABLE });
+        }
+        let params = sk.pub_key.params;
+        JordanSchnorr::check_secret(sk).map_err(RecoveryError::Signing)?;
+        for _ in 0..=MAX_ABORTS {
+            let mut y = JordanSchnorr::<M>::sample_mask(params, rng);
+            let w = std::array::from_fn(|l| sk.pub_key.a.jordan_product(&y[l]));
+            let r = Self::seal(&w, recoverable);
+            let c = Self::challenge(params, context, clear, &r);
+
+            let mut cs = Challenge::expand(&c).apply(&sk.s);
+            let mut z: ResponseVector<M> = std::array::from_fn(|l| y[l] + cs[l]);
+            y.zeroize();
+            cs.zeroize();
+            if z.iter().any(|z| z.exceeds_bound(params.gamma2())) {
+                z.zeroize();
+                continue;
+            }
+            return Ok(RecoverableSignature { z, r, params });
+        }
+        Err(RecoveryError::Signing(SignError::TooManyAborts { attempts: MAX_ABORTS + 1 }))
+    }
+
+    /// VERIFY AND RECOVER
+    /// The embedded part, once the signature checks
+    pub fn verify(pk: &PublicKey<M>, context: &[u8], clear: &[u8], sig: &RecoverableSignature<M>) -> Result<Vec<u8>, RecoveryError> {
+        JordanSchnorr::check_key(pk).map_err(RecoveryError::Verify)?;
+        let params = pk.params;
+        // r is the recoverable part and its tag: no shorter, and no longer
+        // than from_bytes accepts, so a hostile r costs no unbounded work
+        let r_ok = (TAG_BYTES..=MAX_RECOVERABLE + TAG_BYTES).contains(&sig.r.len());
+        if sig.params != params || !jordan_sig::reduced(&sig.z) || !r_ok {
+            return Err(RecoveryError::Verify(VerifyError::ParameterMismatch));
+        }
+        let norm = jordan_sig::norm_inf(&sig.z);
+        if norm > params.gamma2() {
+            return Err(RecoveryError::Verify(VerifyError::BoundViolation { norm, bound: params.gamma2() }));
+        }
+
+        let c = Self::challenge(params, context, clear, &sig.r);
+        let ct = Challenge::expand(&c).apply(&pk.t);
+        let w = std::array::from_fn(|l| pk.a.jordan_product(&sig.z[l]) - ct[l]);
+        Self::open(&w, &sig.r).ok_or(RecoveryError::TagMismatch)
+    }
+
+    // r = Flutter_k(m_r || tag)
+    fn seal(w: &ResponseVector<M>, recoverable: &[u8]) -> Vec<u8> {
+        let key = Self::key(w);
+        let mut r = recoverable.to_vec();
+        r.extend_from_slice(&Self::tag(&key, recoverable));
+        Self::cipher(&key).process(&mut r);
+        r
+    }
+
+    fn open(w: &ResponseVector<M>, r: &[u8]) -> Option<Vec<u8>> {
+        let key = Self::key(w);
+        let mut plain = r.to_vec();
+        Self::cipher(&key).process(&mut plain);
+        let tag = plain.split_off(plain.len() - TAG_BYTES);
+        // Compare without early exit
+        let expected = Self::tag(&key, &plain);
+        (expected.iter().zip(&tag).fold(0u8, |acc, (a, b)| acc | (a ^ b)) == 0).then_some(plain)
+    }
+
+    // Key and IV from the 32-byte k: one cipher per w, never two streams
+    fn cipher(key: &[u8; 32]) -> FlutterCipher {
+        let word = |i: usize| u16::from_le_bytes([key[2 * i], key[2 * i + 1]]);
+        FlutterCipher::new(std::array::from_fn(word), std::array::from_fn(|i| word(8 + i)))
+    }
+
+    fn key(w: &ResponseVector<M>) -> [u8; 32] {
+        let w: Vec<u8> = w.iter().flat_map(AlbertElement::to_bytes).collect();
+        let digest = GSH256::hash_slices(&[KEY_DOMAIN, &M.to_le_bytes(), &w]);
+        std::array::from_fn(|i| digest[i])
+    }
+
+    fn tag(key: &[u8; 32], recoverable: &[u8]) -> Vec<u8> {
+        GSH256::hash_slices(&[TAG_DOMAIN, key, recoverable])[..TAG_BYTES].to_vec()
+    }
+
+    // c = H(domain || M || id || |context| || context || |clear| || clear || r)
+    fn challenge(params: ParamSet, context: &[u8], clear: &[u8], r: &[u8]) -> ChallengeSeed {
+        let mut hasher = Sha256::new();
+        hasher.update(CHALLENGE_DOMAIN);
+        hasher.update(M.to_le_bytes());
+        hasher.update([params.id()]);
+        hasher.update((context.len() as u64).to_le_bytes());
+        hasher.update(context);
+        hasher.update((clear.len() as u64).to_le_bytes());
+        hasher.update(clear);
+        hasher.update(r);
+        hasher.finalize().into()
+    }
+}
+
+// --- SERIALIZATION ---
+//   id || z_0 .. z_9 (packed) || r
+// r runs to the end of the frame, so the recoverable part costs no length
+// byte.
+impl<const M: u64> RecoverableSignature<M> {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = vec![self.params.id()];
+        for z in &self.z {
+            out.extend(z.to_bytes());
+        }
+        out.extend(&self.r);
+        out
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let id = *bytes.first().ok_or(DecodeError::Length { expected: 1, got: 0 })?;
+        let params = ParamSet::from_id(id)
+            .filter(|set| set.modulus() == M)
+            .ok_or(DecodeError::ParameterSet { expected: JordanSchnorr::<M>::PARAMS.id(), got: id })?;
+        let z_bytes = jordan_sig::RESPONSE_ELEMENTS * AlbertElement::<M>::PACKED_BYTES;
+        let min = 1 + z_bytes + TAG_BYTES;
+        if bytes.len() < min || bytes.len() > min + MAX_RECOVERABLE {
+            return Err(DecodeError::Length { expected: min, got: bytes.len() });
+        }
+        let (z, r) = bytes[1..].split_at(z_bytes);
+        Ok(RecoverableSignature { z: jordan_sig::unpack(z)?, r: r.to_vec(), params })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::albert::Q_DILITHIUM;
+    use crate::jordan_sig::Signature;
+
+    const CTX: &[u8] = b"iot/reading";
+
+    #[test]
+    fn the_embedded_part_comes_back_and_saves_bytes() {
+        let mut rng = StdRng::seed_from_u64(4328);
+        let sk = JordanSchnorr::<Q>::keygen(&mut rng);
+        let sig = JordanRecovery::sign(&sk, CTX, b"sensor 7", b"21.5C", &mut rng).unwrap();
+        let sent = RecoverableSignature::<Q>::from_bytes(&sig.to_bytes()).unwrap();
+        assert_eq!(JordanRecovery::verify(&sk.pub_key, CTX, b"sensor 7", &sent), Ok(b"21.5C".to_vec()));
+        // The 32-byte challenge seed gives way to a 17-byte tag
+        assert_eq!(sig.to_bytes().len(), Signature::<Q>::BYTES + b"21.5C".len() - 15);
+
+        let dk = JordanSchnorr::<Q_DILITHIUM>::keygen(&mut rng);
+        let sig = JordanRecovery::sign(&dk, CTX, b"", b"payload", &mut rng).unwrap();
+        assert_eq!(JordanRecovery::verify(&dk.pub_key, CTX, b"", &sig), Ok(b"payload".to_vec()));
+    }
+
+    #[test]
+    fn altered_packets_fail_the_tag() {
+        let mut rng = StdRng::seed_from_u64(4329);
+        let sk = JordanSchnorr::<Q>::keygen(&mut rng);
+        let other = JordanSchnorr::<Q>::keygen(&mut rng);
+        let sig = JordanRecovery::sign(&sk, CTX, b"sensor 7", b"21.5C", &mut rng).unwrap();
+
+        assert_eq!(JordanRecovery::verify(&sk.pub_key, CTX, b"sensor 8", &sig), Err(RecoveryError::TagMismatch));
+        assert_eq!(JordanRecovery::verify(&sk.pub_key, b"iot/other", b"sensor 7", &sig), Err(RecoveryError::TagMismatch));
+        assert_eq!(JordanRecovery::verify(&other.pub_key, CTX, b"sensor 7", &sig), Err(RecoveryError::TagMismatch));
+        let mut bent = sig.clone();
+        bent.r[0] ^= 1;
+        assert_eq!(JordanRecovery::verify(&sk.pub_key, CTX, b"sensor 7", &bent), Err(RecoveryError::TagMismatch));
+
+        // r outside [TAG_BYTES, MAX_RECOVERABLE + TAG_BYTES] is refused unread
+        let mismatch = Err(RecoveryError::Verify(VerifyError::ParameterMismatch));
+        let mut short = sig.clone();
+        short.r.truncate(TAG_BYTES - 1);
+        assert_eq!(JordanRecovery::verify(&sk.pub_key, CTX, b"sensor 7", &short), mismatch);
+        let mut huge = sig.clone();
+        huge.r.resize(MAX_RECOVERABLE + TAG_BYTES + 1, 0);
+        assert_eq!(JordanRecovery::verify(&sk.pub_key, CTX, b"sensor 7", &huge), mismatch);
+
+        let long = [0u8; MAX_RECOVERABLE + 1];
+        assert_eq!(
+            JordanRecovery::sign(&sk, CTX, b"", &long, &mut rng),
+            Err(RecoveryError::TooLong { len: MAX_RECOVERABLE + 1, max: MAX_RECOVERABLE })
+        );
+    }
+}
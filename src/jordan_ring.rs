@@ -0,0 +1,236 @@
+// src/jordan_ring.rs
+// RING SIGNATURES: "one of these keys signed", without saying which.
+//
+// The Abe-Ohkubo-Suzuki ring over JordanSchnorr keys, each with its own
+// generator A_j. Challenge seeds chain around the ring, each expanding to a
+// challenge matrix C_j as in jordan_sig.rs:
+//   c_(j+1) = H(ring, msg, A_j o z_j - C_j t_j)
+// The signer pi starts the chain from a real commitment w = A_pi o y, fills
+// every other z_j at random and closes the ring with z_pi = y + C_pi s. A
+// verifier walks the chain from c_0 and must arrive back at c_0.
+//
+// Anonymity needs the real z_pi to look exactly like the filled ones. The
+// ring masks as `sign` does, y uniform on [-GAMMA1, GAMMA1], and rejects
+// until |z| <= GAMMA2 = GAMMA1 - BETA: that leaves z_pi uniform on
+// [-GAMMA2, GAMMA2] whatever s is - the distribution every filled z_j is
+// drawn from. The price is a signature of n response vectors.
+
+use crate::albert::{AlbertElement, Q};
+use crate::jordan_sig::{
+    self, Challenge, ChallengeSeed, JordanSchnorr, ParamSet, PublicKey, ResponseVector, SecretKey, SignError, VerifyError,
+    MAX_ABORTS,
+};
+use zeroize::Zeroize;
+use rand::prelude::*;
+use sha2::{Digest, Sha256};
+
+const RING_DOMAIN: &[u8] = b"jordan-ring/challenge";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RingSignature<const M: u64 = Q> {
+    pub c0: ChallengeSeed,         // The challenge entering member 0
+    pub z: Vec<ResponseVector<M>>, // One response vector per ring member
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RingError {
+    Empty,
+    // The signer's key is not in the ring
+    NotAMember,
+    // One response per key
+    SizeMismatch { keys: usize, responses: usize },
+    // A member's key or response is refused, as JordanSchnorr::verify would
+    Member { index: usize, error: VerifyError },
+    // The chain does not close back at c0
+    ChallengeMismatch,
+    // The signer's key cannot sign (JordanSchnorr::sign would refuse it),
+    // or every attempt was rejected
+    Signing(SignError),
+}
+
+pub struct JordanRing<const M: u64 = Q>;
+
+impl<const M: u64> JordanRing<M> {
+    /// SIGN FOR A RING
+    /// `ring` must contain sk's public key; its position is not revealed.
+    /// Gives up after MAX_ABORTS rejections, as JordanSchnorr::sign does.
+    pub fn sign<R: Rng + ?Sized>(
+        ring: &[PublicKey<M>],
+        sk: &SecretKey<M>,
+        context: &[u8],
+        msg: &[u8],
+        rng: &mut R,
+    ) -> Result<RingSignature<M>, RingError> {
+        Self::sign_with_limit(ring, sk, context, msg, MAX_ABORTS, rng)
+    }
+
+    fn sign_with_limit<R: Rng + ?Sized>(
+        ring: &[PublicKey<M>],
+        sk: &SecretKey<M>,
+        context: &[u8],
+        msg: &[u8],
+        max_aborts: u32,
+        rng: &mut R,
+    ) -> Result<RingSignature<M>, RingError> {
+        let n = ring.len();
+        if n == 0 {
+            return Err(RingError::Empty);
+        }
+        let pi = ring.iter().position(|pk| *pk == sk.pub_key).ok_or(RingError::NotAMember)?;
+        let params = Self::params(ring)?;
+        JordanSchnorr::check_secret(sk).map_err(RingError::Signing)?;
+        let bound = params.gamma2() as i64;
+        let bound_msg = Self::bind(ring, msg);
+        for _ in 0..=max_aborts {
+            // 1. Real commitment at pi, uniform mask
+            let mut y = JordanSchnorr::<M>::sample_mask(params, rng);
+            let mut c = vec![[0u8; 32]; n];
+            let mut z = vec![std::array::from_fn(|_| AlbertElement::zero()); n];
+            let w = std::array::from_fn(|l| ring[pi].a.jordan_product(&y[l]));
+            c[(pi + 1) % n] = Self::challenge(params, context, &bound_msg, &w);
+
+            // 2. Every other member: a random response, its implied commitment
+            for k in 1..n {
+                let j = (pi + k) % n;
+                z[j] = std::array::from_fn(|_| {
+                    AlbertElement::from_centered(&std::array::from_fn(|_| rng.gen_range(-bound..=bound)))
+                });
+                c[(j + 1) % n] = Self::challenge(params, context, &bound_msg, &Self::commitment(&ring[j], &z[j], &c[j]));
+            }
+
+            // 3. Close the ring; reject unless z_pi is uniform on the bound
+            let mut cs = Challenge::expand(&c[pi]).apply(&sk.s);
+            z[pi] = std::array::from_fn(|l| y[l] + cs[l]);
+            y.zeroize();
+            cs.zeroize();
+            if z[pi].iter().any(|z| z.exceeds_bound(params.gamma2())) {
+                z[pi].zeroize();
+                continue;
+            }
+            return Ok(RingSignature { c0: c[0], z });
+        }
+        Err(RingError::Signing(SignError::TooManyAborts { attempts: max_aborts + 1 }))
+    }
+
+    /// VERIFY A RING SIGNATURE
+    /// Walks c_0 -> c_1 -> .. -> c_n and checks c_n == c_0
+    pub fn verify(ring: &[PublicKey<M>], context: &[u8], msg: &[u8], sig: &RingSignature<M>) -> Result<(), RingError> {
+        if ring.is_empty() {
+            return Err(RingError::Empty);
+        }
+        if sig.z.len() != ring.len() {
+            return Err(RingError::SizeMismatch { keys: ring.len(), responses: sig.z.len() });
+        }
+        let params = Self::params(ring)?;
+        let bound_msg = Self::bind(ring, msg);
+        let mut c = sig.c0;
+        for (index, (pk, z)) in ring.iter().zip(&sig.z).enumerate() {
+            let member = |error| RingError::Member { index, error };
+            JordanSchnorr::check_key(pk).map_err(member)?;
+            if !jordan_sig::reduced(z) {
+                return Err(member(VerifyError::ParameterMismatch));
+            }
+            let norm = jordan_sig::norm_inf(z);
+            if norm > params.gamma2() {
+                return Err(member(VerifyError::BoundViolation { norm, bound: params.gamma2() }));
+            }
+            c = Self::challenge(params, context, &bound_msg, &Self::commitment(pk, z, &c));
+        }
+        if c != sig.c0 {
+            return Err(RingError::ChallengeMismatch);
+        }
+        Ok(())
+    }
+
+    // The ring, in order, bound to the message: the same signature over a
+    // reordered or different ring is a different chain
+    fn bind(ring: &[PublicKey<M>], msg: &[u8]) -> Vec<u8> {
+        let mut hasher = Sha256::new();
+        hasher.update((ring.len() as u64).to_le_bytes());
+        for pk in ring {
+            hasher.update(pk.to_versioned_bytes());
+        }
+        let mut out = hasher.finalize().to_vec();
+        out.extend_from_slice(msg);
+        out
+    }
+
+    // Every member in the first member's set
+    fn params(ring: &[PublicKey<M>]) -> Result<ParamSet, RingError> {
+        let params = ring[0].params;
+        match ring.iter().position(|pk| pk.params != params) {
+            Some(index) => Err(RingError::Member { index, error: VerifyError::ParameterMismatch }),
+            None => Ok(params),
+        }
+    }
+
+    // w = A o z - C t, the commitment a member's response implies
+    fn commitment(pk: &PublicKey<M>, z: &ResponseVector<M>, c: &ChallengeSeed) -> ResponseVector<M> {
+        let ct = Challenge::expand(c).apply(&pk.t);
+        std::array::from_fn(|l| pk.a.jordan_product(&z[l]) - ct[l])
+    }
+
+    fn challenge(params: ParamSet, context: &[u8], bound_msg: &[u8], w: &ResponseVector<M>) -> ChallengeSeed {
+        JordanSchnorr::<M>::hash_to_seed(RING_DOMAIN, params, context, bound_msg, w)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::horizon::TX_CONTEXT;
+
+    fn ring(rng: &mut StdRng, n: usize) -> (Vec<SecretKey>, Vec<PublicKey>) {
+        let sks: Vec<SecretKey> = (0..n).map(|_| JordanSchnorr::keygen(rng)).collect();
+        let pks = sks.iter().map(|sk| sk.pub_key).collect();
+        (sks, pks)
+    }
+
+    #[test]
+    fn any_member_signs_for_the_ring() {
+        let mut rng = StdRng::seed_from_u64(4314);
+        let (sks, pks) = ring(&mut rng, 4);
+        for sk in &sks {
+            let sig = JordanRing::sign(&pks, sk, TX_CONTEXT, b"spend", &mut rng).unwrap();
+            assert_eq!(JordanRing::verify(&pks, TX_CONTEXT, b"spend", &sig), Ok(()));
+            assert_eq!(JordanRing::verify(&pks, TX_CONTEXT, b"other", &sig), Err(RingError::ChallengeMismatch));
+            assert_eq!(JordanRing::verify(&pks, b"elsewhere", b"spend", &sig), Err(RingError::ChallengeMismatch));
+        }
+        // A ring of one is a Schnorr signature under its own domain
+        let sig = JordanRing::sign(&pks[..1], &sks[0], TX_CONTEXT, b"spend", &mut rng).unwrap();
+        assert_eq!(JordanRing::verify(&pks[..1], TX_CONTEXT, b"spend", &sig), Ok(()));
+    }
+
+    #[test]
+    fn the_ring_is_bound_and_outsiders_cannot_sign() {
+        let mut rng = StdRng::seed_from_u64(4315);
+        let (sks, pks) = ring(&mut rng, 3);
+        let sig = JordanRing::sign(&pks, &sks[1], TX_CONTEXT, b"spend", &mut rng).unwrap();
+
+        // Reordered, or with a member swapped out, the chain no longer closes
+        let mut reordered = pks.clone();
+        reordered.swap(0, 2);
+        assert_eq!(JordanRing::verify(&reordered, TX_CONTEXT, b"spend", &sig), Err(RingError::ChallengeMismatch));
+        let (outsider, _) = ring(&mut rng, 1);
+        let swapped = [pks[0], outsider[0].pub_key, pks[2]];
+        assert_eq!(JordanRing::verify(&swapped, TX_CONTEXT, b"spend", &sig), Err(RingError::ChallengeMismatch));
+        assert_eq!(
+            JordanRing::verify(&pks[..2], TX_CONTEXT, b"spend", &sig),
+            Err(RingError::SizeMismatch { keys: 2, responses: 3 })
+        );
+        assert_eq!(JordanRing::sign(&pks, &outsider[0], TX_CONTEXT, b"spend", &mut rng), Err(RingError::NotAMember));
+    }
+
+    #[test]
+    fn signing_gives_up_after_max_aborts() {
+        let mut rng = StdRng::seed_from_u64(4316);
+        let (sks, pks) = ring(&mut rng, 2);
+        let sign = |seed, max_aborts| JordanRing::sign_with_limit(&pks, &sks[0], TX_CONTEXT, b"spend", max_aborts, &mut StdRng::seed_from_u64(seed));
+        // The first seed whose first attempt is rejected: with no retries
+        // allowed it fails, with the usual limit the same coins sign
+        let seed = (0..).find(|&seed| sign(seed, 0).is_err()).unwrap();
+        assert_eq!(sign(seed, 0), Err(RingError::Signing(SignError::TooManyAborts { attempts: 1 })));
+        let sig = sign(seed, MAX_ABORTS).unwrap();
+        assert_eq!(JordanRing::verify(&pks, TX_CONTEXT, b"spend", &sig), Ok(()));
+    }
+}
@@ -2,153 +2,1182 @@
 // Jordan-Dilithium: A Post-Quantum Signature Scheme over J3(O)
 // Designed for UTxO Transaction Signing in the APH Framework.
 
-use crate::albert::{AlbertElement, Scalar};
+use crate::albert::{AlbertElement, Scalar, Q, Q_DILITHIUM};
+use crate::algebra::encoding::{bech32_decode, bech32_encode, hex, Bech32Variant};
+use crate::algebra::DecodeError;
+use crate::flt_cipher::{FlutterCipher, TAG_BYTES};
+use crate::gsh::pwhash::{pwhash, PwHashError, PwHashParams};
+use crate::gsh::GSH256;
+use crate::redact::{self, Redacted};
+use zeroize::{Zeroize, ZeroizeOnDrop};
 use sha2::{Sha256, Digest};
+use p3_maybe_rayon::prelude::*;
 use rand::prelude::*;
+use std::fmt;
+use std::str::FromStr;
 
 // ============================================================================
 // CONFIGURATION
 // ============================================================================
-const GAMMA1: Scalar = 10000; // Rejection sampling bound (approx 2^13)
-const GAMMA2: Scalar = 20000; // Overflow bound
+// Every parameter set (ParamSet) runs over a modulus M (albert::Q, or the
+// prime albert::Q_DILITHIUM). Masks are uniform on [-GAMMA1, GAMMA1], GAMMA1
+// a power of two well below M / 2, and a response is released only if
+// every coefficient lies within GAMMA2 = GAMMA1 - BETA, BETA bounding
+// |C s| (Dilithium's gamma1 - beta). An accepted z is then uniform on
+// [-GAMMA2, GAMMA2] whatever s is.
+const GAMMA1_LIGHT: Scalar = 1 << 13; // Q / 4
+const GAMMA1_128: Scalar = 1 << 17; // Dilithium-2's gamma1
+const GAMMA1_192: Scalar = 1 << 19; // Dilithium-3/5's gamma1
+const SECRET_ETA: u32 = 2; // Secret coefficients from B(2), in [-2, 2]
+// The secrets `sign` accepts reach SECRET_LEVELS B(eta) draws: keygen's,
+// and one per normal HD level (hdwallet/xpub.rs) down to change/index
+const SECRET_LEVELS: u32 = 3;
+
+// The challenge is not a scalar but a sparse ternary matrix C: a key holds
+// KEY_ELEMENTS secret elements s_k (t_k = A o s_k), a signature answers with
+// RESPONSE_ELEMENTS responses z_l = y_l + sum_k C[l][k] s_k, and every row
+// of C has CHALLENGE_WEIGHT entries of +-1 and zeros elsewhere. A scalar
+// c commutes with A o, which is what verification needs, but no scalar
+// range small enough for z to hide s is large enough to guess; the rows
+// of C give
+//   RESPONSE_ELEMENTS * log2(C(KEY_ELEMENTS, CHALLENGE_WEIGHT) 2^CHALLENGE_WEIGHT)
+// = 10 * log2(495 * 16) > 129 bits, and each |sum_k C[l][k] s_k| stays
+// within CHALLENGE_WEIGHT times the secret bound.
+pub const KEY_ELEMENTS: usize = 12;
+pub const RESPONSE_ELEMENTS: usize = 10;
+pub const CHALLENGE_WEIGHT: usize = 4;
+pub const CHALLENGE_BITS: u32 = 129; // Entropy of C, rounded down
+const _: () = assert!(CHALLENGE_BITS >= 128);
+
+// Rejections `sign` tolerates before giving up. An attempt passes with
+// about exp(-270 BETA / GAMMA1): 45% at JD-Light, over 90% at the others,
+// so this many in a row means the parameters, or the rng, are broken.
+pub const MAX_ABORTS: u32 = 256;
+
+const CHALLENGE_DOMAIN: &[u8] = b"jordan-schnorr/challenge";
+const PREHASH_DOMAIN: &[u8] = b"jordan-schnorr/prehash";
+const GENERATOR_DOMAIN: &[u8] = b"jordan-schnorr/generator";
+const POSSESSION_DOMAIN: &[u8] = b"jordan-schnorr/possession";
+const EXPAND_DOMAIN: &[u8] = b"jordan-schnorr/challenge-matrix";
+
+// The secret vector is multiplied through the Albert octonions (algebra/ct.rs)
+const _: () = crate::algebra::ct::assert_constant_time::<crate::albert::Octonion>();
+
+// ============================================================================
+// PARAMETER SETS
+// ============================================================================
+// Named sets, carried in every key and signature. A signature verifies
+// only under a key of its own set; the set's id leads every serialized
+// form and is hashed into the challenge.
+//   JD-Light  Q            B(2) secrets  GAMMA1 2^13  BETA 24
+//   JD-128    Q_DILITHIUM  B(2) secrets  GAMMA1 2^17  BETA 24
+//   JD-192    Q_DILITHIUM  B(4) secrets  GAMMA1 2^19  BETA 48
+// Every set draws its challenges from the same CHALLENGE_BITS. The names
+// are tiers, not proven security levels.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParamSet {
+    JdLight,
+    Jd128,
+    Jd192,
+}
+
+impl ParamSet {
+    pub const ALL: [ParamSet; 3] = [ParamSet::JdLight, ParamSet::Jd128, ParamSet::Jd192];
+
+    // High nibble: format version 2 (matrix challenges); low nibble: the
+    // set. Version 1 keys held one secret element and signed scalar
+    // challenges, which anyone could guess; their ids are refused.
+    pub const fn id(self) -> u8 {
+        match self {
+            ParamSet::JdLight => 0x21,
+            ParamSet::Jd128 => 0x22,
+            ParamSet::Jd192 => 0x23,
+        }
+    }
+
+    pub fn from_id(id: u8) -> Option<Self> {
+        Self::ALL.into_iter().find(|set| set.id() == id)
+    }
+
+    pub const fn modulus(self) -> u64 {
+        match self {
+            ParamSet::JdLight => Q,
+            ParamSet::Jd128 | ParamSet::Jd192 => Q_DILITHIUM,
+        }
+    }
+
+    // The set keygen picks for modulus M; no other modulus has one
+    pub const fn default_for<const M: u64>() -> Self {
+        match M {
+            Q => ParamSet::JdLight,
+            Q_DILITHIUM => ParamSet::Jd128,
+            _ => panic!("jordan_sig: no parameter set for this modulus"),
+        }
+    }
+
+    // Masks are uniform on [-gamma1, gamma1]
+    pub const fn gamma1(self) -> Scalar {
+        match self {
+            ParamSet::JdLight => GAMMA1_LIGHT,
+            ParamSet::Jd128 => GAMMA1_128,
+            ParamSet::Jd192 => GAMMA1_192,
+        }
+    }
+
+    // Largest |s| coefficient `sign` accepts
+    pub const fn secret_bound(self) -> Scalar {
+        (SECRET_LEVELS * self.secret_eta()) as Scalar
+    }
+
+    // Largest |C s| coefficient: CHALLENGE_WEIGHT secrets per row
+    pub const fn beta(self) -> Scalar {
+        CHALLENGE_WEIGHT as Scalar * self.secret_bound()
+    }
+
+    // Centered L-inf bound on z
+    pub const fn gamma2(self) -> Scalar {
+        self.gamma1() - self.beta()
+    }
+
+    // Width of the discrete Gaussian masks of aggregate signing
+    // (jordan_musig.rs), summed over the signers: four standard deviations
+    // reach gamma2
+    pub fn mask_sigma(self) -> f64 {
+        self.gamma2() as f64 / 4.0
+    }
+
+    pub const fn secret_eta(self) -> u32 {
+        match self {
+            ParamSet::Jd192 => 2 * SECRET_ETA,
+            _ => SECRET_ETA,
+        }
+    }
+}
 
 // ============================================================================
 // DATA STRUCTURES
 // ============================================================================
 
-#[derive(Debug, Clone)]
-pub struct SecretKey {
-    pub s: AlbertElement, // The secret vector (Structured Noise)
-    pub pub_key: PublicKey,
+// KEY_ELEMENTS Albert elements: the secrets s_k, or their images t_k
+pub type KeyVector<const M: u64 = Q> = [AlbertElement<M>; KEY_ELEMENTS];
+// RESPONSE_ELEMENTS Albert elements: masks, commitments or responses
+pub type ResponseVector<const M: u64 = Q> = [AlbertElement<M>; RESPONSE_ELEMENTS];
+// What a signature carries in place of C: C is expanded from it
+pub type ChallengeSeed = [u8; 32];
+
+#[derive(Clone)]
+pub struct SecretKey<const M: u64 = Q> {
+    pub s: KeyVector<M>, // The secret vectors (Structured Noise)
+    pub pub_key: PublicKey<M>,
+}
+
+// The secret vector is wiped when the key is dropped; the public half is
+// left as it is.
+impl<const M: u64> Zeroize for SecretKey<M> {
+    fn zeroize(&mut self) {
+        self.s.zeroize();
+    }
+}
+
+impl<const M: u64> Drop for SecretKey<M> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl<const M: u64> ZeroizeOnDrop for SecretKey<M> {}
+const _: () = crate::redact::assert_zeroize_on_drop::<SecretKey>();
+
+// Manual Debug: the secret vector is redacted, the public half is printed.
+impl<const M: u64> fmt::Debug for SecretKey<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecretKey")
+            .field("s", &Redacted(&self.s.iter().flat_map(|s| redact::u64_bytes(&s.coeffs)).collect::<Vec<u8>>()))
+            .field("pub_key", &self.pub_key)
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublicKey<const M: u64 = Q> {
+    pub t: KeyVector<M>,     // t_k = A o s_k
+    pub a: AlbertElement<M>, // The Generator (Public Parameter)
+    pub params: ParamSet,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature<const M: u64 = Q> {
+    pub z: ResponseVector<M>, // Responses
+    pub c: ChallengeSeed,     // Expands to the challenge matrix C
+    pub params: ParamSet,
+}
+
+// The sparse ternary challenge matrix C, row l and column k in C.0[l][k]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Challenge(pub [[i8; KEY_ELEMENTS]; RESPONSE_ELEMENTS]);
+
+impl Challenge {
+    /// C from a seed, through the GSH XOF as hash_to_element draws its
+    /// coefficients. Each row is a partial Fisher-Yates shuffle (SampleInBall
+    /// of Dilithium, per row): for i = KEY_ELEMENTS - CHALLENGE_WEIGHT ..
+    /// KEY_ELEMENTS, draw j <= i by rejection from a nibble, move entry j to
+    /// i and put a sign bit at j. Public values only - the number of draws
+    /// depends on the output.
+    pub fn expand(seed: &ChallengeSeed) -> Self {
+        let mut xof = GSH256::xof(&[EXPAND_DOMAIN, seed]);
+        let mut byte = || {
+            let mut b = [0u8];
+            xof.squeeze(&mut b);
+            b[0]
+        };
+        let mut rows = [[0i8; KEY_ELEMENTS]; RESPONSE_ELEMENTS];
+        for row in rows.iter_mut() {
+            for i in KEY_ELEMENTS - CHALLENGE_WEIGHT..KEY_ELEMENTS {
+                let j = loop {
+                    let j = (byte() & 0x0F) as usize;
+                    if j <= i {
+                        break j;
+                    }
+                };
+                row[i] = row[j];
+                row[j] = if byte() & 1 == 0 { 1 } else { -1 };
+            }
+        }
+        Challenge(rows)
+    }
+
+    /// C v: row l is sum_k C[l][k] v_k. The entries are public, so adding
+    /// or subtracting by sign leaks nothing about v.
+    pub fn apply<const M: u64>(&self, v: &KeyVector<M>) -> ResponseVector<M> {
+        std::array::from_fn(|l| {
+            self.0[l].iter().zip(v).fold(AlbertElement::zero(), |acc, (&c, x)| match c {
+                1 => acc + *x,
+                -1 => acc - *x,
+                _ => acc,
+            })
+        })
+    }
+}
+
+// Largest centered coefficient over every element
+pub(crate) fn norm_inf<const M: u64>(v: &[AlbertElement<M>]) -> u64 {
+    v.iter().map(AlbertElement::norm_inf).max().unwrap_or(0)
+}
+
+pub(crate) fn reduced<const M: u64>(v: &[AlbertElement<M>]) -> bool {
+    v.iter().all(|e| e.coeffs.iter().all(|&x| x < M))
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub struct PublicKey {
-    pub t: AlbertElement, // t = A o s
-    pub a: AlbertElement, // The Generator (Public Parameter)
+// How a prehashed message was digested; the id is signed with the digest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrehashAlgorithm {
+    Sha256 = 1,
+    Gsh32 = 2, // GSH-256 truncated to 32 bytes (merkle::Gsh32)
 }
 
-#[derive(Debug, Clone)]
-pub struct Signature {
-    pub z: AlbertElement, // Response vector
-    pub c: Scalar,        // Challenge (Scalar to ensure associativity)
+impl PrehashAlgorithm {
+    pub fn digest(self, msg: &[u8]) -> [u8; 32] {
+        match self {
+            PrehashAlgorithm::Sha256 => Sha256::digest(msg).into(),
+            PrehashAlgorithm::Gsh32 => crate::merkle::Gsh32::leaf(msg),
+        }
+    }
+
+    fn tagged(self, digest: &[u8; 32]) -> [u8; 33] {
+        let mut out = [self as u8; 33];
+        out[1..].copy_from_slice(digest);
+        out
+    }
+}
+
+// Why verify refused a signature
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerifyError {
+    // H(msg, A o z - C t) is not c: wrong message, key or response
+    ChallengeMismatch,
+    // z is too large to be a masked response
+    BoundViolation { norm: u64, bound: u64 },
+    // A key coefficient is not reduced mod M, or the generator A is zero
+    // (A o z would ignore the response)
+    MalformedKey,
+    // The signature does not belong to this parameter set: it names another
+    // set than the key, z is not reduced mod M or c is outside the
+    // challenge range (or the key's set runs over another modulus)
+    ParameterMismatch,
+}
+
+// Why sign gave up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignError {
+    // Every one of `attempts` responses was rejected (max_aborts + 1)
+    TooManyAborts { attempts: u32 },
+    // The secret is wider than the set's secret_bound (more normal HD
+    // levels than it allows): responses would no longer hide it
+    SecretTooWide { norm: u64, bound: u64 },
+}
+
+// What a successful signing cost, for monitoring rejection rates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SignStats {
+    pub aborts: u32, // Rejected responses before the one returned
+}
+
+// One entry of JordanSchnorr::verify_batch
+pub type BatchItem<'a, const M: u64 = Q> = (PublicKey<M>, &'a [u8], Signature<M>);
+
+// The first entry of a batch that failed to verify
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchError {
+    pub index: usize,
+    pub error: VerifyError,
 }
 
 // ============================================================================
 // IMPLEMENTATION
 // ============================================================================
 
-pub struct JordanSchnorr;
+pub struct JordanSchnorr<const M: u64 = Q>;
+
+impl<const M: u64> JordanSchnorr<M> {
+    // The set keygen uses: JD-Light at Q, JD-128 at Q_DILITHIUM
+    pub const PARAMS: ParamSet = ParamSet::default_for::<M>();
 
-impl JordanSchnorr {
-    
     /// GENERATE KEYPAIR
     /// A: Uniformly random Albert Element (The Generator)
-    /// S: Structured Noise (The Secret) - Low Norm
-    pub fn keygen<R: Rng + ?Sized>(rng: &mut R) -> SecretKey {
-        // 1. Sample Generator A (Public Parameter)
-        // High geometric stiffness
-        let a = AlbertElement::sample_uniform(rng, 1.0, 5000.0);
+    /// S: KEY_ELEMENTS centered binomial noise elements (The Secret) - Low Norm
+    pub fn keygen<R: Rng + ?Sized>(rng: &mut R) -> SecretKey<M> {
+        Self::keygen_with_params(Self::PARAMS, rng)
+    }
+
+    /// GENERATE KEYPAIR IN A NAMED SET
+    /// The set must run over M (JD-192 is a JordanSchnorr<Q_DILITHIUM>
+    /// set); signatures made with the key carry its set.
+    pub fn keygen_with_params<R: Rng + ?Sized>(params: ParamSet, rng: &mut R) -> SecretKey<M> {
+        assert_eq!(params.modulus(), M, "keygen: {params:?} runs over another modulus");
+        // 1. Sample Generator A (Public Parameter), uniform mod M among the
+        // invertible elements: a degenerate A collapses the key space
+        let a = AlbertElement::sample_invertible(rng);
+        Self::keygen_from(params, a, rng)
+    }
+
+    /// GENERATE KEYPAIR UNDER A SHARED GENERATOR
+    /// Keys that aggregate (jordan_musig.rs) must share A, so A o (sum s_i)
+    /// is the sum of their t_i, element by element. A must not be zero
+    /// (check_key).
+    pub fn keygen_with_generator<R: Rng + ?Sized>(a: AlbertElement<M>, rng: &mut R) -> SecretKey<M> {
+        Self::keygen_from(Self::PARAMS, a, rng)
+    }
+
+    /// NOTHING-UP-MY-SLEEVE GENERATOR
+    /// An invertible A that anyone can rederive from `label`, for keys that
+    /// share a generator without trusting whoever picked it: the first
+    /// invertible hash_to_element(label || i) for i = 0, 1, ..
+    pub fn derive_generator(label: &[u8]) -> AlbertElement<M> {
+        (0u32..)
+            .map(|i| AlbertElement::hash_to_element(&[label, &i.to_le_bytes()].concat(), GENERATOR_DOMAIN))
+            .find(|a| a.is_invertible())
+            .unwrap_or_else(|| unreachable!())
+    }
 
+    fn keygen_from<R: Rng + ?Sized>(params: ParamSet, a: AlbertElement<M>, rng: &mut R) -> SecretKey<M> {
         // 2. Sample Secret S (Small Norm)
-        // Using "Structured" sampling to hide in the bulk
-        // Low values (scale ~ 10) to make "Learning" hard but "Checking" easy
-        let s = AlbertElement::sample_structured(rng, 1.91, 10.0, 10.0);
+        // B(eta) coefficients, as in Kyber / Dilithium secrets, sampled in
+        // constant time: small enough that "Checking" stays easy
+        let s: KeyVector<M> = std::array::from_fn(|_| AlbertElement::sample_cbd(rng, params.secret_eta()));
 
-        // 3. Calculate Public Key T = A o S (Jordan Product)
-        let t = a.jordan_product(&s);
+        // 3. Calculate Public Key t_k = A o s_k (Jordan Product)
+        let t = std::array::from_fn(|k| a.jordan_product(&s[k]));
 
         SecretKey {
             s,
-            pub_key: PublicKey { t, a },
+            pub_key: PublicKey { t, a, params },
         }
     }
 
     /// SIGN TRANSACTION
-    /// Uses Fiat-Shamir with Aborts
-    /// 1. y <- Random Mask
-    /// 2. w = A o y
-    /// 3. c = Hash(M || w)
-    /// 4. z = y + c*s
+    /// Uses Fiat-Shamir with Aborts, one row of C per response
+    /// 1. y_l <- Random Mask
+    /// 2. w_l = A o y_l
+    /// 3. c = Hash(M || w), C = expand(c)
+    /// 4. z_l = y_l + sum_k C[l][k] s_k
     /// 5. Reject if z leaks s (norm check)
-    pub fn sign<R: Rng + ?Sized>(sk: &SecretKey, msg: &[u8], rng: &mut R) -> Signature {
-        loop {
-            // 1. Sample Ephemeral Mask y (Random high entropy)
-            let y = AlbertElement::sample_uniform(rng, 1.0, GAMMA1 as f64);
+    ///
+    /// `context` names what the signature is for (e.g. horizon::TX_CONTEXT)
+    /// and is bound into the challenge: a signature made for one context
+    /// never verifies in another, whatever the message bytes. Gives up
+    /// after MAX_ABORTS rejections.
+    pub fn sign<R: Rng + ?Sized>(sk: &SecretKey<M>, context: &[u8], msg: &[u8], rng: &mut R) -> Result<Signature<M>, SignError> {
+        Self::sign_with_stats(sk, context, msg, MAX_ABORTS, rng).map(|(sig, _)| sig)
+    }
+
+    /// SIGN, WITH A CHOSEN ABORT LIMIT
+    /// `sign` with its own limit, also reporting how many responses were
+    /// rejected on the way
+    pub fn sign_with_stats<R: Rng + ?Sized>(
+        sk: &SecretKey<M>,
+        context: &[u8],
+        msg: &[u8],
+        max_aborts: u32,
+        rng: &mut R,
+    ) -> Result<(Signature<M>, SignStats), SignError> {
+        Self::sign_in(CHALLENGE_DOMAIN, sk, context, msg, max_aborts, rng)
+    }
 
-            // 2. Commitment w = A o y
-            let w = sk.pub_key.a.jordan_product(&y);
+    /// VERIFY TRANSACTION
+    /// Check: A o z_l == w_l + sum_k C[l][k] t_k
+    ///        A o (y_l + sum C s) == A o y_l + sum C (A o s)
+    ///        A o y_l + sum C (A o s) == w_l + sum C t  <-- Valid!
+    /// C is integral, so it commutes with A o as a scalar would.
+    pub fn verify(pk: &PublicKey<M>, context: &[u8], msg: &[u8], sig: &Signature<M>) -> Result<(), VerifyError> {
+        Self::verify_in(CHALLENGE_DOMAIN, pk, context, msg, sig)
+    }
+
+    /// SIGN A DIGEST
+    /// The message is hashed by the caller (a hardware signer sees 32 bytes,
+    /// not the transaction). Signed under its own domain with the algorithm
+    /// id, so a prehashed signature never verifies as a signature on the
+    /// digest bytes themselves, nor under another algorithm.
+    pub fn sign_prehashed<R: Rng + ?Sized>(
+        sk: &SecretKey<M>,
+        context: &[u8],
+        alg: PrehashAlgorithm,
+        digest: &[u8; 32],
+        rng: &mut R,
+    ) -> Result<Signature<M>, SignError> {
+        Self::sign_in(PREHASH_DOMAIN, sk, context, &alg.tagged(digest), MAX_ABORTS, rng).map(|(sig, _)| sig)
+    }
 
-            // 3. Challenge c = H(M || w)
-            // We map the hash to a SCALAR. This is the distinct APH innovation.
-            let c = Self::hash_to_scalar(msg, &w);
+    pub fn verify_prehashed(
+        pk: &PublicKey<M>,
+        context: &[u8],
+        alg: PrehashAlgorithm,
+        digest: &[u8; 32],
+        sig: &Signature<M>,
+    ) -> Result<(), VerifyError> {
+        Self::verify_in(PREHASH_DOMAIN, pk, context, &alg.tagged(digest), sig)
+    }
+
+    /// PROOF OF POSSESSION
+    /// A self-signature over the serialized public key, under its own
+    /// domain: registering a key with it shows the registrant holds s, which
+    /// defeats rogue keys (t' - sum t_i) wherever keys are summed
+    /// (jordan_musig.rs). It is never a signature on any message.
+    pub fn prove_possession<R: Rng + ?Sized>(sk: &SecretKey<M>, rng: &mut R) -> Result<Signature<M>, SignError> {
+        Self::sign_in(POSSESSION_DOMAIN, sk, &[], &sk.pub_key.to_versioned_bytes(), MAX_ABORTS, rng).map(|(sig, _)| sig)
+    }
 
-            // 4. Response z = y + c*s
-            // z = y + (s * c)
-            let cs = sk.s.scale(c);
-            let z = y + cs;
+    pub fn verify_possession(pk: &PublicKey<M>, proof: &Signature<M>) -> Result<(), VerifyError> {
+        Self::verify_in(POSSESSION_DOMAIN, pk, &[], &pk.to_versioned_bytes(), proof)
+    }
+
+    fn sign_in<R: Rng + ?Sized>(
+        domain: &[u8],
+        sk: &SecretKey<M>,
+        context: &[u8],
+        msg: &[u8],
+        max_aborts: u32,
+        rng: &mut R,
+    ) -> Result<(Signature<M>, SignStats), SignError> {
+        let params = sk.pub_key.params;
+        Self::check_secret(sk)?;
+        for aborts in 0..=max_aborts {
+            // 1. Sample Ephemeral Masks y (Random high entropy), uniform on
+            // [-GAMMA1, GAMMA1]
+            let mut y = Self::sample_mask(params, rng);
+
+            // 2. Commitments w_l = A o y_l
+            let w = std::array::from_fn(|l| sk.pub_key.a.jordan_product(&y[l]));
+
+            // 3. Challenge seed c = H(M || w), expanded to the matrix C
+            let c = Self::hash_to_seed(domain, params, context, msg, &w);
+
+            // 4. Responses z_l = y_l + sum_k C[l][k] s_k
+            let mut cs = Challenge::expand(&c).apply(&sk.s);
+            let mut z: ResponseVector<M> = std::array::from_fn(|l| y[l] + cs[l]);
+            // y and C s each give s away with z; neither outlives this attempt
+            y.zeroize();
+            cs.zeroize();
 
             // 5. Rejection Sampling
-            // If z is too large, it might reveal the structure of s (via subtraction z - y)
-            // We want z to look like uniform noise from the range [-GAMMA2, GAMMA2]
-            if z.exceeds_bound(GAMMA2) {
+            // Near +-GAMMA1, z = y + C s shows which way C s pushed it. Keeping
+            // only z within GAMMA2 = GAMMA1 - BETA leaves it uniform on
+            // [-GAMMA2, GAMMA2], read centered (Q - 1 is -1, not a huge
+            // coefficient)
+            if z.iter().any(|z| z.exceeds_bound(params.gamma2())) {
+                z.zeroize(); // A rejected z is y + C s for a y never published
                 continue; // Retry with new y
             }
 
-            return Signature { z, c };
+            return Ok((Signature { z, c, params }, SignStats { aborts }));
         }
+        Err(SignError::TooManyAborts { attempts: max_aborts + 1 })
     }
 
-    /// VERIFY TRANSACTION
-    /// Check: A o z == w + c*t
-    ///        A o (y + cs) == A o y + c(A o s)
-    ///        A o y + c(A o s) == w + c*t  <-- Valid!
-    pub fn verify(pk: &PublicKey, msg: &[u8], sig: &Signature) -> bool {
-        // 1. Reconstruct w' = (A o z) - (c * t)
-        let a_dot_z = pk.a.jordan_product(&sig.z);
-        let c_times_t = pk.t.scale(sig.c);
-        
-        // w_prime = a_dot_z - c_times_t
-        let w_prime = a_dot_z - c_times_t;
-
-        // 2. Reconstruct Challenge c' = H(M || w')
-        let c_prime = Self::hash_to_scalar(msg, &w_prime);
-
-        // 3. Verify Challenge Consistency
-        if c_prime != sig.c {
-            return false;
+    /// VERIFY A BATCH
+    /// Every (key, message, signature) under one context; on failure, the
+    /// lowest failing index and why. The challenge hashes w' = A o z - C t,
+    /// so each w' must be rebuilt and a random linear combination of the
+    /// equations cannot replace the per-signature Jordan products (that
+    /// needs w in the signature). What is shared is done once: a key's
+    /// checks run once per distinct key, and the signatures are verified in
+    /// parallel.
+    pub fn verify_batch(context: &[u8], items: &[BatchItem<'_, M>]) -> Result<(), BatchError> {
+        let mut keys: Vec<(&PublicKey<M>, Result<(), VerifyError>)> = Vec::new();
+        let mut key_checks = Vec::with_capacity(items.len());
+        for (pk, _, _) in items {
+            let check = match keys.iter().find(|(seen, _)| *seen == pk) {
+                Some((_, check)) => *check,
+                None => {
+                    let check = Self::check_key(pk);
+                    keys.push((pk, check));
+                    check
+                }
+            };
+            key_checks.push(check);
         }
 
-        // 4. Bound Check
-        if sig.z.exceeds_bound(GAMMA2) {
-            return false;
+        let results: Vec<Result<(), VerifyError>> = items
+            .par_iter()
+            .zip(key_checks.par_iter())
+            .map(|((pk, msg, sig), key)| key.and_then(|()| Self::verify_signature(CHALLENGE_DOMAIN, pk, context, msg, sig)))
+            .collect();
+        match results.into_iter().enumerate().find_map(|(index, r)| r.err().map(|error| BatchError { index, error })) {
+            Some(failure) => Err(failure),
+            None => Ok(()),
         }
+    }
+
+    fn verify_in(domain: &[u8], pk: &PublicKey<M>, context: &[u8], msg: &[u8], sig: &Signature<M>) -> Result<(), VerifyError> {
+        Self::check_key(pk)?;
+        Self::verify_signature(domain, pk, context, msg, sig)
+    }
 
-        true
+    // Well-formedness of a key: in a set over M, reduced mod M, with a
+    // nonzero A. keygen has drawn invertible generators since they were
+    // introduced, but keys made before that carry a uniform A, about half
+    // of them singular; those still verify, so coins locked to them stay
+    // spendable. shadow::reference_verify applies the same rule.
+    pub(crate) fn check_key(pk: &PublicKey<M>) -> Result<(), VerifyError> {
+        if pk.params.modulus() != M {
+            return Err(VerifyError::ParameterMismatch);
+        }
+        if !reduced(&pk.t) || !reduced(&[pk.a]) || pk.a == AlbertElement::zero() {
+            return Err(VerifyError::MalformedKey);
+        }
+        Ok(())
+    }
+
+    // Everything after the key checks
+    fn verify_signature(domain: &[u8], pk: &PublicKey<M>, context: &[u8], msg: &[u8], sig: &Signature<M>) -> Result<(), VerifyError> {
+        // 0. Well-formedness: the key's set, everything reduced mod M
+        let params = pk.params;
+        if sig.params != params || !reduced(&sig.z) {
+            return Err(VerifyError::ParameterMismatch);
+        }
+
+        // 1. Bound Check
+        let norm = norm_inf(&sig.z);
+        if norm > params.gamma2() {
+            return Err(VerifyError::BoundViolation { norm, bound: params.gamma2() });
+        }
+
+        // 2. Reconstruct w'_l = (A o z_l) - sum_k C[l][k] t_k
+        let c_times_t = Challenge::expand(&sig.c).apply(&pk.t);
+        let w_prime = std::array::from_fn(|l| pk.a.jordan_product(&sig.z[l]) - c_times_t[l]);
+
+        // 3. Reconstruct Challenge c' = H(M || w')
+        let c_prime = Self::hash_to_seed(domain, params, context, msg, &w_prime);
+
+        // 4. Verify Challenge Consistency
+        if c_prime != sig.c {
+            return Err(VerifyError::ChallengeMismatch);
+        }
+        Ok(())
     }
 
     // --- UTILITIES ---
 
-    fn hash_to_scalar(msg: &[u8], w: &AlbertElement) -> Scalar {
+    // A mask vector, every coefficient uniform on [-gamma1, gamma1]
+    pub(crate) fn sample_mask<R: Rng + ?Sized>(params: ParamSet, rng: &mut R) -> ResponseVector<M> {
+        let bound = params.gamma1() as i64;
+        std::array::from_fn(|_| AlbertElement::from_centered(&std::array::from_fn(|_| rng.gen_range(-bound..=bound))))
+    }
+
+    // Rejection hides s only while |C s| <= beta
+    pub(crate) fn check_secret(sk: &SecretKey<M>) -> Result<(), SignError> {
+        let (norm, bound) = (norm_inf(&sk.s), sk.pub_key.params.secret_bound());
+        if norm > bound {
+            return Err(SignError::SecretTooWide { norm, bound });
+        }
+        Ok(())
+    }
+
+    // The challenge seed `sign` derives for commitments w; cosigners of an
+    // aggregate signature compute it over their summed commitments
+    pub(crate) fn challenge(params: ParamSet, context: &[u8], msg: &[u8], w: &ResponseVector<M>) -> ChallengeSeed {
+        Self::hash_to_seed(CHALLENGE_DOMAIN, params, context, msg, w)
+    }
+
+    // c = H(domain || M || id || |context| || context || |msg| || msg || w_0 || .. ),
+    // over the packed encoding of every commitment: any change to any
+    // coefficient of w changes the challenge. The whole SHA-256 digest is
+    // the seed, and Challenge::expand turns it into C.
+    pub(crate) fn hash_to_seed(domain: &[u8], params: ParamSet, context: &[u8], msg: &[u8], w: &ResponseVector<M>) -> ChallengeSeed {
         let mut hasher = Sha256::new();
+        hasher.update(domain);
+        hasher.update(M.to_le_bytes());
+        hasher.update([params.id()]);
+        hasher.update((context.len() as u64).to_le_bytes());
+        hasher.update(context);
+        hasher.update((msg.len() as u64).to_le_bytes());
         hasher.update(msg);
-        
-        // Absorb the Albert Element
-        // For prototype, we hash the diagonal alpha and the first coeff of 'a'
-        hasher.update(w.alpha.to_le_bytes());
-        hasher.update(w.a.c[0].to_le_bytes());
-        
-        let result = hasher.finalize();
-        
-        // Fold 256 bits into a single Scalar
-        let mut scalar = 0 as Scalar;
-        for chunk in result.chunks(8) {
-            let val = u64::from_le_bytes(chunk.try_into().unwrap_or([0; 8]));
-            scalar = scalar.wrapping_add(val); 
-        }
-        
-        // Reduce to safe challenge range (small enough to not overflow z immediately)
-        // Keep it small (e.g. 10 bits) for this parameter set
-        scalar % 1024 
-    }
-}
\ No newline at end of file
+        for w in w {
+            hasher.update(w.to_bytes());
+        }
+        hasher.finalize().into()
+    }
+}
+
+// ============================================================================
+// SERIALIZATION
+// ============================================================================
+// Every form starts with the id of its parameter set (ParamSet::id), so a
+// key or signature from another set is refused rather than misread. Albert
+// elements use the packed encoding (algebra/encoding.rs), in index order:
+//   PublicKey  - id || t_0 .. t_11 || a
+//   Signature  - id || z_0 .. z_9 || c (32-byte challenge seed)
+//   SecretKey  - id || s_0 .. s_11 || t_0 .. t_11 || a
+// Only ids of sets over M decode. wire.rs keeps its own fixed layout for Q,
+// where JD-Light is the only set, and owns the plain to_bytes/from_bytes
+// that addresses and Horizon leaves are hashed from; the forms here are the
+// _versioned_ ones so the two never shadow each other.
+
+impl<const M: u64> JordanSchnorr<M> {
+    fn check_prefix(bytes: &[u8], len: usize) -> Result<(ParamSet, &[u8]), DecodeError> {
+        if bytes.len() != len {
+            return Err(DecodeError::Length { expected: len, got: bytes.len() });
+        }
+        match ParamSet::from_id(bytes[0]).filter(|set| set.modulus() == M) {
+            Some(params) => Ok((params, &bytes[1..])),
+            None => Err(DecodeError::ParameterSet { expected: Self::PARAMS.id(), got: bytes[0] }),
+        }
+    }
+}
+
+// N packed elements from the front of `bytes`
+pub(crate) fn unpack<const M: u64, const N: usize>(bytes: &[u8]) -> Result<[AlbertElement<M>; N], DecodeError> {
+    let mut out = [AlbertElement::zero(); N];
+    for (e, chunk) in out.iter_mut().zip(bytes.chunks_exact(AlbertElement::<M>::PACKED_BYTES)) {
+        *e = AlbertElement::from_bytes(chunk)?;
+    }
+    Ok(out)
+}
+
+impl<const M: u64> PublicKey<M> {
+    pub const BYTES: usize = 1 + (KEY_ELEMENTS + 1) * AlbertElement::<M>::PACKED_BYTES;
+
+    pub fn to_versioned_bytes(&self) -> Vec<u8> {
+        let mut out = vec![self.params.id()];
+        for e in self.t.iter().chain([&self.a]) {
+            out.extend(e.to_bytes());
+        }
+        out
+    }
+
+    pub fn from_versioned_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let (params, body) = JordanSchnorr::<M>::check_prefix(bytes, Self::BYTES)?;
+        let (t, a) = body.split_at(KEY_ELEMENTS * AlbertElement::<M>::PACKED_BYTES);
+        Ok(PublicKey { t: unpack(t)?, a: AlbertElement::from_bytes(a)?, params })
+    }
+}
+
+impl<const M: u64> Signature<M> {
+    pub const BYTES: usize = 1 + RESPONSE_ELEMENTS * AlbertElement::<M>::PACKED_BYTES + 32;
+
+    pub fn to_versioned_bytes(&self) -> Vec<u8> {
+        let mut out = vec![self.params.id()];
+        for z in &self.z {
+            out.extend(z.to_bytes());
+        }
+        out.extend(self.c);
+        out
+    }
+
+    pub fn from_versioned_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let (params, body) = JordanSchnorr::<M>::check_prefix(bytes, Self::BYTES)?;
+        let (z, c) = body.split_at(RESPONSE_ELEMENTS * AlbertElement::<M>::PACKED_BYTES);
+        Ok(Signature { z: unpack(z)?, c: c.try_into().unwrap_or_else(|_| unreachable!()), params })
+    }
+}
+
+impl<const M: u64> SecretKey<M> {
+    pub const BYTES: usize = PublicKey::<M>::BYTES + KEY_ELEMENTS * AlbertElement::<M>::PACKED_BYTES;
+
+    pub fn to_versioned_bytes(&self) -> Vec<u8> {
+        let mut out = vec![self.pub_key.params.id()];
+        for s in &self.s {
+            out.extend(s.to_bytes());
+        }
+        out.extend(&self.pub_key.to_versioned_bytes()[1..]);
+        out
+    }
+
+    pub fn from_versioned_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let (params, body) = JordanSchnorr::<M>::check_prefix(bytes, Self::BYTES)?;
+        let (s, pk) = body.split_at(KEY_ELEMENTS * AlbertElement::<M>::PACKED_BYTES);
+        let mut pub_key = vec![params.id()];
+        pub_key.extend(pk);
+        Ok(SecretKey { s: unpack(s)?, pub_key: PublicKey::from_versioned_bytes(&pub_key)? })
+    }
+}
+
+// ============================================================================
+// ENCRYPTED EXPORT
+// ============================================================================
+// A secret key at rest, sealed under a passphrase:
+//   "OLCK" || version || memory_kib (u32 LE) || iterations (u32 LE)
+//          || salt (16) || nonce (16) || sealed SecretKey bytes || tag (32)
+// The sealing key is GSH-PW (gsh/pwhash.rs) of the passphrase and salt; the
+// body is sealed by FlutterCipher::seal with the whole header as associated
+// data, so lowering the stored cost breaks the tag. Import refuses costs
+// PwHashParams::new refuses instead of running whatever a file asks for.
+
+const KEYFILE_MAGIC: &[u8; 4] = b"OLCK";
+pub const KEYFILE_VERSION: u8 = 1;
+const KEYFILE_HEADER_BYTES: usize = 4 + 1 + 4 + 4 + 16 + 16;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyFileError {
+    // Not a key file, or truncated
+    Format,
+    Version { got: u8 },
+    // The stored cost is one pwhash refuses
+    Cost(PwHashError),
+    // Or the file was modified
+    WrongPassphrase,
+    // The sealed bytes are not a key of this modulus
+    Key(DecodeError),
+}
+
+impl<const M: u64> SecretKey<M> {
+    /// The key sealed under `passphrase`, at the wallet default cost
+    pub fn export_encrypted<R: Rng + ?Sized>(&self, passphrase: &[u8], rng: &mut R) -> Vec<u8> {
+        self.export_encrypted_with(passphrase, PwHashParams::default(), rng).unwrap_or_else(|_| unreachable!())
+    }
+
+    pub fn export_encrypted_with<R: Rng + ?Sized>(
+        &self,
+        passphrase: &[u8],
+        cost: PwHashParams,
+        rng: &mut R,
+    ) -> Result<Vec<u8>, PwHashError> {
+        let salt: [u8; 16] = rng.gen();
+        let nonce: [u16; 8] = rng.gen();
+        let mut out = Vec::with_capacity(KEYFILE_HEADER_BYTES + Self::BYTES + TAG_BYTES);
+        out.extend_from_slice(KEYFILE_MAGIC);
+        out.push(KEYFILE_VERSION);
+        out.extend_from_slice(&cost.memory_kib.to_le_bytes());
+        out.extend_from_slice(&cost.iterations.to_le_bytes());
+        out.extend_from_slice(&salt);
+        out.extend(nonce.iter().flat_map(|n| n.to_le_bytes()));
+
+        let mut kek = pwhash(passphrase, &salt, &cost)?;
+        let mut plain = self.to_versioned_bytes();
+        let sealed = FlutterCipher::seal(&kek, nonce, &out, &plain);
+        kek.zeroize();
+        plain.zeroize();
+        out.extend(sealed);
+        Ok(out)
+    }
+
+    pub fn import_encrypted(bytes: &[u8], passphrase: &[u8]) -> Result<Self, KeyFileError> {
+        if bytes.len() < KEYFILE_HEADER_BYTES + TAG_BYTES || &bytes[..4] != KEYFILE_MAGIC {
+            return Err(KeyFileError::Format);
+        }
+        if bytes[4] != KEYFILE_VERSION {
+            return Err(KeyFileError::Version { got: bytes[4] });
+        }
+        let (header, sealed) = bytes.split_at(KEYFILE_HEADER_BYTES);
+        let word = |at: usize| u32::from_le_bytes([header[at], header[at + 1], header[at + 2], header[at + 3]]);
+        let cost = PwHashParams::new(word(5), word(9)).map_err(KeyFileError::Cost)?;
+        let (salt, nonce) = (&header[13..29], &header[29..]);
+        let nonce = std::array::from_fn(|i| u16::from_le_bytes([nonce[2 * i], nonce[2 * i + 1]]));
+
+        let mut kek = pwhash(passphrase, salt, &cost).map_err(KeyFileError::Cost)?;
+        let opened = FlutterCipher::open(&kek, nonce, header, sealed);
+        kek.zeroize();
+        let mut plain = opened.ok_or(KeyFileError::WrongPassphrase)?;
+        let key = Self::from_versioned_bytes(&plain).map_err(KeyFileError::Key);
+        plain.zeroize();
+        key
+    }
+}
+// ============================================================================
+// FINGERPRINTS
+// ============================================================================
+// A short name for a public key, for address books, logs and prompts: the
+// first 20 bytes of GSH-256 over the serialized key, so the parameter set
+// is part of the name. Display is bech32 under "jdk", whose checksum turns
+// a mistyped fingerprint into an error instead of another key; key_id is
+// the first 8 bytes, for log lines where a collision only costs a grep.
+
+const FINGERPRINT_DOMAIN: &[u8] = b"jordan-schnorr/fingerprint";
+pub const FINGERPRINT_HRP: &str = "jdk";
+pub const FINGERPRINT_BYTES: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Fingerprint(pub [u8; FINGERPRINT_BYTES]);
+
+impl Fingerprint {
+    pub fn key_id(&self) -> u64 {
+        u64::from_be_bytes(self.0[..8].try_into().unwrap_or_else(|_| unreachable!()))
+    }
+
+    pub fn to_hex(&self) -> String {
+        hex(&self.0)
+    }
+}
+
+impl fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&bech32_encode(FINGERPRINT_HRP, &self.0, Bech32Variant::Bech32))
+    }
+}
+
+impl FromStr for Fingerprint {
+    type Err = DecodeError;
+
+    fn from_str(s: &str) -> Result<Self, DecodeError> {
+        let bytes = bech32_decode(FINGERPRINT_HRP, s.trim(), Bech32Variant::Bech32)?;
+        let got = bytes.len();
+        bytes.try_into().map(Fingerprint).map_err(|_| DecodeError::Length { expected: FINGERPRINT_BYTES, got })
+    }
+}
+
+impl<const M: u64> PublicKey<M> {
+    pub fn fingerprint(&self) -> Fingerprint {
+        let digest = GSH256::hash_slices(&[FINGERPRINT_DOMAIN, &self.to_versioned_bytes()]);
+        Fingerprint(std::array::from_fn(|i| digest[i]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Dilithium = JordanSchnorr<Q_DILITHIUM>;
+    const CTX: &[u8] = b"test";
+
+    #[test]
+    fn prime_parameter_set_signs_and_verifies() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let sk = Dilithium::keygen(&mut rng);
+        let sig = Dilithium::sign(&sk, CTX, b"utxo", &mut rng).unwrap();
+        assert_eq!(Dilithium::verify(&sk.pub_key, CTX, b"utxo", &sig), Ok(()));
+        assert_eq!(Dilithium::verify(&sk.pub_key, CTX, b"utx0", &sig), Err(VerifyError::ChallengeMismatch));
+        assert_eq!(ParamSet::ALL.map(ParamSet::gamma2), [8168, 131_048, 524_240]);
+        assert!(norm_inf(&sk.s) <= SECRET_ETA as u64);
+        assert!(sk.pub_key.a.is_invertible());
+        assert_eq!(sk.pub_key.params, ParamSet::Jd128);
+
+        // Bounds are centered: -1 is small, Q/2 is not
+        let mut z = AlbertElement::<Q>::zero();
+        z.coeffs[crate::albert::A + 3] = Q - 1;
+        assert!(!z.exceeds_bound(1) && z.norm_inf() == 1 && z.norm_l2_sq() == 1);
+        assert_eq!(AlbertElement::<Q>::from_centered(&z.to_centered()), z);
+        z.set_beta(Q / 2);
+        assert!(z.exceeds_bound(ParamSet::JdLight.gamma2()));
+
+        // NTT-friendly: 1753 is a primitive 512th root of unity mod q
+        let pow = |mut b: u64, mut e: u64| {
+            let mut r = 1;
+            while e > 0 {
+                if e & 1 == 1 { r = r * b % Q_DILITHIUM }
+                b = b * b % Q_DILITHIUM;
+                e >>= 1;
+            }
+            r
+        };
+        assert_eq!(pow(1753, 256), Q_DILITHIUM - 1);
+    }
+
+    #[test]
+    fn named_sets_never_verify_across() {
+        let mut rng = StdRng::seed_from_u64(4319);
+        let strong = Dilithium::keygen_with_params(ParamSet::Jd192, &mut rng);
+        let sig = Dilithium::sign(&strong, CTX, b"utxo", &mut rng).unwrap();
+        assert_eq!((sig.params, Dilithium::verify(&strong.pub_key, CTX, b"utxo", &sig)), (ParamSet::Jd192, Ok(())));
+        assert!(norm_inf(&strong.s) <= 2 * SECRET_ETA as u64);
+
+        // Relabelled either way, the pair is refused before any hashing
+        let relabelled = Signature { params: ParamSet::Jd128, ..sig.clone() };
+        assert_eq!(Dilithium::verify(&strong.pub_key, CTX, b"utxo", &relabelled), Err(VerifyError::ParameterMismatch));
+        let default = Dilithium::keygen(&mut rng);
+        assert_eq!(Dilithium::verify(&default.pub_key, CTX, b"utxo", &sig), Err(VerifyError::ParameterMismatch));
+        // ...as is a key naming a set over another modulus
+        let foreign = PublicKey { params: ParamSet::JdLight, ..strong.pub_key };
+        assert_eq!(Dilithium::verify(&foreign, CTX, b"utxo", &sig), Err(VerifyError::ParameterMismatch));
+
+        // The id travels with the bytes
+        let bytes = sig.to_versioned_bytes();
+        assert_eq!(bytes[0], 0x23);
+        assert_eq!(Signature::<Q_DILITHIUM>::from_versioned_bytes(&bytes), Ok(sig));
+        assert_eq!(PublicKey::<Q_DILITHIUM>::from_versioned_bytes(&strong.pub_key.to_versioned_bytes()), Ok(strong.pub_key));
+        assert_eq!(ParamSet::from_id(0x24), None);
+        // Format 1, with its scalar challenge, is gone
+        assert_eq!(ParamSet::from_id(0x13), None);
+    }
+
+    #[test]
+    fn batches_report_the_first_failure() {
+        let mut rng = StdRng::seed_from_u64(29);
+        let keys: Vec<SecretKey> = (0..3).map(|_| JordanSchnorr::<Q>::keygen(&mut rng)).collect();
+        let msgs: Vec<Vec<u8>> = (0..8u8).map(|i| vec![i; 40]).collect();
+        // Keys repeat, as when one owner spends several outputs
+        let mut items: Vec<BatchItem> = msgs
+            .iter()
+            .enumerate()
+            .map(|(i, m)| {
+                let sk = &keys[i % 3];
+                (sk.pub_key, m.as_slice(), JordanSchnorr::<Q>::sign(sk, CTX, m, &mut rng).unwrap())
+            })
+            .collect();
+        assert_eq!(JordanSchnorr::<Q>::verify_batch(CTX, &items), Ok(()));
+        assert_eq!(JordanSchnorr::<Q>::verify_batch(CTX, &[]), Ok(()));
+        assert_eq!(JordanSchnorr::<Q>::verify_batch(b"other", &items), Err(BatchError { index: 0, error: VerifyError::ChallengeMismatch }));
+
+        items[6].1 = b"tampered";
+        items[5].0.a = AlbertElement::zero();
+        assert_eq!(JordanSchnorr::<Q>::verify_batch(CTX, &items), Err(BatchError { index: 5, error: VerifyError::MalformedKey }));
+        items[5].0 = keys[2].pub_key;
+        assert_eq!(JordanSchnorr::<Q>::verify_batch(CTX, &items), Err(BatchError { index: 6, error: VerifyError::ChallengeMismatch }));
+    }
+
+    #[test]
+    fn prehashed_signatures_are_domain_separated() {
+        let mut rng = StdRng::seed_from_u64(23);
+        let sk = JordanSchnorr::<Q>::keygen(&mut rng);
+        let tx = vec![0x5A; 4096];
+        let digest = PrehashAlgorithm::Sha256.digest(&tx);
+        let sig = JordanSchnorr::<Q>::sign_prehashed(&sk, CTX, PrehashAlgorithm::Sha256, &digest, &mut rng).unwrap();
+        assert_eq!(JordanSchnorr::<Q>::verify_prehashed(&sk.pub_key, CTX, PrehashAlgorithm::Sha256, &digest, &sig), Ok(()));
+
+        // Not under another algorithm, not as a plain signature on the digest or its tagged form
+        let mismatch = Err(VerifyError::ChallengeMismatch);
+        assert_eq!(JordanSchnorr::<Q>::verify_prehashed(&sk.pub_key, CTX, PrehashAlgorithm::Gsh32, &digest, &sig), mismatch);
+        assert_eq!(JordanSchnorr::<Q>::verify(&sk.pub_key, CTX, &digest, &sig), mismatch);
+        assert_eq!(JordanSchnorr::<Q>::verify(&sk.pub_key, CTX, &PrehashAlgorithm::Sha256.tagged(&digest), &sig), mismatch);
+
+        let gsh = PrehashAlgorithm::Gsh32.digest(&tx);
+        let sig = JordanSchnorr::<Q>::sign_prehashed(&sk, CTX, PrehashAlgorithm::Gsh32, &gsh, &mut rng).unwrap();
+        assert_eq!(JordanSchnorr::<Q>::verify_prehashed(&sk.pub_key, CTX, PrehashAlgorithm::Gsh32, &gsh, &sig), Ok(()));
+    }
+
+    #[test]
+    fn challenges_are_sparse_ternary_with_full_entropy() {
+        // Each row: choose CHALLENGE_WEIGHT of KEY_ELEMENTS places, a sign each
+        let row_bits = ((KEY_ELEMENTS - CHALLENGE_WEIGHT + 1..=KEY_ELEMENTS).product::<usize>() as f64
+            / (1..=CHALLENGE_WEIGHT).product::<usize>() as f64
+            * (1u64 << CHALLENGE_WEIGHT) as f64)
+            .log2();
+        assert_eq!((RESPONSE_ELEMENTS as f64 * row_bits).floor() as u32, CHALLENGE_BITS);
+
+        let mut seen = [[0u32; KEY_ELEMENTS]; RESPONSE_ELEMENTS];
+        for seed in 0..200u8 {
+            let c = Challenge::expand(&[seed; 32]);
+            for (row, count) in c.0.iter().zip(seen.iter_mut()) {
+                assert_eq!(row.iter().filter(|&&x| x != 0).count(), CHALLENGE_WEIGHT);
+                assert!(row.iter().all(|x| (-1..=1).contains(x)));
+                for (x, n) in row.iter().zip(count.iter_mut()) {
+                    *n += (*x != 0) as u32;
+                }
+            }
+        }
+        // Every position is reached in every row
+        assert!(seen.iter().flatten().all(|&n| n > 0));
+    }
+
+    #[test]
+    fn challenge_binds_the_whole_commitment() {
+        let light = ParamSet::JdLight;
+        // Commitments agreeing on alpha and a[0] used to share a challenge
+        let mut rng = StdRng::seed_from_u64(19);
+        let w: ResponseVector = std::array::from_fn(|_| AlbertElement::random(&mut rng));
+        let seed = |v: &ResponseVector, msg: &[u8]| JordanSchnorr::<Q>::hash_to_seed(CHALLENGE_DOMAIN, light, CTX, msg, v);
+        let base = seed(&w, b"utxo");
+        for l in 0..RESPONSE_ELEMENTS {
+            for i in crate::albert::A + 1..27 {
+                let mut v = w;
+                v[l].coeffs[i] = (v[l].coeffs[i] + 1) % Q;
+                assert_ne!(seed(&v, b"utxo"), base);
+            }
+        }
+        // The message length is bound, so msg / w boundaries cannot shift
+        assert_ne!(base, seed(&w, b"utx"));
+
+        // A signature for one context does not verify in another, even when
+        // the context / message split moves
+        let sk = JordanSchnorr::<Q>::keygen(&mut rng);
+        let sig = JordanSchnorr::<Q>::sign(&sk, b"horizon/tx", b"utxo", &mut rng).unwrap();
+        assert_eq!(JordanSchnorr::<Q>::verify(&sk.pub_key, b"horizon/tx", b"utxo", &sig), Ok(()));
+        assert_eq!(JordanSchnorr::<Q>::verify(&sk.pub_key, b"p2p/handshake", b"utxo", &sig), Err(VerifyError::ChallengeMismatch));
+        assert_eq!(JordanSchnorr::<Q>::verify(&sk.pub_key, b"horizon/t", b"xutxo", &sig), Err(VerifyError::ChallengeMismatch));
+    }
+
+    #[test]
+    fn signing_gives_up_after_max_aborts() {
+        let mut rng = StdRng::seed_from_u64(4322);
+        let sk = JordanSchnorr::<Q>::keygen(&mut rng);
+        let sign = |seed, max_aborts| JordanSchnorr::<Q>::sign_with_stats(&sk, CTX, b"utxo", max_aborts, &mut StdRng::seed_from_u64(seed));
+
+        // The first seed whose signing rejects at least once
+        let (seed, (sig, stats)) = (0..).map(|seed| (seed, sign(seed, MAX_ABORTS).unwrap())).find(|(_, (_, stats))| stats.aborts > 0).unwrap();
+        assert_eq!(JordanSchnorr::<Q>::verify(&sk.pub_key, CTX, b"utxo", &sig), Ok(()));
+
+        // The same coins under a limit one short run out
+        assert_eq!(sign(seed, stats.aborts - 1), Err(SignError::TooManyAborts { attempts: stats.aborts }));
+        assert_eq!(sign(seed, stats.aborts), Ok((sig, stats)));
+    }
+
+    #[test]
+    fn responses_are_rejected_down_to_gamma2() {
+        let mut rng = StdRng::seed_from_u64(4295);
+        let sk = JordanSchnorr::<Q>::keygen(&mut rng);
+        let light = ParamSet::JdLight;
+        assert!(light.gamma1() <= Q / 4 && light.gamma2() == light.gamma1() - 24);
+
+        // The bound rejects: about half of all attempts at JD-Light
+        let (mut aborts, mut widest) = (0, 0);
+        for i in 0..40u8 {
+            let (sig, stats) = JordanSchnorr::<Q>::sign_with_stats(&sk, CTX, &[i], MAX_ABORTS, &mut rng).unwrap();
+            aborts += stats.aborts;
+            widest = widest.max(norm_inf(&sig.z));
+        }
+        assert!((20..=100).contains(&aborts), "{aborts} aborts in 40 signatures");
+        // Accepted responses fill [-gamma2, gamma2]
+        assert!(widest <= light.gamma2() && widest > light.gamma2() - 8);
+
+        // A secret past the bound is refused rather than leaked
+        let mut wide = JordanSchnorr::<Q>::keygen(&mut rng);
+        wide.s[5].set_beta(light.secret_bound() + 1);
+        let bound = light.secret_bound();
+        assert_eq!(JordanSchnorr::sign(&wide, CTX, b"utxo", &mut rng), Err(SignError::SecretTooWide { norm: bound + 1, bound }));
+    }
+
+    #[test]
+    fn possession_proofs_bind_the_key_and_nothing_else() {
+        let mut rng = StdRng::seed_from_u64(4326);
+        let (sk, other) = (JordanSchnorr::<Q>::keygen(&mut rng), JordanSchnorr::<Q>::keygen(&mut rng));
+        let proof = JordanSchnorr::prove_possession(&sk, &mut rng).unwrap();
+        assert_eq!(JordanSchnorr::verify_possession(&sk.pub_key, &proof), Ok(()));
+        assert_eq!(JordanSchnorr::verify_possession(&other.pub_key, &proof), Err(VerifyError::ChallengeMismatch));
+
+        // Not a signature on the key bytes in any context, and no signature
+        // on them proves possession
+        assert_eq!(JordanSchnorr::verify(&sk.pub_key, &[], &sk.pub_key.to_versioned_bytes(), &proof), Err(VerifyError::ChallengeMismatch));
+        let signed = JordanSchnorr::sign(&sk, &[], &sk.pub_key.to_versioned_bytes(), &mut rng).unwrap();
+        assert_eq!(JordanSchnorr::verify_possession(&sk.pub_key, &signed), Err(VerifyError::ChallengeMismatch));
+    }
+
+    #[test]
+    fn fingerprints_name_keys_compactly() {
+        let mut rng = StdRng::seed_from_u64(4324);
+        let (a, b) = (JordanSchnorr::<Q>::keygen(&mut rng).pub_key, JordanSchnorr::<Q>::keygen(&mut rng).pub_key);
+        let fp = a.fingerprint();
+        assert_eq!(fp, a.fingerprint());
+        assert_ne!(fp, b.fingerprint());
+        assert_eq!(fp.to_hex().len(), 40);
+        assert_eq!(fp.key_id().to_be_bytes(), fp.0[..8]);
+
+        let text = fp.to_string();
+        assert!(text.starts_with("jdk1"));
+        assert_eq!(text.parse(), Ok(fp));
+        assert_eq!(text.to_uppercase().parse(), Ok(fp));
+        // One mistyped letter is caught by the checksum
+        let mut typo = text.into_bytes();
+        typo[5] = if typo[5] == b'q' { b'p' } else { b'q' };
+        assert_eq!(String::from_utf8(typo).unwrap().parse::<Fingerprint>(), Err(DecodeError::InvalidBech32));
+
+        // The set is part of the key's name
+        let relabelled = PublicKey { params: ParamSet::Jd128, ..a };
+        assert_ne!(relabelled.fingerprint(), fp);
+    }
+
+    #[test]
+    fn secret_keys_export_under_a_passphrase() {
+        let mut rng = StdRng::seed_from_u64(4323);
+        let sk = JordanSchnorr::<Q>::keygen(&mut rng);
+        let cost = PwHashParams::new(64, 1).unwrap();
+        let file = sk.export_encrypted_with(b"correct horse", cost, &mut rng).unwrap();
+        assert_eq!(file.len(), KEYFILE_HEADER_BYTES + SecretKey::<Q>::BYTES + TAG_BYTES);
+        let restored = SecretKey::<Q>::import_encrypted(&file, b"correct horse").unwrap();
+        assert_eq!((restored.s, restored.pub_key), (sk.s, sk.pub_key));
+        assert!(!file.windows(8).any(|w| sk.to_versioned_bytes().windows(8).any(|s| s == w)));
+
+        assert_eq!(SecretKey::<Q>::import_encrypted(&file, b"battery staple").map(|_| ()), Err(KeyFileError::WrongPassphrase));
+        // The cost is authenticated: a cheaper one fails the tag
+        let mut cheaper = file.clone();
+        cheaper[5..9].copy_from_slice(&8u32.to_le_bytes());
+        assert_eq!(SecretKey::<Q>::import_encrypted(&cheaper, b"correct horse").map(|_| ()), Err(KeyFileError::WrongPassphrase));
+        let mut costly = file.clone();
+        costly[9..13].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(matches!(SecretKey::<Q>::import_encrypted(&costly, b"correct horse"), Err(KeyFileError::Cost(PwHashError::TooManyIterations { .. }))));
+        let mut future = file.clone();
+        future[4] = 2;
+        assert_eq!(SecretKey::<Q>::import_encrypted(&future, b"correct horse").map(|_| ()), Err(KeyFileError::Version { got: 2 }));
+        assert_eq!(SecretKey::<Q_DILITHIUM>::import_encrypted(&file, b"correct horse").map(|_| ()), Err(KeyFileError::Key(DecodeError::Length { expected: SecretKey::<Q_DILITHIUM>::BYTES, got: SecretKey::<Q>::BYTES })));
+    }
+
+    #[test]
+    fn keys_and_signatures_round_trip_through_bytes() {
+        let mut rng = StdRng::seed_from_u64(13);
+        let sk = JordanSchnorr::<Q>::keygen(&mut rng);
+        let sig = JordanSchnorr::<Q>::sign(&sk, CTX, b"utxo", &mut rng).unwrap();
+        let (pk_bytes, sig_bytes) = (sk.pub_key.to_versioned_bytes(), sig.to_versioned_bytes());
+        assert_eq!((pk_bytes.len(), sig_bytes.len(), sk.to_versioned_bytes().len()), (664, 543, 1276));
+
+        let (pk, sig) = (PublicKey::<Q>::from_versioned_bytes(&pk_bytes).unwrap(), Signature::<Q>::from_versioned_bytes(&sig_bytes).unwrap());
+        assert_eq!(JordanSchnorr::<Q>::verify(&pk, CTX, b"utxo", &sig), Ok(()));
+        let restored = SecretKey::<Q>::from_versioned_bytes(&sk.to_versioned_bytes()).unwrap();
+        assert_eq!((restored.s, restored.pub_key), (sk.s, sk.pub_key));
+
+        // Another parameter set's bytes are refused by their prefix, not misread
+        let dilithium = JordanSchnorr::<Q_DILITHIUM>::keygen(&mut rng).pub_key.to_versioned_bytes();
+        let mut relabelled = pk_bytes.clone();
+        relabelled[0] = dilithium[0];
+        assert_eq!(PublicKey::<Q>::from_versioned_bytes(&relabelled), Err(DecodeError::ParameterSet { expected: 0x21, got: 0x22 }));
+        assert!(matches!(PublicKey::<Q>::from_versioned_bytes(&dilithium), Err(DecodeError::Length { .. })));
+        assert_eq!(PublicKey::<Q_DILITHIUM>::from_versioned_bytes(&dilithium).map(|pk| pk.to_versioned_bytes()), Ok(dilithium));
+
+        // verify says which check failed
+        let mut loud = sig.clone();
+        loud.z[3].set_beta(Q / 2);
+        assert_eq!(JordanSchnorr::<Q>::verify(&pk, CTX, b"utxo", &loud), Err(VerifyError::BoundViolation { norm: Q / 2, bound: ParamSet::JdLight.gamma2() }));
+        loud.z[3].set_beta(Q);
+        assert_eq!(JordanSchnorr::<Q>::verify(&pk, CTX, b"utxo", &loud), Err(VerifyError::ParameterMismatch));
+        let degenerate = PublicKey { a: AlbertElement::zero(), ..pk };
+        assert_eq!(JordanSchnorr::<Q>::verify(&degenerate, CTX, b"utxo", &sig), Err(VerifyError::MalformedKey));
+
+        // A key from before invertible generators, with a singular A
+        let legacy = JordanSchnorr::<Q>::keygen_with_generator(AlbertElement::idempotent(0), &mut rng);
+        let signed = JordanSchnorr::sign(&legacy, CTX, b"utxo", &mut rng).unwrap();
+        assert_eq!(JordanSchnorr::<Q>::verify(&legacy.pub_key, CTX, b"utxo", &signed), Ok(()));
+
+        // Packed elements carry no stray padding bits
+        let mut padded = sig_bytes;
+        padded[AlbertElement::<Q>::PACKED_BYTES] = 0xFF;
+        assert_eq!(Signature::<Q>::from_versioned_bytes(&padded), Err(DecodeError::Padding));
+    }
+
+    #[test]
+    fn secrets_are_wiped_in_place() {
+        let mut rng = StdRng::seed_from_u64(4321);
+        let mut sk: SecretKey = JordanSchnorr::keygen(&mut rng);
+        let pk = sk.pub_key;
+        sk.zeroize();
+        assert_eq!(sk.s, [AlbertElement::zero(); KEY_ELEMENTS]);
+        // The public half is not secret and is kept
+        assert_eq!(sk.pub_key, pk);
+    }
+}
@@ -0,0 +1,243 @@
+// src/jordan_threshold.rs
+// THRESHOLD SIGNING: any t of n devices sign for one JordanSchnorr key.
+//
+// Shamir sharing needs Lagrange coefficients, and Z_2^15 is not a field.
+// Replicated sharing works over any ring: s = sum r_F over every set F of
+// t - 1 parties, and party i holds each r_F with i outside F. A set T of t
+// or more signers misses no F, so between them they hold every piece; the
+// owner of F in T is its lowest member outside F, so each piece is used
+// exactly once. Shares grow with C(n, t - 1) pieces: sized for custody (a
+// handful of devices), not committees. n <= MAX_PARTIES.
+//
+// The pieces are not uniform mod M but short: each r_F is a fresh keygen
+// secret under one generator A, and the shared key is t = sum A o r_F, as
+// in jordan_musig.rs. Any t - 1 parties lack exactly the r_F for their own
+// set F, and what they know of it, A o r_F = t - sum of the rest, is an
+// ordinary public key: finding r_F is breaking one. So the dealer makes a
+// fresh key; an existing key cannot be split this way.
+//
+// Signing follows jordan_musig.rs - commit to w_i, reveal, respond - with
+// z_i = y_i + C R_i, R_i the sum of the pieces i owns in T. Every co-signer
+// sees z_i, and with t = 2 could rebuild it from z and its own half
+// whatever masks were added, so z_i must be safe to publish on its own:
+// each signer runs the rejection step of JordanMusig::respond on its z_i,
+// which makes it a plain Gaussian sample whatever R_i is. Short pieces are
+// what make that possible; C R_i for a uniform R_i is uniform, and no
+// rejection step hides it. Then z = sum z_i = y + C s, an ordinary
+// Signature under the shared key, and the full s never exists anywhere.
+
+use crate::albert::{AlbertElement, Q};
+use crate::jordan_musig::{Commitment, JordanMusig, MusigError, Nonce};
+use crate::jordan_sig::{JordanSchnorr, KeyVector, PublicKey, ResponseVector, Signature};
+use crate::redact::{self, Redacted};
+use zeroize::Zeroize;
+use rand::prelude::*;
+use std::fmt;
+
+pub const MAX_PARTIES: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThresholdError {
+    // Fewer than t distinct signers
+    TooFewSigners { threshold: usize, got: usize },
+    // A signer index is not below n, or repeats
+    UnknownSigner { index: usize },
+    // This share is not one of the signers
+    NotASigner { index: usize },
+    // Commit / reveal / combine, as in jordan_musig
+    Session(MusigError),
+}
+
+impl From<MusigError> for ThresholdError {
+    fn from(e: MusigError) -> Self {
+        ThresholdError::Session(e)
+    }
+}
+
+// One device's share of the key
+#[derive(Clone)]
+pub struct KeyShare<const M: u64 = Q> {
+    pub index: usize,
+    pub threshold: usize,
+    pub parties: usize,
+    pub key: PublicKey<M>,
+    pieces: Vec<(u32, KeyVector<M>)>, // (F as a bit set, r_F), i not in F
+}
+
+// Manual Debug: pieces are redacted
+impl<const M: u64> fmt::Debug for KeyShare<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pieces: Vec<u8> = self.pieces.iter().flat_map(|(_, r)| r.iter().flat_map(|r| redact::u64_bytes(&r.coeffs))).collect();
+        f.debug_struct("KeyShare")
+            .field("index", &self.index)
+            .field("threshold", &self.threshold)
+            .field("parties", &self.parties)
+            .field("key", &self.key)
+            .field("pieces", &Redacted(&pieces))
+            .finish()
+    }
+}
+
+pub struct JordanThreshold<const M: u64 = Q>;
+
+impl<const M: u64> JordanThreshold<M> {
+    /// SHARE GENERATION
+    /// A fresh key and its n shares, any `threshold` of which sign for
+    /// it. The dealer sees every piece once; nothing it keeps afterwards
+    /// should outlive the call.
+    pub fn deal<R: Rng + ?Sized>(threshold: usize, parties: usize, rng: &mut R) -> Vec<KeyShare<M>> {
+        assert!(1 <= threshold && threshold <= parties && parties <= MAX_PARTIES, "deal: need 1 <= t <= n <= {MAX_PARTIES}");
+        let sets: Vec<u32> = (0..1u32 << parties).filter(|f| f.count_ones() as usize == threshold - 1).collect();
+        let a = AlbertElement::sample_invertible(rng);
+        let pieces: Vec<_> = sets.iter().map(|_| JordanSchnorr::keygen_with_generator(a, rng)).collect();
+        let first = pieces[0].pub_key;
+        let t = pieces.iter().skip(1).fold(first.t, |t, piece| std::array::from_fn(|k| t[k] + piece.pub_key.t[k]));
+        let key = PublicKey { t, ..first };
+
+        (0..parties)
+            .map(|index| KeyShare {
+                index,
+                threshold,
+                parties,
+                key,
+                pieces: sets.iter().zip(&pieces).filter(|(f, _)| *f & (1 << index) == 0).map(|(&f, piece)| (f, piece.s)).collect(),
+            })
+            .collect()
+    }
+
+    /// ROUND 1
+    /// `signers` is the signing set T, the same list (any order) on every
+    /// device in it
+    pub fn commit<R: Rng + ?Sized>(share: &KeyShare<M>, signers: &[usize], rng: &mut R) -> Result<(Nonce<M>, Commitment), ThresholdError> {
+        Self::check_signers(share, signers)?;
+        Ok(JordanMusig::commit_under(&share.key, signers.len(), rng))
+    }
+
+    /// ROUND 2
+    /// Reveals in the order of `signers`; the summed commitment w
+    pub fn open(
+        signers: &[usize],
+        commitments: &[Commitment],
+        reveals: &[ResponseVector<M>],
+    ) -> Result<ResponseVector<M>, ThresholdError> {
+        Ok(JordanMusig::open_n(signers.len(), commitments, reveals)?)
+    }
+
+    /// RESPOND
+    /// z_i = y_i + C R_i, or Session(Abort) if this signer's rejection step
+    /// refuses it: restart from round 1
+    pub fn respond<R: Rng + ?Sized>(
+        share: &KeyShare<M>,
+        signers: &[usize],
+        nonce: Nonce<M>,
+        context: &[u8],
+        msg: &[u8],
+        w: &ResponseVector<M>,
+        rng: &mut R,
+    ) -> Result<ResponseVector<M>, ThresholdError> {
+        Self::check_signers(share, signers)?;
+        let set = signers.iter().fold(0u32, |acc, &j| acc | 1 << j);
+        // The lowest signer outside F owns it
+        let owner = |f: u32| (set & !f).trailing_zeros() as usize;
+        let mut owned = Self::sum(share.pieces.iter().filter(|&&(f, _)| owner(f) == share.index).map(|(_, r)| r));
+        let c = JordanSchnorr::<M>::challenge(share.key.params, context, msg, w);
+        let z = JordanMusig::respond_rejecting(&owned, nonce, &c, rng);
+        owned.zeroize();
+        Ok(z?)
+    }
+
+    /// COMBINE
+    /// The signature under the shared key, or Session(Abort): restart from
+    /// round 1
+    pub fn combine(
+        key: &PublicKey<M>,
+        signers: &[usize],
+        context: &[u8],
+        msg: &[u8],
+        w: &ResponseVector<M>,
+        partials: &[ResponseVector<M>],
+    ) -> Result<Signature<M>, ThresholdError> {
+        Ok(JordanMusig::combine_n(key, signers.len(), context, msg, w, partials)?)
+    }
+
+    fn check_signers(share: &KeyShare<M>, signers: &[usize]) -> Result<(), ThresholdError> {
+        let mut seen = 0u32;
+        for &index in signers {
+            if index >= share.parties || seen & (1 << index) != 0 {
+                return Err(ThresholdError::UnknownSigner { index });
+            }
+            seen |= 1 << index;
+        }
+        if signers.len() < share.threshold {
+            return Err(ThresholdError::TooFewSigners { threshold: share.threshold, got: signers.len() });
+        }
+        if seen & (1 << share.index) == 0 {
+            return Err(ThresholdError::NotASigner { index: share.index });
+        }
+        Ok(())
+    }
+
+    fn sum<'a>(pieces: impl Iterator<Item = &'a KeyVector<M>>) -> KeyVector<M> {
+        pieces.fold(std::array::from_fn(|_| AlbertElement::zero()), |acc, r| std::array::from_fn(|k| acc[k] + r[k]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::horizon::TX_CONTEXT;
+    use crate::jordan_sig::norm_inf;
+
+    fn sign_with(rng: &mut StdRng, shares: &[KeyShare], signers: &[usize], msg: &[u8]) -> Signature {
+        let devices: Vec<&KeyShare> = signers.iter().map(|&i| &shares[i]).collect();
+        loop {
+            let (nonces, commitments): (Vec<_>, Vec<_>) =
+                devices.iter().map(|share| JordanThreshold::commit(share, signers, rng).unwrap()).unzip();
+            let reveals: Vec<_> = nonces.iter().map(|nonce| nonce.w).collect();
+            let w = JordanThreshold::open(signers, &commitments, &reveals).unwrap();
+            let partials: Result<Vec<_>, _> = devices
+                .iter()
+                .zip(nonces)
+                .map(|(share, nonce)| JordanThreshold::respond(share, signers, nonce, TX_CONTEXT, msg, &w, rng))
+                .collect();
+            match partials.and_then(|partials| JordanThreshold::combine(&shares[0].key, signers, TX_CONTEXT, msg, &w, &partials)) {
+                Ok(sig) => return sig,
+                Err(e) => assert_eq!(e, ThresholdError::Session(MusigError::Abort)),
+            }
+        }
+    }
+
+    #[test]
+    fn any_t_of_n_sign_for_the_shared_key() {
+        let mut rng = StdRng::seed_from_u64(4313);
+        let shares = JordanThreshold::deal(2, 3, &mut rng);
+        let key = shares[0].key;
+        for signers in [[0, 1], [0, 2], [2, 1]] {
+            let sig = sign_with(&mut rng, &shares, &signers, b"spend");
+            assert_eq!(JordanSchnorr::verify(&key, TX_CONTEXT, b"spend", &sig), Ok(()));
+        }
+        let sig = sign_with(&mut rng, &shares, &[0, 1, 2], b"all three");
+        assert_eq!(JordanSchnorr::verify(&key, TX_CONTEXT, b"all three", &sig), Ok(()));
+
+        // 3-of-4: each piece is held by the two parties outside its set
+        let shares = JordanThreshold::deal(3, 4, &mut rng);
+        let key = shares[0].key;
+        assert!(shares.iter().all(|share| share.pieces.len() == 3 && share.key == key));
+        // ...and every piece is as short as a keygen secret
+        let eta = key.params.secret_eta() as u64;
+        assert!(shares.iter().flat_map(|share| &share.pieces).all(|(_, r)| norm_inf(r) <= eta));
+        let sig = sign_with(&mut rng, &shares, &[3, 0, 2], b"spend");
+        assert_eq!(JordanSchnorr::verify(&key, TX_CONTEXT, b"spend", &sig), Ok(()));
+    }
+
+    #[test]
+    fn signing_sets_are_checked() {
+        let mut rng = StdRng::seed_from_u64(4314);
+        let shares: Vec<KeyShare> = JordanThreshold::deal(2, 3, &mut rng);
+        let commit = |signers: &[usize], rng: &mut StdRng| JordanThreshold::commit(&shares[0], signers, rng).map(|_| ());
+        assert_eq!(commit(&[0], &mut rng), Err(ThresholdError::TooFewSigners { threshold: 2, got: 1 }));
+        assert_eq!(commit(&[0, 0], &mut rng), Err(ThresholdError::UnknownSigner { index: 0 }));
+        assert_eq!(commit(&[0, 3], &mut rng), Err(ThresholdError::UnknownSigner { index: 3 }));
+        assert_eq!(commit(&[1, 2], &mut rng), Err(ThresholdError::NotASigner { index: 0 }));
+    }
+}
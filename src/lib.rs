@@ -1,26 +1,56 @@
-// src/lib.rs
-pub mod flutter_topology;
-pub mod vdf;
-pub mod sedenion;
-pub mod gsh;
-pub mod synergeia_sim;
-pub mod hdwallet;
-pub mod albert;
-pub mod flt_cipher;
-pub mod jordan_sig;
-pub mod horizon;
-pub mod horizon_net;
-pub mod stark;
-pub mod stark_vdf;
-
-// Placeholder for the Octonion algebra
-#[derive(Clone, Debug, Copy, PartialEq, Eq)] 
-pub struct Octonion {
-    pub c: [u64; 8], 
-}
-
-impl Octonion {
-    pub fn mul(_a: Octonion, _b: Octonion) -> Octonion {
-        Octonion { c: [0; 8] }
-    }
-}
\ No newline at end of file
+// src/lib.rs
+pub mod flutter_topology;
+pub mod vdf;
+pub mod sedenion;
+pub mod trigintaduonion;
+pub mod gsh;
+pub mod gsh_io;
+pub mod synergeia_sim;
+pub mod hdwallet;
+pub mod albert;
+pub mod flt_cipher;
+pub mod jordan_sig;
+pub mod jordan_musig;
+pub mod jordan_threshold;
+pub mod jordan_ring;
+pub mod jordan_forward;
+pub mod jordan_recovery;
+pub mod address;
+pub mod horizon;
+pub mod horizon_net;
+pub mod mmr;
+pub mod epoch;
+pub mod stark;
+pub mod stark_vdf;
+pub mod redact;
+pub mod soak;
+pub mod replay;
+pub mod storage;
+pub mod merkle;
+pub mod wire;
+pub mod timelock;
+pub mod algebra;
+pub mod audit;
+pub mod mirror;
+pub mod mempool;
+pub mod timeline;
+pub mod wallet;
+pub mod payment;
+pub mod vdf_service;
+pub mod compress;
+pub mod shadow;
+pub mod faultnet;
+pub mod fee_market;
+// Experimental, not for production: only with `--features research`
+#[cfg(feature = "research")]
+pub mod research;
+#[cfg(feature = "research")]
+pub mod jordan_kem;
+#[cfg(feature = "research")]
+pub mod jordan_nike;
+// PyO3 extension module: only with `--features python`
+#[cfg(feature = "python")]
+mod python;
+
+// The canonical octonion (generic over its scalar backend)
+pub use algebra::{NonAssociativeAlgebra, Octonion};
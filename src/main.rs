@@ -1,229 +1,492 @@
-use olc_research::gsh;
-use olc_research::synergeia_sim;
-use olc_research::hdwallet;
-use olc_research::flt_cipher;
-use olc_research::jordan_sig;
-use olc_research::horizon;
-use olc_research::horizon_net;
-use olc_research::stark_vdf;
-
-
-fn main() {
-    
-    println!("===========================================");
-    stark_vdf::test_e2e_proof();
-
-    println!("===========================================");
-
-    println!("===========================================");
-    println!("=== HORIZON: Stateless PQ Blockchain ===");
-    println!("===========================================");
-    println!("State Model: Holographic (Root encodes Bulk)");
-
-    // 1. Setup: Create the Global Accumulator (The "Bulk")
-    let mut accumulator = horizon::HorizonAccumulator::new();
-    let mut rng = rand::thread_rng();
-
-    // 2. User A receives a UTXO (Minting)
-    println!("[1] Minting UTXO for User A...");
-    let alice_keys = jordan_sig::JordanSchnorr::keygen(&mut rng);
-    let bob_keys = jordan_sig::JordanSchnorr::keygen(&mut rng);
-
-    let utxo_a = horizon::Utxo {
-        id: [0xAA; 32],
-        owner: alice_keys.pub_key,
-        amount: 50,
-    };
-    
-    // Position in the tree (Address space)
-    let utxo_index = 12345; 
-    accumulator.add_utxo(&utxo_a, utxo_index);
-    
-    let genesis_root = accumulator.root.clone();
-    println!("    Genesis Horizon (Root): {}...", &genesis_root[0..16]);
-
-    // 3. Stateless Validator comes online
-    // It knows ONLY the Root, not the UTXO set.
-    let validator = horizon::HorizonValidator::new(genesis_root.clone());
-
-    // 4. User A creates a Transaction to User B
-    println!("\n[2] User A creates Transaction (A -> B)...");
-    
-    // A. User A generates their own Witness (Merkle Proof)
-    // This is the "Holographic Projection" of their funds.
-    let witness = accumulator.generate_witness(utxo_index);
-    
-    // B. User A Signs the UTXO
-    let msg = utxo_a.hash().into_bytes();
-    let sig = jordan_sig::JordanSchnorr::sign(&alice_keys, &msg, &mut rng);
-
-    let tx = horizon::Transaction {
-        input_utxo: utxo_a,
-        witness: witness,
-        signature: sig,
-        new_owner: bob_keys.pub_key,
-        new_amount: 50,
-    };
-
-    // 5. Validator Processes Tx (Statelessly)
-    println!("\n[3] Validator verifying Tx (Stateless)...");
-    match validator.process_transaction(&tx) {
-        Some(new_root) => {
-            println!("    [SUCCESS] Transaction Valid.");
-            println!("    Old Horizon: {}...", &validator.state_root[0..16]);
-            println!("    New Horizon: {}...", &new_root[0..16]);
-        },
-        None => println!("    [FAILURE] Transaction Invalid."),
-    }
-
-    println!("=== HORIZON: Network Bootstrapping Demo ===");
-
-    // 1. Genesis
-    let genesis_root = "GENESIS_ROOT_HASH_0000".to_string();
-    
-    // 2. Node A (Local) - Has 1 block
-    let mut node_a = horizon_net::HorizonPeer::new(genesis_root.clone());
-    node_a.mine_next_block("STATE_ROOT_A1".to_string(), 1000); // 1000 iterations
-
-    // 3. Node B (Remote) - Has 3 blocks (Longer/Heavier chain)
-    let mut node_b = horizon_net::HorizonPeer::new(genesis_root.clone());
-    node_b.mine_next_block("STATE_ROOT_B1".to_string(), 1000);
-    node_b.mine_next_block("STATE_ROOT_B2".to_string(), 1000);
-    node_b.mine_next_block("STATE_ROOT_B3".to_string(), 1000);
-
-    println!("Node A Tip: {}...", node_a.current_horizon);
-    println!("Node B Tip: {}...", node_b.current_horizon);
-
-    // 4. Node A bootstraps from Node B
-    // In a stateful chain, A would need to download blocks B1, B2, B3 AND verify all Tx.
-    // In Horizon, A only verifies the VDFs in the headers.
-    horizon_net::NetworkBootstrapper::sync(&mut node_a, &node_b.chain);
-
-    println!("Node A New Tip: {}...", node_a.current_horizon);
-    
-    if node_a.current_horizon == node_b.current_horizon {
-        println!("[SUCCESS] Instant Bootstrap complete.");
-        println!("Node A is ready to validate transactions on the new Horizon.");
-    }
-
-
-    println!("\n\n===========================================");
-    println!("=== JORDAN-DILITHIUM: Post-Quantum Sig ===");
-    println!("===========================================");
-    
-    // 1. Key Generation
-    println!("[1] Generating Keys (Lattice setup)...");
-    let mut rng = rand::thread_rng();
-    let keypair = jordan_sig::JordanSchnorr::keygen(&mut rng);
-    println!("    Public Key Generator (Alpha): {}", keypair.pub_key.a.alpha);
-    println!("    Public Key Target (Alpha): {}", keypair.pub_key.t.alpha);
-
-    // 2. Signing
-    let tx_msg = b"User A sends 50 BTC to User B";
-    println!("\n[2] Signing Transaction: {:?}", String::from_utf8_lossy(tx_msg));
-    let signature = jordan_sig::JordanSchnorr::sign(&keypair, tx_msg, &mut rng);
-    println!("    Signature Challenge (c): {}", signature.c);
-    println!("    Signature Response (z alpha): {}", signature.z.alpha);
-
-    // 3. Verification
-    println!("\n[3] Verifying Transaction...");
-    let valid = jordan_sig::JordanSchnorr::verify(&keypair.pub_key, tx_msg, &signature);
-    
-    if valid {
-        println!("    [SUCCESS] Signature is VALID.");
-        println!("    Artin's Theorem bypassed via scalar challenge.");
-    } else {
-        println!("    [FAILURE] Invalid Signature.");
-    }
-    
-    // 4. Forgery Test
-    println!("\n[4] Attempting Forgery...");
-    let fake_msg = b"User A sends 5000 BTC to User B";
-    let valid_forge = jordan_sig::JordanSchnorr::verify(&keypair.pub_key, fake_msg, &signature);
-    if !valid_forge {
-        println!("    [SUCCESS] Forgery detected and rejected.");
-    } else {
-        println!("    [FAILURE] Forgery accepted!");
-    }
-
-    println!("=== FLUTTER: IoT Vacuum Cipher ===");
-    
-    // 1. Define Key and Nonce (128-bit each)
-    let key = [0x1337, 0xC0DE, 0xDEAD, 0xBEEF, 0xCAFE, 0xBABE, 0x8080, 0xFFFF];
-    let nonce = [0, 1, 2, 3, 4, 5, 6, 7];
-
-    println!("Key: {:X?}", key);
-    println!("Nonce: {:X?}", nonce);
-
-    // 2. Initialize Cipher
-    let mut flutter = flt_cipher::FlutterCipher::new(key, nonce);
-    println!("\n[System Initialized]");
-    println!("State (Post-Warmup): {:?}", flutter.state);
-
-    // 3. Encrypt a Payload
-    let payload = b"Hello, Vacuum!";
-    let mut buffer = payload.to_vec();
-    
-    println!("\nOriginal: {:?}", String::from_utf8_lossy(&buffer));
-    
-    flutter.process(&mut buffer);
-    println!("Encrypted (Hex): {:02X?}", buffer);
-
-    // 4. Decrypt (Re-init cipher with same key/nonce)
-    let mut decryptor = flt_cipher::FlutterCipher::new(key, nonce);
-    decryptor.process(&mut buffer);
-    
-    println!("Decrypted: {:?}", String::from_utf8_lossy(&buffer));
-    
-    if buffer == payload {
-        println!("\n[SUCCESS] Integrity Check Passed.");
-    } else {
-        println!("\n[FAIL] Decryption mismatch.");
-    }
-
-
-    println!("=== FLUTTER ENGINE: Bi-Octonion HD Wallet ===");
-
-    // 1. Setup Engine (Cosmological Constant)
-    let kappa = 0x1910;
-    let c_bytes = [0xAB; 16];
-    let engine = hdwallet::FlutterEngine::new(kappa, c_bytes);
-
-    // 2. Master Seed
-    let seed = hdwallet::MasterSeed { seed_bytes: [0x42; 32] };
-    
-    // 3. Derive Identity
-    println!("Deriving KeyPair #0...");
-    let kp = seed.derive_keypair(&engine, 0);
-    println!("Public Key (Z_final):\nLeft: {:?}\nRight: {:?}", kp.public_key.left.c, kp.public_key.right.c);
-
-    // 4. Sign Message
-    let msg = b"Octonions Rule The Vacuum";
-    println!("\nSigning Message: {:?}", String::from_utf8_lossy(msg));
-    let sig = kp.sign(&engine, msg);
-    println!("Signature Generated ({} Chain States)", sig.revealed_states.len());
-
-    // 5. Verify
-    let valid = hdwallet::verify(&engine, &kp.public_key, msg, &sig);
-    if valid {
-        println!("\n[SUCCESS] Signature Verified.");
-    } else {
-        println!("\n[FAIL] Verification Failed.");
-    }
-
-    // 7. Run GSH-256 Demo
-    println!("\n\n===========================================");
-    println!("=== GSH-256: Geometric Stiffness Hash ===");
-    println!("===========================================");
-    let input = b"The vacuum is empty.";
-    let hash = gsh::GSH256::hash_bytes(input);
-    println!("Input: {:?}", String::from_utf8_lossy(input));
-    println!("Hash: {}", hash);
-    let input = b"The vacuum is not empty, but merely highly conductive.";
-    let hash = gsh::GSH256::hash_bytes(input);
-    println!("Input: {:?}", String::from_utf8_lossy(input));
-    println!("Hash: {}", hash);
-
-    // 8. Run Synergeia Consensus Simulation
-    synergeia_sim::run_simulation(10_000);
+use olc_research::address;
+use olc_research::gsh;
+use olc_research::synergeia_sim;
+use olc_research::fee_market;
+use olc_research::faultnet;
+use olc_research::hdwallet;
+use olc_research::flt_cipher;
+use olc_research::jordan_sig;
+use olc_research::horizon;
+use olc_research::horizon_net;
+use olc_research::stark_vdf;
+use olc_research::redact;
+use olc_research::soak;
+use olc_research::replay;
+use olc_research::wallet;
+use olc_research::vdf_service;
+use std::time::Duration;
+
+// CLI entry point.
+//   olc                  -> full protocol demo
+//   olc soak [minutes]   -> long-running two-node soak test (default 60 min)
+//   olc replay <journal> -> re-run a recorded node session
+//   olc wallet <amount> [coin...] -> draft a payment from a demo wallet,
+//                           optionally spending exactly the given coins
+//   olc vdf-service [addr] [jobs file] -> REST daemon for VDF evaluations
+//                           (API tokens from OLC_VDF_TOKENS, comma-separated)
+//   olc fee-market [blocks] -> fee/backlog equilibrium under a demand spike
+//   olc chaos [seed]     -> header-sync convergence over a faulty network
+//   olc research [trials]-> attack the experimental candidates (--features research)
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    match args.get(1).map(String::as_str) {
+        Some("soak") => run_soak(args.get(2)),
+        Some("replay") => run_replay(args.get(2)),
+        Some("wallet") => run_wallet(&args[2..]),
+        Some("vdf-service") => run_vdf_service(args.get(2), args.get(3)),
+        Some("fee-market") => run_fee_market(args.get(2)),
+        Some("chaos") => run_chaos(args.get(2)),
+        #[cfg(feature = "research")]
+        Some("research") => run_research(args.get(2)),
+        Some(other) => {
+            eprintln!("Unknown command '{}'. Usage: olc [soak [minutes] | replay <journal> | wallet <amount> [coin...] | vdf-service [addr] [jobs file] | fee-market [blocks] | chaos [seed]]", other);
+            std::process::exit(2);
+        }
+        None => run_demo(),
+    }
+}
+
+fn run_soak(minutes: Option<&String>) {
+    let minutes: u64 = match minutes.map(|m| m.parse()) {
+        None => 60,
+        Some(Ok(m)) => m,
+        Some(Err(_)) => {
+            eprintln!("soak: minutes must be a positive integer");
+            std::process::exit(2);
+        }
+    };
+    let config = soak::SoakConfig {
+        duration: Duration::from_secs(minutes * 60),
+        // Shadow validation is on in debug builds; OLC_SHADOW=1 forces it in release
+        shadow: cfg!(debug_assertions) || std::env::var_os("OLC_SHADOW").is_some(),
+        ..soak::SoakConfig::default()
+    };
+
+    println!("=== SOAK: two-node regtest for {} min ===", minutes);
+    match soak::run_soak(&config) {
+        Ok(report) => {
+            println!("[SUCCESS] Roots reconciled for the whole run.");
+            println!("{:#?}", report);
+        }
+        Err(failure) => {
+            println!("[FAILURE] {:?}", failure);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run_replay(path: Option<&String>) {
+    let path = match path {
+        Some(p) => std::path::Path::new(p),
+        None => {
+            eprintln!("replay: missing journal path");
+            std::process::exit(2);
+        }
+    };
+    let journal = match replay::Journal::load(path) {
+        Ok(Ok(journal)) => journal,
+        Ok(Err(e)) => {
+            eprintln!("replay: {} line {}: {}", path.display(), e.line, e.reason);
+            std::process::exit(2);
+        }
+        Err(e) => {
+            eprintln!("replay: {}: {}", path.display(), e);
+            std::process::exit(2);
+        }
+    };
+
+    println!("=== REPLAY: {} journal entries ===", journal.entries.len());
+    match replay::ReplayNode::replay(&journal) {
+        Ok(node) => {
+            for (i, outcome) in node.outcomes.iter().enumerate() {
+                println!("[{:>4}] {:?}", i, outcome);
+            }
+            println!("[SUCCESS] Tip height {}, horizon {}", node.peer.chain.len() - 1, node.peer.current_horizon);
+        }
+        Err(e) => {
+            println!("[FAILURE] {:?}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run_wallet(args: &[String]) {
+    let parsed: Result<Vec<u64>, _> = args.iter().map(|a| a.parse()).collect();
+    let (amount, coins) = match parsed.as_deref() {
+        Ok([amount, coins @ ..]) => (*amount, coins.to_vec()),
+        _ => {
+            eprintln!("wallet: usage: olc wallet <amount> [coin...]");
+            std::process::exit(2);
+        }
+    };
+
+    // Demo wallet: four coins, the largest frozen as cold storage
+    let mut rng = rand::thread_rng();
+    let mut w = wallet::Wallet::new(jordan_sig::JordanSchnorr::keygen(&mut rng));
+    let recipient = jordan_sig::JordanSchnorr::keygen(&mut rng).pub_key.address();
+    let fee = horizon::SPEND_SIZE_BYTES as u64;
+    let mut ids = Vec::new();
+    for (i, net) in [1_000u64, 2_000, 4_000, 8_000].iter().enumerate() {
+        let utxo = horizon::Utxo::new([i as u8 + 1; 32], w.address(), net + fee);
+        ids.push(utxo.id);
+        w.receive(utxo, i as u64);
+    }
+    w.set_label(&ids[3], Some("cold storage".to_string())).unwrap();
+    w.freeze(&ids[3]).unwrap();
+
+    println!("=== WALLET: {} spendable of {} (fee {} per input) ===", w.spendable_balance(), w.balance(), fee);
+    println!("  watch-only descriptor: {}", w.descriptor());
+    for (i, coin) in w.coins().enumerate() {
+        let flags = if coin.frozen { " [frozen]" } else { "" };
+        println!("  coin {}: {}{} {}", i, coin.utxo.amount, flags, coin.label.as_deref().unwrap_or(""));
+    }
+    let mut builder = hdwallet::builder::TxBuilder::new(&w, recipient, amount, 1);
+    if !coins.is_empty() {
+        match coins.iter().map(|&c| ids.get(c as usize).copied()).collect::<Option<Vec<_>>>() {
+            Some(inputs) => builder = builder.with_inputs(inputs),
+            None => {
+                eprintln!("wallet: coins are numbered 0..{}", ids.len());
+                std::process::exit(2);
+            }
+        }
+    }
+    match builder.build() {
+        Ok(draft) => {
+            println!("[DRAFT] pay {} from {} coin(s), change {} from {} coin(s), fee {}",
+                draft.amount, draft.payment.len(), draft.change_amount, draft.change.len(), draft.fee());
+        }
+        Err(e) => {
+            println!("[REJECTED] {:?}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run_vdf_service(addr: Option<&String>, jobs: Option<&String>) {
+    let addr = addr.map_or("127.0.0.1:8787", String::as_str);
+    let jobs = std::path::PathBuf::from(jobs.map_or("vdf_jobs.store", String::as_str));
+    let tokens: Vec<String> = std::env::var("OLC_VDF_TOKENS")
+        .unwrap_or_default()
+        .split(',')
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect();
+    if tokens.is_empty() {
+        eprintln!("vdf-service: set OLC_VDF_TOKENS to a comma-separated list of API tokens");
+        std::process::exit(2);
+    }
+    let queue = match vdf_service::JobQueue::open(jobs.clone()) {
+        Ok(queue) => queue,
+        Err(e) => {
+            eprintln!("vdf-service: {}: {:?}", jobs.display(), e);
+            std::process::exit(2);
+        }
+    };
+    let listener = match std::net::TcpListener::bind(addr) {
+        Ok(l) => l,
+        Err(e) => {
+            eprintln!("vdf-service: cannot bind {}: {}", addr, e);
+            std::process::exit(2);
+        }
+    };
+
+    println!("=== VDF SERVICE on http://{} ({} pending jobs, {} tokens) ===", addr, queue.pending(), tokens.len());
+    let service = vdf_service::VdfService::new(vdf_service::ServiceConfig::default(), queue, vdf_service::Auth::new(&tokens));
+    if let Err(e) = vdf_service::serve(listener, std::sync::Arc::new(std::sync::Mutex::new(service))) {
+        eprintln!("vdf-service: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run_fee_market(blocks: Option<&String>) {
+    let mut config = fee_market::FeeMarketConfig::default();
+    match blocks.map(|b| b.parse::<std::num::NonZeroU64>()) {
+        None => {}
+        Some(Ok(b)) => config.blocks = b.get(),
+        Some(Err(_)) => {
+            eprintln!("fee-market: blocks must be a positive integer");
+            std::process::exit(2);
+        }
+    }
+    fee_market::run_simulation(&config);
+}
+
+fn run_chaos(seed: Option<&String>) {
+    let mut config = faultnet::ConvergenceConfig::default();
+    match seed.map(|s| s.parse()) {
+        None => {}
+        Some(Ok(s)) => {
+            config.seed = s;
+            config.faults.seed = s;
+        }
+        Some(Err(_)) => {
+            eprintln!("chaos: seed must be a non-negative integer");
+            std::process::exit(2);
+        }
+    }
+
+    if let Err(e) = config.validate() {
+        eprintln!("chaos: bad configuration: {:?}", e);
+        std::process::exit(2);
+    }
+
+    println!("=== CHAOS: {} nodes, faults {:?} ===", config.nodes, config.faults);
+    let report = faultnet::run_convergence(&config).expect("config validated above");
+    println!("{:#?}", report);
+    match report.converged_after_ms {
+        Some(ms) => println!("[SUCCESS] Converged {} ms after mining stopped.", ms),
+        None => {
+            println!("[FAILURE] No common tip within {} ms.", config.deadline_ms);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(feature = "research")]
+fn run_research(trials: Option<&String>) {
+    use olc_research::research::attacks;
+
+    let trials: std::num::NonZeroUsize = match trials.map(|t| t.parse()) {
+        None => std::num::NonZeroUsize::new(32).unwrap(),
+        Some(Ok(t)) => t,
+        Some(Err(_)) => {
+            eprintln!("research: trials must be a positive integer");
+            std::process::exit(2);
+        }
+    };
+    println!("=== RESEARCH: attacking experimental candidates (NOT FOR PRODUCTION) ===");
+    let report = attacks::run_attacks(0x5EED, trials);
+    println!("{:#?}", report);
+    if report.linearization_breaks > 0 {
+        println!("[BROKEN] Conjugation DH: shared secret recovered by linearization in {}/{} trials.", report.linearization_breaks, report.trials);
+    }
+    if report.bracketing_ambiguous > 0 {
+        println!("[BROKEN] Bracketing commitment: {} commitments opened to a second shape.", report.bracketing_ambiguous);
+    }
+}
+
+fn run_demo() {
+    
+    println!("===========================================");
+    stark_vdf::test_e2e_proof();
+
+    println!("===========================================");
+
+    println!("===========================================");
+    println!("=== HORIZON: Stateless PQ Blockchain ===");
+    println!("===========================================");
+    println!("State Model: Holographic (Root encodes Bulk)");
+
+    // 1. Setup: Create the Global Accumulator (The "Bulk")
+    let mut accumulator = horizon::HorizonAccumulator::new();
+    let mut rng = rand::thread_rng();
+
+    // 2. User A receives a UTXO (Minting)
+    println!("[1] Minting UTXO for User A...");
+    // Each user's signing key comes from their HD seed (m/44'/0'/0'/0/0)
+    let receive = hdwallet::path::DerivationPath::bip44(0, 0, 0, 0).unwrap();
+    let mut alice_keys = hdwallet::MasterSeed { seed_bytes: [0xA1; 32] }.derive_jordan_keypair(&receive).unwrap();
+    let bob_keys = hdwallet::MasterSeed { seed_bytes: [0xB0; 32] }.derive_jordan_keypair(&receive).unwrap();
+    println!("    Alice: {}", alice_keys.pub_key.address().to_bech32(address::Network::Main));
+    println!("    Bob:   {}", bob_keys.pub_key.address().to_bech32(address::Network::Main));
+
+    let utxo_a = horizon::Utxo::new([0xAA; 32], alice_keys.pub_key.address(), 50);
+    
+    // Position in the tree (Address space)
+    let utxo_index = 12345; 
+    accumulator.add_utxo(&utxo_a, utxo_index);
+    
+    let genesis_root = accumulator.root.clone();
+    println!("    Genesis Horizon (Root): {}...", &genesis_root[0..16]);
+
+    // 3. Stateless Validator comes online
+    // It knows ONLY the Root, not the UTXO set.
+    // Shadow validation is on in debug builds; OLC_SHADOW=1 forces it in release
+    let mut node_config = horizon::NodeConfig::new();
+    if cfg!(debug_assertions) || std::env::var_os("OLC_SHADOW").is_some() {
+        node_config.enable_shadow(std::env::temp_dir());
+    }
+    let validator = horizon::HorizonValidator::with_config(genesis_root.clone(), node_config);
+
+    // 4. User A creates a Transaction to User B
+    println!("\n[2] User A creates Transaction (A -> B)...");
+    
+    // A. User A generates their own Witness (Merkle Proof)
+    // This is the "Holographic Projection" of their funds.
+    let witness = accumulator.generate_witness(utxo_index);
+    
+    // B. User A's wallet builds the spend and signs it with their key
+    let coin = hdwallet::watch::WatchedCoin { utxo: utxo_a, witness };
+    let tx = hdwallet::builder::TxBuilder::new(&[coin][..], bob_keys.pub_key.address(), 50, 0)
+        .sign(&mut alice_keys, &mut rng)
+        .expect("spend")
+        .remove(0);
+
+    // 5. Validator Processes Tx (Statelessly)
+    println!("\n[3] Validator verifying Tx (Stateless)...");
+    match validator.process_transaction(&tx) {
+        Some(new_root) => {
+            println!("    [SUCCESS] Transaction Valid.");
+            println!("    Old Horizon: {}...", &validator.state_root[0..16]);
+            println!("    New Horizon: {}...", &new_root[0..16]);
+        },
+        None => println!("    [FAILURE] Transaction Invalid."),
+    }
+
+    println!("=== HORIZON: Network Bootstrapping Demo ===");
+
+    // 1. Genesis
+    let genesis_root = "GENESIS_ROOT_HASH_0000".to_string();
+    
+    // 2. Node A (Local) - Has 1 block
+    let mut node_a = horizon_net::HorizonPeer::new(genesis_root.clone());
+    node_a.mine_next_block("STATE_ROOT_A1".to_string(), 1000); // 1000 iterations
+
+    // 3. Node B (Remote) - Has 3 blocks (Longer/Heavier chain)
+    let mut node_b = horizon_net::HorizonPeer::new(genesis_root.clone());
+    node_b.mine_next_block("STATE_ROOT_B1".to_string(), 1000);
+    node_b.mine_next_block("STATE_ROOT_B2".to_string(), 1000);
+    node_b.mine_next_block("STATE_ROOT_B3".to_string(), 1000);
+
+    println!("Node A Tip: {}...", node_a.current_horizon);
+    println!("Node B Tip: {}...", node_b.current_horizon);
+
+    // 4. Node A bootstraps from Node B
+    // In a stateful chain, A would need to download blocks B1, B2, B3 AND verify all Tx.
+    // In Horizon, A only verifies the VDFs in the headers.
+    horizon_net::NetworkBootstrapper::sync(&mut node_a, &node_b.chain);
+
+    println!("Node A New Tip: {}...", node_a.current_horizon);
+    
+    if node_a.current_horizon == node_b.current_horizon {
+        println!("[SUCCESS] Instant Bootstrap complete.");
+        println!("Node A is ready to validate transactions on the new Horizon.");
+    }
+
+
+    println!("\n\n===========================================");
+    println!("=== JORDAN-DILITHIUM: Post-Quantum Sig ===");
+    println!("===========================================");
+    
+    // 1. Key Generation
+    println!("[1] Generating Keys (Lattice setup)...");
+    let mut rng = rand::thread_rng();
+    let keypair: jordan_sig::SecretKey = jordan_sig::JordanSchnorr::keygen(&mut rng);
+    println!("    Public Key Generator (Alpha): {}", keypair.pub_key.a.alpha());
+    println!("    Public Key Target (t_0 Alpha): {}", keypair.pub_key.t[0].alpha());
+    println!("    Fingerprint: {}", keypair.pub_key.fingerprint());
+
+    // 2. Signing
+    let tx_msg = b"User A sends 50 BTC to User B";
+    println!("\n[2] Signing Transaction: {:?}", String::from_utf8_lossy(tx_msg));
+    let signature = jordan_sig::JordanSchnorr::sign(&keypair, horizon::TX_CONTEXT, tx_msg, &mut rng).expect("signing aborted");
+    println!("    Signature Challenge Seed (c): {}", signature.c.iter().map(|b| format!("{:02x}", b)).collect::<String>());
+    println!("    Signature Response (z_0 alpha): {}", signature.z[0].alpha());
+
+    // 3. Verification
+    println!("\n[3] Verifying Transaction...");
+    let valid = jordan_sig::JordanSchnorr::verify(&keypair.pub_key, horizon::TX_CONTEXT, tx_msg, &signature).is_ok();
+    
+    if valid {
+        println!("    [SUCCESS] Signature is VALID.");
+        println!("    Challenge: sparse ternary matrix expanded from c.");
+    } else {
+        println!("    [FAILURE] Invalid Signature.");
+    }
+    
+    // 4. Forgery Test
+    println!("\n[4] Attempting Forgery...");
+    let fake_msg = b"User A sends 5000 BTC to User B";
+    let forged = jordan_sig::JordanSchnorr::verify(&keypair.pub_key, horizon::TX_CONTEXT, fake_msg, &signature);
+    if let Err(reason) = forged {
+        println!("    [SUCCESS] Forgery detected and rejected: {:?}", reason);
+    } else {
+        println!("    [FAILURE] Forgery accepted!");
+    }
+
+    println!("=== FLUTTER: IoT Vacuum Cipher ===");
+    
+    // 1. Define Key and Nonce (128-bit each)
+    let key = [0x1337, 0xC0DE, 0xDEAD, 0xBEEF, 0xCAFE, 0xBABE, 0x8080, 0xFFFF];
+    let nonce = [0, 1, 2, 3, 4, 5, 6, 7];
+
+    println!("Key: {:?}", redact::Redacted(&redact::u16_bytes(&key)));
+    println!("Nonce: {:X?}", nonce);
+
+    // 2. Initialize Cipher
+    let mut flutter = flt_cipher::FlutterCipher::new(key, nonce);
+    println!("\n[System Initialized]");
+    println!("Cipher (Post-Warmup): {:?}", flutter);
+
+    // 3. Encrypt a Payload
+    let payload = b"Hello, Vacuum!";
+    let mut buffer = payload.to_vec();
+    
+    println!("\nOriginal: {:?}", String::from_utf8_lossy(&buffer));
+    
+    flutter.process(&mut buffer);
+    println!("Encrypted (Hex): {:02X?}", buffer);
+
+    // 4. Decrypt (Re-init cipher with same key/nonce)
+    let mut decryptor = flt_cipher::FlutterCipher::new(key, nonce);
+    decryptor.process(&mut buffer);
+    
+    println!("Decrypted: {:?}", String::from_utf8_lossy(&buffer));
+    
+    if buffer == payload {
+        println!("\n[SUCCESS] Integrity Check Passed.");
+    } else {
+        println!("\n[FAIL] Decryption mismatch.");
+    }
+
+
+    println!("=== FLUTTER ENGINE: Bi-Octonion HD Wallet ===");
+
+    // 1. Setup Engine (Cosmological Constant)
+    let kappa = 0x1910;
+    let c_bytes = [0xAB; 16];
+    let engine = hdwallet::FlutterEngine::new(kappa, c_bytes);
+
+    // 2. Master Seed
+    let seed = hdwallet::MasterSeed { seed_bytes: [0x42; 32] };
+    
+    // 3. Derive Identity
+    println!("Deriving KeyPair #0...");
+    let kp = seed.derive_keypair(&engine, 0);
+    println!("Public Key (Z_final):\nLeft: {}\nRight: {}", kp.public_key.left, kp.public_key.right);
+
+    // 4. Sign Message
+    let msg = b"Octonions Rule The Vacuum";
+    println!("\nSigning Message: {:?}", String::from_utf8_lossy(msg));
+    let sig = kp.sign(&engine, msg);
+    println!("Signature Generated ({} Chain States)", sig.revealed_states.len());
+
+    // 5. Verify
+    let valid = hdwallet::verify(&engine, &kp.public_key, msg, &sig);
+    if valid {
+        println!("\n[SUCCESS] Signature Verified.");
+    } else {
+        println!("\n[FAIL] Verification Failed.");
+    }
+
+    // 7. Run GSH-256 Demo
+    println!("\n\n===========================================");
+    println!("=== GSH-256: Geometric Stiffness Hash ===");
+    println!("===========================================");
+    match gsh::GSH256::self_test() {
+        Ok(()) => println!("Self-test: {} known-answer vectors OK", gsh::GSH256_VECTORS.len()),
+        Err(f) => println!("Self-test FAILED on '{}'", f.label),
+    }
+    let input = b"The vacuum is empty.";
+    let hash = gsh::GSH256::hash_bytes(input);
+    println!("Input: {:?}", String::from_utf8_lossy(input));
+    println!("Hash: {}", hash);
+    let input = b"The vacuum is not empty, but merely highly conductive.";
+    let hash = gsh::GSH256::hash_bytes(input);
+    println!("Input: {:?}", String::from_utf8_lossy(input));
+    println!("Hash: {}", hash);
+    println!("GSH-128 (IoT profile): {}", gsh::hash_with::<gsh::GSH128>(input));
+
+    // 8. Run Synergeia Consensus Simulation
+    synergeia_sim::run_simulation(10_000);
 }
\ No newline at end of file
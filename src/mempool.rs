@@ -0,0 +1,666 @@
+// src/mempool.rs
+// MEMPOOL: Unconfirmed transactions waiting for a block.
+//
+// Policy, not consensus. Signature and witness checks stay with the
+// HorizonValidator; the pool only decides what it is willing to hold:
+//   * Generations: entries are bucketed by arrival time (generation_ms wide)
+//     and a whole generation expires once it is max_generations old.
+//   * Size: above max_bytes the lowest-feerate entries go first (with their
+//     descendants). A newcomer never evicts anything paying at least its
+//     rate, and never its own ancestors.
+//   * Replace-by-fee: a tx spending an input already spent in the pool must
+//     pay a strictly higher feerate than the one it replaces, cover the fees
+//     of everything it evicts plus its own relay cost, and evict at most
+//     max_replacements entries (the conflict and its descendants).
+//   * Packages: at most max_ancestors unconfirmed txs in a chain, and at
+//     most max_descendants hanging off any one ancestor.
+//   * Relay floor: min_relay_fee per byte. A parent below it can still get
+//     in as a package with a child paying for both (CPFP, accept_package),
+//     judged by the package's aggregate feerate.
+//   * Block templates pick ancestor packages by aggregate feerate, so the
+//     same parent+child pair is mined for what it pays together.
+// Every transaction has one output whose id is `Transaction::id()`, so a
+// child names its parent through `input_utxo.id`.
+// Time is passed in explicitly (ms), as in audit.rs.
+
+use crate::horizon::Transaction;
+use crate::wire::Message;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
+
+pub type TxId = [u8; 32];
+
+// --- CONFIGURATION ---
+#[derive(Clone, Debug)]
+pub struct MempoolConfig {
+    pub max_bytes: usize,
+    pub generation_ms: u64,
+    pub max_generations: u64,
+    pub max_ancestors: usize,    // Chain length, including the tx itself
+    pub max_descendants: usize,  // Per ancestor, including the ancestor
+    pub max_replacements: usize, // Entries one replacement may evict
+    pub incremental_fee: u64,    // Per byte a replacement pays on top
+    pub min_relay_fee: u64,      // Per byte, alone or as a package
+}
+
+impl Default for MempoolConfig {
+    fn default() -> Self {
+        MempoolConfig {
+            max_bytes: 4 * 1024 * 1024,
+            generation_ms: 60 * 60 * 1000,
+            max_generations: 24 * 14,
+            max_ancestors: 25,
+            max_descendants: 25,
+            max_replacements: 100,
+            incremental_fee: 1,
+            min_relay_fee: 1,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MempoolError {
+    AlreadyKnown,
+    NegativeFee,
+    TooLarge { size: usize, max: usize },
+    InsufficientFeerate { replacing: TxId },
+    InsufficientFee { required: u64, got: u64 },
+    TooManyReplacements { count: usize, max: usize },
+    TooManyAncestors { count: usize, max: usize },
+    TooManyDescendants { ancestor: TxId, count: usize, max: usize },
+    BelowMinFee { fee: u64, required: u64 },
+    MempoolFull,
+    // Package relay
+    PackageTooLarge { count: usize, max: usize },
+    PackageNotAChain { index: usize }, // txs[index] does not spend txs[index - 1]
+    PackageFeeTooLow { fee: u64, required: u64 },
+}
+
+// --- FEE RATES ---
+// Fee per byte kept as a fraction; compared by cross-multiplication.
+#[derive(Clone, Copy, Debug)]
+pub struct FeeRate {
+    pub fee: u64,
+    pub size: usize,
+}
+
+impl Ord for FeeRate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.fee as u128 * other.size as u128).cmp(&(other.fee as u128 * self.size as u128))
+    }
+}
+
+impl PartialOrd for FeeRate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for FeeRate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for FeeRate {}
+
+// --- ENTRIES ---
+#[derive(Clone, Debug)]
+pub struct MempoolEntry {
+    pub tx: Transaction,
+    pub fee: u64,
+    pub size: usize,
+    pub added_ms: u64,
+    pub fee_delta: i64, // Set by the prioritise RPC; counts for every policy
+}
+
+impl MempoolEntry {
+    pub fn modified_fee(&self) -> u64 {
+        (self.fee as i64).saturating_add(self.fee_delta).max(0) as u64
+    }
+
+    pub fn feerate(&self) -> FeeRate {
+        FeeRate { fee: self.modified_fee(), size: self.size }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Accepted {
+    pub txid: TxId,
+    pub replaced: Vec<TxId>, // RBF conflicts and their descendants
+    pub trimmed: Vec<TxId>,  // Evicted to make room
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockTemplate {
+    pub txids: Vec<TxId>, // Parents before children
+    pub total_fee: u64,   // Modified fees
+    pub total_bytes: usize,
+}
+
+// --- THE POOL ---
+#[derive(Clone)]
+pub struct Mempool {
+    pub config: MempoolConfig,
+    entries: BTreeMap<TxId, MempoolEntry>,
+    spends: BTreeMap<[u8; 32], TxId>, // Input UTXO id -> pool tx spending it
+    total_bytes: usize,
+}
+
+impl Mempool {
+    pub fn new(config: MempoolConfig) -> Self {
+        Mempool { config, entries: BTreeMap::new(), spends: BTreeMap::new(), total_bytes: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn total_bytes(&self) -> usize {
+        self.total_bytes
+    }
+
+    pub fn contains(&self, txid: &TxId) -> bool {
+        self.entries.contains_key(txid)
+    }
+
+    pub fn get(&self, txid: &TxId) -> Option<&MempoolEntry> {
+        self.entries.get(txid)
+    }
+
+    // Unconfirmed parents of `tx`, nearest first
+    fn ancestors_of(&self, tx: &Transaction) -> Vec<TxId> {
+        let mut out = Vec::new();
+        let mut next = tx.input_utxo.id;
+        while let Some(parent) = self.entries.get(&next) {
+            out.push(next);
+            next = parent.tx.input_utxo.id;
+        }
+        out
+    }
+
+    pub fn ancestors(&self, txid: &TxId) -> Vec<TxId> {
+        self.entries.get(txid).map(|e| self.ancestors_of(&e.tx)).unwrap_or_default()
+    }
+
+    // Every pool tx that (transitively) spends `txid`'s output
+    pub fn descendants(&self, txid: &TxId) -> Vec<TxId> {
+        let mut out = Vec::new();
+        let mut stack = vec![*txid];
+        while let Some(id) = stack.pop() {
+            if let Some(&child) = self.spends.get(&id) {
+                out.push(child);
+                stack.push(child);
+            }
+        }
+        out
+    }
+
+    pub fn accept(&mut self, tx: Transaction, now_ms: u64) -> Result<Accepted, MempoolError> {
+        self.accept_at_rate(tx, now_ms, None)
+    }
+
+    // `package_rate` stands in for the tx's own feerate in the relay floor
+    // and trim checks when it arrives as part of a package.
+    fn accept_at_rate(&mut self, tx: Transaction, now_ms: u64, package_rate: Option<FeeRate>) -> Result<Accepted, MempoolError> {
+        let txid = tx.id();
+        if self.entries.contains_key(&txid) {
+            return Err(MempoolError::AlreadyKnown);
+        }
+        let fee = tx.fee().ok_or(MempoolError::NegativeFee)?;
+        let size = tx.size_bytes();
+        if size > self.config.max_bytes {
+            return Err(MempoolError::TooLarge { size, max: self.config.max_bytes });
+        }
+        let rate = FeeRate { fee, size };
+        let policy_rate = package_rate.unwrap_or(rate);
+        let required = self.config.min_relay_fee * policy_rate.size as u64;
+        if policy_rate.fee < required {
+            return Err(MempoolError::BelowMinFee { fee: policy_rate.fee, required });
+        }
+
+        // 1. Replace-by-fee
+        let mut replaced = Vec::new();
+        if let Some(&conflict) = self.spends.get(&tx.input_utxo.id) {
+            if rate <= self.entries[&conflict].feerate() {
+                return Err(MempoolError::InsufficientFeerate { replacing: conflict });
+            }
+            replaced.push(conflict);
+            replaced.extend(self.descendants(&conflict));
+            if replaced.len() > self.config.max_replacements {
+                return Err(MempoolError::TooManyReplacements { count: replaced.len(), max: self.config.max_replacements });
+            }
+            let replaced_fees: u64 = replaced.iter().map(|id| self.entries[id].modified_fee()).sum();
+            let required = replaced_fees + self.config.incremental_fee * size as u64;
+            if fee < required {
+                return Err(MempoolError::InsufficientFee { required, got: fee });
+            }
+        }
+
+        // 2. Package limits (as the pool would look after the replacement)
+        let ancestors = self.ancestors_of(&tx);
+        if ancestors.len() + 1 > self.config.max_ancestors {
+            return Err(MempoolError::TooManyAncestors { count: ancestors.len() + 1, max: self.config.max_ancestors });
+        }
+        for ancestor in &ancestors {
+            let kept = self.descendants(ancestor).iter().filter(|d| !replaced.contains(d)).count();
+            // The ancestor itself, its remaining descendants and the newcomer
+            let count = kept + 2;
+            if count > self.config.max_descendants {
+                return Err(MempoolError::TooManyDescendants { ancestor: *ancestor, count, max: self.config.max_descendants });
+            }
+        }
+
+        // 3. Room: plan the trim before touching anything
+        let replaced_bytes: usize = replaced.iter().map(|id| self.entries[id].size).sum();
+        let need = (self.total_bytes - replaced_bytes + size).saturating_sub(self.config.max_bytes);
+        let trimmed = if need > 0 {
+            let mut protected: BTreeSet<TxId> = ancestors.into_iter().collect();
+            protected.extend(replaced.iter().copied());
+            self.eviction_plan(need, &protected, policy_rate).ok_or(MempoolError::MempoolFull)?
+        } else {
+            Vec::new()
+        };
+
+        for id in replaced.iter().chain(&trimmed) {
+            self.remove_entry(id);
+        }
+        self.spends.insert(tx.input_utxo.id, txid);
+        self.total_bytes += size;
+        self.entries.insert(txid, MempoolEntry { tx, fee, size, added_ms: now_ms, fee_delta: 0 });
+        Ok(Accepted { txid, replaced, trimmed })
+    }
+
+    // Aggregate feerate of a set of pool entries
+    fn package_rate(&self, ids: &[TxId]) -> FeeRate {
+        ids.iter().fold(FeeRate { fee: 0, size: 0 }, |acc, id| {
+            let e = &self.entries[id];
+            FeeRate { fee: acc.fee + e.modified_fee(), size: acc.size + e.size }
+        })
+    }
+
+    // What evicting `txid` costs the pool: its own rate, or that of the
+    // package it forms with its descendants if higher (a child paying for
+    // its parent keeps the parent from being trimmed first).
+    fn descendant_score(&self, txid: &TxId) -> FeeRate {
+        let own = self.entries[txid].feerate();
+        let package: Vec<TxId> = std::iter::once(*txid).chain(self.descendants(txid)).collect();
+        own.max(self.package_rate(&package))
+    }
+
+    // Lowest descendant score first, each victim with its descendants, until
+    // `need` bytes are free. None if that would touch anything paying >= `rate`.
+    fn eviction_plan(&self, need: usize, protected: &BTreeSet<TxId>, rate: FeeRate) -> Option<Vec<TxId>> {
+        let mut candidates: Vec<(TxId, FeeRate)> = self
+            .entries
+            .keys()
+            .filter(|id| !protected.contains(*id))
+            .map(|id| (*id, self.descendant_score(id)))
+            .collect();
+        candidates.sort_by_key(|(_, score)| *score);
+
+        let mut plan: Vec<TxId> = Vec::new();
+        let mut freed = 0;
+        for (id, score) in &candidates {
+            if freed >= need {
+                break;
+            }
+            if plan.contains(id) {
+                continue;
+            }
+            if *score >= rate {
+                return None;
+            }
+            for victim in std::iter::once(*id).chain(self.descendants(id)) {
+                if !plan.contains(&victim) {
+                    freed += self.entries[&victim].size;
+                    plan.push(victim);
+                }
+            }
+        }
+        (freed >= need).then_some(plan)
+    }
+
+    fn remove_entry(&mut self, txid: &TxId) -> Option<MempoolEntry> {
+        let entry = self.entries.remove(txid)?;
+        self.spends.remove(&entry.tx.input_utxo.id);
+        self.total_bytes -= entry.size;
+        Some(entry)
+    }
+
+    // Drop a tx (e.g. mined or invalidated) along with everything spending it
+    pub fn remove(&mut self, txid: &TxId) -> Vec<TxId> {
+        let mut removed = Vec::new();
+        if self.entries.contains_key(txid) {
+            for id in std::iter::once(*txid).chain(self.descendants(txid)) {
+                self.remove_entry(&id);
+                removed.push(id);
+            }
+        }
+        removed
+    }
+
+    // Expire every generation that is max_generations old or more
+    pub fn expire(&mut self, now_ms: u64) -> Vec<TxId> {
+        let generation_ms = self.config.generation_ms.max(1);
+        let current = now_ms / generation_ms;
+        let stale: Vec<TxId> = self
+            .entries
+            .iter()
+            .filter(|(_, e)| current.saturating_sub(e.added_ms / generation_ms) >= self.config.max_generations)
+            .map(|(id, _)| *id)
+            .collect();
+        stale.iter().flat_map(|id| self.remove(id)).collect()
+    }
+
+    // Highest modified feerate first
+    pub fn by_priority(&self) -> Vec<TxId> {
+        let mut ids: Vec<&TxId> = self.entries.keys().collect();
+        ids.sort_by(|a, b| self.entries[*b].feerate().cmp(&self.entries[*a].feerate()));
+        ids.into_iter().copied().collect()
+    }
+
+    pub fn prioritise(&mut self, txid: &TxId, fee_delta: i64) -> bool {
+        match self.entries.get_mut(txid) {
+            Some(entry) => {
+                entry.fee_delta = entry.fee_delta.saturating_add(fee_delta);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+// --- PACKAGES ---
+impl Mempool {
+    // A parent+child chain (parents first). Each member that pays the relay
+    // floor on its own goes in as usual; from the first one that does not,
+    // the rest is judged as one package by aggregate feerate. All or nothing.
+    pub fn accept_package(&mut self, txs: Vec<Transaction>, now_ms: u64) -> Result<Vec<Accepted>, MempoolError> {
+        if txs.len() > self.config.max_ancestors {
+            return Err(MempoolError::PackageTooLarge { count: txs.len(), max: self.config.max_ancestors });
+        }
+        if let Some(index) = (1..txs.len()).find(|&i| txs[i].input_utxo.id != txs[i - 1].id()) {
+            return Err(MempoolError::PackageNotAChain { index });
+        }
+
+        let mut trial = self.clone();
+        let mut accepted = Vec::new();
+        let mut rest = Vec::new();
+        for tx in txs.into_iter().filter(|tx| !self.contains(&tx.id())) {
+            if rest.is_empty() {
+                match trial.accept(tx.clone(), now_ms) {
+                    Ok(a) => {
+                        accepted.push(a);
+                        continue;
+                    }
+                    Err(MempoolError::BelowMinFee { .. }) => {}
+                    Err(e) => return Err(e),
+                }
+            }
+            rest.push(tx);
+        }
+
+        if !rest.is_empty() {
+            let mut rate = FeeRate { fee: 0, size: 0 };
+            for tx in &rest {
+                rate.fee += tx.fee().ok_or(MempoolError::NegativeFee)?;
+                rate.size += tx.size_bytes();
+            }
+            let required = self.config.min_relay_fee * rate.size as u64;
+            if rate.fee < required {
+                return Err(MempoolError::PackageFeeTooLow { fee: rate.fee, required });
+            }
+            for tx in rest {
+                accepted.push(trial.accept_at_rate(tx, now_ms, Some(rate))?);
+            }
+        }
+        *self = trial;
+        Ok(accepted)
+    }
+
+    // `txid` with its unconfirmed ancestors, parents first (relay answer)
+    pub fn package_for(&self, txid: &TxId) -> Vec<Transaction> {
+        if !self.contains(txid) {
+            return Vec::new();
+        }
+        let mut ids = self.ancestors(txid);
+        ids.reverse();
+        ids.push(*txid);
+        ids.iter().map(|id| self.entries[id].tx.clone()).collect()
+    }
+
+    // P2P package relay. Returns the reply to send back, if any.
+    pub fn on_message(&mut self, msg: &Message, now_ms: u64) -> Option<Result<Message, MempoolError>> {
+        match msg {
+            Message::GetPackage { txid } => Some(Ok(Message::Package(self.package_for(txid)))),
+            Message::Package(txs) => self.accept_package(txs.clone(), now_ms).err().map(Err),
+            _ => None,
+        }
+    }
+
+    // Greedy by ancestor-package feerate: repeatedly take the tx whose
+    // not-yet-selected ancestors plus itself pay the best aggregate rate.
+    pub fn block_template(&self, max_bytes: usize) -> BlockTemplate {
+        let mut template = BlockTemplate { txids: Vec::new(), total_fee: 0, total_bytes: 0 };
+        let mut selected: BTreeSet<TxId> = BTreeSet::new();
+        loop {
+            let mut best: Option<(FeeRate, Vec<TxId>)> = None;
+            for id in self.entries.keys().filter(|id| !selected.contains(*id)) {
+                let mut package: Vec<TxId> = self.ancestors(id).into_iter().filter(|a| !selected.contains(a)).collect();
+                package.reverse();
+                package.push(*id);
+                let rate = self.package_rate(&package);
+                if template.total_bytes + rate.size > max_bytes {
+                    continue;
+                }
+                if best.as_ref().is_none_or(|(r, _)| rate > *r) {
+                    best = Some((rate, package));
+                }
+            }
+            let Some((rate, package)) = best else { break };
+            template.total_fee += rate.fee;
+            template.total_bytes += rate.size;
+            selected.extend(package.iter().copied());
+            template.txids.extend(package);
+        }
+        template
+    }
+}
+
+// --- MEMPOOL RPC ---
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MempoolCall {
+    Info,
+    Entry { txid: TxId },
+    List, // In priority order
+    Prioritise { txid: TxId, fee_delta: i64 },
+    BlockTemplate { max_bytes: usize },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EntryInfo {
+    pub txid: TxId,
+    pub fee: u64,
+    pub modified_fee: u64,
+    pub size: usize,
+    pub added_ms: u64,
+    pub ancestors: usize,
+    pub descendants: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MempoolReply {
+    Info { count: usize, bytes: usize, max_bytes: usize },
+    Entry(Option<EntryInfo>),
+    List(Vec<EntryInfo>),
+    Prioritised(bool),
+    BlockTemplate(BlockTemplate),
+}
+
+impl Mempool {
+    fn entry_info(&self, txid: &TxId) -> Option<EntryInfo> {
+        let e = self.entries.get(txid)?;
+        Some(EntryInfo {
+            txid: *txid,
+            fee: e.fee,
+            modified_fee: e.modified_fee(),
+            size: e.size,
+            added_ms: e.added_ms,
+            ancestors: self.ancestors(txid).len(),
+            descendants: self.descendants(txid).len(),
+        })
+    }
+
+    pub fn handle(&mut self, call: &MempoolCall) -> MempoolReply {
+        match call {
+            MempoolCall::Info => {
+                MempoolReply::Info { count: self.len(), bytes: self.total_bytes, max_bytes: self.config.max_bytes }
+            }
+            MempoolCall::Entry { txid } => MempoolReply::Entry(self.entry_info(txid)),
+            MempoolCall::List => {
+                MempoolReply::List(self.by_priority().iter().filter_map(|id| self.entry_info(id)).collect())
+            }
+            MempoolCall::Prioritise { txid, fee_delta } => MempoolReply::Prioritised(self.prioritise(txid, *fee_delta)),
+            MempoolCall::BlockTemplate { max_bytes } => MempoolReply::BlockTemplate(self.block_template(*max_bytes)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::albert::AlbertElement;
+    use crate::horizon::{Utxo, Witness};
+    use crate::jordan_sig::{ParamSet, PublicKey, Signature, KEY_ELEMENTS, RESPONSE_ELEMENTS};
+
+    fn key(tag: u64) -> PublicKey {
+        let mut t = AlbertElement::zero();
+        t.set_alpha(tag);
+        PublicKey { t: [t; KEY_ELEMENTS], a: AlbertElement::zero(), params: ParamSet::JdLight }
+    }
+
+    // Spend `input_id` (worth `value`) leaving `fee`; `tag` varies the output
+    fn spend(input_id: [u8; 32], value: u64, fee: u64, tag: u64) -> Transaction {
+        Transaction {
+            input_utxo: Utxo::new(input_id, key(0).address(), value),
+            witness: Witness { siblings: Vec::new(), index: 0 },
+            owner_key: key(0),
+            signature: Signature { z: [AlbertElement::zero(); RESPONSE_ELEMENTS], c: [0; 32], params: ParamSet::JdLight },
+            new_owner: key(tag).address(),
+            new_amount: value - fee,
+        }
+    }
+
+    #[test]
+    fn replacement_edge_cases() {
+        let mut pool = Mempool::new(MempoolConfig { max_replacements: 2, ..MempoolConfig::default() });
+        let parent = spend([1; 32], 10_000, 300, 1);
+        let parent_id = pool.accept(parent.clone(), 0).unwrap().txid;
+        let child = spend(parent_id, 9_700, 300, 2);
+        let child_id = pool.accept(child.clone(), 0).unwrap().txid;
+        assert_eq!(pool.accept(child, 0), Err(MempoolError::AlreadyKnown));
+
+        // Same feerate as the original is not a replacement
+        assert_eq!(pool.accept(spend([1; 32], 10_000, 300, 3), 0), Err(MempoolError::InsufficientFeerate { replacing: parent_id }));
+        // Higher feerate, but not enough to pay for the evicted child too
+        assert_eq!(
+            pool.accept(spend([1; 32], 10_000, 500, 3), 0),
+            Err(MempoolError::InsufficientFee { required: 600 + 256, got: 500 })
+        );
+        // Prioritising the original raises the bar for its replacement
+        assert_eq!(pool.handle(&MempoolCall::Prioritise { txid: parent_id, fee_delta: 100 }), MempoolReply::Prioritised(true));
+        assert!(matches!(pool.accept(spend([1; 32], 10_000, 856, 3), 0), Err(MempoolError::InsufficientFee { required: 956, .. })));
+
+        let done = pool.accept(spend([1; 32], 10_000, 956, 3), 0).unwrap();
+        assert_eq!(done.replaced, vec![parent_id, child_id]);
+        assert_eq!(pool.len(), 1);
+
+        // Too much churn: a chain of three behind the conflict
+        let mut pool = Mempool::new(MempoolConfig { max_replacements: 2, min_relay_fee: 0, ..MempoolConfig::default() });
+        let mut input = [2; 32];
+        for tag in 0..3 {
+            input = pool.accept(spend(input, 10_000 - 100 * tag, 100, tag), 0).unwrap().txid;
+        }
+        assert_eq!(
+            pool.accept(spend([2; 32], 10_000, 5_000, 9), 0),
+            Err(MempoolError::TooManyReplacements { count: 3, max: 2 })
+        );
+        let mut overspend = spend([3; 32], 100, 0, 0);
+        overspend.new_amount = 200;
+        assert_eq!(pool.accept(overspend, 0), Err(MempoolError::NegativeFee));
+    }
+
+    #[test]
+    fn limits_generations_and_size_eviction() {
+        let config = MempoolConfig {
+            max_bytes: 3 * 256,
+            generation_ms: 1_000,
+            max_generations: 2,
+            max_ancestors: 2,
+            min_relay_fee: 0,
+            ..MempoolConfig::default()
+        };
+        let mut pool = Mempool::new(config);
+        let a = pool.accept(spend([1; 32], 10_000, 100, 0), 0).unwrap().txid;
+        let b = pool.accept(spend(a, 9_900, 300, 0), 1_500).unwrap().txid;
+        assert_eq!(pool.accept(spend(b, 9_600, 900, 0), 1_500), Err(MempoolError::TooManyAncestors { count: 3, max: 2 }));
+
+        // Full: a richer tx evicts the cheapest entry (and its child b), a
+        // poorer one is turned away
+        let c = pool.accept(spend([3; 32], 10_000, 220, 0), 1_500).unwrap().txid;
+        assert_eq!(pool.accept(spend([4; 32], 10_000, 50, 0), 1_500), Err(MempoolError::MempoolFull));
+        let d = pool.accept(spend([5; 32], 10_000, 250, 0), 1_500).unwrap();
+        assert_eq!(d.trimmed, vec![a, b]);
+        assert_eq!(pool.by_priority(), vec![d.txid, c]);
+        assert_eq!(pool.total_bytes(), 2 * 256);
+
+        // Generation 0 entries expire at generation 2; c and d arrived in generation 1
+        let e = pool.accept(spend([6; 32], 10_000, 10, 0), 500).unwrap().txid;
+        assert_eq!(pool.expire(2_000), vec![e]);
+        assert_eq!(pool.expire(2_999), Vec::<TxId>::new());
+        assert_eq!(pool.expire(3_000).len(), 2);
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    fn child_pays_for_parent_as_a_package() {
+        use crate::wire::Wire;
+
+        let mut pool = Mempool::new(MempoolConfig::default());
+        let parent = spend([1; 32], 10_000, 0, 1);
+        let child = spend(parent.id(), 10_000, 600, 2);
+        let (parent_id, child_id) = (parent.id(), child.id());
+        assert_eq!(pool.accept(parent.clone(), 0), Err(MempoolError::BelowMinFee { fee: 0, required: 256 }));
+
+        // Not enough for both, not a chain: nothing changes
+        let stingy = spend(parent.id(), 10_000, 200, 3);
+        assert_eq!(
+            pool.accept_package(vec![parent.clone(), stingy], 0),
+            Err(MempoolError::PackageFeeTooLow { fee: 200, required: 512 })
+        );
+        let stranger = spend([9; 32], 10_000, 600, 4);
+        assert_eq!(pool.accept_package(vec![parent.clone(), stranger], 0), Err(MempoolError::PackageNotAChain { index: 1 }));
+        assert!(pool.is_empty());
+
+        // Relayed over the wire, accepted together
+        let mut sender = Mempool::new(MempoolConfig { min_relay_fee: 0, ..MempoolConfig::default() });
+        sender.accept(parent, 0).unwrap();
+        sender.accept(child, 0).unwrap();
+        let reply = sender.on_message(&Message::GetPackage { txid: child_id }, 0).unwrap().unwrap();
+        let relayed = Message::from_bytes(&reply.to_bytes()).unwrap();
+        assert_eq!(pool.on_message(&relayed, 0), None);
+        assert_eq!(pool.ancestors(&child_id), vec![parent_id]);
+
+        // The template takes the pair for its 600 / 512 over a lone 280 / 256
+        let lone = pool.accept(spend([5; 32], 10_000, 280, 5), 0).unwrap().txid;
+        assert_eq!(pool.block_template(512).txids, vec![parent_id, child_id]);
+        let full = pool.block_template(4_096);
+        assert_eq!(full.txids, vec![parent_id, child_id, lone]);
+        assert_eq!((full.total_fee, full.total_bytes), (880, 768));
+    }
+}
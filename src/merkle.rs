@@ -0,0 +1,347 @@
+// src/merkle.rs
+// MERKLE TREES: Shared binary and sparse Merkle trees over a pluggable hash.
+//
+// Two digest flavours are provided:
+//   * GshHex - hex-string digests, parent = GSH-256(left_hex || right_hex).
+//              The encoding the header MMR has always used.
+//   * Gsh32  - 32-byte digests, parent = gsh::hash_two(left, right), i.e.
+//              GSH-256(left || right)[..32] without heap allocation. Used by
+//              the Horizon state tree and the STARK trace commitment.
+//
+// Both trees produce the same MerkleProof type (leaf index + sibling path,
+// bottom-up), and MultiProof shares interior nodes between several leaves.
+
+use crate::gsh::{self, GSH256};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+pub trait MerkleHash {
+    type Digest: Clone + Debug + PartialEq;
+
+    // Placeholder for absent leaves (sparse tree and padding)
+    fn empty_leaf() -> Self::Digest;
+    fn combine(left: &Self::Digest, right: &Self::Digest) -> Self::Digest;
+}
+
+pub struct GshHex;
+
+impl MerkleHash for GshHex {
+    type Digest = String;
+
+    fn empty_leaf() -> String {
+        "0".repeat(64)
+    }
+
+    fn combine(left: &String, right: &String) -> String {
+        GSH256::hash_bytes(&(left.clone() + right).into_bytes())
+    }
+}
+
+pub struct Gsh32;
+
+impl Gsh32 {
+    // Leaf digest for raw data
+    pub fn leaf(data: &[u8]) -> [u8; 32] {
+        let mut out = [0u8; 32];
+        out.copy_from_slice(&GSH256::hash_raw(data)[..32]);
+        out
+    }
+
+    pub fn to_hex(digest: &[u8; 32]) -> String {
+        digest.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    pub fn from_hex(hex: &str) -> Option<[u8; 32]> {
+        if hex.len() != 64 || !hex.is_ascii() {
+            return None;
+        }
+        let mut out = [0u8; 32];
+        for (i, b) in out.iter_mut().enumerate() {
+            *b = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok()?;
+        }
+        Some(out)
+    }
+}
+
+impl MerkleHash for Gsh32 {
+    type Digest = [u8; 32];
+
+    fn empty_leaf() -> [u8; 32] {
+        [0u8; 32]
+    }
+
+    fn combine(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        gsh::hash_two(left, right)
+    }
+}
+
+// --- PROOFS ---
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MerkleProof<D> {
+    pub index: u64,
+    pub siblings: Vec<D>, // Bottom-up
+}
+
+// Fold a leaf up its authentication path.
+pub fn root_from_path<H: MerkleHash>(leaf: &H::Digest, index: u64, siblings: &[H::Digest]) -> H::Digest {
+    let mut curr = leaf.clone();
+    let mut idx = index;
+    for sibling in siblings {
+        curr = if idx & 1 == 0 { H::combine(&curr, sibling) } else { H::combine(sibling, &curr) };
+        idx /= 2;
+    }
+    curr
+}
+
+impl<D: Clone + Debug + PartialEq> MerkleProof<D> {
+    pub fn verify<H: MerkleHash<Digest = D>>(&self, leaf: &D, root: &D) -> bool {
+        root_from_path::<H>(leaf, self.index, &self.siblings) == *root
+    }
+}
+
+// Proof for several leaves at once. `nodes` holds, level by level and in
+// ascending index order, every sibling that cannot be recomputed from the
+// proven leaves themselves.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MultiProof<D> {
+    pub indices: Vec<u64>, // Sorted, deduplicated
+    pub depth: usize,
+    pub nodes: Vec<D>,
+}
+
+impl<D: Clone + Debug + PartialEq> MultiProof<D> {
+    // `leaves` must be given in the order of `self.indices`.
+    pub fn verify<H: MerkleHash<Digest = D>>(&self, leaves: &[D], root: &D) -> bool {
+        if leaves.len() != self.indices.len() || self.indices.is_empty() {
+            return false;
+        }
+        let mut known: Vec<(u64, D)> = self.indices.iter().copied().zip(leaves.iter().cloned()).collect();
+        let mut nodes = self.nodes.iter();
+
+        for _ in 0..self.depth {
+            let mut next: Vec<(u64, D)> = Vec::with_capacity(known.len());
+            let mut i = 0;
+            while i < known.len() {
+                let (idx, ref digest) = known[i];
+                let parent = if i + 1 < known.len() && known[i + 1].0 == idx ^ 1 {
+                    // Both children known (idx is even here)
+                    i += 1;
+                    H::combine(digest, &known[i].1)
+                } else {
+                    let sibling = match nodes.next() {
+                        Some(s) => s,
+                        None => return false,
+                    };
+                    if idx & 1 == 0 { H::combine(digest, sibling) } else { H::combine(sibling, digest) }
+                };
+                next.push((idx / 2, parent));
+                i += 1;
+            }
+            known = next;
+        }
+        nodes.next().is_none() && known.len() == 1 && known[0].1 == *root
+    }
+}
+
+// Sibling indices a multiproof has to carry, level by level.
+fn multiproof_plan(indices: &[u64], depth: usize) -> Vec<(usize, u64)> {
+    let mut plan = Vec::new();
+    let mut known: BTreeSet<u64> = indices.iter().copied().collect();
+    for level in 0..depth {
+        for &idx in &known {
+            if !known.contains(&(idx ^ 1)) {
+                plan.push((level, idx ^ 1));
+            }
+        }
+        known = known.iter().map(|i| i / 2).collect();
+    }
+    plan
+}
+
+// --- DENSE BINARY TREE ---
+// Built once from a list of leaf digests; padded with empty leaves up to the
+// next power of two.
+pub struct MerkleTree<H: MerkleHash> {
+    levels: Vec<Vec<H::Digest>>, // levels[0] = leaves, last = [root]
+    leaf_count: usize,
+}
+
+impl<H: MerkleHash> MerkleTree<H> {
+    pub fn new(leaves: Vec<H::Digest>) -> Self {
+        let leaf_count = leaves.len();
+        let width = leaf_count.max(1).next_power_of_two();
+        let mut level = leaves;
+        level.resize(width, H::empty_leaf());
+
+        let mut levels = vec![level];
+        while levels.last().unwrap().len() > 1 {
+            let prev = levels.last().unwrap();
+            let next = prev.chunks(2).map(|pair| H::combine(&pair[0], &pair[1])).collect();
+            levels.push(next);
+        }
+        MerkleTree { levels, leaf_count }
+    }
+
+    pub fn root(&self) -> H::Digest {
+        self.levels.last().unwrap()[0].clone()
+    }
+
+    pub fn len(&self) -> usize {
+        self.leaf_count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.leaf_count == 0
+    }
+
+    pub fn depth(&self) -> usize {
+        self.levels.len() - 1
+    }
+
+    pub fn prove(&self, index: usize) -> Option<MerkleProof<H::Digest>> {
+        if index >= self.leaf_count {
+            return None;
+        }
+        let siblings = (0..self.depth())
+            .map(|level| self.levels[level][(index >> level) ^ 1].clone())
+            .collect();
+        Some(MerkleProof { index: index as u64, siblings })
+    }
+
+    pub fn prove_many(&self, indices: &[usize]) -> Option<MultiProof<H::Digest>> {
+        if indices.is_empty() || indices.iter().any(|&i| i >= self.leaf_count) {
+            return None;
+        }
+        let indices: Vec<u64> = indices.iter().map(|&i| i as u64).collect::<BTreeSet<_>>().into_iter().collect();
+        let nodes = multiproof_plan(&indices, self.depth())
+            .into_iter()
+            .map(|(level, idx)| self.levels[level][idx as usize].clone())
+            .collect();
+        Some(MultiProof { indices, depth: self.depth(), nodes })
+    }
+}
+
+// --- SPARSE TREE ---
+// Fixed-depth tree over a 2^depth index space; only touched nodes are stored,
+// everything else is the precomputed empty subtree of its height.
+pub struct SparseMerkleTree<H: MerkleHash> {
+    depth: usize,
+    nodes: HashMap<(usize, u64), H::Digest>, // (Level, Index) -> Digest
+    empty: Vec<H::Digest>,                   // empty[h] = root of an empty subtree of height h
+    _hash: PhantomData<H>,
+}
+
+impl<H: MerkleHash> SparseMerkleTree<H> {
+    pub fn new(depth: usize) -> Self {
+        assert!(depth <= 64, "index space is u64");
+        let mut empty = vec![H::empty_leaf()];
+        for h in 0..depth {
+            empty.push(H::combine(&empty[h], &empty[h]));
+        }
+        SparseMerkleTree { depth, nodes: HashMap::new(), empty, _hash: PhantomData }
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn root(&self) -> H::Digest {
+        self.node(self.depth, 0)
+    }
+
+    fn node(&self, level: usize, index: u64) -> H::Digest {
+        self.nodes.get(&(level, index)).cloned().unwrap_or_else(|| self.empty[level].clone())
+    }
+
+    pub fn leaf(&self, index: u64) -> H::Digest {
+        self.node(0, index)
+    }
+
+    // Every leaf that was ever written, including ones reset to empty
+    pub fn leaves(&self) -> impl Iterator<Item = (u64, &H::Digest)> {
+        self.nodes.iter().filter(|((level, _), _)| *level == 0).map(|((_, index), d)| (*index, d))
+    }
+
+    pub fn update(&mut self, index: u64, leaf: H::Digest) {
+        let mut idx = index;
+        let mut curr = leaf;
+        self.nodes.insert((0, idx), curr.clone());
+        for level in 0..self.depth {
+            let sibling = self.node(level, idx ^ 1);
+            curr = if idx & 1 == 0 { H::combine(&curr, &sibling) } else { H::combine(&sibling, &curr) };
+            idx /= 2;
+            self.nodes.insert((level + 1, idx), curr.clone());
+        }
+    }
+
+    pub fn remove(&mut self, index: u64) {
+        self.update(index, H::empty_leaf());
+    }
+
+    pub fn prove(&self, index: u64) -> MerkleProof<H::Digest> {
+        let siblings = (0..self.depth)
+            .map(|level| self.node(level, (index >> level) ^ 1))
+            .collect();
+        MerkleProof { index, siblings }
+    }
+
+    pub fn prove_many(&self, indices: &[u64]) -> Option<MultiProof<H::Digest>> {
+        if indices.is_empty() {
+            return None;
+        }
+        let indices: Vec<u64> = indices.iter().copied().collect::<BTreeSet<_>>().into_iter().collect();
+        let nodes = multiproof_plan(&indices, self.depth)
+            .into_iter()
+            .map(|(level, idx)| self.node(level, idx))
+            .collect();
+        Some(MultiProof { indices, depth: self.depth, nodes })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dense_tree_single_and_multi_proofs() {
+        let leaves: Vec<[u8; 32]> = (0..6u8).map(|i| Gsh32::leaf(&[i])).collect();
+        let tree = MerkleTree::<Gsh32>::new(leaves.clone());
+        let root = tree.root();
+        assert_eq!(tree.depth(), 3);
+
+        for (i, leaf) in leaves.iter().enumerate() {
+            let proof = tree.prove(i).unwrap();
+            assert!(proof.verify::<Gsh32>(leaf, &root));
+            assert!(!proof.verify::<Gsh32>(&Gsh32::leaf(b"x"), &root));
+        }
+        assert!(tree.prove(6).is_none());
+
+        let multi = tree.prove_many(&[4, 1, 0]).unwrap();
+        assert_eq!(multi.nodes.len(), 3); // 5, (2,3), (6,7) subtree
+        assert!(multi.verify::<Gsh32>(&[leaves[0], leaves[1], leaves[4]], &root));
+        assert!(!multi.verify::<Gsh32>(&[leaves[0], leaves[2], leaves[4]], &root));
+    }
+
+    #[test]
+    fn sparse_tree_proofs_and_removal() {
+        let mut tree = SparseMerkleTree::<GshHex>::new(16);
+        let empty_root = tree.root();
+        tree.update(3, "aa".into());
+        tree.update(40_000, "bb".into());
+        let root = tree.root();
+
+        let proof = tree.prove(40_000);
+        assert_eq!(proof.siblings.len(), 16);
+        assert!(proof.verify::<GshHex>(&"bb".to_string(), &root));
+        assert!(tree.prove(7).verify::<GshHex>(&GshHex::empty_leaf(), &root)); // Non-membership
+
+        let multi = tree.prove_many(&[3, 40_000]).unwrap();
+        assert!(multi.verify::<GshHex>(&["aa".to_string(), "bb".to_string()], &root));
+
+        tree.remove(3);
+        tree.remove(40_000);
+        assert_eq!(tree.root(), empty_root);
+    }
+}
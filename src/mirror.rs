@@ -0,0 +1,210 @@
+// src/mirror.rs
+// WATCH-ONLY MIRROR: The full Bulk for explorers, without trusting anyone.
+//
+// A mirror holds no keys and validates no transactions. It rebuilds the
+// Horizon accumulator purely from per-block StateDiffs and checks, block by
+// block, that the result hashes to the root committed in that block's
+// header (and that the headers themselves link up). The first diff that
+// does not reproduce its header root halts the mirror at the last good
+// height and names the offending block; the bad diff is rolled back, so
+// everything the mirror serves was proven consistent with the chain.
+//
+// Explorers read it through the indexer RPC (`IndexerCall` / `handle`).
+
+use crate::horizon::{HorizonAccumulator, StateDiff, Witness};
+use crate::horizon_net::BlockHeader;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MirrorError {
+    GenesisMismatch { expected: String, got: String },
+    OutOfOrder { expected: u64, got: u64 },
+    MissingHeader { height: u64 },
+    BrokenLinkage { height: u64 },
+    // The diff for `height` does not reproduce the header root
+    Divergence { height: u64, block_id: String, expected_root: String, computed_root: String },
+    Halted { height: u64 }, // Already diverged there; resync required
+}
+
+pub struct MirrorNode {
+    headers: Vec<BlockHeader>,
+    acc: HorizonAccumulator,
+    height: u64, // Last applied block
+    divergence: Option<MirrorError>,
+}
+
+impl MirrorNode {
+    // Start from the (empty) genesis state; headers[0] must commit to it.
+    pub fn new(genesis: BlockHeader) -> Result<Self, MirrorError> {
+        let acc = HorizonAccumulator::new();
+        if genesis.horizon_root != acc.root {
+            return Err(MirrorError::GenesisMismatch { expected: genesis.horizon_root, got: acc.root });
+        }
+        Ok(MirrorNode { headers: vec![genesis], acc, height: 0, divergence: None })
+    }
+
+    pub fn height(&self) -> u64 {
+        self.height
+    }
+
+    pub fn root(&self) -> &str {
+        &self.acc.root
+    }
+
+    pub fn divergence(&self) -> Option<&MirrorError> {
+        self.divergence.as_ref()
+    }
+
+    // Headers may run ahead of the diffs; linkage is checked on arrival.
+    pub fn add_header(&mut self, header: BlockHeader) -> Result<(), MirrorError> {
+        let height = self.headers.len() as u64;
+        if header.prev_hash != self.headers.last().unwrap().id() {
+            return Err(MirrorError::BrokenLinkage { height });
+        }
+        self.headers.push(header);
+        Ok(())
+    }
+
+    pub fn apply(&mut self, diff: &StateDiff) -> Result<(), MirrorError> {
+        if let Some(MirrorError::Divergence { height, .. }) = &self.divergence {
+            return Err(MirrorError::Halted { height: *height });
+        }
+        let expected = self.height + 1;
+        if diff.height != expected {
+            return Err(MirrorError::OutOfOrder { expected, got: diff.height });
+        }
+        let header = self.headers.get(expected as usize).ok_or(MirrorError::MissingHeader { height: expected })?;
+
+        let undo = self.acc.apply_diff(diff);
+        if self.acc.root != header.horizon_root {
+            let err = MirrorError::Divergence {
+                height: expected,
+                block_id: header.id(),
+                expected_root: header.horizon_root.clone(),
+                computed_root: self.acc.root.clone(),
+            };
+            self.acc.apply_diff(&undo);
+            self.divergence = Some(err.clone());
+            return Err(err);
+        }
+        self.height = expected;
+        Ok(())
+    }
+
+    // Catch up from a stream of diffs; stops at the first error.
+    pub fn sync(&mut self, diffs: &[StateDiff]) -> Result<u64, MirrorError> {
+        for diff in diffs {
+            self.apply(diff)?;
+        }
+        Ok(self.height)
+    }
+}
+
+// --- INDEXER RPC ---
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IndexerCall {
+    Status,
+    Leaf { index: u64 },
+    Witness { index: u64 },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IndexerReply {
+    Status { height: u64, root: String, divergence: Option<MirrorError> },
+    // Every answer carries the height and root it was proven against
+    Leaf { height: u64, root: String, leaf: [u8; 32] },
+    Witness { height: u64, root: String, witness: Witness },
+}
+
+impl MirrorNode {
+    pub fn handle(&self, call: &IndexerCall) -> IndexerReply {
+        let (height, root) = (self.height, self.acc.root.clone());
+        match *call {
+            IndexerCall::Status => IndexerReply::Status { height, root, divergence: self.divergence.clone() },
+            IndexerCall::Leaf { index } => IndexerReply::Leaf { height, root, leaf: self.acc.leaf(index) },
+            IndexerCall::Witness { index } => {
+                IndexerReply::Witness { height, root, witness: self.acc.generate_witness(index) }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::horizon::Utxo;
+    use crate::horizon_net::HorizonPeer;
+    use crate::jordan_sig::JordanSchnorr;
+    use crate::merkle::{self, Gsh32};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    // A producer chain of `blocks` blocks plus the diffs that built it
+    fn produce(blocks: u64) -> (HorizonPeer, Vec<StateDiff>) {
+        let mut rng = StdRng::seed_from_u64(3);
+        let owner = JordanSchnorr::keygen(&mut rng).pub_key;
+        let mut acc = HorizonAccumulator::new();
+        let mut peer = HorizonPeer::new(acc.root.clone());
+        let mut diffs = Vec::new();
+        for h in 1..=blocks {
+            let mut diff = StateDiff::new(h);
+            diff.insert(&Utxo::new([h as u8; 32], owner.address(), h), h * 10);
+            if h > 1 {
+                diff.remove((h - 1) * 10);
+            }
+            acc.apply_diff(&diff);
+            peer.mine_next_block(acc.root.clone(), 100);
+            diffs.push(diff);
+        }
+        (peer, diffs)
+    }
+
+    fn mirror_of(peer: &HorizonPeer) -> MirrorNode {
+        let mut mirror = MirrorNode::new(peer.chain[0].clone()).unwrap();
+        for header in &peer.chain[1..] {
+            mirror.add_header(header.clone()).unwrap();
+        }
+        mirror
+    }
+
+    #[test]
+    fn mirror_rebuilds_the_bulk_and_serves_proofs() {
+        let (peer, diffs) = produce(5);
+        let mut mirror = mirror_of(&peer);
+        assert_eq!(mirror.sync(&diffs), Ok(5));
+        assert_eq!(mirror.root(), peer.current_horizon);
+
+        match mirror.handle(&IndexerCall::Witness { index: 50 }) {
+            IndexerReply::Witness { height, root, witness } => {
+                assert_eq!(height, 5);
+                let IndexerReply::Leaf { leaf, .. } = mirror.handle(&IndexerCall::Leaf { index: 50 }) else {
+                    panic!("expected a leaf reply");
+                };
+                let computed = merkle::root_from_path::<Gsh32>(&leaf, 50, &witness.siblings);
+                assert_eq!(Gsh32::to_hex(&computed), root);
+            }
+            other => panic!("unexpected reply {:?}", other),
+        }
+    }
+
+    #[test]
+    fn tampered_diff_is_flagged_at_its_block() {
+        let (peer, mut diffs) = produce(4);
+        diffs[2].changes[0].1[0] ^= 1; // Block 3 serves a forged leaf
+        let mut mirror = mirror_of(&peer);
+
+        let err = mirror.sync(&diffs).unwrap_err();
+        match &err {
+            MirrorError::Divergence { height, block_id, expected_root, .. } => {
+                assert_eq!(*height, 3);
+                assert_eq!(*block_id, peer.chain[3].id());
+                assert_eq!(*expected_root, peer.chain[3].horizon_root);
+            }
+            other => panic!("unexpected error {:?}", other),
+        }
+        // Rolled back to the last proven state, and halted there
+        assert_eq!(mirror.height(), 2);
+        assert_eq!(mirror.root(), peer.chain[2].horizon_root);
+        assert_eq!(mirror.apply(&diffs[3]), Err(MirrorError::Halted { height: 3 }));
+        assert!(matches!(mirror.handle(&IndexerCall::Status), IndexerReply::Status { divergence: Some(_), .. }));
+    }
+}
@@ -0,0 +1,192 @@
+// src/mmr.rs
+// Merkle Mountain Range over GSH-256.
+//
+// Append-only commitment to an ever-growing list (block header ids).
+// Unlike the sparse tree in horizon.rs the MMR never rewrites old nodes:
+// appending a leaf only merges equal-height peaks, so the root after the
+// n-th header is a permanent commitment to headers [0, n).
+
+use crate::gsh::GSH256;
+use crate::merkle::{GshHex, MerkleHash};
+
+fn hash_pair(left: &str, right: &str) -> String {
+    GshHex::combine(&left.to_string(), &right.to_string())
+}
+
+// Bag the peaks (left = highest) into one root, binding the leaf count.
+fn bag_peaks(peaks: &[String], leaf_count: u64) -> String {
+    let mut acc = match peaks.last() {
+        Some(p) => p.clone(),
+        None => return GSH256::hash_bytes(b"MMR_EMPTY"),
+    };
+    for peak in peaks.iter().rev().skip(1) {
+        acc = hash_pair(peak, &acc);
+    }
+    GSH256::hash_bytes(format!("{}:{}", leaf_count, acc).as_bytes())
+}
+
+// Inclusion proof for one leaf: the path up to its peak plus every peak.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MmrProof {
+    pub leaf_index: u64,
+    pub leaf_count: u64,
+    pub siblings: Vec<String>,
+    pub peaks: Vec<String>,
+}
+
+impl MmrProof {
+    pub fn verify(&self, leaf: &str, root: &str) -> bool {
+        if self.leaf_index >= self.leaf_count {
+            return false;
+        }
+
+        // 1. Climb to the peak
+        let mut curr = leaf.to_string();
+        let mut idx = self.leaf_index;
+        for sibling in &self.siblings {
+            curr = if idx & 1 == 0 { hash_pair(&curr, sibling) } else { hash_pair(sibling, &curr) };
+            idx /= 2;
+        }
+
+        // 2. Locate which peak this leaf belongs to. Peaks are ordered by
+        // descending height; a peak of height h covers 2^h leaves.
+        let mut start = 0u64;
+        let mut peak_pos = None;
+        let mut pos = 0;
+        for h in (0..64).rev() {
+            if self.leaf_count & (1 << h) != 0 {
+                if self.leaf_index < start + (1 << h) {
+                    if self.siblings.len() != h {
+                        return false;
+                    }
+                    peak_pos = Some(pos);
+                    break;
+                }
+                start += 1 << h;
+                pos += 1;
+            }
+        }
+
+        match peak_pos {
+            Some(p) if self.peaks.get(p) == Some(&curr) => {
+                bag_peaks(&self.peaks, self.leaf_count) == root
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Mmr {
+    // levels[h][i] = node i at height h (only completed pairs are stored above 0)
+    levels: Vec<Vec<String>>,
+}
+
+impl Mmr {
+    pub fn new() -> Self {
+        Mmr { levels: Vec::new() }
+    }
+
+    pub fn len(&self) -> u64 {
+        self.levels.first().map_or(0, |l| l.len() as u64)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn append(&mut self, leaf: String) {
+        let mut node = leaf;
+        let mut h = 0;
+        loop {
+            if self.levels.len() == h {
+                self.levels.push(Vec::new());
+            }
+            self.levels[h].push(node);
+            let level = &self.levels[h];
+            if level.len() % 2 == 1 {
+                break;
+            }
+            node = hash_pair(&level[level.len() - 2], &level[level.len() - 1]);
+            h += 1;
+        }
+    }
+
+    // Peaks, left to right (descending height)
+    pub fn peaks(&self) -> Vec<String> {
+        let mut peaks = Vec::new();
+        for level in self.levels.iter().rev() {
+            if level.len() % 2 == 1 {
+                peaks.push(level.last().unwrap().clone());
+            }
+        }
+        peaks
+    }
+
+    pub fn root(&self) -> String {
+        bag_peaks(&self.peaks(), self.len())
+    }
+
+    pub fn prove(&self, leaf_index: u64) -> Option<MmrProof> {
+        if leaf_index >= self.len() {
+            return None;
+        }
+        let mut siblings = Vec::new();
+        let mut idx = leaf_index as usize;
+        for level in &self.levels {
+            let sibling = idx ^ 1;
+            // Stop at the peak: the sibling does not exist (yet)
+            if sibling >= level.len() {
+                break;
+            }
+            siblings.push(level[sibling].clone());
+            idx /= 2;
+        }
+        Some(MmrProof {
+            leaf_index,
+            leaf_count: self.len(),
+            siblings,
+            peaks: self.peaks(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proofs_verify_for_every_leaf() {
+        let mut mmr = Mmr::new();
+        for i in 0..11 {
+            mmr.append(format!("leaf{}", i));
+            let root = mmr.root();
+            for j in 0..=i {
+                let proof = mmr.prove(j).unwrap();
+                assert!(proof.verify(&format!("leaf{}", j), &root), "n={} leaf={}", i + 1, j);
+                assert!(!proof.verify("forged", &root));
+            }
+        }
+        assert_eq!(mmr.prove(11), None);
+    }
+
+    #[test]
+    fn proofs_bind_their_position_and_count() {
+        let mut mmr = Mmr::new();
+        for i in 0..11 {
+            mmr.append(format!("leaf{}", i));
+        }
+        let root = mmr.root();
+        let proof = mmr.prove(5).unwrap();
+        assert!(proof.verify("leaf5", &root));
+
+        // Sibling slot, same subtree, other peak, past the end
+        for leaf_index in [4, 6, 9, 11] {
+            assert!(!MmrProof { leaf_index, ..proof.clone() }.verify("leaf5", &root), "index {}", leaf_index);
+        }
+        // Same peaks under another count, and a count the root never had
+        for leaf_count in [10, 12, 5] {
+            assert!(!MmrProof { leaf_count, ..proof.clone() }.verify("leaf5", &root), "count {}", leaf_count);
+        }
+    }
+}
@@ -0,0 +1,349 @@
+// src/payment.rs
+// PAYMENT REQUESTS AND THE ADDRESS BOOK: what frontends exchange and keep.
+//
+// A payment URI is how a payee asks to be paid, BIP-21 style:
+//   olc:<bech32m address>?amount=<base units>&label=<text>&message=<text>
+// One scheme for every network; the address's hrp says which (address.rs),
+// and `parse_for` refuses a request meant for another network. Amounts are
+// whole base units, as everywhere in Horizon - no decimal point to misread.
+// Text is percent-encoded (RFC 3986, UTF-8); everything but the unreserved
+// characters is escaped on output. Unknown parameters are ignored unless
+// they start with "req-", which marks them as must-understand.
+//
+// The address book keeps contacts by the fingerprint of their Jordan key
+// (jordan_sig.rs), not by address: the key is what a contact hands over
+// (as a wallet descriptor) and what stays the same when names change, and
+// the address follows from it. It persists as one storage::Store namespace,
+// one entry per contact:
+//   <fingerprint>\t<descriptor>\t<name>
+// The descriptor's own checksum and the fingerprint are both checked on
+// load, so a hand-edited or damaged entry is refused rather than paid.
+//   v1: current
+
+use crate::address::{Address, AddressError, Network};
+use crate::jordan_sig::{Fingerprint, PublicKey};
+use crate::storage::{Store, StoreError};
+use crate::wallet::Descriptor;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+pub const URI_SCHEME: &str = "olc";
+pub const ADDRESS_BOOK_NAMESPACE: &str = "addressbook";
+pub const ADDRESS_BOOK_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UriError {
+    Scheme,
+    Address(AddressError),
+    WrongNetwork { expected: Network, got: Network },
+    BadAmount(String),
+    // Not valid %XX escapes, or not UTF-8 once unescaped
+    BadEscape,
+    DuplicateParam(String),
+    // A "req-" parameter this parser does not know
+    Required(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaymentRequest {
+    pub network: Network,
+    pub address: Address,
+    pub amount: Option<u64>,
+    pub label: Option<String>,
+    pub message: Option<String>,
+}
+
+impl PaymentRequest {
+    pub fn new(network: Network, address: Address) -> Self {
+        PaymentRequest { network, address, amount: None, label: None, message: None }
+    }
+
+    pub fn to_uri(&self) -> String {
+        let mut uri = format!("{}:{}", URI_SCHEME, self.address.to_bech32(self.network));
+        let mut params = Vec::new();
+        if let Some(amount) = self.amount {
+            params.push(format!("amount={}", amount));
+        }
+        if let Some(label) = &self.label {
+            params.push(format!("label={}", escape(label)));
+        }
+        if let Some(message) = &self.message {
+            params.push(format!("message={}", escape(message)));
+        }
+        if !params.is_empty() {
+            uri.push('?');
+            uri.push_str(&params.join("&"));
+        }
+        uri
+    }
+
+    pub fn parse(uri: &str) -> Result<Self, UriError> {
+        let uri = uri.trim();
+        let rest = match uri.split_once(':') {
+            Some((scheme, rest)) if scheme.eq_ignore_ascii_case(URI_SCHEME) => rest,
+            _ => return Err(UriError::Scheme),
+        };
+        let (address, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let (network, address) = Address::parse(address).map_err(UriError::Address)?;
+        let mut request = PaymentRequest::new(network, address);
+        for param in query.split('&').filter(|p| !p.is_empty()) {
+            let (key, value) = param.split_once('=').unwrap_or((param, ""));
+            let duplicate = || UriError::DuplicateParam(key.to_string());
+            match key {
+                "amount" => {
+                    let amount = value.parse().map_err(|_| UriError::BadAmount(value.to_string()))?;
+                    request.amount.replace(amount).map_or(Ok(()), |_| Err(duplicate()))?
+                }
+                "label" => request.label.replace(unescape(value)?).map_or(Ok(()), |_| Err(duplicate()))?,
+                "message" => request.message.replace(unescape(value)?).map_or(Ok(()), |_| Err(duplicate()))?,
+                _ if key.starts_with("req-") => return Err(UriError::Required(key.to_string())),
+                _ => {}
+            }
+        }
+        Ok(request)
+    }
+
+    /// A request for `network` only
+    pub fn parse_for(uri: &str, network: Network) -> Result<Self, UriError> {
+        let request = Self::parse(uri)?;
+        if request.network != network {
+            return Err(UriError::WrongNetwork { expected: network, got: request.network });
+        }
+        Ok(request)
+    }
+}
+
+impl fmt::Display for PaymentRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_uri())
+    }
+}
+
+impl FromStr for PaymentRequest {
+    type Err = UriError;
+
+    fn from_str(s: &str) -> Result<Self, UriError> {
+        Self::parse(s)
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => out.push(byte as char),
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
+
+fn unescape(text: &str) -> Result<String, UriError> {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut rest = text.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = tail.get(..2).filter(|h| h.iter().all(u8::is_ascii_hexdigit)).ok_or(UriError::BadEscape)?;
+            bytes.push(u8::from_str_radix(std::str::from_utf8(hex).unwrap(), 16).unwrap());
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| UriError::BadEscape)
+}
+
+// --- ADDRESS BOOK ---
+#[derive(Debug)]
+pub enum AddressBookError {
+    // Empty, or holding control characters (which the store cannot keep)
+    BadName,
+    UnknownContact(Fingerprint),
+    WrongVersion { got: u32 },
+    Corrupt { key: String },
+    Store(StoreError),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Contact {
+    pub name: String,
+    pub key: PublicKey,
+}
+
+impl Contact {
+    pub fn address(&self) -> Address {
+        self.key.address()
+    }
+
+    /// A request to pay this contact, labelled with their name
+    pub fn request(&self, network: Network, amount: Option<u64>) -> PaymentRequest {
+        PaymentRequest { amount, label: Some(self.name.clone()), ..PaymentRequest::new(network, self.address()) }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AddressBook {
+    contacts: BTreeMap<Fingerprint, Contact>,
+}
+
+impl AddressBook {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add or rename the contact behind `key`
+    pub fn insert(&mut self, name: &str, key: PublicKey) -> Result<Fingerprint, AddressBookError> {
+        let name = name.trim();
+        if name.is_empty() || name.chars().any(char::is_control) {
+            return Err(AddressBookError::BadName);
+        }
+        let fingerprint = key.fingerprint();
+        self.contacts.insert(fingerprint, Contact { name: name.to_string(), key });
+        Ok(fingerprint)
+    }
+
+    pub fn remove(&mut self, fingerprint: &Fingerprint) -> Result<Contact, AddressBookError> {
+        self.contacts.remove(fingerprint).ok_or(AddressBookError::UnknownContact(*fingerprint))
+    }
+
+    pub fn get(&self, fingerprint: &Fingerprint) -> Option<&Contact> {
+        self.contacts.get(fingerprint)
+    }
+
+    /// Who an address belongs to, e.g. to name the payee of a parsed request
+    pub fn find_address(&self, address: &Address) -> Option<(&Fingerprint, &Contact)> {
+        self.contacts.iter().find(|(_, c)| c.address() == *address)
+    }
+
+    pub fn find_name<'a>(&'a self, name: &'a str) -> impl Iterator<Item = (&'a Fingerprint, &'a Contact)> + 'a {
+        self.contacts.iter().filter(move |(_, c)| c.name == name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Fingerprint, &Contact)> {
+        self.contacts.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.contacts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.contacts.is_empty()
+    }
+
+    // --- PERSISTENCE ---
+    pub fn export(&self, store: &mut Store) {
+        let ns = store.namespace_mut(ADDRESS_BOOK_NAMESPACE, ADDRESS_BOOK_SCHEMA_VERSION);
+        ns.version = ADDRESS_BOOK_SCHEMA_VERSION;
+        ns.entries.clear();
+        for (fingerprint, contact) in &self.contacts {
+            ns.entries.insert(fingerprint.to_string(), format!("{}\t{}", Descriptor::Key(contact.key), contact.name));
+        }
+    }
+
+    pub fn import(store: &Store) -> Result<Self, AddressBookError> {
+        let mut book = AddressBook::new();
+        let Some(ns) = store.namespace(ADDRESS_BOOK_NAMESPACE) else { return Ok(book) };
+        if ns.version != ADDRESS_BOOK_SCHEMA_VERSION {
+            return Err(AddressBookError::WrongVersion { got: ns.version });
+        }
+        for (key, value) in &ns.entries {
+            let corrupt = || AddressBookError::Corrupt { key: key.clone() };
+            let (descriptor, name) = value.split_once('\t').ok_or_else(corrupt)?;
+            let Ok(Descriptor::Key(contact)) = Descriptor::parse(descriptor) else { return Err(corrupt()) };
+            if key.parse::<Fingerprint>().ok() != Some(contact.fingerprint()) {
+                return Err(corrupt());
+            }
+            book.insert(name, contact).map_err(|_| corrupt())?;
+        }
+        Ok(book)
+    }
+
+    /// The book in the store file at `path`; empty if there is no file yet
+    pub fn load(path: &Path) -> Result<Self, AddressBookError> {
+        if !path.exists() {
+            return Ok(AddressBook::new());
+        }
+        Self::import(&Store::load(path).map_err(AddressBookError::Store)?)
+    }
+
+    /// Write the book into the store file at `path`, keeping its other namespaces
+    pub fn save(&self, path: &Path) -> Result<(), AddressBookError> {
+        let mut store = if path.exists() { Store::load(path).map_err(AddressBookError::Store)? } else { Store::new() };
+        self.export(&mut store);
+        store.save(path).map_err(AddressBookError::Store)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jordan_sig::JordanSchnorr;
+    use rand::prelude::*;
+
+    #[test]
+    fn requests_round_trip_and_hostile_uris_are_refused() {
+        let address = Address([7; 32]);
+        let request = PaymentRequest {
+            amount: Some(1_500),
+            label: Some("Bob's café".into()),
+            message: Some("rent & bills, 100%".into()),
+            ..PaymentRequest::new(Network::Test, address)
+        };
+        let uri = request.to_uri();
+        assert!(uri.starts_with("olc:tolc1") && !uri.contains(' '));
+        assert_eq!(uri.parse(), Ok(request.clone()));
+        assert_eq!(PaymentRequest::parse_for(&uri, Network::Test), Ok(request));
+        assert_eq!(
+            PaymentRequest::parse_for(&uri, Network::Main),
+            Err(UriError::WrongNetwork { expected: Network::Main, got: Network::Test })
+        );
+
+        let bare = address.to_bech32(Network::Main);
+        assert_eq!(PaymentRequest::parse(&format!("OLC:{}?foo=bar", bare)), Ok(PaymentRequest::new(Network::Main, address)));
+        let bad = |query: &str| PaymentRequest::parse(&format!("olc:{}?{}", bare, query));
+        assert_eq!(bad("amount=1.5"), Err(UriError::BadAmount("1.5".into())));
+        assert_eq!(bad("amount=1&amount=2"), Err(UriError::DuplicateParam("amount".into())));
+        assert_eq!(bad("label=%4"), Err(UriError::BadEscape));
+        assert_eq!(bad("label=%+1"), Err(UriError::BadEscape));
+        assert_eq!(bad("label=%FF"), Err(UriError::BadEscape));
+        assert_eq!(bad("req-expires=10"), Err(UriError::Required("req-expires".into())));
+        assert_eq!(PaymentRequest::parse(&format!("bitcoin:{}", bare)), Err(UriError::Scheme));
+    }
+
+    #[test]
+    fn address_book_persists_by_fingerprint() {
+        let mut rng = StdRng::seed_from_u64(4343);
+        let (alice, bob) = (JordanSchnorr::keygen(&mut rng).pub_key, JordanSchnorr::keygen(&mut rng).pub_key);
+        let mut book = AddressBook::new();
+        let a = book.insert("Alice", alice).unwrap();
+        book.insert("Bob", bob).unwrap();
+        assert_eq!(book.insert("Alice", alice).unwrap(), a);
+        assert_eq!(book.len(), 2);
+        assert!(matches!(book.insert("Eve\tSmith", bob), Err(AddressBookError::BadName)));
+        assert_eq!(book.find_address(&alice.address()).map(|(f, _)| *f), Some(a));
+        let request = book.get(&a).unwrap().request(Network::Main, Some(10));
+        assert_eq!(request.label.as_deref(), Some("Alice"));
+
+        let dir = std::env::temp_dir().join(format!("olc-addressbook-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("book.store");
+        let mut other = Store::new();
+        other.namespace_mut("headers", 1).entries.insert("0".into(), "genesis".into());
+        other.save(&path).unwrap();
+        book.save(&path).unwrap();
+        assert_eq!(AddressBook::load(&path).unwrap(), book);
+        assert!(Store::load(&path).unwrap().namespace("headers").is_some());
+
+        // An entry whose fingerprint does not match its key
+        let mut store = Store::load(&path).unwrap();
+        let entries = &mut store.namespace_mut(ADDRESS_BOOK_NAMESPACE, 1).entries;
+        let value = entries.remove(&a.to_string()).unwrap();
+        entries.insert(bob.fingerprint().to_string(), value);
+        assert!(matches!(AddressBook::import(&store), Err(AddressBookError::Corrupt { .. })));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,231 @@
+// src/python.rs
+// PYTHON BINDINGS: The algebra layer as a PyO3 extension module.
+//
+// For prototyping only; Rust stays the reference. Elements cross the
+// boundary as flat lists of u64 coefficients, so numpy arrays go in
+// directly (np.uint64 entries extract as ints) and results come back
+// ready for np.array(...): an 8-vector per octonion, a 16-vector per
+// sedenion, an (iterations + 1) x 8 list for a VDF trace.
+//
+//   >>> import olc_research as olc
+//   >>> x = olc.Octonion.from_seed(1)
+//   >>> (x * x.inverse()).coeffs()
+//   [1, 0, 0, 0, 0, 0, 0, 0]
+
+// The #[pymethods] expansion trips this on every PyResult return
+#![allow(clippy::useless_conversion)]
+
+use crate::gsh::GSH256;
+use crate::jordan_sig::{self, JordanSchnorr};
+use crate::sedenion;
+use crate::vdf::{self, Fp};
+use pyo3::exceptions::{PyValueError, PyZeroDivisionError};
+use pyo3::prelude::*;
+use rand::rngs::{OsRng, StdRng};
+use rand::SeedableRng;
+
+fn fixed<const N: usize>(values: Vec<u64>) -> PyResult<[u64; N]> {
+    values
+        .try_into()
+        .map_err(|v: Vec<u64>| PyValueError::new_err(format!("expected {} coefficients, got {}", N, v.len())))
+}
+
+// Coefficients must already be reduced; silently taking them mod p would
+// hide an off-by-p in the caller's model
+fn octonion(values: Vec<u64>) -> PyResult<vdf::Octonion> {
+    let mut coeffs = [Fp::zero(); 8];
+    for (slot, value) in coeffs.iter_mut().zip(fixed::<8>(values)?) {
+        *slot = Fp::canonical(value).ok_or_else(|| PyValueError::new_err(format!("coefficient {value} is not below p = {}", vdf::P)))?;
+    }
+    Ok(vdf::Octonion::new(coeffs))
+}
+
+fn octonion_coeffs(o: &vdf::Octonion) -> Vec<u64> {
+    o.coeffs.iter().map(|c| c.0).collect()
+}
+
+// --- OCTONIONS OVER GOLDILOCKS ---
+#[pyclass(name = "Octonion", eq)]
+#[derive(Clone, PartialEq)]
+pub struct PyOctonion(vdf::Octonion);
+
+#[pymethods]
+impl PyOctonion {
+    #[new]
+    fn new(coeffs: Vec<u64>) -> PyResult<Self> {
+        Ok(PyOctonion(octonion(coeffs)?))
+    }
+
+    #[staticmethod]
+    fn from_seed(seed: u64) -> Self {
+        PyOctonion(vdf::Octonion::from_seed(seed))
+    }
+
+    fn coeffs(&self) -> Vec<u64> {
+        octonion_coeffs(&self.0)
+    }
+
+    fn conjugate(&self) -> Self {
+        PyOctonion(self.0.conjugate())
+    }
+
+    fn norm(&self) -> u64 {
+        self.0.norm().0
+    }
+
+    fn inverse(&self) -> PyResult<Self> {
+        self.0.inverse().map(PyOctonion).map_err(|_| PyZeroDivisionError::new_err("octonion has zero norm"))
+    }
+
+    fn __add__(&self, rhs: &Self) -> Self {
+        PyOctonion(self.0 + rhs.0)
+    }
+
+    fn __sub__(&self, rhs: &Self) -> Self {
+        PyOctonion(self.0 - rhs.0)
+    }
+
+    fn __mul__(&self, rhs: &Self) -> Self {
+        PyOctonion(self.0 * rhs.0)
+    }
+
+    fn __truediv__(&self, rhs: &Self) -> PyResult<Self> {
+        self.0.try_div(&rhs.0).map(PyOctonion).map_err(|_| PyZeroDivisionError::new_err("octonion has zero norm"))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Octonion({:?})", self.coeffs())
+    }
+}
+
+// --- SEDENIONS OVER Z_2^64 (the GSH ring) ---
+#[pyclass(name = "Sedenion", eq)]
+#[derive(Clone, PartialEq)]
+pub struct PySedenion(sedenion::Sedenion);
+
+#[pymethods]
+impl PySedenion {
+    #[new]
+    fn new(coeffs: Vec<u64>) -> PyResult<Self> {
+        let c = fixed::<16>(coeffs)?;
+        let (mut low, mut high) = ([0u64; 8], [0u64; 8]);
+        low.copy_from_slice(&c[..8]);
+        high.copy_from_slice(&c[8..]);
+        Ok(PySedenion(sedenion::Sedenion::new(sedenion::Octonion::new(low), sedenion::Octonion::new(high))))
+    }
+
+    fn coeffs(&self) -> Vec<u64> {
+        self.0.low.coeffs.iter().chain(&self.0.high.coeffs).copied().collect()
+    }
+
+    fn conjugate(&self) -> Self {
+        PySedenion(self.0.conjugate())
+    }
+
+    fn norm_sq(&self) -> u64 {
+        self.0.norm_sq()
+    }
+
+    fn is_zero_divisor(&self) -> bool {
+        self.0.is_zero_divisor()
+    }
+
+    fn __add__(&self, rhs: &Self) -> Self {
+        PySedenion(self.0 + rhs.0)
+    }
+
+    fn __sub__(&self, rhs: &Self) -> Self {
+        PySedenion(self.0 - rhs.0)
+    }
+
+    fn __mul__(&self, rhs: &Self) -> Self {
+        PySedenion(self.0 * rhs.0)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Sedenion({:?})", self.coeffs())
+    }
+}
+
+#[pyfunction]
+fn associator(x: &PySedenion, y: &PySedenion, z: &PySedenion) -> PySedenion {
+    PySedenion(sedenion::associator(x.0, y.0, z.0))
+}
+
+// --- VDF ---
+// One step Z -> Z^2 + C + [Z, C, H(Z)]
+#[pyfunction]
+fn vdf_step(z: Vec<u64>, c: Vec<u64>) -> PyResult<Vec<u64>> {
+    Ok(octonion_coeffs(&vdf::evaluate_vdf(octonion(z)?, octonion(c)?, 1).final_state))
+}
+
+#[pyfunction]
+fn vdf_trace(z: Vec<u64>, c: Vec<u64>, iterations: usize) -> PyResult<Vec<Vec<u64>>> {
+    let trace = vdf::evaluate_vdf(octonion(z)?, octonion(c)?, iterations).trace;
+    Ok(trace.iter().map(octonion_coeffs).collect())
+}
+
+// --- GSH-256 ---
+#[pyfunction]
+fn gsh256(data: &[u8]) -> String {
+    GSH256::hash_bytes(data)
+}
+
+// --- JORDAN SIGNATURES ---
+// Keys are seeded so that notebooks are reproducible; not for real keys.
+// Signing draws its nonce from the OS: a caller-chosen seed reused across
+// two messages would hand out the key.
+#[pyclass(name = "SecretKey")]
+#[derive(Clone)]
+pub struct PySecretKey(jordan_sig::SecretKey);
+
+#[pyclass(name = "PublicKey", eq)]
+#[derive(Clone, PartialEq)]
+pub struct PyPublicKey(jordan_sig::PublicKey);
+
+#[pyclass(name = "Signature", eq)]
+#[derive(Clone, PartialEq)]
+pub struct PySignature(jordan_sig::Signature);
+
+#[pymethods]
+impl PySecretKey {
+    #[staticmethod]
+    fn generate(seed: u64) -> Self {
+        PySecretKey(JordanSchnorr::keygen(&mut StdRng::seed_from_u64(seed)))
+    }
+
+    fn public_key(&self) -> PyPublicKey {
+        PyPublicKey(self.0.pub_key)
+    }
+
+    fn sign(&self, context: &[u8], msg: &[u8]) -> PyResult<PySignature> {
+        JordanSchnorr::sign(&self.0, context, msg, &mut OsRng)
+            .map(PySignature)
+            .map_err(|e| PyValueError::new_err(format!("signing failed: {e:?}")))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
+
+#[pymethods]
+impl PyPublicKey {
+    fn verify(&self, context: &[u8], msg: &[u8], sig: &PySignature) -> bool {
+        JordanSchnorr::verify(&self.0, context, msg, &sig.0).is_ok()
+    }
+}
+
+#[pymodule]
+fn olc_research(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyOctonion>()?;
+    m.add_class::<PySedenion>()?;
+    m.add_class::<PySecretKey>()?;
+    m.add_class::<PyPublicKey>()?;
+    m.add_class::<PySignature>()?;
+    m.add_function(wrap_pyfunction!(associator, m)?)?;
+    m.add_function(wrap_pyfunction!(vdf_step, m)?)?;
+    m.add_function(wrap_pyfunction!(vdf_trace, m)?)?;
+    m.add_function(wrap_pyfunction!(gsh256, m)?)?;
+    Ok(())
+}
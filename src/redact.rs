@@ -0,0 +1,72 @@
+// src/redact.rs
+// Secret-aware formatting helpers.
+//
+// Secret material (Albert secrets, master seeds, cipher state) must never end
+// up in logs through a stray `{:?}`. Types holding secrets implement Debug by
+// hand and route every secret field through `Redacted`, which prints only the
+// length and a short SHA-256 fingerprint. The fingerprint lets two log lines be
+// correlated ("same seed?") without revealing anything useful.
+//
+// Building with the `reveal-secrets` feature prints the raw bytes instead.
+// That feature exists for local diagnostics and tests only; never enable it in
+// a deployed node or wallet.
+//
+// Wiping is the zeroize crate's: Zeroize overwrites a value through
+// volatile writes the optimizer may not drop, and ZeroizeOnDrop marks a
+// type whose Drop impl does so. Modules holding secrets pin the latter with
+// `const _: () = assert_zeroize_on_drop::<T>();`, so removing the Drop
+// fails to compile. AlbertElement is Copy: code handling secrets keeps each
+// temporary in one named binding and zeroizes that binding once done.
+
+use sha2::{Digest, Sha256};
+use std::fmt;
+use zeroize::ZeroizeOnDrop;
+
+// Compile-time check for types that must wipe themselves
+pub const fn assert_zeroize_on_drop<T: ZeroizeOnDrop>() {}
+
+pub struct Redacted<'a>(pub &'a [u8]);
+
+impl Redacted<'_> {
+    // First 4 bytes of SHA-256, hex encoded
+    pub fn fingerprint(&self) -> String {
+        let digest = Sha256::digest(self.0);
+        digest[..4].iter().map(|b| format!("{:02x}", b)).collect()
+    }
+}
+
+impl fmt::Debug for Redacted<'_> {
+    #[cfg(not(feature = "reveal-secrets"))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<redacted {} bytes, fp={}>", self.0.len(), self.fingerprint())
+    }
+
+    #[cfg(feature = "reveal-secrets")]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02x?}", self.0)
+    }
+}
+
+// Little-endian byte view of u16 coefficient arrays (Flutter octonions)
+pub fn u16_bytes(coeffs: &[u16]) -> Vec<u8> {
+    coeffs.iter().flat_map(|c| c.to_le_bytes()).collect()
+}
+
+// Little-endian byte view of u64 coefficient arrays (Albert elements)
+pub fn u64_bytes(coeffs: &[u64]) -> Vec<u8> {
+    coeffs.iter().flat_map(|c| c.to_le_bytes()).collect()
+}
+
+#[cfg(all(test, not(feature = "reveal-secrets")))]
+mod tests {
+    use crate::hdwallet::MasterSeed;
+
+    #[test]
+    fn debug_output_hides_seed_bytes() {
+        let seed = MasterSeed { seed_bytes: [0x42; 32] };
+        let out = format!("{:?}", seed);
+        assert!(out.contains("<redacted 32 bytes, fp="));
+        assert!(!out.contains("42, 42"));
+        assert!(!out.contains("66, 66"));
+    }
+}
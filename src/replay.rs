@@ -0,0 +1,347 @@
+// src/replay.rs
+// DETERMINISTIC REPLAY: Record and replay node sessions.
+//
+// Every input that enters a node from outside - peer header announcements,
+// RPC calls, wall-clock reads and the RNG seed - goes through a ReplayNode.
+// In record mode the node pulls them from the live environment and appends
+// them to a Journal; in replay mode the same Journal is fed back in order,
+// so the session (fork choices, mined headers, generated keys) is reproduced
+// bit-for-bit. Journals are plain text and can be attached to bug reports:
+//
+//   olc replay session.journal
+
+use crate::horizon_net::{BlockHeader, HorizonPeer, NetworkBootstrapper, SyncOutcome};
+use crate::jordan_sig::{JordanSchnorr, SecretKey};
+use crate::vdf::{Fp, Octonion};
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// --- JOURNAL ---
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RpcCall {
+    Mine { horizon_root: String, difficulty: u64 },
+    NewAddress,
+    TipHeight,
+}
+
+// One external input. Peer ids must not contain whitespace.
+#[derive(Clone, Debug)]
+pub enum JournalEntry {
+    Genesis(String),
+    Seed(u64),
+    Clock(u64),
+    PeerHeaders { peer: String, headers: Vec<BlockHeader> },
+    Rpc(RpcCall),
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Journal {
+    pub entries: Vec<JournalEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JournalError {
+    pub line: usize,
+    pub reason: String,
+}
+
+impl Journal {
+    // Line-based text format; headers follow their `headers` line.
+    pub fn encode(&self) -> String {
+        let mut out = String::new();
+        for entry in &self.entries {
+            match entry {
+                JournalEntry::Genesis(root) => out += &format!("genesis {}\n", root),
+                JournalEntry::Seed(seed) => out += &format!("seed {}\n", seed),
+                JournalEntry::Clock(t) => out += &format!("clock {}\n", t),
+                JournalEntry::PeerHeaders { peer, headers } => {
+                    out += &format!("headers {} {}\n", peer, headers.len());
+                    for h in headers {
+                        let proof: Vec<String> = h.vdf_proof.coeffs.iter().map(|c| c.0.to_string()).collect();
+                        out += &format!("h {} {} {} {} {}\n",
+                            h.prev_hash, h.horizon_root, proof.join(","), h.vdf_iterations, h.timestamp);
+                    }
+                }
+                JournalEntry::Rpc(RpcCall::Mine { horizon_root, difficulty }) => {
+                    out += &format!("rpc mine {} {}\n", horizon_root, difficulty)
+                }
+                JournalEntry::Rpc(RpcCall::NewAddress) => out += "rpc new_address\n",
+                JournalEntry::Rpc(RpcCall::TipHeight) => out += "rpc tip_height\n",
+            }
+        }
+        out
+    }
+
+    pub fn decode(text: &str) -> Result<Journal, JournalError> {
+        let mut entries = Vec::new();
+        let mut lines = text.lines().enumerate().filter(|(_, l)| !l.trim().is_empty());
+
+        while let Some((n, line)) = lines.next() {
+            let line_no = n + 1;
+            let err = |reason: &str| JournalError { line: line_no, reason: reason.to_string() };
+            let fields: Vec<&str> = line.split_whitespace().collect();
+
+            let entry = match fields.as_slice() {
+                ["genesis", root] => JournalEntry::Genesis(root.to_string()),
+                ["seed", seed] => JournalEntry::Seed(seed.parse().map_err(|_| err("bad seed"))?),
+                ["clock", t] => JournalEntry::Clock(t.parse().map_err(|_| err("bad clock"))?),
+                ["headers", peer, count] => {
+                    let count: usize = count.parse().map_err(|_| err("bad header count"))?;
+                    // The count is untrusted: reserve no more than the rest of the text can hold
+                    let mut headers = Vec::with_capacity(count.min(bytes_after(text, line) / MIN_HEADER_LINE));
+                    for _ in 0..count {
+                        let (n, line) = lines.next().ok_or_else(|| err("truncated header list"))?;
+                        headers.push(decode_header(line).ok_or(JournalError {
+                            line: n + 1,
+                            reason: "bad header".to_string(),
+                        })?);
+                    }
+                    JournalEntry::PeerHeaders { peer: peer.to_string(), headers }
+                }
+                ["rpc", "mine", root, difficulty] => JournalEntry::Rpc(RpcCall::Mine {
+                    horizon_root: root.to_string(),
+                    difficulty: difficulty.parse().map_err(|_| err("bad difficulty"))?,
+                }),
+                ["rpc", "new_address"] => JournalEntry::Rpc(RpcCall::NewAddress),
+                ["rpc", "tip_height"] => JournalEntry::Rpc(RpcCall::TipHeight),
+                _ => return Err(err("unknown entry")),
+            };
+            entries.push(entry);
+        }
+        Ok(Journal { entries })
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(path, self.encode())
+    }
+
+    pub fn load(path: &Path) -> std::io::Result<Result<Journal, JournalError>> {
+        Ok(Journal::decode(&std::fs::read_to_string(path)?))
+    }
+}
+
+// "h p r 0,0,0,0,0,0,0,0 0 0\n": one-character hashes, zero coefficients
+const MIN_HEADER_LINE: usize = 26;
+
+// Bytes of `text` after `line`, which is one of text.lines()
+fn bytes_after(text: &str, line: &str) -> usize {
+    let start = line.as_ptr() as usize - text.as_ptr() as usize;
+    text.len() - start - line.len()
+}
+
+fn decode_header(line: &str) -> Option<BlockHeader> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let ["h", prev_hash, horizon_root, proof, iterations, timestamp] = fields.as_slice() else {
+        return None;
+    };
+    let mut coeffs = [Fp::zero(); 8];
+    let parts: Vec<&str> = proof.split(',').collect();
+    if parts.len() != 8 {
+        return None;
+    }
+    for i in 0..8 {
+        coeffs[i] = Fp(parts[i].parse().ok()?);
+    }
+    Some(BlockHeader {
+        prev_hash: prev_hash.to_string(),
+        horizon_root: horizon_root.to_string(),
+        vdf_proof: Octonion::new(coeffs),
+        vdf_iterations: iterations.parse().ok()?,
+        timestamp: timestamp.parse().ok()?,
+    })
+}
+
+// --- THE NODE ---
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Synced(SyncOutcome),
+    Mined { height: usize, id: String },
+    Address(String), // Fingerprint of the new public key (bech32)
+    TipHeight(usize),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReplayError {
+    MissingPreamble,
+    Exhausted { wanted: &'static str },
+    UnexpectedEntry { index: usize, wanted: &'static str },
+}
+
+enum Mode {
+    Record,
+    Replay { cursor: usize },
+}
+
+pub struct ReplayNode {
+    pub peer: HorizonPeer,
+    pub keys: Vec<SecretKey>,
+    pub last_seen: HashMap<String, u64>, // Peer -> clock at last announcement
+    pub outcomes: Vec<Outcome>,
+    rng: StdRng,
+    journal: Journal,
+    mode: Mode,
+}
+
+impl ReplayNode {
+    // Start a live session; the RNG seed is drawn from OS entropy and journaled.
+    pub fn record(genesis_root: String) -> Self {
+        let seed = rand::thread_rng().gen();
+        let journal = Journal {
+            entries: vec![JournalEntry::Genesis(genesis_root.clone()), JournalEntry::Seed(seed)],
+        };
+        ReplayNode::start(genesis_root, seed, journal, Mode::Record)
+    }
+
+    // Re-run a recorded session from start to finish.
+    pub fn replay(journal: &Journal) -> Result<ReplayNode, ReplayError> {
+        let (genesis, seed) = match journal.entries.as_slice() {
+            [JournalEntry::Genesis(root), JournalEntry::Seed(seed), ..] => (root.clone(), *seed),
+            _ => return Err(ReplayError::MissingPreamble),
+        };
+        let mut node = ReplayNode::start(genesis, seed, journal.clone(), Mode::Replay { cursor: 2 });
+
+        while let Some(index) = node.cursor() {
+            let entry = match node.journal.entries.get(index) {
+                Some(entry) => entry.clone(),
+                None => break,
+            };
+            node.mode = Mode::Replay { cursor: index + 1 };
+            match entry {
+                JournalEntry::PeerHeaders { peer, headers } => {
+                    node.deliver_headers(&peer, &headers)?;
+                }
+                JournalEntry::Rpc(call) => {
+                    node.rpc(call)?;
+                }
+                _ => return Err(ReplayError::UnexpectedEntry { index, wanted: "peer message or rpc" }),
+            }
+        }
+        Ok(node)
+    }
+
+    fn start(genesis_root: String, seed: u64, journal: Journal, mode: Mode) -> Self {
+        ReplayNode {
+            peer: HorizonPeer::new(genesis_root),
+            keys: Vec::new(),
+            last_seen: HashMap::new(),
+            outcomes: Vec::new(),
+            rng: StdRng::seed_from_u64(seed),
+            journal,
+            mode,
+        }
+    }
+
+    pub fn journal(&self) -> &Journal {
+        &self.journal
+    }
+
+    fn cursor(&self) -> Option<usize> {
+        match self.mode {
+            Mode::Record => None,
+            Mode::Replay { cursor } => Some(cursor),
+        }
+    }
+
+    // Record mode only: the replay driver has already consumed the entry.
+    fn log(&mut self, entry: JournalEntry) {
+        if let Mode::Record = self.mode {
+            self.journal.entries.push(entry);
+        }
+    }
+
+    // Wall-clock read (milliseconds since the Unix epoch).
+    pub fn clock(&mut self) -> Result<u64, ReplayError> {
+        match self.mode {
+            Mode::Record => {
+                let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_millis() as u64);
+                self.journal.entries.push(JournalEntry::Clock(now));
+                Ok(now)
+            }
+            Mode::Replay { cursor } => match self.journal.entries.get(cursor) {
+                Some(JournalEntry::Clock(t)) => {
+                    self.mode = Mode::Replay { cursor: cursor + 1 };
+                    Ok(*t)
+                }
+                Some(_) => Err(ReplayError::UnexpectedEntry { index: cursor, wanted: "clock" }),
+                None => Err(ReplayError::Exhausted { wanted: "clock" }),
+            },
+        }
+    }
+
+    // A peer announced its header chain.
+    pub fn deliver_headers(&mut self, peer: &str, headers: &[BlockHeader]) -> Result<SyncOutcome, ReplayError> {
+        self.log(JournalEntry::PeerHeaders { peer: peer.to_string(), headers: headers.to_vec() });
+        let now = self.clock()?;
+        self.last_seen.insert(peer.to_string(), now);
+
+        let outcome = NetworkBootstrapper::try_sync(&mut self.peer, headers);
+        self.outcomes.push(Outcome::Synced(outcome.clone()));
+        Ok(outcome)
+    }
+
+    pub fn rpc(&mut self, call: RpcCall) -> Result<Outcome, ReplayError> {
+        self.log(JournalEntry::Rpc(call.clone()));
+        let outcome = match call {
+            RpcCall::Mine { horizon_root, difficulty } => {
+                self.peer.mine_next_block(horizon_root, difficulty);
+                Outcome::Mined { height: self.peer.chain.len() - 1, id: self.peer.chain.last().unwrap().id() }
+            }
+            RpcCall::NewAddress => {
+                let key = JordanSchnorr::keygen(&mut self.rng);
+                let fingerprint = key.pub_key.fingerprint().to_string();
+                self.keys.push(key);
+                Outcome::Address(fingerprint)
+            }
+            RpcCall::TipHeight => Outcome::TipHeight(self.peer.chain.len() - 1),
+        };
+        self.outcomes.push(outcome.clone());
+        Ok(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replayed_session_matches_recording() {
+        let mut remote = HorizonPeer::new("GENESIS".to_string());
+        for i in 0..3 {
+            remote.mine_next_block(format!("ROOT_{}", i), 500);
+        }
+
+        let mut live = ReplayNode::record("GENESIS".to_string());
+        live.rpc(RpcCall::NewAddress).unwrap();
+        live.rpc(RpcCall::Mine { horizon_root: "LOCAL".to_string(), difficulty: 100 }).unwrap();
+        live.deliver_headers("10.0.0.7:9000", &remote.chain).unwrap();
+        live.rpc(RpcCall::NewAddress).unwrap();
+        live.rpc(RpcCall::TipHeight).unwrap();
+
+        // Round-trip through the on-disk format
+        let journal = Journal::decode(&live.journal().encode()).unwrap();
+        let replayed = ReplayNode::replay(&journal).unwrap();
+
+        assert_eq!(replayed.outcomes, live.outcomes);
+        assert_eq!(replayed.last_seen, live.last_seen);
+        assert_eq!(replayed.peer.current_horizon, "ROOT_2");
+        assert_eq!(replayed.peer.chain.last().unwrap().id(), live.peer.chain.last().unwrap().id());
+    }
+
+    #[test]
+    fn truncated_journal_is_reported() {
+        let mut live = ReplayNode::record("GENESIS".to_string());
+        live.deliver_headers("peer", &HorizonPeer::new("GENESIS".to_string()).chain).unwrap();
+
+        let mut journal = live.journal().clone();
+        journal.entries.pop(); // Drop the clock read
+        assert_eq!(ReplayNode::replay(&journal).err(), Some(ReplayError::Exhausted { wanted: "clock" }));
+        assert_eq!(Journal::decode("headers p 2\nh x").unwrap_err().line, 2);
+        // A huge count reserves nothing up front
+        assert_eq!(Journal::decode(&format!("headers p {}\n", usize::MAX)).unwrap_err().reason, "truncated header list");
+    }
+}
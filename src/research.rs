@@ -0,0 +1,15 @@
+// src/research.rs
+// RESEARCH: Experimental constructions. NOT FOR PRODUCTION.
+//
+// Only compiled with the `research` Cargo feature. Nothing in the node,
+// wallet, hashes or ciphers depends on this module, and nothing here has a
+// security argument behind it: these are candidate hardness assumptions
+// built on the non-associative structure, shipped together with the attacks
+// that try to break them so a claim can be falsified inside the crate
+// (`olc research` runs them all).
+//
+//   octo_dh  - candidate one-way functions (conjugation, bracketing)
+//   attacks  - linearization and small-parameter brute force against them
+
+pub mod octo_dh;
+pub mod attacks;
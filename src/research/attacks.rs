@@ -0,0 +1,128 @@
+// src/research/attacks.rs
+// ATTACKS on the research candidates. Experimental, not for production.
+//
+// A candidate survives only while every attack here comes back empty-handed.
+//
+// - Linearization (conjugation DH): g^a lies in span{1, g}, since every
+//   octonion satisfies g^2 = 2 Re(g) g - N(g). So any invertible
+//   h = u + v g with h x = A h does the job of g^a: it commutes with g, and
+//   h B h^-1 = g^(a+b) x g^-(a+b). The equation is linear in (u, v), so
+//   the key is recovered without ever learning a.
+// - Brute force (conjugation DH): walk g^e x g^-e for e up to a bound.
+// - Brute force (bracketing): evaluate all Catalan(n - 1) trees and keep
+//   every one that matches; more than one match breaks binding.
+
+use super::octo_dh::{bracket_commit, bracket_elements, inverse, inverse_mod_2_16, Bracketing, ConjugationParams, Octonion};
+use std::num::NonZeroUsize;
+
+// Solve u d1 + v d2 = 0 with one of (u, v) fixed to 1. A lane with an odd
+// coefficient pivots directly; if every lane is even the solution line is
+// short enough (2^16 points) to scan.
+fn solve_line(d1: &Octonion, d2: &Octonion) -> Option<u16> {
+    if let Some(i) = (0..8).find(|&i| d1.coeffs[i] & 1 == 1) {
+        let u = d2.coeffs[i].wrapping_neg().wrapping_mul(inverse_mod_2_16(d1.coeffs[i]));
+        return (d1.scale(u) + *d2).is_zero().then_some(u);
+    }
+    (0..=u16::MAX).find(|&u| (d1.scale(u) + *d2).is_zero())
+}
+
+// Find h in span{1, g} with h x h^-1 = public.
+pub fn linearize_conjugation(params: &ConjugationParams, public: &Octonion) -> Option<Octonion> {
+    let d1 = params.x - *public;
+    let d2 = params.g * params.x - *public * params.g;
+    // h = u + g (v = 1), then h = 1 + v g (u = 1)
+    let candidates = [
+        solve_line(&d1, &d2).map(|u| Octonion::from_real(u) + params.g),
+        solve_line(&d2, &d1).map(|v| Octonion::one() + params.g.scale(v)),
+    ];
+    candidates.into_iter().flatten().find(|h| inverse(h).is_some())
+}
+
+// Shared secret from the two public keys alone.
+pub fn recover_shared(params: &ConjugationParams, alice: &Octonion, bob: &Octonion) -> Option<Octonion> {
+    let h = linearize_conjugation(params, alice)?;
+    Some((h * *bob) * inverse(&h)?)
+}
+
+pub fn brute_force_exponent(params: &ConjugationParams, public: &Octonion, max_exp: u64) -> Option<u64> {
+    let g_inv = inverse(&params.g)?;
+    let mut y = params.x;
+    for e in 0..=max_exp {
+        if y == *public {
+            return Some(e);
+        }
+        y = (params.g * y) * g_inv;
+    }
+    None
+}
+
+pub fn brute_force_bracketing(elements: &[Octonion], commitment: &Octonion) -> Vec<Bracketing> {
+    Bracketing::all(elements.len())
+        .into_iter()
+        .filter(|shape| bracket_commit(elements, shape) == *commitment)
+        .collect()
+}
+
+// --- FALSIFICATION RUN ---
+#[derive(Clone, Debug, Default)]
+pub struct AttackReport {
+    pub trials: usize,
+    pub linearization_breaks: usize, // Shared secret recovered from public keys
+    pub exponents_recovered: usize,  // Brute force within `max_exp`
+    pub bracketing_factors: usize,
+    pub bracketing_unique: usize,    // Commitments opened to exactly the true shape
+    pub bracketing_ambiguous: usize, // Commitments with a second valid opening
+}
+
+pub fn run_attacks(seed: u64, trials: NonZeroUsize) -> AttackReport {
+    const MAX_EXP: u64 = 4096;
+    const FACTORS: usize = 6;
+    let trials = trials.get();
+    let mut report = AttackReport { trials, bracketing_factors: FACTORS, ..AttackReport::default() };
+    let shapes = Bracketing::all(FACTORS);
+
+    for t in 0..trials as u64 {
+        let params = ConjugationParams::from_seed(seed ^ t);
+        let (a, b) = (seed.wrapping_add(t * 7919) % MAX_EXP, (seed ^ 0xB0B).wrapping_add(t) % MAX_EXP);
+        let (alice, bob) = (params.public_key(a), params.public_key(b));
+        if recover_shared(&params, &alice, &bob) == Some(params.shared(a, &bob)) {
+            report.linearization_breaks += 1;
+        }
+        if brute_force_exponent(&params, &alice, MAX_EXP).map(|e| params.public_key(e)) == Some(alice) {
+            report.exponents_recovered += 1;
+        }
+
+        let elements = bracket_elements(seed.wrapping_add(t), FACTORS);
+        let secret = &shapes[t as usize % shapes.len()];
+        match brute_force_bracketing(&elements, &bracket_commit(&elements, secret)).len() {
+            1 => report.bracketing_unique += 1,
+            _ => report.bracketing_ambiguous += 1,
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linearization_breaks_conjugation_dh() {
+        let params = ConjugationParams::from_seed(5);
+        let (a, b) = (40_001, 77_777);
+        let (alice, bob) = (params.public_key(a), params.public_key(b));
+        assert_eq!(recover_shared(&params, &alice, &bob), Some(params.shared(b, &alice)));
+
+        // Small exponents fall to plain enumeration as well
+        let small = params.public_key(300);
+        let e = brute_force_exponent(&params, &small, 1_000).unwrap();
+        assert_eq!(params.public_key(e), small);
+    }
+
+    #[test]
+    fn falsification_run_reports_every_trial() {
+        let report = run_attacks(1, NonZeroUsize::new(4).unwrap());
+        assert_eq!(report.linearization_breaks, 4);
+        assert_eq!(report.bracketing_unique + report.bracketing_ambiguous, 4);
+    }
+}
@@ -0,0 +1,162 @@
+// src/research/octo_dh.rs
+// CANDIDATE ONE-WAY FUNCTIONS over octonion conjugation and bracketing.
+// Experimental, not for production (see research.rs).
+//
+// Everything runs over Octonion<u16> (Z_2^16 lanes): small enough that the
+// brute-force attacks in attacks.rs finish on real parameters, large enough
+// that the algebra behaves like the production backends.
+//
+// 1. Conjugation DH (Ko-Lee style). Public (g, x). Alice publishes
+//    A = g^a x g^-a, Bob publishes B = g^b x g^-b, both derive
+//    g^(a+b) x g^-(a+b). Agreement works because g and x generate an
+//    associative subalgebra (Artin's theorem) - which is also its weakness.
+// 2. Bracketing commitment. Public elements x_1..x_n; the secret is the
+//    bracketing (a binary tree) used to multiply them in order. Hardness
+//    would rest on recovering the tree from the product alone.
+
+pub type Octonion = crate::algebra::Octonion<u16>;
+
+// --- RING HELPERS ---
+
+// Inverse of an odd u16 mod 2^16 (Newton: correct bits double each step)
+pub fn inverse_mod_2_16(n: u16) -> u16 {
+    let mut x = n;
+    for _ in 0..4 {
+        x = x.wrapping_mul(2u16.wrapping_sub(n.wrapping_mul(x)));
+    }
+    x
+}
+
+// x^-1 = x* / N(x); exists iff the norm is odd (a unit mod 2^16)
+pub fn inverse(x: &Octonion) -> Option<Octonion> {
+    let n = x.norm_sq();
+    if n & 1 == 0 {
+        return None;
+    }
+    Some(x.conj().scale(inverse_mod_2_16(n)))
+}
+
+// Square-and-multiply; well defined since octonions are power-associative
+pub fn pow(g: &Octonion, mut e: u64) -> Octonion {
+    let (mut acc, mut base) = (Octonion::one(), *g);
+    while e > 0 {
+        if e & 1 == 1 {
+            acc = acc * base;
+        }
+        base = base * base;
+        e >>= 1;
+    }
+    acc
+}
+
+fn seeded(seed: u64) -> Octonion {
+    let mut s = seed;
+    Octonion::new(core::array::from_fn(|_| {
+        s = s.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (s >> 48) as u16
+    }))
+}
+
+// --- CANDIDATE 1: CONJUGATION DH ---
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConjugationParams {
+    pub g: Octonion, // Invertible (odd norm)
+    pub x: Octonion,
+}
+
+impl ConjugationParams {
+    pub fn from_seed(seed: u64) -> Self {
+        let mut g = seeded(seed);
+        g.coeffs[0] ^= (g.norm_sq() & 1) ^ 1; // Force an odd norm
+        ConjugationParams { g, x: seeded(seed ^ 0x5EED) }
+    }
+
+    // g^e y g^-e
+    pub fn conjugate(&self, e: u64, y: &Octonion) -> Octonion {
+        let ge = pow(&self.g, e);
+        (ge * *y) * inverse(&ge).expect("g has odd norm, so every power is invertible")
+    }
+
+    pub fn public_key(&self, secret: u64) -> Octonion {
+        self.conjugate(secret, &self.x)
+    }
+
+    pub fn shared(&self, secret: u64, their_public: &Octonion) -> Octonion {
+        self.conjugate(secret, their_public)
+    }
+}
+
+// --- CANDIDATE 2: BRACKETING COMMITMENT ---
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Bracketing {
+    Leaf,
+    Node(Box<Bracketing>, Box<Bracketing>),
+}
+
+impl Bracketing {
+    pub fn leaves(&self) -> usize {
+        match self {
+            Bracketing::Leaf => 1,
+            Bracketing::Node(l, r) => l.leaves() + r.leaves(),
+        }
+    }
+
+    // Every bracketing of n factors: Catalan(n - 1) trees
+    pub fn all(n: usize) -> Vec<Bracketing> {
+        if n <= 1 {
+            return vec![Bracketing::Leaf];
+        }
+        let mut out = Vec::new();
+        for k in 1..n {
+            for l in Bracketing::all(k) {
+                for r in Bracketing::all(n - k) {
+                    out.push(Bracketing::Node(Box::new(l.clone()), Box::new(r)));
+                }
+            }
+        }
+        out
+    }
+
+    // Multiply `elements` in order under this bracketing
+    pub fn evaluate(&self, elements: &[Octonion]) -> Octonion {
+        assert_eq!(elements.len(), self.leaves(), "bracketing does not match the element count");
+        match self {
+            Bracketing::Leaf => elements[0],
+            Bracketing::Node(l, r) => {
+                let (left, right) = elements.split_at(l.leaves());
+                l.evaluate(left) * r.evaluate(right)
+            }
+        }
+    }
+}
+
+pub fn bracket_elements(seed: u64, n: usize) -> Vec<Octonion> {
+    (0..n as u64).map(|i| seeded(seed.wrapping_add(i).wrapping_mul(0x9E37_79B9))).collect()
+}
+
+pub fn bracket_commit(elements: &[Octonion], shape: &Bracketing) -> Octonion {
+    shape.evaluate(elements)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conjugation_agreement_and_bracketing_shapes() {
+        let params = ConjugationParams::from_seed(11);
+        let (a, b) = (1234, 987);
+        let (alice, bob) = (params.public_key(a), params.public_key(b));
+        assert_ne!(alice, bob);
+        assert_eq!(params.shared(a, &bob), params.shared(b, &alice));
+        assert_eq!(params.shared(a, &bob), params.public_key(a + b));
+
+        // Catalan(3) = 5 shapes of four factors, and non-associativity
+        // makes them commit to different values
+        let elements = bracket_elements(3, 4);
+        let shapes = Bracketing::all(4);
+        assert_eq!(shapes.len(), 5);
+        let values: Vec<_> = shapes.iter().map(|s| bracket_commit(&elements, s)).collect();
+        assert_ne!(values[0], values[4]);
+    }
+}
@@ -3,24 +3,14 @@
 // Sedenions are Non-Commutative, Non-Associative, and Non-Alternative.
 // They represent the "Chaos" phase of the APH vacuum (Beta -> 0).
 
-//use crate::vdf::Octonion; // Reuse the robust Octonion from VDF module
-use std::ops::{Add, Mul, BitXor};
+use crate::algebra::cayley_dickson as cd;
+use std::ops::{Add, BitXor, Mul, Neg, Sub};
 
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub struct Octonion {
-    pub coeffs: [u64; 8],
-}
+// The canonical algebra::Octonion over Z_2^64 (wrapping u64 lanes).
+pub type Octonion = crate::algebra::Octonion<u64>;
 
 impl Octonion {
-    pub fn new(coeffs: [u64; 8]) -> Self {
-        Octonion { coeffs }
-    }
-
-    pub fn zero() -> Self {
-        Octonion { coeffs: [0; 8] }
-    }
-
     // A heuristic "random" generator for the seed
     pub fn from_seed(seed: u64) -> Self {
         let s = seed;
@@ -36,96 +26,15 @@ impl Octonion {
         ])
     }
 
-    // Returns a u64 "norm" (sum of squares modulo 2^64).
-    pub fn norm_sq(&self) -> u64 {
-        self.coeffs.iter().fold(0u64, |acc, &x| acc.wrapping_add(x.wrapping_mul(x)))
-    }
-
-    // Check if exactly zero
-    pub fn is_zero(&self) -> bool {
-        self.coeffs.iter().all(|&x| x == 0)
-    }
-
     // Rotate coefficients to create a 3rd independent generator
     // This breaks Artin's Theorem (2-generator associativity)
     pub fn rotate(&self) -> Self {
-        let mut new_c = [0; 8];
-        for i in 0..8 {
-            new_c[i] = self.coeffs[(i + 1) % 8];
-        }
+        let mut new_c = self.coeffs;
+        new_c.rotate_left(1);
         Octonion::new(new_c)
     }
 }
 
-// ----------------------------------------------------------------------------
-// Arithmetic Implementation (Cayley-Dickson over Z_2^64)
-// ----------------------------------------------------------------------------
-impl Add for Octonion {
-    type Output = Self;
-    fn add(self, other: Self) -> Self {
-        let mut c = [0; 8];
-        for i in 0..8 { c[i] = self.coeffs[i].wrapping_add(other.coeffs[i]); }
-        Octonion::new(c)
-    }
-}
-
-// Full non-associative multiplication
-impl Mul for Octonion {
-    type Output = Self;
-    fn mul(self, other: Self) -> Self {
-        let a = &self.coeffs;
-        let b = &other.coeffs;
-        let mut res = [0; 8];
-        
-        // Optimized naive multiplication for benchmarking
-        // Real part
-        res[0] = a[0].wrapping_mul(b[0])
-            .wrapping_sub(a[1].wrapping_mul(b[1])).wrapping_sub(a[2].wrapping_mul(b[2]))
-            .wrapping_sub(a[3].wrapping_mul(b[3])).wrapping_sub(a[4].wrapping_mul(b[4]))
-            .wrapping_sub(a[5].wrapping_mul(b[5])).wrapping_sub(a[6].wrapping_mul(b[6]))
-            .wrapping_sub(a[7].wrapping_mul(b[7]));
-
-        // Imaginary parts
-        res[1] = a[0].wrapping_mul(b[1]).wrapping_add(a[1].wrapping_mul(b[0]))
-            .wrapping_add(a[2].wrapping_mul(b[3])).wrapping_sub(a[3].wrapping_mul(b[2]))
-            .wrapping_add(a[4].wrapping_mul(b[5])).wrapping_sub(a[5].wrapping_mul(b[4]))
-            .wrapping_sub(a[6].wrapping_mul(b[7])).wrapping_add(a[7].wrapping_mul(b[6]));
-
-        res[2] = a[0].wrapping_mul(b[2]).wrapping_sub(a[1].wrapping_mul(b[3]))
-            .wrapping_add(a[2].wrapping_mul(b[0])).wrapping_add(a[3].wrapping_mul(b[1]))
-            .wrapping_add(a[4].wrapping_mul(b[6])).wrapping_add(a[5].wrapping_mul(b[7]))
-            .wrapping_sub(a[6].wrapping_mul(b[4])).wrapping_sub(a[7].wrapping_mul(b[5]));
-               
-        res[3] = a[0].wrapping_mul(b[3]).wrapping_add(a[1].wrapping_mul(b[2]))
-            .wrapping_sub(a[2].wrapping_mul(b[1])).wrapping_add(a[3].wrapping_mul(b[0]))
-            .wrapping_add(a[4].wrapping_mul(b[7])).wrapping_sub(a[5].wrapping_mul(b[6]))
-            .wrapping_add(a[6].wrapping_mul(b[5])).wrapping_sub(a[7].wrapping_mul(b[4]));
-
-        res[4] = a[0].wrapping_mul(b[4]).wrapping_sub(a[1].wrapping_mul(b[5]))
-            .wrapping_sub(a[2].wrapping_mul(b[6])).wrapping_sub(a[3].wrapping_mul(b[7]))
-            .wrapping_add(a[4].wrapping_mul(b[0])).wrapping_add(a[5].wrapping_mul(b[1]))
-            .wrapping_add(a[6].wrapping_mul(b[2])).wrapping_add(a[7].wrapping_mul(b[3]));
-
-        res[5] = a[0].wrapping_mul(b[5]).wrapping_add(a[1].wrapping_mul(b[4]))
-            .wrapping_sub(a[2].wrapping_mul(b[7])).wrapping_add(a[3].wrapping_mul(b[6]))
-            .wrapping_sub(a[4].wrapping_mul(b[1])).wrapping_add(a[5].wrapping_mul(b[0]))
-            .wrapping_sub(a[6].wrapping_mul(b[3])).wrapping_add(a[7].wrapping_mul(b[2]));
-
-        res[6] = a[0].wrapping_mul(b[6]).wrapping_add(a[1].wrapping_mul(b[7]))
-            .wrapping_add(a[2].wrapping_mul(b[4])).wrapping_sub(a[3].wrapping_mul(b[5]))
-            .wrapping_sub(a[4].wrapping_mul(b[2])).wrapping_add(a[5].wrapping_mul(b[3]))
-            .wrapping_add(a[6].wrapping_mul(b[0])).wrapping_sub(a[7].wrapping_mul(b[1]));
-
-        res[7] = a[0].wrapping_mul(b[7]).wrapping_sub(a[1].wrapping_mul(b[6]))
-            .wrapping_add(a[2].wrapping_mul(b[5])).wrapping_add(a[3].wrapping_mul(b[4]))
-            .wrapping_sub(a[4].wrapping_mul(b[3])).wrapping_sub(a[5].wrapping_mul(b[2]))
-            .wrapping_add(a[6].wrapping_mul(b[1])).wrapping_add(a[7].wrapping_mul(b[0]));
-
-        Octonion::new(res)
-    }
-}
-
-
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Sedenion {
     pub low: Octonion,  // Coefficients 0-7
@@ -144,70 +53,137 @@ impl Sedenion {
         }
     }
 
+    pub(crate) fn to_cd(self) -> cd::Sedenion<u64> {
+        cd::Sedenion::new(self.low.to_cd(), self.high.to_cd())
+    }
+
+    pub(crate) fn from_cd(s: cd::Sedenion<u64>) -> Self {
+        Sedenion::new(Octonion::from_cd(s.low), Octonion::from_cd(s.high))
+    }
+
+    pub fn one() -> Self {
+        Sedenion::new(Octonion::one(), Octonion::zero())
+    }
+
     // Determine the conjugate of the Sedenion
-    // S* = (L*, -H)
+    // S* = (L*, -H): the real part is kept, all 15 imaginary parts negated
     pub fn conjugate(&self) -> Self {
-        // We need an octonion conjugate helper. 
-        // Since Octonion structs are arrays, we calculate manually for now to avoid altering vdf.rs too much.
-        let l = self.low.coeffs;
-        let h = self.high.coeffs;
-        
-        // Conjugate of Octonion: (s, -v)
-        // Here we just negate everything for the algebraic conjugate approximation in modular arithmetic
-        // Real implementation would preserve the scalar part. 
-        // For hashing, a full bitwise negation is a stronger mixer.
-        let mut l_conj = [0u64; 8];
-        l_conj[0] = l[0]; // Keep real part (usually) but for hash we might want full diffusion
-        for i in 1..8 { l_conj[i] = l[i].wrapping_neg(); }
-
-        let mut h_neg = [0u64; 8];
-        for i in 0..8 { h_neg[i] = h[i].wrapping_neg(); }
-
-        Sedenion {
-            low: Octonion::new(l_conj),
-            high: Octonion::new(h_neg),
+        Sedenion::new(self.low.conj(), -self.high)
+    }
+
+    // Sum of squared coefficients (mod 2^64); S * S* = norm as a real
+    pub fn norm_sq(&self) -> u64 {
+        self.low.norm_sq().wrapping_add(self.high.norm_sq())
+    }
+
+    // S^-1 = S* / N(S). Over Z_2^64 that needs N(S) to be a unit (odd);
+    // otherwise S has no inverse (zero, and the zero divisors of the
+    // hash ring) and we return None.
+    pub fn inverse(&self) -> Option<Self> {
+        let n = self.norm_sq();
+        if n & 1 == 0 {
+            return None;
         }
+        Some(self.conjugate() * inverse_mod_2_64(n))
     }
 }
 
-// Cayley-Dickson Construction:
-// (A, B) * (C, D) = (AC - D*B_conj, A_conj*D + CB)
-impl Mul for Sedenion {
-    type Output = Self;
+// --- ZERO DIVISORS ---
+// x is a zero divisor if x * y = 0 or y * x = 0 for some y != 0. Over
+// Z_2^64 the left map y -> x * y is a 16x16 matrix that is injective iff
+// its determinant is odd, i.e. iff it has full rank mod 2; likewise for the
+// right map. So the test is two GF(2) rank computations.
+//
+// In this ring zero divisors are common, not exotic: every x with an even
+// norm has x * x = 0 mod 2 (the real-sedenion pairs below are on top of
+// that). A GSH-style mixer multiplying by such a state loses entropy, so
+// callers holding a degenerate state should reject or perturb it.
+impl Sedenion {
+    pub fn basis(i: usize) -> Self {
+        let mut s = Sedenion::zero();
+        if i < 8 { s.low.coeffs[i] = 1 } else { s.high.coeffs[i - 8] = 1 }
+        s
+    }
 
-    fn mul(self, other: Self) -> Self {
-        let a = self.low;
-        let b = self.high;
-        let c = other.low;
-        let d = other.high;
-
-        // We need a conjugate function for Octonions for strict C-D construction.
-        // We define a local helper since vdf::Octonion might not expose it publicly.
-        let oct_conj = |o: Octonion| -> Octonion {
-            let mut coeffs = o.coeffs;
-            for i in 1..8 { coeffs[i] = coeffs[i].wrapping_neg(); }
-            Octonion::new(coeffs)
-        };
-
-        let b_conj = oct_conj(b);
-        let a_conj = oct_conj(a);
-
-        // Term 1: AC - D * B_conj
-        let ac = a * c;
-        let d_b_conj = d * b_conj;
-        // Subtraction in modular arithmetic is adding the negation
-        let mut term1_coeffs = [0u64; 8];
-        for i in 0..8 {
-            term1_coeffs[i] = ac.coeffs[i].wrapping_sub(d_b_conj.coeffs[i]);
+    fn lanes(&self) -> [u64; 16] {
+        let mut out = [0u64; 16];
+        out[..8].copy_from_slice(&self.low.coeffs);
+        out[8..].copy_from_slice(&self.high.coeffs);
+        out
+    }
+
+    pub fn is_zero_divisor(&self) -> bool {
+        if *self == Sedenion::zero() {
+            return false;
         }
-        let term1 = Octonion::new(term1_coeffs);
+        let left = (0..16).map(|j| parity_mask(*self * Sedenion::basis(j)));
+        let right = (0..16).map(|j| parity_mask(Sedenion::basis(j) * *self));
+        gf2_rank(left) < 16 || gf2_rank(right) < 16
+    }
+}
 
-        // Term 2: A_conj * D + C * B
-        let a_conj_d = a_conj * d;
-        let cb = c * b;
-        let term2 = a_conj_d + cb;
+// Low bit of each coefficient, packed into a column over GF(2)
+fn parity_mask(s: Sedenion) -> u16 {
+    s.lanes().iter().enumerate().fold(0, |m, (i, &c)| m | (((c & 1) as u16) << i))
+}
+
+fn gf2_rank(columns: impl Iterator<Item = u16>) -> usize {
+    let mut pivots: Vec<u16> = Vec::new();
+    for mut col in columns {
+        for &p in &pivots {
+            col = col.min(col ^ p);
+        }
+        if col != 0 {
+            pivots.push(col);
+            pivots.sort_unstable_by(|a, b| b.cmp(a));
+        }
+    }
+    pivots.len()
+}
+
+// The standard zero-divisor pairs of the real sedenions: (e_a + s e_b) and
+// (e_c + t e_d) over imaginary units with signs s, t = +-1 whose product
+// vanishes, e.g. (e_3 + e_10)(e_6 - e_15) = 0. Coefficients are small, so a
+// zero product mod 2^64 is a zero product over the integers.
+pub fn zero_divisor_pairs() -> Vec<(Sedenion, Sedenion)> {
+    let two_unit = |a: usize, b: usize, negate: bool| {
+        let e_b = Sedenion::basis(b);
+        Sedenion::basis(a) + if negate { -e_b } else { e_b }
+    };
+    let mut units = Vec::new();
+    for a in 1..16 {
+        for b in a + 1..16 {
+            units.push(two_unit(a, b, false));
+            units.push(two_unit(a, b, true));
+        }
+    }
+    let mut pairs = Vec::new();
+    for &x in &units {
+        for &y in &units {
+            if x * y == Sedenion::zero() {
+                pairs.push((x, y));
+            }
+        }
+    }
+    pairs
+}
+
+// Multiplicative inverse of an odd u64 mod 2^64 (Newton: each step doubles
+// the number of correct low bits, starting from 3 bits since n*n = 1 mod 8).
+fn inverse_mod_2_64(n: u64) -> u64 {
+    let mut x = n;
+    for _ in 0..5 {
+        x = x.wrapping_mul(2u64.wrapping_sub(n.wrapping_mul(x)));
+    }
+    x
+}
+
+// Cayley-Dickson Construction: one more doubling of the octonions.
+impl Mul for Sedenion {
+    type Output = Self;
 
-        Sedenion::new(term1, term2)
+    fn mul(self, other: Self) -> Self {
+        Sedenion::from_cd(self.to_cd() * other.to_cd())
     }
 }
 
@@ -218,6 +194,28 @@ impl Add for Sedenion {
     }
 }
 
+impl Sub for Sedenion {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        Sedenion::new(self.low - other.low, self.high - other.high)
+    }
+}
+
+impl Neg for Sedenion {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Sedenion::new(-self.low, -self.high)
+    }
+}
+
+// Scalar multiplication
+impl Mul<u64> for Sedenion {
+    type Output = Self;
+    fn mul(self, s: u64) -> Self {
+        Sedenion::new(self.low.scale(s), self.high.scale(s))
+    }
+}
+
 impl BitXor for Sedenion {
     type Output = Self;
     fn bitxor(self, other: Self) -> Self {
@@ -235,16 +233,53 @@ impl BitXor for Sedenion {
 // This is the core "Sponge" function for GSH-256.
 // In Sedenions, this is non-zero and highly chaotic.
 pub fn associator(x: Sedenion, y: Sedenion, z: Sedenion) -> Sedenion {
-    let xy_z = (x * y) * z;
-    let x_yz = x * (y * z);
-    
-    let mut l = [0u64; 8];
-    let mut h = [0u64; 8];
-    
-    for i in 0..8 {
-        l[i] = xy_z.low.coeffs[i].wrapping_sub(x_yz.low.coeffs[i]);
-        h[i] = xy_z.high.coeffs[i].wrapping_sub(x_yz.high.coeffs[i]);
-    }
-    
-    Sedenion::new(Octonion::new(l), Octonion::new(h))
-}
\ No newline at end of file
+    crate::algebra::NonAssociativeAlgebra::associator(x, y, z)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inverse_and_conjugate() {
+        let x = Sedenion::new(Octonion::from_seed(3), Octonion::from_seed(11));
+        let c = x.conjugate();
+        assert_eq!(c.low.coeffs[0], x.low.coeffs[0]);
+        assert_eq!(x * c, Sedenion::new(Octonion::from_real(x.norm_sq()), Octonion::zero()));
+        assert_eq!(x - x, Sedenion::zero());
+        assert_eq!(x + (-x), Sedenion::zero());
+
+        // Make the norm odd so the inverse exists
+        let mut y = x;
+        y.low.coeffs[0] ^= (y.norm_sq() & 1) ^ 1;
+        let inv = y.inverse().unwrap();
+        assert_eq!(y * inv, Sedenion::one());
+        assert_eq!(inv * y, Sedenion::one());
+
+        // Even norm: e_1 + e_10 squares to -2, so it has no inverse
+        let mut z = Sedenion::zero();
+        z.low.coeffs[1] = 1;
+        z.high.coeffs[2] = 1;
+        assert_eq!(z.inverse(), None);
+        assert_eq!(Sedenion::zero().inverse(), None);
+    }
+
+    #[test]
+    fn zero_divisors_are_detected() {
+        let pairs = zero_divisor_pairs();
+        let (x, y) = (Sedenion::basis(3) + Sedenion::basis(10), Sedenion::basis(6) - Sedenion::basis(15));
+        assert!(pairs.contains(&(x, y)));
+        for (x, y) in &pairs {
+            assert_eq!(*x * *y, Sedenion::zero());
+            assert!(x.is_zero_divisor() && y.is_zero_divisor());
+        }
+
+        // Units of the ring are not; even-norm states are (x * x = 0 mod 2)
+        assert!(!Sedenion::one().is_zero_divisor());
+        assert!(!Sedenion::basis(5).is_zero_divisor());
+        assert!(!Sedenion::zero().is_zero_divisor());
+        let mut even = Sedenion::one();
+        even.high.coeffs[3] = 1;
+        assert!(even.is_zero_divisor());
+    }
+}
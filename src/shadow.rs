@@ -0,0 +1,381 @@
+// src/shadow.rs
+// SHADOW VALIDATION: A slow, independent re-check of every accepted block.
+//
+// The consensus path (HorizonValidator) is built for speed: streamed GSH
+// absorption, the Cayley-Dickson tower, shared Merkle helpers. A bug in
+// any of them is a chain split waiting to happen, and a single path cannot
+// notice its own mistakes. In debug mode every transaction the node accepts
+// is re-validated here by a reference written straight from the spec:
+//   * UTXO encoding and signing message rebuilt field by field,
+//   * the revealed key re-hashed and compared with the output's address,
+//   * Merkle paths re-hashed level by level over one-shot GSH of the
+//     concatenated children (no hash_slices, no merkle::root_from_path),
+//   * Jordan-Schnorr verified with explicit 3x3 Hermitian matrices whose
+//     octonion entries multiply through the FanoTable, not the tower,
+//   * no early exits: every check runs and the verdict is decided at the end.
+// Any disagreement between the two paths is a Divergence; the node halts
+// and writes a forensic dump (both verdicts, the roots, the transaction).
+// A node turns this on with NodeConfig::enable_shadow; the soak harness
+// calls shadow_check itself so it can report instead of halting.
+// Vetoes by validation hooks are local policy, not consensus, and are not
+// shadowed.
+
+use crate::albert::{AlbertElement, Octonion, Scalar, Q};
+use crate::algebra::fano::CANONICAL;
+use crate::gsh::GSH256;
+use crate::horizon::{HorizonValidator, SpendCondition, Transaction, TxRejection, Utxo};
+use crate::jordan_sig::{PublicKey, Signature};
+use crate::wire::Wire;
+use sha2::{Digest, Sha256};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+// Spec constants, restated rather than imported so that a change to the
+// optimized side shows up as a divergence instead of silently moving both.
+const SIG_BOUND: Scalar = 8192 - 24; // JD-Light gamma1 - beta, on centered coefficients
+const KEY_ELEMENTS: usize = 12; // s_k and t_k per key
+const RESPONSE_ELEMENTS: usize = 10; // z_l and rows of C per signature
+const CHALLENGE_WEIGHT: usize = 4; // Nonzero entries per row of C
+const PARAM_SET_ID: u8 = 0x21; // JD-Light, the only set over Q
+const CHALLENGE_DOMAIN: &[u8] = b"jordan-schnorr/challenge";
+const EXPAND_DOMAIN: &[u8] = b"jordan-schnorr/challenge-matrix";
+const TX_CONTEXT: &[u8] = b"olc/horizon/tx/v1";
+const SPEND_DOMAIN: &[u8] = b"OLC-SPEND-v1";
+const EMPTY_LEAF: [u8; 32] = [0u8; 32];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Accept { new_root: String },
+    BadOwner,
+    BadSignature,
+    BadWitness,
+}
+
+impl Verdict {
+    // The optimized path's answer in comparable form; None for hook vetoes
+    pub fn of(result: &Result<String, TxRejection>) -> Option<Verdict> {
+        match result {
+            Ok(root) => Some(Verdict::Accept { new_root: root.clone() }),
+            Err(TxRejection::OwnerMismatch { .. }) => Some(Verdict::BadOwner),
+            Err(TxRejection::InvalidSignature(_)) => Some(Verdict::BadSignature),
+            Err(TxRejection::InvalidWitness { .. }) => Some(Verdict::BadWitness),
+            Err(TxRejection::Vetoed(_)) => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Divergence {
+    pub height: u64,
+    pub tx_index: usize,
+    pub state_root: String,
+    pub optimized: Verdict,
+    pub reference: Verdict,
+    pub tx: Transaction,
+}
+
+impl Divergence {
+    pub fn dump(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "CONSENSUS DIVERGENCE at height {} (tx {})", self.height, self.tx_index);
+        let _ = writeln!(out, "state root: {}", self.state_root);
+        let _ = writeln!(out, "optimized:  {:?}", self.optimized);
+        let _ = writeln!(out, "reference:  {:?}", self.reference);
+        let _ = writeln!(out, "leaf:       {}", hex(&reference_leaf(&self.tx.input_utxo)));
+        let _ = writeln!(out, "index:      {}", self.tx.witness.index);
+        for (level, sibling) in self.tx.witness.siblings.iter().enumerate() {
+            let _ = writeln!(out, "sibling {:>2}: {}", level, hex(sibling));
+        }
+        let _ = writeln!(out, "owner ok (reference):     {}", reference_owner(&self.tx));
+        let _ = writeln!(out, "signature ok (reference): {}", reference_verify(&self.tx.owner_key, &reference_message(&self.tx), &self.tx.signature));
+        let _ = writeln!(out, "transaction: {:#?}", self.tx);
+        out
+    }
+
+    // Written next to the node's data; returns the file path
+    pub fn write_dump(&self, dir: &Path) -> std::io::Result<PathBuf> {
+        let path = dir.join(format!("olc_divergence_{}_{}.txt", self.height, self.tx_index));
+        std::fs::write(&path, self.dump())?;
+        Ok(path)
+    }
+}
+
+// --- THE SHADOW ---
+// Re-validate one transaction the optimized path has already judged
+pub fn shadow_check(
+    height: u64,
+    tx_index: usize,
+    validator: &HorizonValidator,
+    tx: &Transaction,
+    optimized: &Result<String, TxRejection>,
+) -> Result<(), Box<Divergence>> {
+    let Some(optimized) = Verdict::of(optimized) else { return Ok(()) };
+    let reference = reference_verdict(&validator.state_root, tx);
+    if optimized == reference {
+        return Ok(());
+    }
+    Err(Box::new(Divergence {
+        height,
+        tx_index,
+        state_root: validator.state_root.clone(),
+        optimized,
+        reference,
+        tx: tx.clone(),
+    }))
+}
+
+pub fn reference_verdict(state_root: &str, tx: &Transaction) -> Verdict {
+    let owner_ok = reference_owner(tx);
+    let sig_ok = reference_verify(&tx.owner_key, &reference_message(tx), &tx.signature);
+    let leaf = reference_leaf(&tx.input_utxo);
+    let root = hex(&reference_root(&leaf, tx.witness.index, &tx.witness.siblings));
+    let new_root = hex(&reference_root(&EMPTY_LEAF, tx.witness.index, &tx.witness.siblings));
+    // Same precedence as the spec: owner, signature, then inclusion
+    if !owner_ok {
+        Verdict::BadOwner
+    } else if !sig_ok {
+        Verdict::BadSignature
+    } else if root != state_root {
+        Verdict::BadWitness
+    } else {
+        Verdict::Accept { new_root }
+    }
+}
+
+// --- REFERENCE PRIMITIVES ---
+// Leaf schema v1 (horizon.rs), field by field
+fn utxo_encoding(utxo: &Utxo) -> Vec<u8> {
+    let mut bytes = b"OLC-UTXO-LEAF-v1".to_vec();
+    bytes.push(1);
+    bytes.extend(utxo.id);
+    bytes.extend(utxo.owner.0);
+    bytes.extend(utxo.amount.to_le_bytes());
+    bytes.extend(utxo.asset);
+    bytes.push(match utxo.condition {
+        SpendCondition::OwnerSignature => 0,
+    });
+    bytes.extend(utxo.created_at.to_le_bytes());
+    bytes
+}
+
+// The address is the first 32 bytes of GSH-256 over the wire-encoded key
+fn reference_owner(tx: &Transaction) -> bool {
+    truncate(&GSH256::hash_raw(&tx.owner_key.to_bytes())) == tx.input_utxo.owner.0
+}
+
+// Spend domain, the input's leaf encoding, then the output it pays
+fn reference_message(tx: &Transaction) -> Vec<u8> {
+    let mut bytes = SPEND_DOMAIN.to_vec();
+    bytes.extend(utxo_encoding(&tx.input_utxo));
+    bytes.extend(tx.new_owner.0);
+    bytes.extend(tx.new_amount.to_le_bytes());
+    hex(&GSH256::hash_raw(&bytes)).into_bytes()
+}
+
+fn reference_leaf(utxo: &Utxo) -> [u8; 32] {
+    truncate(&GSH256::hash_raw(&utxo_encoding(utxo)))
+}
+
+// Level k: bit k of the index says whether the running node is a right child
+pub fn reference_root(leaf: &[u8; 32], index: u64, siblings: &[[u8; 32]]) -> [u8; 32] {
+    let mut node = *leaf;
+    for (level, sibling) in siblings.iter().enumerate() {
+        let is_right = index.checked_shr(level as u32).unwrap_or(0) & 1 == 1;
+        let mut children = Vec::with_capacity(64);
+        if is_right {
+            children.extend_from_slice(sibling);
+            children.extend_from_slice(&node);
+        } else {
+            children.extend_from_slice(&node);
+            children.extend_from_slice(sibling);
+        }
+        node = truncate(&GSH256::hash_raw(&children));
+    }
+    node
+}
+
+// Check w'_l = A o z_l - sum_k C[l][k] t_k with H(msg, w') == c, C expanded
+// from c, and |z| <= bound, under a key with reduced coefficients and a
+// nonzero A (singular A is fine: keys from before invertible generators
+// carry one)
+pub fn reference_verify(pk: &PublicKey, msg: &[u8], sig: &Signature) -> bool {
+    let matrix = reference_challenge(&sig.c);
+    let mut w = Vec::with_capacity(RESPONSE_ELEMENTS);
+    for (row, z) in matrix.iter().zip(&sig.z) {
+        let mut ct = AlbertElement::zero();
+        for (&entry, t) in row.iter().zip(&pk.t) {
+            ct = ct + t.scale(if entry < 0 { Q - 1 } else { entry as Scalar });
+        }
+        w.push(from_matrix(&jordan(&to_matrix(&pk.a), &to_matrix(z))) - ct);
+    }
+
+    let mut hasher = Sha256::new();
+    hasher.update(CHALLENGE_DOMAIN);
+    hasher.update(Q.to_le_bytes());
+    hasher.update([PARAM_SET_ID]);
+    hasher.update((TX_CONTEXT.len() as u64).to_le_bytes());
+    hasher.update(TX_CONTEXT);
+    hasher.update((msg.len() as u64).to_le_bytes());
+    hasher.update(msg);
+    for w in &w {
+        hasher.update(pack(w));
+    }
+    let digest: [u8; 32] = hasher.finalize().into();
+
+    let challenge_ok = digest == sig.c;
+    let bound_ok = sig.z.iter().flat_map(|z| z.coeffs).map(|x| x % Q).all(|x| x.min(Q - x) <= SIG_BOUND);
+    let key_ok = pk.t.iter().chain([&pk.a]).flat_map(|e| e.coeffs).all(|x| x < Q) & pk.a.coeffs.iter().any(|&x| x != 0);
+    challenge_ok & bound_ok & key_ok
+}
+
+// Row by row from the XOF over the seed: positions 8 to 11 in turn each
+// take a position j <= i from the low nibble of a byte (bytes with a larger
+// nibble are skipped), j's entry moves to i, and j gets +1 or -1 from the
+// low bit of the next byte
+fn reference_challenge(seed: &[u8; 32]) -> [[i8; KEY_ELEMENTS]; RESPONSE_ELEMENTS] {
+    let mut xof = GSH256::xof(&[EXPAND_DOMAIN, seed]);
+    let mut next = || {
+        let mut byte = [0u8];
+        xof.squeeze(&mut byte);
+        byte[0]
+    };
+    let mut rows = [[0i8; KEY_ELEMENTS]; RESPONSE_ELEMENTS];
+    for row in &mut rows {
+        for i in KEY_ELEMENTS - CHALLENGE_WEIGHT..KEY_ELEMENTS {
+            let mut j = 16;
+            while j > i {
+                j = (next() % 16) as usize;
+            }
+            row[i] = row[j];
+            row[j] = [1, -1][(next() % 2) as usize];
+        }
+    }
+    rows
+}
+
+// 27 coefficients of 15 bits, least significant bit first, zero padded
+fn pack(x: &AlbertElement) -> Vec<u8> {
+    let mut bits = Vec::new();
+    for &c in &x.coeffs {
+        bits.extend((0..15).map(|i| (c % Q) >> i & 1));
+    }
+    bits.chunks(8).map(|byte| byte.iter().enumerate().map(|(i, &b)| (b as u8) << i).sum()).collect()
+}
+
+type Matrix = [[Octonion; 3]; 3];
+
+fn real(s: Scalar) -> Octonion {
+    let mut o = Octonion::zero();
+    o.coeffs[0].0 = s % Q;
+    o
+}
+
+// [[alpha, c, b], [c*, beta, a], [b*, a*, gamma]]
+fn to_matrix(x: &AlbertElement) -> Matrix {
+    [
+        [real(x.alpha()), x.c(), x.b()],
+        [x.c().conj(), real(x.beta()), x.a()],
+        [x.b().conj(), x.a().conj(), real(x.gamma())],
+    ]
+}
+
+fn from_matrix(m: &Matrix) -> AlbertElement {
+    AlbertElement::from_parts(m[0][0].coeffs[0].0, m[1][1].coeffs[0].0, m[2][2].coeffs[0].0, m[1][2], m[0][2], m[0][1])
+}
+
+// XY + YX, entry by entry
+fn jordan(x: &Matrix, y: &Matrix) -> Matrix {
+    let mut out = [[Octonion::zero(); 3]; 3];
+    for i in 0..3 {
+        for j in 0..3 {
+            for k in 0..3 {
+                out[i][j] = out[i][j] + CANONICAL.mul(&x[i][k], &y[k][j]) + CANONICAL.mul(&y[i][k], &x[k][j]);
+            }
+        }
+    }
+    out
+}
+
+fn truncate(digest: &[u8; 64]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&digest[..32]);
+    out
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::horizon::HorizonAccumulator;
+    use crate::jordan_sig::JordanSchnorr;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn spend(seed: u64) -> (HorizonAccumulator, Transaction) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut acc = HorizonAccumulator::new();
+        let (alice, bob) = (JordanSchnorr::keygen(&mut rng), JordanSchnorr::keygen(&mut rng));
+        let utxo = Utxo::new([seed as u8; 32], alice.pub_key.address(), 9);
+        acc.add_utxo(&utxo, 5 + seed);
+        acc.add_utxo(&Utxo::new([0xEE; 32], bob.pub_key.address(), 1), 2);
+        let msg = crate::horizon::spend_message(&utxo, &bob.pub_key.address(), 9);
+        let tx = Transaction {
+            witness: acc.generate_witness(5 + seed),
+            owner_key: alice.pub_key,
+            signature: JordanSchnorr::sign(&alice, TX_CONTEXT, &msg, &mut rng).unwrap(),
+            input_utxo: utxo,
+            new_owner: bob.pub_key.address(),
+            new_amount: 9,
+        };
+        (acc, tx)
+    }
+
+    #[test]
+    fn reference_agrees_with_the_optimized_path() {
+        for seed in 0..4 {
+            let (acc, tx) = spend(seed);
+            let validator = HorizonValidator::new(acc.root.clone());
+            let optimized = validator.validate_transaction(&tx);
+            assert!(optimized.is_ok());
+            assert_eq!(shadow_check(1, 0, &validator, &tx, &optimized), Ok(()));
+
+            // Rejections must agree too
+            let mut forged = tx.clone();
+            forged.signature.c[0] ^= 1;
+            assert_eq!(reference_verdict(&acc.root, &forged), Verdict::BadSignature);
+            assert_eq!(shadow_check(1, 0, &validator, &forged, &validator.validate_transaction(&forged)), Ok(()));
+            let redirected = Transaction { new_amount: 1, ..tx.clone() };
+            assert_eq!(reference_verdict(&acc.root, &redirected), Verdict::BadSignature);
+            let mut stolen = tx.clone();
+            stolen.owner_key = JordanSchnorr::keygen(&mut StdRng::seed_from_u64(seed + 100)).pub_key;
+            assert_eq!(reference_verdict(&acc.root, &stolen), Verdict::BadOwner);
+            assert_eq!(shadow_check(1, 0, &validator, &stolen, &validator.validate_transaction(&stolen)), Ok(()));
+            let mut moved = tx.clone();
+            moved.witness.index ^= 1;
+            assert_eq!(shadow_check(1, 0, &validator, &moved, &validator.validate_transaction(&moved)), Ok(()));
+        }
+    }
+
+    #[test]
+    fn disagreement_halts_with_a_dump() {
+        let (acc, tx) = spend(1);
+        let validator = HorizonValidator::new(acc.root.clone());
+        // An optimized path that wrongly accepted a forged spend
+        let mut forged = tx.clone();
+        forged.new_amount = 1_000_000;
+        forged.signature.c[0] ^= 1;
+        let lie = Ok(validator.validate_transaction(&tx).unwrap());
+        let divergence = shadow_check(7, 3, &validator, &forged, &lie).unwrap_err();
+        assert_eq!(divergence.reference, Verdict::BadSignature);
+
+        let dir = std::env::temp_dir();
+        let path = divergence.write_dump(&dir).unwrap();
+        let dump = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert!(dump.starts_with("CONSENSUS DIVERGENCE at height 7 (tx 3)"));
+        assert!(dump.contains(&acc.root) && dump.contains("signature ok (reference): false"));
+    }
+}
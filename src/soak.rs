@@ -0,0 +1,322 @@
+// src/soak.rs
+// SOAK TEST: Long-running two-node regtest harness.
+//
+// Unit tests exercise each component once; leaks and slow state drift only
+// show up after hours of continuous operation. This harness runs:
+//   * Node A - miner + bridge node (holds the Bulk, produces witnesses)
+//   * Node B - stateless validator (headers + root only)
+// and loops: random spends -> stateless validation on B -> mining on A ->
+// header sync, with periodic reorgs, snapshot exports, and RSS checks. A
+// reorg is a rival miner outpacing A from the parent of its new tip: both
+// nodes switch, and the bridge disconnects the orphaned block's spends.
+// Any root mismatch, shadow-validation divergence (debug builds, see
+// shadow.rs) or unbounded memory growth aborts the run.
+
+use crate::gsh_io::{self, GshWriter};
+use crate::horizon::{spend_message, HorizonAccumulator, HorizonValidator, Transaction, Utxo, TX_CONTEXT};
+use crate::horizon_net::{HorizonPeer, NetworkBootstrapper, SyncOutcome};
+use crate::jordan_sig::{JordanSchnorr, SecretKey, SignError};
+use crate::shadow;
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+// --- CONFIGURATION ---
+#[derive(Clone, Debug)]
+pub struct SoakConfig {
+    pub duration: Duration,
+    pub max_blocks: Option<u64>,   // Stop early (used by tests)
+    pub seed: u64,                 // Deterministic RNG seed
+    pub utxo_slots: u64,           // Size of the recycled leaf index pool
+    pub txs_per_block: usize,
+    pub reorg_every: u64,          // Blocks between reorgs
+    pub snapshot_every: u64,       // Blocks between snapshot exports
+    pub keep_headers: usize,       // Headers retained after a snapshot checkpoint
+    pub rss_budget_kb: u64,        // Allowed RSS growth over the post-warmup baseline
+    pub difficulty: u64,
+    pub shadow: bool,              // Re-check every accepted tx (shadow.rs)
+}
+
+impl Default for SoakConfig {
+    fn default() -> Self {
+        SoakConfig {
+            duration: Duration::from_secs(60 * 60),
+            max_blocks: None,
+            seed: 0x50A4,
+            utxo_slots: 256,
+            txs_per_block: 4,
+            reorg_every: 25,
+            snapshot_every: 100,
+            keep_headers: 64,
+            rss_budget_kb: 256 * 1024,
+            difficulty: 1000,
+            shadow: cfg!(debug_assertions),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct SoakReport {
+    pub blocks: u64,
+    pub transactions: u64,
+    pub reorgs: u64,
+    pub orphaned_txs: u64,         // Spends disconnected by reorgs
+    pub snapshots: u64,
+    pub baseline_rss_kb: Option<u64>,
+    pub peak_rss_kb: Option<u64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SoakFailure {
+    RootMismatch { block: u64, expected: String, got: String },
+    TxRejected { block: u64, reason: String },
+    SignFailed { block: u64, error: SignError },
+    SyncFailed { block: u64, outcome: SyncOutcome },
+    SnapshotChecksum { block: u64 },
+    RssExceeded { block: u64, baseline_kb: u64, current_kb: u64 },
+    // Optimized and reference validation disagreed; `dump` is the forensic file
+    Divergence { block: u64, dump: String },
+    Io(String),
+}
+
+// Resident set size in KiB (Linux only; None elsewhere). statm counts
+// pages, whose size is the kernel's to choose (16 KiB on some arm64).
+#[cfg(unix)]
+pub fn current_rss_kb() -> Option<u64> {
+    let statm = std::fs::read_to_string("/proc/self/statm").ok()?;
+    let pages: u64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+    // SAFETY: sysconf only reads a system constant
+    let page_size = u64::try_from(unsafe { libc::sysconf(libc::_SC_PAGESIZE) }).ok()?;
+    Some(pages * page_size / 1024)
+}
+
+#[cfg(not(unix))]
+pub fn current_rss_kb() -> Option<u64> {
+    None
+}
+
+// A spendable output held by the soak "wallet".
+#[derive(Clone)]
+struct Holding {
+    index: u64,
+    utxo: Utxo,
+    key: usize, // Index into the key pool
+}
+
+pub fn run_soak(config: &SoakConfig) -> Result<SoakReport, SoakFailure> {
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let mut report = SoakReport::default();
+
+    // Key pool: keygen is not what we are soaking, so generate once.
+    let keys: Vec<SecretKey> = (0..8).map(|_| JordanSchnorr::keygen(&mut rng)).collect();
+
+    // 1. Genesis: fill every slot of the index pool
+    let mut bulk = HorizonAccumulator::new();
+    let mut holdings = Vec::new();
+    for index in 0..config.utxo_slots {
+        let key = rng.gen_range(0..keys.len());
+        let mut id = [0u8; 32];
+        rng.fill(&mut id);
+        let utxo = Utxo::new(id, keys[key].pub_key.address(), 100);
+        bulk.add_utxo(&utxo, index);
+        holdings.push(Holding { index, utxo, key });
+    }
+
+    let mut node_a = HorizonPeer::new(bulk.root.clone());
+    let mut node_b = HorizonPeer::new(bulk.root.clone());
+
+    let snapshot_path = snapshot_path();
+    let started = Instant::now();
+
+    while started.elapsed() < config.duration
+        && config.max_blocks.is_none_or(|max| report.blocks < max)
+    {
+        let block = report.blocks + 1;
+        let parent_root = bulk.root.clone();
+        // What this block's spends replaced, to disconnect it on a reorg
+        let mut undo: Vec<(usize, Holding)> = Vec::new();
+
+        // 2. Random spends, validated statelessly against Node B's horizon
+        for _ in 0..config.txs_per_block {
+            let slot = rng.gen_range(0..holdings.len());
+            let holding = &holdings[slot];
+            let index = holding.index;
+
+            // Witness refresh: the root moved since the UTXO was created
+            let witness = bulk.generate_witness(index);
+            let new_key = rng.gen_range(0..keys.len());
+            let msg = spend_message(&holding.utxo, &keys[new_key].pub_key.address(), holding.utxo.amount);
+            let signature = JordanSchnorr::sign(&keys[holding.key], TX_CONTEXT, &msg, &mut rng)
+                .map_err(|error| SoakFailure::SignFailed { block, error })?;
+
+            let tx = Transaction {
+                input_utxo: holding.utxo.clone(),
+                witness,
+                owner_key: keys[holding.key].pub_key,
+                signature,
+                new_owner: keys[new_key].pub_key.address(),
+                new_amount: holding.utxo.amount,
+            };
+
+            let validator = HorizonValidator::new(node_b.current_horizon.clone());
+            let verdict = validator.validate_transaction(&tx);
+            if config.shadow {
+                if let Err(divergence) = shadow::shadow_check(block, report.transactions as usize, &validator, &tx, &verdict) {
+                    let dump = divergence.write_dump(&std::env::temp_dir()).map_err(|e| SoakFailure::Io(e.to_string()))?;
+                    return Err(SoakFailure::Divergence { block, dump: dump.display().to_string() });
+                }
+            }
+            let new_root = verdict.map_err(|e| SoakFailure::TxRejected { block, reason: format!("{:?}", e) })?;
+
+            // Bridge applies the same transition to the Bulk; roots must agree.
+            bulk.remove_utxo(index);
+            if bulk.root != new_root {
+                return Err(SoakFailure::RootMismatch { block, expected: bulk.root.clone(), got: new_root });
+            }
+
+            // Recycle the slot for the output so the Bulk stays bounded
+            let mut id = [0u8; 32];
+            rng.fill(&mut id);
+            let output = Utxo { created_at: block, ..Utxo::new(id, tx.new_owner, tx.new_amount) };
+            bulk.add_utxo(&output, index);
+            undo.push((slot, std::mem::replace(&mut holdings[slot], Holding { index, utxo: output, key: new_key })));
+
+            node_b.current_horizon = bulk.root.clone();
+            report.transactions += 1;
+        }
+
+        // 3. Mining on A, header sync on B
+        node_a.mine_next_block(bulk.root.clone(), config.difficulty);
+        expect_switch(block, NetworkBootstrapper::try_sync(&mut node_b, &node_a.chain))?;
+        expect_root(block, &node_b.current_horizon, &bulk.root)?;
+
+        // 4. Periodic reorg: a rival mines two blocks without this block's
+        // spends on top of its parent. The heavier fork wins on both nodes,
+        // and the bridge rolls the Bulk back to the fork's root.
+        if block % config.reorg_every == 0 {
+            let fork_point = node_a.chain.len() - 1;
+            let mut rival = HorizonPeer { chain: node_a.chain[..fork_point].to_vec(), current_horizon: parent_root.clone() };
+            rival.mine_next_block(parent_root.clone(), config.difficulty);
+            rival.mine_next_block(parent_root, config.difficulty);
+            expect_switch(block, NetworkBootstrapper::try_sync(&mut node_a, &rival.chain))?;
+            expect_switch(block, NetworkBootstrapper::try_sync(&mut node_b, &rival.chain))?;
+
+            report.orphaned_txs += undo.len() as u64;
+            for (slot, spent) in undo.into_iter().rev() {
+                bulk.remove_utxo(spent.index);
+                bulk.add_utxo(&spent.utxo, spent.index);
+                holdings[slot] = spent;
+            }
+            expect_root(block, &node_a.current_horizon, &bulk.root)?;
+            expect_root(block, &node_b.current_horizon, &bulk.root)?;
+            report.reorgs += 1;
+        }
+
+        // 5. Snapshot export + checkpoint pruning + memory check
+        if block % config.snapshot_every == 0 {
+            export_snapshot(block, &snapshot_path, &node_a, &bulk)?;
+            prune(&mut node_a, config.keep_headers);
+            prune(&mut node_b, config.keep_headers);
+            report.snapshots += 1;
+
+            if let Some(rss) = current_rss_kb() {
+                let baseline = *report.baseline_rss_kb.get_or_insert(rss);
+                report.peak_rss_kb = Some(report.peak_rss_kb.unwrap_or(0).max(rss));
+                if rss > baseline + config.rss_budget_kb {
+                    return Err(SoakFailure::RssExceeded { block, baseline_kb: baseline, current_kb: rss });
+                }
+            }
+        }
+
+        report.blocks = block;
+    }
+
+    let _ = std::fs::remove_file(&snapshot_path);
+    Ok(report)
+}
+
+fn expect_switch(block: u64, outcome: SyncOutcome) -> Result<(), SoakFailure> {
+    match outcome {
+        SyncOutcome::Switched { .. } => Ok(()),
+        outcome => Err(SoakFailure::SyncFailed { block, outcome }),
+    }
+}
+
+fn expect_root(block: u64, got: &str, expected: &str) -> Result<(), SoakFailure> {
+    if got != expected {
+        return Err(SoakFailure::RootMismatch { block, expected: expected.to_string(), got: got.to_string() });
+    }
+    Ok(())
+}
+
+fn snapshot_path() -> PathBuf {
+    std::env::temp_dir().join(format!("olc_soak_{}.snap", std::process::id()))
+}
+
+// Write headers + root through a hashing writer, then re-read the file and
+// check that the on-disk checksum matches what was written.
+fn export_snapshot(
+    block: u64,
+    path: &PathBuf,
+    peer: &HorizonPeer,
+    bulk: &HorizonAccumulator,
+) -> Result<(), SoakFailure> {
+    let io_err = |e: std::io::Error| SoakFailure::Io(e.to_string());
+
+    let file = File::create(path).map_err(io_err)?;
+    let mut writer: GshWriter<File> = GshWriter::new(file);
+    writeln!(writer, "root {}", bulk.root).map_err(io_err)?;
+    for header in &peer.chain {
+        writeln!(writer, "header {} {}", header.id(), header.horizon_root).map_err(io_err)?;
+    }
+    let (mut file, written) = writer.finalize();
+    file.flush().map_err(io_err)?;
+    drop(file);
+
+    let on_disk = gsh_io::hash_file(path).map_err(io_err)?;
+    if on_disk != written {
+        return Err(SoakFailure::SnapshotChecksum { block });
+    }
+    Ok(())
+}
+
+// Checkpoint: drop all but the most recent headers. The oldest retained
+// header acts as the new trusted anchor for continuity checks.
+fn prune(peer: &mut HorizonPeer, keep: usize) {
+    if peer.chain.len() > keep {
+        let excess = peer.chain.len() - keep;
+        peer.chain.drain(..excess);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_soak_reconciles() {
+        let config = SoakConfig {
+            max_blocks: Some(6),
+            utxo_slots: 8,
+            txs_per_block: 2,
+            reorg_every: 2,
+            snapshot_every: 3,
+            keep_headers: 4,
+            ..SoakConfig::default()
+        };
+        let report = run_soak(&config).expect("soak diverged");
+        assert_eq!(report.blocks, 6);
+        assert_eq!(report.transactions, 12);
+        assert_eq!(report.reorgs, 3);
+        assert_eq!(report.orphaned_txs, 6);
+        if cfg!(target_os = "linux") {
+            // statm pages times the real page size, not a 4 KiB guess
+            let rss = current_rss_kb().unwrap();
+            assert!(report.peak_rss_kb.is_some() && rss > 0 && rss < 64 * 1024 * 1024);
+        }
+        assert_eq!(report.snapshots, 2);
+    }
+}
@@ -1,3 +1,6 @@
+use crate::gsh::GSH256;
+use crate::merkle::{self, Gsh32, MerkleTree};
+use crate::algebra::octonion::mul_batch;
 use crate::vdf::{Octonion, algebraic_hash_oracle, associator};
 
 // ============================================================================
@@ -16,21 +19,41 @@ pub struct PublicInputs {
 /// A simulated STARK Proof. 
 /// In a real system, this contains the FRI proximity proofs, Merkle roots of 
 /// the execution trace, and O(log^2 T) queried trace rows for constraint validation.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct StarkProof {
     pub trace_merkle_root: [u8; 32],
+    // Openings of the boundary rows Z_0 and Z_T against the trace root
+    pub z_0_auth_path: Vec<[u8; 32]>,
+    pub z_t_auth_path: Vec<[u8; 32]>,
     // A subset of queried rows from the execution trace (for asymmetric verification)
     pub queried_rows: Vec<TraceQuery>,
     // FRI Proof simulating the low-degree testing
     pub fri_proof_valid: bool, 
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct TraceQuery {
     pub step: usize,
     pub z_current: Octonion,
     pub z_next: Octonion,
-    pub merkle_auth_path: Vec<[u8; 32]>,
+    pub merkle_auth_path: Vec<[u8; 32]>, // Opens row `step`
+    pub next_auth_path: Vec<[u8; 32]>,   // Opens row `step + 1`
+}
+
+const QUERY_DOMAIN: &[u8] = b"OLC-STARK-QUERIES-v1";
+
+/// Fiat-Shamir query positions: squeezed from the GSH XOF over the whole
+/// trace root and T, so the prover cannot choose which transitions get
+/// checked after committing, nor predict them from part of the root.
+pub fn query_steps(trace_root: &[u8; 32], t: usize, count: usize) -> Vec<usize> {
+    let mut xof = GSH256::xof(&[QUERY_DOMAIN, trace_root, &(t as u64).to_le_bytes()]);
+    (0..count).map(|_| (xof.next_u64() % t.max(1) as u64) as usize).collect()
+}
+
+/// Leaf digest of one trace row (8 little-endian Goldilocks coefficients).
+pub fn trace_row_leaf(z: &Octonion) -> [u8; 32] {
+    let bytes: Vec<u8> = z.coeffs.iter().flat_map(|c| c.0.to_le_bytes()).collect();
+    Gsh32::leaf(&bytes)
 }
 
 // ============================================================================
@@ -47,8 +70,23 @@ impl OctoStarkAir {
     /// If the prover computed the step correctly, this will return Octonion::zero().
     /// Degree analysis: Z^2 (deg 2) + [Z, C, Z^7] (deg 8). Total AIR Degree = 8.
     pub fn transition_constraint(z_current: &Octonion, z_next: &Octonion, c: &Octonion) -> Octonion {
+        Self::constraint_with_square(z_current, *z_current * *z_current, z_next, c)
+    }
+
+    /// Every transition of `trace` at once. The squares are independent, so
+    /// they go through mul_batch across the rayon pool.
+    pub fn transition_constraints(trace: &[Octonion], c: &Octonion) -> Vec<Octonion> {
+        let rows = &trace[..trace.len().saturating_sub(1)];
+        let squares = mul_batch(rows, rows);
+        rows.iter()
+            .zip(&trace[1..])
+            .zip(squares)
+            .map(|((z_current, z_next), sq)| Self::constraint_with_square(z_current, sq, z_next, c))
+            .collect()
+    }
+
+    fn constraint_with_square(z_current: &Octonion, sq: Octonion, z_next: &Octonion, c: &Octonion) -> Octonion {
         // Reconstruct the expected next state algebraically
-        let sq = *z_current * *z_current;
         let dynamic_gen = algebraic_hash_oracle(z_current);
         let assoc = associator(*z_current, *c, dynamic_gen);
         
@@ -80,34 +118,33 @@ impl StarkProver {
         assert_eq!(trace.len(), t + 1, "Trace length must match T + 1");
 
         // 1. Sanity check: Ensure trace is valid before proving
-        for i in 0..t {
-            let constraint = OctoStarkAir::transition_constraint(&trace[i], &trace[i + 1], &pub_inputs.c);
-            assert!(constraint.is_zero(), "Trace invalid at step {}", i);
+        let constraints = OctoStarkAir::transition_constraints(trace, &pub_inputs.c);
+        if let Some(i) = constraints.iter().position(|k| !k.is_zero()) {
+            panic!("Trace invalid at step {}", i);
         }
 
-        // 2. Commit to the Execution Trace (Simulated Merkle Root over `trace`)
-        let trace_merkle_root = [0xAA; 32]; 
+        // 2. Commit to the Execution Trace (GSH Merkle tree over the rows)
+        let tree = MerkleTree::<Gsh32>::new(trace.iter().map(trace_row_leaf).collect());
+        let trace_merkle_root = tree.root();
 
         // 3. Answer Verifier's pseudo-random FRI queries (Fiat-Shamir)
         // We simulate picking `security_level_queries` random points to reveal.
         let mut queried_rows = Vec::with_capacity(security_level_queries);
-        let mut prng = 0x1337_CAFE_BEEF_DEAD_u64;
         
-        for _ in 0..security_level_queries {
-            // Deterministic pseudo-random step selection for simulation
-            prng = prng.wrapping_mul(6364136223846793005).wrapping_add(1);
-            let step = (prng as usize) % t; 
-            
+        for step in query_steps(&trace_merkle_root, t, security_level_queries) {
             queried_rows.push(TraceQuery {
                 step,
                 z_current: trace[step],
                 z_next: trace[step + 1],
-                merkle_auth_path: vec![[0xCC; 32]; 5], // Mock Merkle Path
+                merkle_auth_path: tree.prove(step).unwrap().siblings,
+                next_auth_path: tree.prove(step + 1).unwrap().siblings,
             });
         }
 
         StarkProof {
             trace_merkle_root,
+            z_0_auth_path: tree.prove(0).unwrap().siblings,
+            z_t_auth_path: tree.prove(t).unwrap().siblings,
             queried_rows,
             fri_proof_valid: true, // Honest prover generates valid FRI
         }
@@ -126,9 +163,14 @@ impl StarkVerifier {
     /// The verifier's workload depends ONLY on the number of FRI queries (e.g., 40),
     /// providing strict sub-millisecond verification regardless of if T = 1,000,000.
     pub fn verify(proof: &StarkProof, pub_inputs: &PublicInputs) -> bool {
+        let opens = |z: &Octonion, step: usize, path: &[[u8; 32]]| {
+            merkle::root_from_path::<Gsh32>(&trace_row_leaf(z), step as u64, path) == proof.trace_merkle_root
+        };
+
         // 1. Validate Boundary Constraints (Z_0 and Z_T)
-        // (In a real STARK, we check the Merkle proofs for the first and last trace elements)
-        let valid_boundaries = true; 
+        // The first and last trace rows must open to the public inputs.
+        let valid_boundaries = opens(&pub_inputs.z_0, 0, &proof.z_0_auth_path)
+            && opens(&pub_inputs.z_t, pub_inputs.t_iterations, &proof.z_t_auth_path);
         if !valid_boundaries {
             println!("   [!] Boundary constraint failure.");
             return false;
@@ -139,7 +181,13 @@ impl StarkVerifier {
         // Because of the FRI low-degree testing, if the trace was invalid anywhere, 
         // the polynomials would have astronomically high degree, failing the FRI check 
         // and mismatching the Merkle roots at these queried points.
-        for query in &proof.queried_rows {
+        let expected_steps = query_steps(&proof.trace_merkle_root, pub_inputs.t_iterations, proof.queried_rows.len());
+        for (query, expected_step) in proof.queried_rows.iter().zip(expected_steps) {
+            if query.step != expected_step {
+                println!("   [!] Query position {} was not derived from the trace root!", query.step);
+                return false;
+            }
+
             // Re-evaluate the constraint polynomial at this specific step
             let constraint_res = OctoStarkAir::transition_constraint(
                 &query.z_current,
@@ -152,7 +200,13 @@ impl StarkVerifier {
                 return false;
             }
 
-            // (In a real STARK: Check query.merkle_auth_path against proof.trace_merkle_root)
+            // Both rows must open against the committed trace root
+            if !opens(&query.z_current, query.step, &query.merkle_auth_path)
+                || !opens(&query.z_next, query.step + 1, &query.next_auth_path)
+            {
+                println!("   [!] Trace row at step {} does not open against the Merkle root!", query.step);
+                return false;
+            }
         }
 
         // 3. Verify the FRI Low-Degree Proof
@@ -163,4 +217,22 @@ impl StarkVerifier {
 
         true // Proof is valid!
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vdf::evaluate_vdf;
+
+    #[test]
+    fn batched_constraints_match_the_single_step_check() {
+        let c = Octonion::from_seed(2);
+        let mut trace = evaluate_vdf(Octonion::from_seed(1), c, 64).trace;
+        trace[40] = Octonion::from_seed(3);
+        let batched = OctoStarkAir::transition_constraints(&trace, &c);
+        assert_eq!(batched.len(), 64);
+        for (i, k) in batched.iter().enumerate() {
+            assert_eq!(*k, OctoStarkAir::transition_constraint(&trace[i], &trace[i + 1], &c));
+            assert_eq!(k.is_zero(), i != 39 && i != 40);
+        }
+    }
+}
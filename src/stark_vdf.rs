@@ -1,7 +1,8 @@
 use p3_air::{Air, AirBuilder, AirBuilderWithPublicValues, BaseAir};
 use p3_dft::Radix2Dit;
-use p3_field::AbstractField;
+use p3_field::{AbstractField, PrimeField64};
 use p3_baby_bear::BabyBear;
+use p3_goldilocks::Goldilocks;
 use p3_matrix::dense::RowMajorMatrix;
 use p3_matrix::Matrix;
 
@@ -21,12 +22,19 @@ use p3_uni_stark::StarkConfig;
 use p3_challenger::{HashChallenger, SerializingChallenger32};
 use p3_merkle_tree::FieldMerkleTreeMmcs;
 
+use crate::algebra::encoding::{read_coeffs, DecodeError};
+#[cfg(feature = "serde")]
+use crate::algebra::encoding::serde_bytes;
+
 /// An Octonion represented by 8 elements in a Field.
 /// This structure acts as the state variable for the VDF.
 /// Zn+1 = Zn^2 + C + [Zn, C, H(Zn)]
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub struct Octonion<F>(pub [F; 8]);
 
+// Associated functions rather than operator traits so AIR expressions and
+// concrete field values share one code path.
+#[allow(clippy::should_implement_trait)]
 impl<F: AbstractField> Octonion<F> {
     /// Non-associative multiplication over the Fano Plane.
     /// This is the primary serial bottleneck that ensures sequential hardness.
@@ -36,6 +44,7 @@ impl<F: AbstractField> Octonion<F> {
         let mut r = core::array::from_fn(|_| F::zero());
 
         // Fano Plane Multiplication Table Logic (Hardened against regressions)
+        // Orientation: algebra::fano::CANONICAL (123 145 176 246 257 347 365)
         r[0] = a[0].clone() * b[0].clone() - a[1].clone() * b[1].clone() - a[2].clone() * b[2].clone() - a[3].clone() * b[3].clone() - a[4].clone() * b[4].clone() - a[5].clone() * b[5].clone() - a[6].clone() * b[6].clone() - a[7].clone() * b[7].clone();
         r[1] = a[0].clone() * b[1].clone() + a[1].clone() * b[0].clone() + a[2].clone() * b[3].clone() - a[3].clone() * b[2].clone() + a[4].clone() * b[5].clone() - a[5].clone() * b[4].clone() - a[6].clone() * b[7].clone() + a[7].clone() * b[6].clone();
         r[2] = a[0].clone() * b[2].clone() - a[1].clone() * b[3].clone() + a[2].clone() * b[0].clone() + a[3].clone() * b[1].clone() + a[4].clone() * b[6].clone() + a[5].clone() * b[7].clone() - a[6].clone() * b[4].clone() - a[7].clone() * b[5].clone();
@@ -49,19 +58,11 @@ impl<F: AbstractField> Octonion<F> {
     }
 
     pub fn add(a: Self, b: Self) -> Self {
-        let mut r = core::array::from_fn(|_| F::zero());
-        for i in 0..8 {
-            r[i] = a.0[i].clone() + b.0[i].clone();
-        }
-        Octonion(r)
+        Octonion(core::array::from_fn(|i| a.0[i].clone() + b.0[i].clone()))
     }
 
     pub fn sub(a: Self, b: Self) -> Self {
-        let mut r = core::array::from_fn(|_| F::zero());
-        for i in 0..8 {
-            r[i] = a.0[i].clone() - b.0[i].clone();
-        }
-        Octonion(r)
+        Octonion(core::array::from_fn(|i| a.0[i].clone() - b.0[i].clone()))
     }
 
     /// The Associator measures the failure of the associative law.
@@ -71,8 +72,102 @@ impl<F: AbstractField> Octonion<F> {
         let a_bd = Self::mul(a, Self::mul(b, d));
         Self::sub(ab_d, a_bd)
     }
+
+    /// Conjugate: real part kept, imaginary parts negated.
+    pub fn conj(a: Self) -> Self {
+        let mut r = a.0.map(|x| -x);
+        r[0] = -r[0].clone();
+        Octonion(r)
+    }
 }
 
+/// A Sedenion represented by 16 elements in a Field: the GSH-256 state
+/// (sedenion.rs) lifted into the arithmetization, so absorptions can be
+/// constrained inside the STARK. Same Cayley-Dickson rule as the algebra
+/// tower: (a, b)(c, d) = (ac - d*b, da + bc*).
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Sedenion<F>(pub [F; 16]);
+
+pub type GoldilocksSedenion = Sedenion<Goldilocks>;
+
+#[allow(clippy::should_implement_trait)]
+impl<F: AbstractField> Sedenion<F> {
+    pub fn from_halves(low: Octonion<F>, high: Octonion<F>) -> Self {
+        let mut halves = low.0.into_iter().chain(high.0);
+        Sedenion(core::array::from_fn(|_| halves.next().unwrap()))
+    }
+
+    pub fn low(&self) -> Octonion<F> {
+        Octonion(core::array::from_fn(|i| self.0[i].clone()))
+    }
+
+    pub fn high(&self) -> Octonion<F> {
+        Octonion(core::array::from_fn(|i| self.0[i + 8].clone()))
+    }
+
+    pub fn mul(x: Self, y: Self) -> Self {
+        let (a, b, c, d) = (x.low(), x.high(), y.low(), y.high());
+        let low = Octonion::sub(Octonion::mul(a.clone(), c.clone()), Octonion::mul(Octonion::conj(d.clone()), b.clone()));
+        let high = Octonion::add(Octonion::mul(d, a), Octonion::mul(b, Octonion::conj(c)));
+        Self::from_halves(low, high)
+    }
+
+    pub fn add(a: Self, b: Self) -> Self {
+        Self::from_halves(Octonion::add(a.low(), b.low()), Octonion::add(a.high(), b.high()))
+    }
+
+    pub fn sub(a: Self, b: Self) -> Self {
+        Self::from_halves(Octonion::sub(a.low(), b.low()), Octonion::sub(a.high(), b.high()))
+    }
+
+    /// [A, B, D] = (AB)D - A(BD): the GSH-256 compression term.
+    pub fn associator(a: Self, b: Self, d: Self) -> Self {
+        let ab_d = Self::mul(Self::mul(a.clone(), b.clone()), d.clone());
+        let a_bd = Self::mul(a, Self::mul(b, d));
+        Self::sub(ab_d, a_bd)
+    }
+}
+
+// Canonical bytes for the field-backed variants: each coefficient as its
+// reduced u64, little-endian, same layout as algebra::encoding (so a
+// Goldilocks octonion encodes exactly like the vdf::Octonion it mirrors).
+macro_rules! field_bytes {
+    ($ty:ident, $n:expr) => {
+        impl<F: PrimeField64> $ty<F> {
+            pub const BYTES: usize = 8 * $n;
+
+            pub fn to_bytes(&self) -> Vec<u8> {
+                self.0.iter().flat_map(|c| c.as_canonical_u64().to_le_bytes()).collect()
+            }
+
+            pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+                let read = |b: &[u8]| {
+                    let raw = u64::from_le_bytes(b.try_into().ok()?);
+                    (raw < F::ORDER_U64).then(|| F::from_canonical_u64(raw))
+                };
+                read_coeffs::<F, $n>(bytes, 8, read).map($ty)
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<F: PrimeField64> serde::Serialize for $ty<F> {
+            fn serialize<Z: serde::Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> {
+                serde_bytes::serialize(&self.to_bytes(), serializer)
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de, F: PrimeField64> serde::Deserialize<'de> for $ty<F> {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let bytes = serde_bytes::deserialize(deserializer)?;
+                Self::from_bytes(&bytes).map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+field_bytes!(Octonion, 8);
+field_bytes!(Sedenion, 16);
+
 /// OctoStarkAir: The production-grade AIR for the VDF.
 /// Defines the polynomial constraints for non-associative sequential delay.
 #[derive(Clone, Debug)]
@@ -134,10 +229,8 @@ impl<AB: AirBuilder<F = BabyBear> + AirBuilderWithPublicValues> Air<AB> for Octo
 
         let expected_next = Octonion::add(Octonion::add(z_sq, c_expr), assoc);
 
-        for i in 0..8 {
-            builder
-                .when_transition()
-                .assert_eq(next[i], expected_next.0[i].clone());
+        for (next_i, expected_i) in next.iter().zip(expected_next.0) {
+            builder.when_transition().assert_eq(*next_i, expected_i);
         }
     }
 }
@@ -317,7 +410,7 @@ pub fn test_e2e_proof() {
 
     // 4. Proving Phase (The zk-Argument)
     println!("\n[Step 2] PROVER: Compressing Hourglass Trace into STARK Proof...");
-    let byte_chall_prove = ByteChallenger::new(vec![], byte_hash.clone());
+    let byte_chall_prove = ByteChallenger::new(vec![], byte_hash);
     let mut challenger_prove = Challenger::new(byte_chall_prove);
     let start_prove = Instant::now();
 
@@ -330,7 +423,7 @@ pub fn test_e2e_proof() {
 
     // 5. Verification Phase (Logarithmic Time)
     println!("\n[Step 3] VERIFIER: Validating VDF via Succinct Argument...");
-    let byte_chall_verify = ByteChallenger::new(vec![], byte_hash.clone());
+    let byte_chall_verify = ByteChallenger::new(vec![], byte_hash);
     let mut challenger_verify = Challenger::new(byte_chall_verify);
     let start_verify = Instant::now();
 
@@ -366,4 +459,87 @@ mod tests {
         let trace = run_vdf_grind(seed, c, 1);
         assert_ne!(seed, trace[1]);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn field_sedenion_agrees_with_integer_and_tower() {
+        use crate::algebra::cayley_dickson::{self as cd, Algebra};
+        use crate::sedenion::{self, Sedenion as IntSedenion};
+        use crate::vdf::Fp;
+        use p3_field::PrimeField64;
+
+        // Small signed coefficients: the u64 sedenion never wraps past
+        // +-2^63, so reading its lanes as i64 gives the integer result.
+        let small = |seed: i64| -> [i64; 16] { core::array::from_fn(|i| ((seed * 7919 + i as i64 * 104_729) % 2001) - 1000) };
+        let to_int = |c: [i64; 16]| {
+            let lanes = c.map(|x| x as u64);
+            IntSedenion::new(sedenion::Octonion::new(lanes[..8].try_into().unwrap()), sedenion::Octonion::new(lanes[8..].try_into().unwrap()))
+        };
+        let to_field = |c: [i64; 16]| Sedenion(c.map(|x| Goldilocks::from_canonical_u64((x as i128).rem_euclid(Goldilocks::ORDER_U64 as i128) as u64)));
+        let reduce = |s: IntSedenion| -> [u64; 16] {
+            let lanes: Vec<u64> = s.low.coeffs.iter().chain(&s.high.coeffs).copied().collect();
+            core::array::from_fn(|i| {
+                let v = lanes[i] as i64 as i128;
+                v.rem_euclid(Goldilocks::ORDER_U64 as i128) as u64
+            })
+        };
+        let canon = |s: GoldilocksSedenion| s.0.map(|x| x.as_canonical_u64());
+
+        let (x, y, z) = (small(1), small(2), small(3));
+        assert_eq!(canon(Sedenion::mul(to_field(x), to_field(y))), reduce(to_int(x) * to_int(y)));
+        assert_eq!(
+            canon(Sedenion::associator(to_field(x), to_field(y), to_field(z))),
+            reduce(sedenion::associator(to_int(x), to_int(y), to_int(z)))
+        );
+
+        // Full-range elements: same product as the tower over vdf::Fp
+        let big = |seed: u64| -> [u64; 16] { core::array::from_fn(|i| Fp::new(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15).rotate_left(i as u32 * 4)).0) };
+        let (a, b) = (big(5), big(6));
+        let tower = cd::Sedenion::<Fp>::from_coeffs(&a.map(Fp)).mul(cd::Sedenion::from_coeffs(&b.map(Fp)));
+        let mut expected = [Fp(0); 16];
+        tower.write_coeffs(&mut expected);
+        let field = Sedenion::mul(Sedenion(a.map(Goldilocks::from_canonical_u64)), Sedenion(b.map(Goldilocks::from_canonical_u64)));
+        assert_eq!(canon(field), expected.map(|f| f.0));
+
+        // Canonical bytes match the vdf::Fp layout, and round-trip
+        assert_eq!(field.low().to_bytes(), crate::vdf::Octonion::new(expected[..8].try_into().unwrap()).to_bytes());
+        assert_eq!(GoldilocksSedenion::from_bytes(&field.to_bytes()), Ok(field));
+        assert!(Octonion::<Goldilocks>::from_bytes(&[0xFF; 64]).is_err());
+    }
+
+    // Differential test: the hand-written Fano table here against the
+    // Cayley-Dickson tower the cipher (Z_2^16) and GSH (Z_2^64) use. Small
+    // signed coefficients are exact over the integers in every backend, so
+    // one adapter lifts them into each ring and compares the results.
+    #[test]
+    fn fano_table_agrees_with_cipher_and_gsh_octonions() {
+        use crate::{flt_cipher, sedenion};
+        use p3_field::PrimeField64;
+
+        let field = |c: [i64; 8]| Octonion(c.map(|x| Goldilocks::from_canonical_u64((x as i128).rem_euclid(Goldilocks::ORDER_U64 as i128) as u64)));
+        // Field result back to a signed integer (|x| < p/2 here)
+        let signed = |o: Octonion<Goldilocks>| {
+            o.0.map(|x| {
+                let v = x.as_canonical_u64();
+                if v > Goldilocks::ORDER_U64 / 2 { v as i64 - Goldilocks::ORDER_U64 as i64 } else { v as i64 }
+            })
+        };
+        let check = |a: [i64; 8], b: [i64; 8]| {
+            let want = signed(Octonion::mul(field(a), field(b)));
+            let u16s = (flt_cipher::Octonion::new(a.map(|x| x as u16)) * flt_cipher::Octonion::new(b.map(|x| x as u16))).coeffs;
+            let u64s = (sedenion::Octonion::new(a.map(|x| x as u64)) * sedenion::Octonion::new(b.map(|x| x as u64))).coeffs;
+            assert_eq!(u16s, want.map(|x| x as u16), "u16 tower vs Fano table: {:?} * {:?}", a, b);
+            assert_eq!(u64s, want.map(|x| x as u64), "u64 tower vs Fano table: {:?} * {:?}", a, b);
+        };
+
+        let basis = |i: usize| -> [i64; 8] { core::array::from_fn(|k| (k == i) as i64) };
+        for i in 0..8 {
+            for j in 0..8 {
+                check(basis(i), basis(j));
+            }
+        }
+        let small = |seed: i64| -> [i64; 8] { core::array::from_fn(|i| ((seed * 7919 + i as i64 * 104_729) % 2001) - 1000) };
+        for seed in 0..64 {
+            check(small(seed), small(seed + 1000));
+        }
+    }
+}
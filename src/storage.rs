@@ -0,0 +1,432 @@
+// src/storage.rs
+// VERSIONED STORAGE & MIGRATIONS
+//
+// A Store is a set of named namespaces ("horizon", "headers", ...), each a
+// sorted key/value map stamped with its own schema version. Format changes
+// (hash swap, leaf encoding) ship as Migration steps that lift exactly one
+// namespace from version N to N + 1. The Migrator:
+//   1. plans the ordered chain of steps for every namespace,
+//   2. dry-runs the whole plan on an in-memory copy (incl. validation),
+//   3. copies the store file to a backup, and only then
+//   4. writes the migrated store back.
+// A failure in steps 1-2 leaves the file untouched.
+//
+// On-disk format (text, one store per file):
+//   olc-store 1
+//   namespace <name> <version>
+//   <key>\t<value>
+// Keys and values are escaped (\\ \t \n \r), so no tab or line break in
+// them can end a field early or forge a record.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+const MAGIC: &str = "olc-store 1";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Namespace {
+    pub version: u32,
+    pub entries: BTreeMap<String, String>,
+}
+
+impl Namespace {
+    pub fn new(version: u32) -> Self {
+        Namespace { version, entries: BTreeMap::new() }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Store {
+    pub namespaces: BTreeMap<String, Namespace>,
+}
+
+#[derive(Debug)]
+pub enum StoreError {
+    Io(std::io::Error),
+    Corrupt { line: usize, reason: String },
+}
+
+impl Store {
+    pub fn new() -> Self {
+        Store { namespaces: BTreeMap::new() }
+    }
+
+    pub fn namespace(&self, name: &str) -> Option<&Namespace> {
+        self.namespaces.get(name)
+    }
+
+    // Get or create a namespace stamped with `version`
+    pub fn namespace_mut(&mut self, name: &str, version: u32) -> &mut Namespace {
+        self.namespaces.entry(name.to_string()).or_insert_with(|| Namespace::new(version))
+    }
+
+    pub fn encode(&self) -> String {
+        let mut out = format!("{}\n", MAGIC);
+        for (name, ns) in &self.namespaces {
+            out += &format!("namespace {} {}\n", name, ns.version);
+            for (k, v) in &ns.entries {
+                out += &entry_line(k, v);
+            }
+        }
+        out
+    }
+
+    pub fn decode(text: &str) -> Result<Store, StoreError> {
+        let corrupt = |line: usize, reason: &str| StoreError::Corrupt { line, reason: reason.to_string() };
+        let mut lines = text.lines().enumerate();
+        match lines.next() {
+            Some((_, MAGIC)) => {}
+            _ => return Err(corrupt(1, "missing store header")),
+        }
+
+        let mut store = Store::new();
+        let mut current: Option<String> = None;
+        for (n, line) in lines {
+            if let Some(rest) = line.strip_prefix("namespace ") {
+                let mut parts = rest.split(' ');
+                let (name, version) = match (parts.next(), parts.next().and_then(|v| v.parse().ok())) {
+                    (Some(name), Some(version)) => (name.to_string(), version),
+                    _ => return Err(corrupt(n + 1, "bad namespace line")),
+                };
+                store.namespaces.insert(name.clone(), Namespace::new(version));
+                current = Some(name);
+            } else if let Some((k, v)) = line.split_once('\t') {
+                let ns = current.as_ref()
+                    .and_then(|name| store.namespaces.get_mut(name))
+                    .ok_or_else(|| corrupt(n + 1, "entry outside namespace"))?;
+                match (unescape(k), unescape(v)) {
+                    (Some(k), Some(v)) => ns.entries.insert(k, v),
+                    _ => return Err(corrupt(n + 1, "bad escape")),
+                };
+            } else if !line.is_empty() {
+                return Err(corrupt(n + 1, "unrecognized line"));
+            }
+        }
+        Ok(store)
+    }
+
+    pub fn load(path: &Path) -> Result<Store, StoreError> {
+        Store::decode(&std::fs::read_to_string(path).map_err(StoreError::Io)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), StoreError> {
+        std::fs::write(path, self.encode()).map_err(StoreError::Io)
+    }
+}
+
+// One `<key>\t<value>` record, escaped, with its newline; for writers
+// that append to a store file rather than rewrite it
+pub fn entry_line(key: &str, value: &str) -> String {
+    format!("{}\t{}\n", escape(key), escape(value))
+}
+
+fn escape(field: &str) -> String {
+    let mut out = String::with_capacity(field.len());
+    for c in field.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn unescape(field: &str) -> Option<String> {
+    let mut out = String::with_capacity(field.len());
+    let mut chars = field.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        out.push(match chars.next()? {
+            '\\' => '\\',
+            't' => '\t',
+            'n' => '\n',
+            'r' => '\r',
+            _ => return None,
+        });
+    }
+    Some(out)
+}
+
+// --- MIGRATIONS ---
+
+pub trait Migration {
+    // Stable identifier used in plans and error reports
+    fn name(&self) -> &str;
+    fn namespace(&self) -> &str;
+    // The step lifts `source_version()` to `source_version() + 1`
+    fn source_version(&self) -> u32;
+
+    fn migrate(&self, ns: &mut Namespace) -> Result<(), String>;
+
+    // Post-condition checked during the dry run
+    fn validate(&self, _ns: &Namespace) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlannedStep {
+    pub namespace: String,
+    pub migration: String,
+    pub from: u32,
+    pub to: u32,
+}
+
+#[derive(Debug)]
+pub enum MigrationError {
+    Store(StoreError),
+    // Namespace version newer than any registered migration produces
+    NewerThanSupported { namespace: String, version: u32, supported: u32 },
+    MissingStep { namespace: String, from: u32 },
+    DuplicateStep { namespace: String, from: u32 },
+    Failed { migration: String, reason: String },
+    ValidationFailed { migration: String, reason: String },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MigrationReport {
+    pub steps: Vec<PlannedStep>,
+    pub backup: Option<PathBuf>, // None when nothing needed migrating
+}
+
+// Steps may be registered in any order; they are applied per namespace in
+// ascending version order.
+#[derive(Default)]
+pub struct Migrator {
+    migrations: Vec<Box<dyn Migration>>,
+}
+
+impl Migrator {
+    pub fn new() -> Self {
+        Migrator { migrations: Vec::new() }
+    }
+
+    pub fn register(&mut self, migration: Box<dyn Migration>) -> &mut Self {
+        self.migrations.push(migration);
+        self
+    }
+
+    // Latest version the registered steps can reach for `namespace`
+    pub fn target_version(&self, namespace: &str) -> Option<u32> {
+        self.migrations.iter()
+            .filter(|m| m.namespace() == namespace)
+            .map(|m| m.source_version() + 1)
+            .max()
+    }
+
+    fn step(&self, namespace: &str, from: u32) -> Result<Option<&dyn Migration>, MigrationError> {
+        let mut found = self.migrations.iter()
+            .filter(|m| m.namespace() == namespace && m.source_version() == from);
+        let first = found.next();
+        if found.next().is_some() {
+            return Err(MigrationError::DuplicateStep { namespace: namespace.to_string(), from });
+        }
+        Ok(first.map(|m| m.as_ref()))
+    }
+
+    pub fn plan(&self, store: &Store) -> Result<Vec<PlannedStep>, MigrationError> {
+        let mut plan = Vec::new();
+        for (name, ns) in &store.namespaces {
+            let target = match self.target_version(name) {
+                Some(t) => t,
+                None => continue, // No migrations for this namespace
+            };
+            if ns.version > target {
+                return Err(MigrationError::NewerThanSupported {
+                    namespace: name.clone(),
+                    version: ns.version,
+                    supported: target,
+                });
+            }
+            for from in ns.version..target {
+                let m = self.step(name, from)?
+                    .ok_or_else(|| MigrationError::MissingStep { namespace: name.clone(), from })?;
+                plan.push(PlannedStep {
+                    namespace: name.clone(),
+                    migration: m.name().to_string(),
+                    from,
+                    to: from + 1,
+                });
+            }
+        }
+        Ok(plan)
+    }
+
+    // Run the full plan on a copy of `store`. Nothing is written.
+    pub fn dry_run(&self, store: &Store) -> Result<(Store, Vec<PlannedStep>), MigrationError> {
+        let plan = self.plan(store)?;
+        let mut migrated = store.clone();
+        for step in &plan {
+            let m = self.step(&step.namespace, step.from)?.expect("planned step exists");
+            let ns = migrated.namespaces.get_mut(&step.namespace).expect("planned namespace exists");
+            m.migrate(ns).map_err(|reason| MigrationError::Failed { migration: step.migration.clone(), reason })?;
+            m.validate(ns).map_err(|reason| MigrationError::ValidationFailed {
+                migration: step.migration.clone(),
+                reason,
+            })?;
+            ns.version = step.to;
+        }
+        Ok((migrated, plan))
+    }
+
+    // Dry-run, back up to `<path>.bak-<unix secs>`, then write in place.
+    pub fn migrate_file(&self, path: &Path) -> Result<MigrationReport, MigrationError> {
+        let store = Store::load(path).map_err(MigrationError::Store)?;
+        let (migrated, steps) = self.dry_run(&store)?;
+        if steps.is_empty() {
+            return Ok(MigrationReport { steps, backup: None });
+        }
+
+        let stamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let mut backup = path.as_os_str().to_owned();
+        backup.push(format!(".bak-{}", stamp));
+        let backup = PathBuf::from(backup);
+        std::fs::copy(path, &backup).map_err(|e| MigrationError::Store(StoreError::Io(e)))?;
+
+        migrated.save(path).map_err(MigrationError::Store)?;
+        Ok(MigrationReport { steps, backup: Some(backup) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // v1 -> v2: keys gain a "utxo:" prefix
+    struct PrefixKeys;
+    impl Migration for PrefixKeys {
+        fn name(&self) -> &str { "prefix-keys" }
+        fn namespace(&self) -> &str { "ledger" }
+        fn source_version(&self) -> u32 { 1 }
+        fn migrate(&self, ns: &mut Namespace) -> Result<(), String> {
+            ns.entries = std::mem::take(&mut ns.entries)
+                .into_iter()
+                .map(|(k, v)| (format!("utxo:{}", k), v))
+                .collect();
+            Ok(())
+        }
+    }
+
+    // v2 -> v3: decimal amounts re-encoded as fixed-width hex
+    struct HexAmounts;
+    impl Migration for HexAmounts {
+        fn name(&self) -> &str { "hex-amounts" }
+        fn namespace(&self) -> &str { "ledger" }
+        fn source_version(&self) -> u32 { 2 }
+        fn migrate(&self, ns: &mut Namespace) -> Result<(), String> {
+            for v in ns.entries.values_mut() {
+                let amount: u64 = v.parse().map_err(|_| format!("bad amount '{}'", v))?;
+                *v = format!("{:016x}", amount);
+            }
+            Ok(())
+        }
+        fn validate(&self, ns: &Namespace) -> Result<(), String> {
+            match ns.entries.values().all(|v| v.len() == 16) {
+                true => Ok(()),
+                false => Err("amount width".to_string()),
+            }
+        }
+    }
+
+    fn fixture(dir: &str, amounts: &[(&str, &str)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("olc_store_{}_{}", dir, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut store = Store::new();
+        let ledger = store.namespace_mut("ledger", 1);
+        for (k, v) in amounts {
+            ledger.entries.insert(k.to_string(), v.to_string());
+        }
+        store.namespace_mut("meta", 7).entries.insert("network".into(), "regtest".into());
+        let path = dir.join("node.store");
+        store.save(&path).unwrap();
+        path
+    }
+
+    fn migrator() -> Migrator {
+        let mut m = Migrator::new();
+        m.register(Box::new(HexAmounts)).register(Box::new(PrefixKeys));
+        m
+    }
+
+    #[test]
+    fn migrates_fixture_across_two_versions() {
+        let path = fixture("ok", &[("a", "100"), ("b", "255")]);
+        let original = std::fs::read_to_string(&path).unwrap();
+
+        let report = migrator().migrate_file(&path).unwrap();
+        let names: Vec<&str> = report.steps.iter().map(|s| s.migration.as_str()).collect();
+        assert_eq!(names, ["prefix-keys", "hex-amounts"]);
+        assert_eq!(std::fs::read_to_string(report.backup.unwrap()).unwrap(), original);
+
+        let store = Store::load(&path).unwrap();
+        let ledger = store.namespace("ledger").unwrap();
+        assert_eq!(ledger.version, 3);
+        assert_eq!(ledger.entries["utxo:b"], "00000000000000ff");
+        assert_eq!(store.namespace("meta").unwrap().version, 7); // Untouched
+
+        // Already current: no-op, no backup
+        assert_eq!(migrator().migrate_file(&path).unwrap().backup, None);
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn horizon_accumulator_round_trips() {
+        use crate::horizon::{HorizonAccumulator, Utxo, HORIZON_NAMESPACE};
+        use crate::jordan_sig::JordanSchnorr;
+        use rand::SeedableRng;
+
+        let key = JordanSchnorr::keygen(&mut rand::rngs::StdRng::seed_from_u64(3));
+        let mut acc = HorizonAccumulator::new();
+        for i in 0..3 {
+            acc.add_utxo(&Utxo::new([i as u8; 32], key.pub_key.address(), 10), i * 7);
+        }
+        acc.remove_utxo(7);
+
+        let mut store = Store::new();
+        acc.export_leaves(&mut store);
+        let decoded = Store::decode(&store.encode()).unwrap();
+        assert_eq!(HorizonAccumulator::import_leaves(&decoded).unwrap().root, acc.root);
+
+        store.namespaces.get_mut(HORIZON_NAMESPACE).unwrap().version = 1;
+        assert!(HorizonAccumulator::import_leaves(&store).is_err());
+    }
+
+    #[test]
+    fn separators_in_entries_cannot_forge_records() {
+        let mut store = Store::new();
+        let ns = store.namespace_mut("meta", 1);
+        ns.entries.insert("k\tey".into(), "v\nnamespace evil 9\nx\ty\\n\r".into());
+        let text = store.encode();
+        assert_eq!(text.lines().count(), 3);
+        let decoded = Store::decode(&text).unwrap();
+        assert_eq!(decoded, store);
+        assert!(decoded.namespace("evil").is_none());
+        assert!(matches!(Store::decode("olc-store 1\nnamespace a 1\nk\tv\\q\n"), Err(StoreError::Corrupt { line: 3, .. })));
+    }
+
+    #[test]
+    fn failed_dry_run_leaves_store_untouched() {
+        let path = fixture("bad", &[("a", "100"), ("b", "not-a-number")]);
+        let original = std::fs::read_to_string(&path).unwrap();
+
+        assert!(matches!(migrator().migrate_file(&path), Err(MigrationError::Failed { .. })));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), original);
+
+        let mut gap = Migrator::new();
+        gap.register(Box::new(HexAmounts));
+        assert!(matches!(
+            gap.plan(&Store::load(&path).unwrap()),
+            Err(MigrationError::MissingStep { from: 1, .. })
+        ));
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+}
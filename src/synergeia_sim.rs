@@ -34,7 +34,7 @@ fn get_success_prob(delta: f64, slope_m: f64, config: &SynergeiaConfig) -> f64 {
     }
     // Recovery phase (Constant difficulty max cap)
     // Limits the probability to avoid instant-mining blocks if they simply take too long.
-    return slope_m * (config.gamma - config.psi); 
+    slope_m * (config.gamma - config.psi)
 }
 
 pub fn run_simulation(blocks: usize) {
@@ -99,8 +99,7 @@ pub fn run_simulation(blocks: usize) {
         integral_error += error;
         
         // Anti-windup for integral term
-        if integral_error > 500.0 { integral_error = 500.0; }
-        if integral_error < -500.0 { integral_error = -500.0; }
+        integral_error = integral_error.clamp(-500.0, 500.0);
 
         let adjustment = (kp * error) + (ki * integral_error);
         
@@ -110,8 +109,7 @@ pub fn run_simulation(blocks: usize) {
         slope_m -= adjustment;
 
         // Clamp slope to sane values to prevent collapse
-        if slope_m < 0.0001 { slope_m = 0.0001; }
-        if slope_m > 0.1 { slope_m = 0.1; }
+        slope_m = slope_m.clamp(0.0001, 0.1);
     }
 
     // Analysis
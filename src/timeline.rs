@@ -0,0 +1,244 @@
+// src/timeline.rs
+// PROOF OF HISTORY: A verifiable timeline folded out of the VDF outputs.
+//
+// Two levels, both append-only:
+//   1. Per block, the events it timestamps (32-byte hashes supplied by
+//      applications) go into a small Merkle tree. The block's tick chains
+//      the previous tick, the block's VDF output and that events root:
+//        tick_h = GSH(DOMAIN, tick_{h-1}, vdf_h, events_root_h)
+//      so every tick sits after the sequential work of all earlier blocks.
+//   2. The ticks are leaves of a Merkle Mountain Range (mmr.rs); its root
+//      after block H commits to the whole history up to H.
+// An EventProof (event path + tick preimage + MMR path, O(log n) hashes in
+// total) shows that an event was committed at or before block H against the
+// timeline root published for H. Applications read it over the timeline RPC.
+
+use crate::gsh::GSH256;
+use crate::horizon_net::BlockHeader;
+use crate::merkle::{Gsh32, MerkleProof, MerkleTree};
+use crate::mmr::{Mmr, MmrProof};
+
+const DOMAIN: &[u8] = b"OLC-TIMELINE-v1";
+const GENESIS_TICK: [u8; 32] = [0u8; 32];
+
+pub type EventHash = [u8; 32];
+
+fn tick(prev: &[u8; 32], vdf_output: &[u8], events_root: &[u8; 32]) -> [u8; 32] {
+    let full = GSH256::hash_slices(&[DOMAIN, prev, vdf_output, events_root]);
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&full[..32]);
+    out
+}
+
+// --- PROOFS ---
+#[derive(Clone, Debug, PartialEq)]
+pub struct EventProof {
+    pub height: u64, // Block the event was committed in
+    pub event_path: MerkleProof<[u8; 32]>,
+    // Preimage of the block's tick
+    pub prev_tick: [u8; 32],
+    pub vdf_output: Vec<u8>,
+    pub events_root: [u8; 32],
+    pub tick_path: MmrProof,
+}
+
+impl EventProof {
+    // True if `event` was committed at or before block `before_height`,
+    // where `timeline_root` is the root published for that block.
+    pub fn verify(&self, event: &EventHash, timeline_root: &str, before_height: u64) -> bool {
+        if self.height > before_height
+            || self.tick_path.leaf_index != self.height
+            || self.tick_path.leaf_count != before_height + 1
+        {
+            return false;
+        }
+        if !self.event_path.verify::<Gsh32>(event, &self.events_root) {
+            return false;
+        }
+        let leaf = Gsh32::to_hex(&tick(&self.prev_tick, &self.vdf_output, &self.events_root));
+        self.tick_path.verify(&leaf, timeline_root)
+    }
+}
+
+// --- THE TIMELINE ---
+struct BlockRecord {
+    prev_tick: [u8; 32],
+    vdf_output: Vec<u8>,
+    events: Vec<EventHash>,
+    events_root: [u8; 32],
+}
+
+#[derive(Default)]
+pub struct Timeline {
+    blocks: Vec<BlockRecord>,
+    ticks: Vec<[u8; 32]>,
+    mmr: Mmr,
+}
+
+impl Timeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> u64 {
+        self.blocks.len() as u64
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    // Fold the next block in; returns its tick.
+    pub fn append_block(&mut self, header: &BlockHeader, events: Vec<EventHash>) -> [u8; 32] {
+        let prev_tick = self.ticks.last().copied().unwrap_or(GENESIS_TICK);
+        let vdf_output = header.vdf_proof.to_bytes();
+        let events_root = MerkleTree::<Gsh32>::new(events.clone()).root();
+        let t = tick(&prev_tick, &vdf_output, &events_root);
+
+        self.mmr.append(Gsh32::to_hex(&t));
+        self.ticks.push(t);
+        self.blocks.push(BlockRecord { prev_tick, vdf_output, events, events_root });
+        t
+    }
+
+    pub fn root(&self) -> String {
+        self.mmr.root()
+    }
+
+    // Root as it was right after block `height`
+    pub fn root_at(&self, height: u64) -> Option<String> {
+        Some(self.mmr_at(height)?.root())
+    }
+
+    fn mmr_at(&self, height: u64) -> Option<Mmr> {
+        if height >= self.len() {
+            return None;
+        }
+        if height + 1 == self.len() {
+            return Some(self.mmr.clone());
+        }
+        let mut mmr = Mmr::new();
+        for t in &self.ticks[..=height as usize] {
+            mmr.append(Gsh32::to_hex(t));
+        }
+        Some(mmr)
+    }
+
+    // First block that committed `event`
+    pub fn find_event(&self, event: &EventHash) -> Option<(u64, usize)> {
+        self.blocks
+            .iter()
+            .enumerate()
+            .find_map(|(h, b)| b.events.iter().position(|e| e == event).map(|i| (h as u64, i)))
+    }
+
+    // Proof against root_at(before_height); None if the event was not
+    // committed by then.
+    pub fn prove_event(&self, event: &EventHash, before_height: u64) -> Option<EventProof> {
+        let (height, index) = self.find_event(event)?;
+        if height > before_height {
+            return None;
+        }
+        let block = &self.blocks[height as usize];
+        Some(EventProof {
+            height,
+            event_path: MerkleTree::<Gsh32>::new(block.events.clone()).prove(index)?,
+            prev_tick: block.prev_tick,
+            vdf_output: block.vdf_output.clone(),
+            events_root: block.events_root,
+            tick_path: self.mmr_at(before_height)?.prove(height)?,
+        })
+    }
+}
+
+// --- TIMELINE RPC ---
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TimelineCall {
+    Root { height: Option<u64> }, // None = tip
+    ProveEvent { event: EventHash, before_height: u64 },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TimelineReply {
+    Root { height: u64, root: String },
+    Proof(EventProof),
+    NotFound,
+}
+
+impl Timeline {
+    pub fn handle(&self, call: &TimelineCall) -> TimelineReply {
+        match *call {
+            TimelineCall::Root { height } => {
+                let height = match height {
+                    Some(h) => h,
+                    None if self.is_empty() => return TimelineReply::NotFound,
+                    None => self.len() - 1,
+                };
+                match self.root_at(height) {
+                    Some(root) => TimelineReply::Root { height, root },
+                    None => TimelineReply::NotFound,
+                }
+            }
+            TimelineCall::ProveEvent { event, before_height } => match self.prove_event(&event, before_height) {
+                Some(proof) => TimelineReply::Proof(proof),
+                None => TimelineReply::NotFound,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::horizon_net::HorizonPeer;
+
+    fn timeline(blocks: u64) -> Timeline {
+        let mut peer = HorizonPeer::new("GENESIS".to_string());
+        let mut tl = Timeline::new();
+        tl.append_block(&peer.chain[0], Vec::new());
+        for h in 1..=blocks {
+            peer.mine_next_block(format!("ROOT_{}", h), 100);
+            let events = (0..h).map(|i| Gsh32::leaf(&[h as u8, i as u8])).collect();
+            tl.append_block(peer.chain.last().unwrap(), events);
+        }
+        tl
+    }
+
+    #[test]
+    fn event_committed_before_height() {
+        let tl = timeline(6);
+        let event = Gsh32::leaf(&[3, 1]); // Second event of block 3
+
+        for h in 3..=6 {
+            let root = tl.root_at(h).unwrap();
+            let proof = tl.prove_event(&event, h).unwrap();
+            assert_eq!(proof.height, 3);
+            assert!(proof.verify(&event, &root, h));
+            // Not a proof for a different event or an earlier cut-off
+            assert!(!proof.verify(&Gsh32::leaf(&[3, 2]), &root, h));
+            assert!(!proof.verify(&event, &root, 2));
+        }
+        assert_eq!(tl.prove_event(&event, 2), None);
+        assert_eq!(tl.root_at(6).unwrap(), tl.root());
+    }
+
+    #[test]
+    fn rpc_serves_roots_and_proofs() {
+        let tl = timeline(4);
+        let event = Gsh32::leaf(&[4, 0]);
+        let TimelineReply::Root { height, root } = tl.handle(&TimelineCall::Root { height: None }) else {
+            panic!("expected the tip root");
+        };
+        assert_eq!(height, 4);
+        match tl.handle(&TimelineCall::ProveEvent { event, before_height: 4 }) {
+            TimelineReply::Proof(mut proof) => {
+                assert!(proof.verify(&event, &root, 4));
+                // Tampering with the tick preimage breaks the chain
+                proof.vdf_output[0] ^= 1;
+                assert!(!proof.verify(&event, &root, 4));
+            }
+            other => panic!("unexpected reply {:?}", other),
+        }
+        assert_eq!(tl.handle(&TimelineCall::ProveEvent { event: [0xEE; 32], before_height: 4 }), TimelineReply::NotFound);
+    }
+}
@@ -0,0 +1,235 @@
+// src/timelock.rs
+// TIMELOCK: "Encrypt to the future" with the Synergeia VDF.
+//
+// A sealed message is encrypted under a key derived from Z_T, the VDF output
+// after T iterations of a published puzzle (Z_0, C). Nobody can derive the
+// key without running the T sequential steps, so the message opens only
+// after that much wall-clock time - or once someone (e.g. the chain) has
+// computed Z_T and published it with a STARK proof, which anyone can check
+// in O(queries) instead of re-grinding.
+//
+// Sealing goes through a Trapdoor: a puzzle the sealer solved ahead of
+// time, holding Z_T. Generating one costs the T steps once, off the
+// sealing path (a background job, or a spare core); `seal` itself only
+// derives keys from the stored Z_T. Any number of messages can be sealed
+// under one trapdoor, and they all open together, since each carries the
+// same puzzle; use a fresh trapdoor for each release time. The trapdoor is
+// a secret until the delay has passed and is wiped on drop.
+// Short delays only in tests.
+//
+// Encryption: Flutter keystream (flt_cipher) + GSH-256 tag over the puzzle,
+// nonce and ciphertext (encrypt-then-MAC).
+
+use crate::flt_cipher::FlutterCipher;
+use crate::gsh::GSH256;
+use crate::redact::Redacted;
+use crate::stark::{PublicInputs, StarkProof, StarkProver, StarkVerifier};
+use crate::vdf::{evaluate_vdf, Octonion};
+use rand::Rng;
+use std::fmt;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+const DOMAIN: &[u8] = b"OLC-TIMELOCK-v1";
+// Fiat-Shamir queries demanded from shortcut proofs
+pub const MIN_QUERIES: usize = 40;
+
+#[derive(Clone, Debug)]
+pub struct TimelockCiphertext {
+    pub z_0: Octonion,
+    pub c: Octonion,
+    pub iterations: usize,
+    pub nonce: [u16; 8],
+    pub body: Vec<u8>,
+    pub tag: [u8; 32],
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OpenError {
+    BadProof,
+    TooFewQueries { got: usize, min: usize },
+    TagMismatch, // Wrong output, or the ciphertext was modified
+}
+
+// A solved puzzle (Z_0, C, T); Z_T is kept as the bytes keys derive from
+pub struct Trapdoor {
+    pub z_0: Octonion,
+    pub c: Octonion,
+    pub iterations: usize,
+    z_t: [u8; 64],
+}
+
+impl Zeroize for Trapdoor {
+    fn zeroize(&mut self) {
+        self.z_t.zeroize();
+    }
+}
+
+impl Drop for Trapdoor {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for Trapdoor {}
+const _: () = crate::redact::assert_zeroize_on_drop::<Trapdoor>();
+
+impl fmt::Debug for Trapdoor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Trapdoor")
+            .field("z_0", &self.z_0)
+            .field("c", &self.c)
+            .field("iterations", &self.iterations)
+            .field("z_t", &Redacted(&self.z_t))
+            .finish()
+    }
+}
+
+impl Trapdoor {
+    /// A fresh puzzle, solved here: the one place the T steps are paid
+    pub fn generate<R: Rng + ?Sized>(iterations: usize, rng: &mut R) -> Self {
+        assert!(iterations > 0, "timelock needs at least one VDF step");
+        let z_0 = Octonion::from_seed(rng.gen());
+        let c = Octonion::from_seed(rng.gen());
+        let z_t = octonion_bytes(&evaluate_vdf(z_0, c, iterations).final_state);
+        Trapdoor { z_0, c, iterations, z_t }
+    }
+}
+
+fn octonion_bytes(z: &Octonion) -> [u8; 64] {
+    let mut out = [0u8; 64];
+    for i in 0..8 {
+        out[i * 8..i * 8 + 8].copy_from_slice(&z.coeffs[i].0.to_le_bytes());
+    }
+    out
+}
+
+// Key schedule: (stream key, MAC key) bound to the whole puzzle.
+fn derive_keys(ct: &TimelockCiphertext, z_t: &[u8; 64]) -> ([u16; 8], [u8; 32]) {
+    let mut okm = GSH256::hash_slices(&[
+        DOMAIN,
+        &octonion_bytes(&ct.z_0),
+        &octonion_bytes(&ct.c),
+        &(ct.iterations as u64).to_le_bytes(),
+        z_t,
+    ]);
+    let mut stream_key = [0u16; 8];
+    for i in 0..8 {
+        stream_key[i] = u16::from_le_bytes([okm[2 * i], okm[2 * i + 1]]);
+    }
+    let mut mac_key = [0u8; 32];
+    mac_key.copy_from_slice(&okm[16..48]);
+    okm.zeroize();
+    (stream_key, mac_key)
+}
+
+fn compute_tag(ct: &TimelockCiphertext, mac_key: &[u8; 32]) -> [u8; 32] {
+    let nonce: Vec<u8> = ct.nonce.iter().flat_map(|n| n.to_le_bytes()).collect();
+    let full = GSH256::hash_slices(&[
+        mac_key,
+        &octonion_bytes(&ct.z_0),
+        &octonion_bytes(&ct.c),
+        &(ct.iterations as u64).to_le_bytes(),
+        &nonce,
+        &(ct.body.len() as u64).to_le_bytes(),
+        &ct.body,
+    ]);
+    let mut tag = [0u8; 32];
+    tag.copy_from_slice(&full[..32]);
+    tag
+}
+
+// Seal `message` so it opens after the trapdoor's T sequential VDF steps;
+// no VDF work here.
+pub fn seal<R: Rng + ?Sized>(message: &[u8], trapdoor: &Trapdoor, rng: &mut R) -> TimelockCiphertext {
+    let (z_0, c, iterations) = (trapdoor.z_0, trapdoor.c, trapdoor.iterations);
+    let mut ct = TimelockCiphertext { z_0, c, iterations, nonce: rng.gen(), body: message.to_vec(), tag: [0; 32] };
+    let (mut stream_key, mut mac_key) = derive_keys(&ct, &trapdoor.z_t);
+    FlutterCipher::new(stream_key, ct.nonce).process(&mut ct.body);
+    stream_key.zeroize();
+    ct.tag = compute_tag(&ct, &mac_key);
+    mac_key.zeroize();
+    ct
+}
+
+fn decrypt(ct: &TimelockCiphertext, z_t: &Octonion) -> Result<Vec<u8>, OpenError> {
+    let (mut stream_key, mut mac_key) = derive_keys(ct, &octonion_bytes(z_t));
+    let expected = compute_tag(ct, &mac_key);
+    mac_key.zeroize();
+    // Compare without early exit
+    if expected.iter().zip(&ct.tag).fold(0u8, |acc, (a, b)| acc | (a ^ b)) != 0 {
+        stream_key.zeroize();
+        return Err(OpenError::TagMismatch);
+    }
+    let mut plain = ct.body.clone();
+    FlutterCipher::new(stream_key, ct.nonce).process(&mut plain);
+    stream_key.zeroize();
+    Ok(plain)
+}
+
+// Shortcut: someone else computed Z_T and proved it.
+pub fn open(ct: &TimelockCiphertext, vdf_output: &Octonion, proof: &StarkProof) -> Result<Vec<u8>, OpenError> {
+    if proof.queried_rows.len() < MIN_QUERIES {
+        return Err(OpenError::TooFewQueries { got: proof.queried_rows.len(), min: MIN_QUERIES });
+    }
+    let inputs = PublicInputs { z_0: ct.z_0, c: ct.c, z_t: *vdf_output, t_iterations: ct.iterations };
+    if !StarkVerifier::verify(proof, &inputs) {
+        return Err(OpenError::BadProof);
+    }
+    decrypt(ct, vdf_output)
+}
+
+// Grind the puzzle: returns Z_T and a proof others can use with `open`.
+pub fn solve(ct: &TimelockCiphertext) -> (Octonion, StarkProof) {
+    let run = evaluate_vdf(ct.z_0, ct.c, ct.iterations);
+    let inputs = PublicInputs { z_0: ct.z_0, c: ct.c, z_t: run.final_state, t_iterations: ct.iterations };
+    let proof = StarkProver::prove(&run.trace, &inputs, MIN_QUERIES);
+    (run.final_state, proof)
+}
+
+// Open by doing the sequential work locally.
+pub fn open_by_grinding(ct: &TimelockCiphertext) -> Result<Vec<u8>, OpenError> {
+    decrypt(ct, &evaluate_vdf(ct.z_0, ct.c, ct.iterations).final_state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn seal_then_open_both_ways() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let msg = b"release after 64 steps";
+        let trapdoor = Trapdoor::generate(64, &mut rng);
+        let ct = seal(msg, &trapdoor, &mut rng);
+        assert_ne!(&ct.body[..], &msg[..]);
+
+        assert_eq!(open_by_grinding(&ct).unwrap(), msg);
+        let (z_t, proof) = solve(&ct);
+        assert_eq!(open(&ct, &z_t, &proof).unwrap(), msg);
+
+        // A second message under the same trapdoor opens with the same output
+        let other = seal(b"and this one", &trapdoor, &mut rng);
+        assert_ne!(other.nonce, ct.nonce);
+        assert_eq!(open(&other, &z_t, &proof).unwrap(), b"and this one");
+        assert!(!format!("{:?}", trapdoor).contains(&format!("{:?}", z_t.coeffs[0])));
+    }
+
+    #[test]
+    fn wrong_output_or_tampering_fails() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut ct = seal(b"secret", &Trapdoor::generate(32, &mut rng), &mut rng);
+        let (z_t, proof) = solve(&ct);
+
+        // A proof for a different claimed output fails the boundary check
+        assert_eq!(open(&ct, &Octonion::from_seed(1), &proof), Err(OpenError::BadProof));
+
+        let mut short = proof.clone();
+        short.queried_rows.truncate(3);
+        assert_eq!(open(&ct, &z_t, &short), Err(OpenError::TooFewQueries { got: 3, min: MIN_QUERIES }));
+
+        ct.body[0] ^= 1;
+        assert_eq!(open(&ct, &z_t, &proof), Err(OpenError::TagMismatch));
+    }
+}
@@ -0,0 +1,146 @@
+// src/trigintaduonion.rs
+// Implements the 32-dimensional Trigintaduonion Algebra (T)
+// One Cayley-Dickson doubling past the sedenions, over the same Z_2^64
+// lanes: T = (S_low, S_high). Flexible like every level of the tower, but
+// further from associative than S; meant as a 2048-bit sponge state for a
+// wider-capacity GSH and for chaos experiments.
+
+use crate::algebra::cayley_dickson as cd;
+use crate::sedenion::{Octonion, Sedenion};
+use std::ops::{Add, BitXor, Mul, Neg, Sub};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Trigintaduonion {
+    pub low: Sedenion,  // Coefficients 0-15
+    pub high: Sedenion, // Coefficients 16-31
+}
+
+impl Trigintaduonion {
+    pub fn new(low: Sedenion, high: Sedenion) -> Self {
+        Trigintaduonion { low, high }
+    }
+
+    pub fn zero() -> Self {
+        Trigintaduonion::new(Sedenion::zero(), Sedenion::zero())
+    }
+
+    pub fn one() -> Self {
+        Trigintaduonion::new(Sedenion::one(), Sedenion::zero())
+    }
+
+    // Four octonion blocks derived from one seed
+    pub fn from_seed(seed: u64) -> Self {
+        let o = |k: u64| Octonion::from_seed(seed.wrapping_add(k.wrapping_mul(0x9E37_79B9_7F4A_7C15)));
+        Trigintaduonion::new(Sedenion::new(o(0), o(1)), Sedenion::new(o(2), o(3)))
+    }
+
+    pub fn basis(i: usize) -> Self {
+        if i < 16 {
+            Trigintaduonion::new(Sedenion::basis(i), Sedenion::zero())
+        } else {
+            Trigintaduonion::new(Sedenion::zero(), Sedenion::basis(i - 16))
+        }
+    }
+
+    fn to_cd(self) -> cd::Trigintaduonion<u64> {
+        cd::Trigintaduonion::new(self.low.to_cd(), self.high.to_cd())
+    }
+
+    fn from_cd(t: cd::Trigintaduonion<u64>) -> Self {
+        Trigintaduonion::new(Sedenion::from_cd(t.low), Sedenion::from_cd(t.high))
+    }
+
+    // T* = (L*, -H): the real part is kept, all 31 imaginary parts negated
+    pub fn conjugate(&self) -> Self {
+        Trigintaduonion::new(self.low.conjugate(), -self.high)
+    }
+
+    // Sum of squared coefficients (mod 2^64)
+    pub fn norm_sq(&self) -> u64 {
+        self.low.norm_sq().wrapping_add(self.high.norm_sq())
+    }
+
+    pub fn lanes(&self) -> [u64; 32] {
+        let mut out = [0u64; 32];
+        cd::Algebra::write_coeffs(&self.to_cd(), &mut out);
+        out
+    }
+}
+
+impl Mul for Trigintaduonion {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Trigintaduonion::from_cd(self.to_cd() * other.to_cd())
+    }
+}
+
+impl Add for Trigintaduonion {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Trigintaduonion::new(self.low + other.low, self.high + other.high)
+    }
+}
+
+impl Sub for Trigintaduonion {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        Trigintaduonion::new(self.low - other.low, self.high - other.high)
+    }
+}
+
+impl Neg for Trigintaduonion {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Trigintaduonion::new(-self.low, -self.high)
+    }
+}
+
+// Scalar multiplication
+impl Mul<u64> for Trigintaduonion {
+    type Output = Self;
+    fn mul(self, s: u64) -> Self {
+        Trigintaduonion::new(self.low * s, self.high * s)
+    }
+}
+
+impl BitXor for Trigintaduonion {
+    type Output = Self;
+    fn bitxor(self, other: Self) -> Self {
+        Trigintaduonion::new(self.low ^ other.low, self.high ^ other.high)
+    }
+}
+
+// The Trigintaduonion Associator: [X, Y, Z] = (XY)Z - X(YZ)
+pub fn associator(x: Trigintaduonion, y: Trigintaduonion, z: Trigintaduonion) -> Trigintaduonion {
+    crate::algebra::NonAssociativeAlgebra::associator(x, y, z)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra::laws::{self, Law};
+
+    #[test]
+    fn extends_the_sedenions() {
+        // Sedenions embed as the low half
+        let (s, t) = (Sedenion::new(Octonion::from_seed(1), Octonion::from_seed(2)), Sedenion::new(Octonion::from_seed(3), Octonion::from_seed(4)));
+        let lift = |s: Sedenion| Trigintaduonion::new(s, Sedenion::zero());
+        assert_eq!(lift(s) * lift(t), lift(s * t));
+        assert_eq!(crate::sedenion::associator(s, t, s), associator(lift(s), lift(t), lift(s)).low);
+
+        let x = Trigintaduonion::from_seed(5);
+        assert_eq!(x * Trigintaduonion::one(), x);
+        assert_eq!(x * x.conjugate(), Trigintaduonion::new(Sedenion::new(Octonion::from_real(x.norm_sq()), Octonion::zero()), Sedenion::zero()));
+        // e_i e_i = -1 for every imaginary unit
+        for i in 1..32 {
+            assert_eq!(Trigintaduonion::basis(i) * Trigintaduonion::basis(i), -Trigintaduonion::one(), "e_{}", i);
+        }
+        assert_eq!(x.lanes()[16..24], x.high.low.coeffs);
+
+        // Only flexibility survives, and every sampled triple fails to associate
+        let survey = laws::survey(16, Trigintaduonion::from_seed);
+        assert_eq!(survey.satisfied(), vec![Law::Flexible]);
+        assert_eq!(survey.non_associativity(), 1.0);
+    }
+}
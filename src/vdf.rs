@@ -1,10 +1,10 @@
-use std::ops::{Add, Mul, Sub};
+use std::ops::{Add, Mul, Neg, Sub};
 
 // ============================================================================
 // 1. STARK-Friendly Prime Field (Goldilocks Prime)
 // p = 2^64 - 2^32 + 1 = 0xFFFFFFFF00000001
 // ============================================================================
-const P: u64 = 0xFFFFFFFF00000001;
+pub const P: u64 = 0xFFFFFFFF00000001;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Fp(pub u64);
@@ -12,9 +12,18 @@ pub struct Fp(pub u64);
 impl Fp {
     #[inline(always)]
     pub fn new(val: u64) -> Self {
+        if cfg!(feature = "ct") {
+            return Fp(ct_canonical(val));
+        }
         // Simple modulo reduction
         Fp(if val >= P { val % P } else { val })
     }
+
+    // No reduction: None unless `val` is already below P
+    #[inline(always)]
+    pub fn canonical(val: u64) -> Option<Self> {
+        (val < P).then_some(Fp(val))
+    }
     
     #[inline(always)]
     pub fn zero() -> Self {
@@ -35,12 +44,23 @@ impl Fp {
         }
         res
     }
+
+    // Multiplicative inverse via Fermat (x^(p-2)); None for zero
+    pub fn inverse(&self) -> Option<Self> {
+        if self.0 == 0 {
+            return None;
+        }
+        Some(self.pow(P - 2))
+    }
 }
 
 impl Add for Fp {
     type Output = Self;
     #[inline(always)]
     fn add(self, rhs: Self) -> Self {
+        if cfg!(feature = "ct") {
+            return Fp(ct_add(self.0, rhs.0));
+        }
         let sum = (self.0 as u128) + (rhs.0 as u128);
         Fp(if sum >= P as u128 { (sum - P as u128) as u64 } else { sum as u64 })
     }
@@ -50,6 +70,9 @@ impl Sub for Fp {
     type Output = Self;
     #[inline(always)]
     fn sub(self, rhs: Self) -> Self {
+        if cfg!(feature = "ct") {
+            return Fp(ct_sub(self.0, rhs.0));
+        }
         let diff = if self.0 >= rhs.0 {
             self.0 - rhs.0
         } else {
@@ -59,93 +82,111 @@ impl Sub for Fp {
     }
 }
 
+impl Neg for Fp {
+    type Output = Self;
+    #[inline(always)]
+    fn neg(self) -> Self {
+        Fp::zero() - self
+    }
+}
+
 impl Mul for Fp {
     type Output = Self;
     #[inline(always)]
     fn mul(self, rhs: Self) -> Self {
         let prod = (self.0 as u128) * (rhs.0 as u128);
+        if cfg!(feature = "ct") {
+            return Fp(ct_reduce128(prod));
+        }
         Fp((prod % (P as u128)) as u64)
     }
 }
 
-// ============================================================================
-// 2. Octonion Algebra over F_p
-// ============================================================================
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub struct Octonion {
-    pub coeffs: [Fp; 8],
+// --- BRANCH-FREE REDUCTION (`--features ct`) ---
+// The default ops above branch on the operands and reduce products with a
+// u128 division, whose timing is the platform's business. With `ct` the
+// same results come from masks and the Goldilocks identities
+// 2^64 = 2^32 - 1 and 2^96 = -1 (mod p), with no data-dependent branch.
+const EPSILON: u64 = (1 << 32) - 1; // 2^64 mod p
+
+#[inline(always)]
+fn ct_mask(bit: bool) -> u64 {
+    (std::hint::black_box(bit as u64)).wrapping_neg()
 }
 
-impl Octonion {
-    pub fn new(coeffs: [Fp; 8]) -> Self {
-        Octonion { coeffs }
-    }
+// x mod p for any u64 (all of which are < 2p)
+#[inline(always)]
+fn ct_canonical(x: u64) -> u64 {
+    let (reduced, borrow) = x.overflowing_sub(P);
+    let keep = ct_mask(borrow);
+    (x & keep) | (reduced & !keep)
+}
 
-    pub fn zero() -> Self {
-        Octonion { coeffs: [Fp::zero(); 8] }
-    }
+#[inline(always)]
+fn ct_add(a: u64, b: u64) -> u64 {
+    // a + b = sum + carry * 2^64 = sum + carry * EPSILON (mod p)
+    let (sum, carry) = a.overflowing_add(b);
+    ct_canonical(sum.wrapping_add(EPSILON & ct_mask(carry)))
+}
 
-    pub fn is_zero(&self) -> bool {
-        self.coeffs.iter().all(|&x| x.0 == 0)
-    }
+#[inline(always)]
+fn ct_sub(a: u64, b: u64) -> u64 {
+    let (diff, borrow) = a.overflowing_sub(b);
+    diff.wrapping_add(P & ct_mask(borrow))
+}
 
+#[inline(always)]
+fn ct_reduce128(x: u128) -> u64 {
+    let (lo, hi) = (x as u64, (x >> 64) as u64);
+    let (hi_hi, hi_lo) = (hi >> 32, hi & EPSILON);
+    // lo - hi_hi * 2^96 - ... : 2^96 = -1
+    let (t0, borrow) = lo.overflowing_sub(hi_hi);
+    let t0 = t0.wrapping_sub(EPSILON & ct_mask(borrow));
+    // + hi_lo * 2^64 = hi_lo * EPSILON
+    let (t1, carry) = t0.overflowing_add(hi_lo * EPSILON);
+    ct_canonical(t1.wrapping_add(EPSILON & ct_mask(carry)))
+}
+
+// ============================================================================
+// 2. Octonion Algebra over F_p
+// ============================================================================
+// The canonical algebra::Octonion over the Goldilocks field.
+pub type Octonion = crate::algebra::Octonion<Fp>;
+pub use crate::algebra::octonion::associator;
+use crate::algebra::nonassociative::quadratic_map;
+
+impl Octonion {
     // Deterministic pseudo-random initialization mapping to F_p
     pub fn from_seed(seed: u64) -> Self {
         let mut coeffs = [Fp::zero(); 8];
         let mut current = seed;
-        for i in 0..8 {
+        for c in &mut coeffs {
             current = current.wrapping_mul(6364136223846793005).wrapping_add(1);
-            coeffs[i] = Fp::new(current);
+            *c = Fp::new(current);
         }
         Octonion::new(coeffs)
     }
-}
 
-impl Add for Octonion {
-    type Output = Self;
-    #[inline(always)]
-    fn add(self, other: Self) -> Self {
-        let mut c = [Fp::zero(); 8];
-        for i in 0..8 { c[i] = self.coeffs[i] + other.coeffs[i]; }
-        Octonion::new(c)
+    pub fn conjugate(&self) -> Self {
+        self.conj()
     }
-}
 
-impl Sub for Octonion {
-    type Output = Self;
-    #[inline(always)]
-    fn sub(self, other: Self) -> Self {
-        let mut c = [Fp::zero(); 8];
-        for i in 0..8 { c[i] = self.coeffs[i] - other.coeffs[i]; }
-        Octonion::new(c)
+    // x^-1 = conj(x) / N(x), with N the field norm (algebra::octonion).
+    // Over F_p the norm form is isotropic, so nonzero octonions with
+    // N(x) = 0 exist; those have no inverse.
+    pub fn inverse(&self) -> Result<Self, DivisionError> {
+        self.try_inverse().ok_or(DivisionError::ZeroNorm)
     }
-}
-
-// Full Non-Associative Fano Plane Multiplication over F_p
-impl Mul for Octonion {
-    type Output = Self;
-    #[inline(always)]
-    fn mul(self, other: Self) -> Self {
-        let a = &self.coeffs;
-        let b = &other.coeffs;
-        let mut res = [Fp::zero(); 8];
-        
-        res[0] = a[0]*b[0] - a[1]*b[1] - a[2]*b[2] - a[3]*b[3] - a[4]*b[4] - a[5]*b[5] - a[6]*b[6] - a[7]*b[7];
-        res[1] = a[0]*b[1] + a[1]*b[0] + a[2]*b[3] - a[3]*b[2] + a[4]*b[5] - a[5]*b[4] - a[6]*b[7] + a[7]*b[6];
-        res[2] = a[0]*b[2] - a[1]*b[3] + a[2]*b[0] + a[3]*b[1] + a[4]*b[6] + a[5]*b[7] - a[6]*b[4] - a[7]*b[5];
-        res[3] = a[0]*b[3] + a[1]*b[2] - a[2]*b[1] + a[3]*b[0] + a[4]*b[7] - a[5]*b[6] + a[6]*b[5] - a[7]*b[4];
-        res[4] = a[0]*b[4] - a[1]*b[5] - a[2]*b[6] - a[3]*b[7] + a[4]*b[0] + a[5]*b[1] + a[6]*b[2] + a[7]*b[3];
-        res[5] = a[0]*b[5] + a[1]*b[4] - a[2]*b[7] + a[3]*b[6] - a[4]*b[1] + a[5]*b[0] - a[6]*b[3] + a[7]*b[2];
-        res[6] = a[0]*b[6] + a[1]*b[7] + a[2]*b[4] - a[3]*b[5] - a[4]*b[2] + a[5]*b[3] + a[6]*b[0] - a[7]*b[1];
-        res[7] = a[0]*b[7] - a[1]*b[6] + a[2]*b[5] + a[3]*b[4] - a[4]*b[3] - a[5]*b[2] + a[6]*b[1] + a[7]*b[0];
 
-        Octonion::new(res)
+    // x / y = x * y^-1; (x * y) / y = x by alternativity
+    pub fn try_div(&self, y: &Self) -> Result<Self, DivisionError> {
+        Ok(*self * y.inverse()?)
     }
 }
 
-// The Associator: [A, B, C] = (AB)C - A(BC)
-pub fn associator(x: Octonion, y: Octonion, z: Octonion) -> Octonion {
-    ((x * y) * z) - (x * (y * z))
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DivisionError {
+    ZeroNorm,
 }
 
 // ============================================================================
@@ -154,16 +195,12 @@ pub fn associator(x: Octonion, y: Octonion, z: Octonion) -> Octonion {
 // 3rd element out of the current state, preventing associative trapping.
 // ============================================================================
 pub fn algebraic_hash_oracle(x: &Octonion) -> Octonion {
-    let mut y = [Fp::zero(); 8];
-    
     // Non-linear S-Box layer: x -> x^7 (7 is coprime to P-1, ensuring a true permutation)
-    for i in 0..8 {
-        y[i] = x.coeffs[i].pow(7);
-    }
+    let y = x.coeffs.map(|c| c.pow(7));
     
     // Linear Diffusion layer (MDS matrix mapping simulation)
     let mut sum = Fp::zero();
-    for i in 0..8 { sum = sum + y[i]; }
+    for y_i in y { sum = sum + y_i; }
     
     let mut res = [Fp::zero(); 8];
     for i in 0..8 {
@@ -184,19 +221,32 @@ pub struct OctoStarkTrace {
 }
 
 pub fn evaluate_vdf(z_0: Octonion, c: Octonion, iterations: usize) -> OctoStarkTrace {
+    evaluate_vdf_with(z_0, c, iterations, MulBackend::Schoolbook)
+}
+
+// Same recurrence, same trace; only the multiplications differ (section 5).
+pub fn evaluate_vdf_with(z_0: Octonion, c: Octonion, iterations: usize, backend: MulBackend) -> OctoStarkTrace {
     let mut z = z_0;
     
     // Pre-allocate the trace vector to avoid reallocation overhead
     let mut trace = Vec::with_capacity(iterations + 1);
     trace.push(z);
+    let c_zorn = Zorn::from_octonion(&c);
     
     for _ in 0..iterations {
         // Z_{n+1} = Z_n^2 + C + [Z_n, C, H(Z_n)]
-        let sq = z * z;
         let dynamic_generator = algebraic_hash_oracle(&z);
-        let assoc = associator(z, c, dynamic_generator);
-        
-        z = sq + c + assoc;
+        z = match backend {
+            MulBackend::Schoolbook => {
+                let (map, assoc) = quadratic_map(z, c, dynamic_generator);
+                map + assoc
+            }
+            MulBackend::Zorn => {
+                let (zz, h) = (Zorn::from_octonion(&z), Zorn::from_octonion(&dynamic_generator));
+                let assoc = zz.mul(&c_zorn).mul(&h).sub(&zz.mul(&c_zorn.mul(&h)));
+                zz.mul(&zz).add(&assoc).to_octonion() + c
+            }
+        };
         trace.push(z);
     }
     
@@ -205,3 +255,181 @@ pub fn evaluate_vdf(z_0: Octonion, c: Octonion, iterations: usize) -> OctoStarkT
         trace,
     }
 }
+
+// ============================================================================
+// 5. Zorn Vector-Matrix Form
+// Over F_p the octonions are split (p = 1 mod 4, so i = sqrt(-1) = 2^48
+// exists), hence isomorphic to Zorn's algebra of matrices
+//     [ a  x ]     a, b in F_p;  x, y in F_p^3
+//     [ y  b ]
+// with the product
+//     [a x; y b][c x'; y' d] = [ac + x.y',  a x' + d x + y × y';
+//                               c y + b y' - x × x',  bd + y.x']
+// That is 32 base-field multiplications against 64 for the Cayley-Dickson
+// schoolbook expansion. The isomorphism is the basis
+//     e = (1 + i e1)/2,  f = (1 - i e1)/2          (idempotents: a, b)
+//     u_k = (e_2k + i e_2k+1)/2, except u_3 = (e6 - i e7)/2   (x)
+//     v_k = -(e_2k - i e_2k+1)/2, except v_3 = -(e6 + i e7)/2 (y)
+// so converting costs a handful of multiplications by i and 1/2, and the
+// grind loop wins as soon as it does more than one product per step.
+// ============================================================================
+const SQRT_NEG_ONE: Fp = Fp(1 << 48); // 2^96 = -1 mod p
+const HALF: Fp = Fp(0x7FFFFFFF80000001); // (p + 1) / 2
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MulBackend {
+    Schoolbook,
+    Zorn,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Zorn {
+    pub a: Fp,
+    pub x: [Fp; 3],
+    pub y: [Fp; 3],
+    pub b: Fp,
+}
+
+fn dot3(u: &[Fp; 3], v: &[Fp; 3]) -> Fp {
+    u[0] * v[0] + u[1] * v[1] + u[2] * v[2]
+}
+
+fn cross3(u: &[Fp; 3], v: &[Fp; 3]) -> [Fp; 3] {
+    [u[1] * v[2] - u[2] * v[1], u[2] * v[0] - u[0] * v[2], u[0] * v[1] - u[1] * v[0]]
+}
+
+impl Zorn {
+    pub fn from_octonion(o: &Octonion) -> Self {
+        let c = &o.coeffs;
+        let i = SQRT_NEG_ONE;
+        let (i1, i3, i5, i7) = (i * c[1], i * c[3], i * c[5], i * c[7]);
+        Zorn {
+            a: c[0] - i1,
+            x: [c[2] - i3, c[4] - i5, c[6] + i7],
+            y: [-c[2] - i3, -c[4] - i5, -c[6] + i7],
+            b: c[0] + i1,
+        }
+    }
+
+    pub fn to_octonion(&self) -> Octonion {
+        let i_half = SQRT_NEG_ONE * HALF;
+        let (x, y) = (&self.x, &self.y);
+        Octonion::new([
+            (self.a + self.b) * HALF,
+            (self.a - self.b) * i_half,
+            (x[0] - y[0]) * HALF,
+            (x[0] + y[0]) * i_half,
+            (x[1] - y[1]) * HALF,
+            (x[1] + y[1]) * i_half,
+            (x[2] - y[2]) * HALF,
+            -((x[2] + y[2]) * i_half),
+        ])
+    }
+
+    pub fn mul(&self, rhs: &Self) -> Self {
+        let (yy, xx) = (cross3(&self.y, &rhs.y), cross3(&self.x, &rhs.x));
+        let mut x = [Fp::zero(); 3];
+        let mut y = [Fp::zero(); 3];
+        for k in 0..3 {
+            x[k] = self.a * rhs.x[k] + rhs.b * self.x[k] + yy[k];
+            y[k] = rhs.a * self.y[k] + self.b * rhs.y[k] - xx[k];
+        }
+        Zorn {
+            a: self.a * rhs.a + dot3(&self.x, &rhs.y),
+            x,
+            y,
+            b: self.b * rhs.b + dot3(&self.y, &rhs.x),
+        }
+    }
+
+    pub fn add(&self, rhs: &Self) -> Self {
+        let mut out = *self;
+        out.a = out.a + rhs.a;
+        out.b = out.b + rhs.b;
+        for k in 0..3 {
+            out.x[k] = out.x[k] + rhs.x[k];
+            out.y[k] = out.y[k] + rhs.y[k];
+        }
+        out
+    }
+
+    pub fn sub(&self, rhs: &Self) -> Self {
+        let mut out = *self;
+        out.a = out.a - rhs.a;
+        out.b = out.b - rhs.b;
+        for k in 0..3 {
+            out.x[k] = out.x[k] - rhs.x[k];
+            out.y[k] = out.y[k] - rhs.y[k];
+        }
+        out
+    }
+
+    // det = ab - x.y is the octonion norm
+    pub fn det(&self) -> Fp {
+        self.a * self.b - dot3(&self.x, &self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inverse_division_and_multiplicative_norm() {
+        let (x, y) = (Octonion::from_seed(1), Octonion::from_seed(2));
+        assert_eq!((x * y).norm(), x.norm() * y.norm());
+        assert_eq!(x * x.inverse().unwrap(), Octonion::one());
+        assert_eq!(x.inverse().unwrap() * x, Octonion::one());
+        assert_eq!((x * y).try_div(&y), Ok(x));
+        assert_eq!(x.conjugate().coeffs[0], x.coeffs[0]);
+
+        // 1 + e1 * sqrt(-1) is nonzero but isotropic: N = 1 + (-1) = 0
+        let i = Fp(7).pow((P - 1) / 4); // 7 is a non-residue, so this squares to -1
+        assert_eq!(i * i, -Fp(1));
+        let mut iso = Octonion::one();
+        iso.coeffs[1] = i;
+        assert_eq!(iso.inverse(), Err(DivisionError::ZeroNorm));
+        assert_eq!(Octonion::zero().inverse(), Err(DivisionError::ZeroNorm));
+    }
+
+    #[test]
+    fn zorn_form_is_an_isomorphism() {
+        assert_eq!(SQRT_NEG_ONE * SQRT_NEG_ONE, -Fp(1));
+        assert_eq!(HALF + HALF, Fp(1));
+        for seed in 0..8 {
+            let (x, y) = (Octonion::from_seed(seed), Octonion::from_seed(seed + 100));
+            let (zx, zy) = (Zorn::from_octonion(&x), Zorn::from_octonion(&y));
+            assert_eq!(zx.to_octonion(), x);
+            assert_eq!(zx.mul(&zy).to_octonion(), x * y);
+            assert_eq!(zx.det(), x.norm());
+        }
+        assert_eq!(Zorn::from_octonion(&Octonion::one()).mul(&Zorn::from_octonion(&Octonion::from_seed(3))).to_octonion(), Octonion::from_seed(3));
+
+        let (z0, c) = (Octonion::from_seed(11), Octonion::from_seed(12));
+        let school = evaluate_vdf_with(z0, c, 50, MulBackend::Schoolbook);
+        let zorn = evaluate_vdf_with(z0, c, 50, MulBackend::Zorn);
+        assert_eq!(school.trace, zorn.trace);
+    }
+
+    #[test]
+    fn constant_time_reduction_matches_reference() {
+        let edges = [0, 1, 2, EPSILON, EPSILON + 1, P - 2, P - 1, 1 << 63, u64::MAX - 1];
+        let reference = |x: u128| (x % P as u128) as u64;
+        for &a in &edges {
+            assert_eq!(ct_canonical(a), reference(a as u128));
+            for &b in &edges {
+                let (a, b) = (a % P, b % P);
+                assert_eq!(ct_add(a, b), reference(a as u128 + b as u128));
+                assert_eq!(ct_sub(a, b), reference(a as u128 + P as u128 - b as u128));
+                assert_eq!(ct_reduce128(a as u128 * b as u128), reference(a as u128 * b as u128));
+            }
+        }
+        let mut x = 0x0123_4567_89AB_CDEFu64;
+        for _ in 0..1000 {
+            x = x.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let y = x.rotate_left(29);
+            assert_eq!(ct_reduce128(x as u128 * y as u128), reference(x as u128 * y as u128));
+            assert_eq!(ct_canonical(x), x % P);
+        }
+    }
+}
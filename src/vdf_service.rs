@@ -0,0 +1,724 @@
+// src/vdf_service.rs
+// VDF SERVICE: Provable delay / randomness over HTTP, without a chain.
+//
+// Clients submit a seed and a delay T; the service runs the octonion VDF
+// for T steps from a start state derived from the seed, proves the run
+// (stark.rs) and hands back the final state and the proof. Anyone can check
+// the result with StarkVerifier against `public_inputs(seed, T, output)`.
+//
+// REST API (every call needs `Authorization: Bearer <token>`):
+//   POST /jobs?seed=<hex>&delay=<T>   -> 202 {"id": N, "status": "queued"}
+//   GET  /jobs/<N>                    -> 200 {"id", "status", "delay", ...}
+//   GET  /jobs/<N>/result             -> 200 {"output": hex, "proof": hex}
+//                                        (409 until the job is done)
+// Clients only see their own jobs; other ids answer 404.
+//
+// Jobs live in a storage.rs Store file used as an append-only log: every
+// change appends one `<id>\t<job>` line, and since a later line for a key
+// replaces an earlier one, loading the file replays the log. A restart
+// resumes the queue (jobs caught running are queued again) and compacts
+// the file, as does the log outgrowing the live jobs several times over.
+// Finished jobs are evicted `retain_ms` after submission (a tombstone line),
+// so neither memory nor the file grows with every job ever served.
+//
+// Evaluation happens on a worker thread outside the service lock. Each
+// connection is served on its own thread (at most MAX_CONNECTIONS at once,
+// further ones are closed unanswered) and gets CONNECTION_TIMEOUT in total
+// to send its request and take the response, so a client trickling bytes
+// holds only its own thread, and only that long.
+
+use crate::algebra::ct::ConstantTimeEq;
+use crate::algebra::encoding::{hex, unhex};
+use crate::gsh::GSH256;
+use crate::merkle::Gsh32;
+use crate::stark::{PublicInputs, StarkProof, StarkProver};
+use crate::storage::{entry_line, Store, StoreError};
+use crate::vdf::{self, Fp, Octonion};
+use crate::wire::{Reader, Wire};
+use std::collections::BTreeMap;
+use std::fs::OpenOptions;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const DOMAIN: &[u8] = b"OLC-VDF-SERVICE-v1";
+const NAMESPACE: &str = "vdf_jobs";
+const SCHEMA_VERSION: u32 = 1;
+const MAX_SEED_BYTES: usize = 1024;
+const MAX_REQUEST_BYTES: usize = 16 * 1024;
+pub const CONNECTION_TIMEOUT: Duration = Duration::from_secs(5);
+pub const MAX_CONNECTIONS: usize = 64;
+// Log value of an evicted job
+const EVICTED: &str = "evicted";
+// Compact once the log holds this many records per live job (plus slack)
+const COMPACT_FACTOR: usize = 4;
+const COMPACT_SLACK: usize = 64;
+
+#[derive(Clone, Debug)]
+pub struct ServiceConfig {
+    pub max_delay: usize,
+    pub max_pending: usize, // Queued + running, across all clients
+    pub queries: usize,     // STARK queries per proof
+    pub retain_ms: u64,     // How long a finished job stays fetchable, from submission
+}
+
+impl Default for ServiceConfig {
+    fn default() -> Self {
+        ServiceConfig { max_delay: 1 << 20, max_pending: 64, queries: 40, retain_ms: 24 * 60 * 60 * 1000 }
+    }
+}
+
+// --- THE COMPUTATION ---
+fn octonion_from_digest(digest: &[u8; 64]) -> Octonion {
+    Octonion::new(std::array::from_fn(|i| Fp::new(u64::from_le_bytes(digest[8 * i..8 * i + 8].try_into().unwrap()))))
+}
+
+// A singular state (field norm 0) is a zero divisor, and squaring it loses
+// information from the first step, so derivation retries with a counter.
+// That happens with probability ~2^-64: ordinary seeds never move.
+fn derive(label: &[u8], seed: &[u8]) -> Octonion {
+    let mut z = octonion_from_digest(&GSH256::hash_slices(&[DOMAIN, label, seed]));
+    let mut retry = 0u32;
+    while z.is_singular() {
+        retry += 1;
+        z = octonion_from_digest(&GSH256::hash_slices(&[DOMAIN, label, seed, &retry.to_le_bytes()]));
+    }
+    z
+}
+
+// Start state and delay constant for a client seed, both invertible
+pub fn start_state(seed: &[u8]) -> (Octonion, Octonion) {
+    (derive(b"z0", seed), derive(b"c", seed))
+}
+
+pub fn public_inputs(seed: &[u8], delay: usize, output: Octonion) -> PublicInputs {
+    let (z_0, c) = start_state(seed);
+    PublicInputs { z_0, c, z_t: output, t_iterations: delay }
+}
+
+pub fn evaluate(seed: &[u8], delay: usize, queries: usize) -> (Octonion, StarkProof) {
+    let (z_0, c) = start_state(seed);
+    let run = vdf::evaluate_vdf(z_0, c, delay);
+    let proof = StarkProver::prove(&run.trace, &public_inputs(seed, delay, run.final_state), queries);
+    (run.final_state, proof)
+}
+
+// --- JOBS ---
+#[derive(Clone, Debug, PartialEq)]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Done { output: Octonion, proof: StarkProof },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Job {
+    pub id: u64,
+    pub client: String,
+    pub seed: Vec<u8>,
+    pub delay: usize,
+    pub submitted_ms: u64,
+    pub status: JobStatus,
+}
+
+impl Job {
+    fn status_name(&self) -> &'static str {
+        match self.status {
+            JobStatus::Queued => "queued",
+            JobStatus::Running => "running",
+            JobStatus::Done { .. } => "done",
+        }
+    }
+
+    // "<status> <delay> <submitted_ms> <client> <seed> [<output> <proof>]"
+    fn encode(&self) -> String {
+        let head = format!("{} {} {} {} {}", self.status_name(), self.delay, self.submitted_ms, self.client, hex(&self.seed));
+        match &self.status {
+            JobStatus::Done { output, proof } => format!("{} {} {}", head, hex(&output.to_bytes()), hex(&proof.to_bytes())),
+            _ => head,
+        }
+    }
+
+    fn decode(id: u64, value: &str) -> Option<Job> {
+        let mut parts = value.splitn(6, ' ');
+        let status = parts.next()?;
+        let delay = parts.next()?.parse().ok()?;
+        let submitted_ms = parts.next()?.parse().ok()?;
+        let client = parts.next()?.to_string();
+        let seed = unhex(parts.next()?)?;
+        let rest = parts.next().unwrap_or("");
+        let status = match status {
+            // A job interrupted mid-run starts over
+            "queued" | "running" => JobStatus::Queued,
+            "done" => {
+                let (output, proof) = rest.split_once(' ')?;
+                JobStatus::Done { output: decode_wire(&unhex(output)?)?, proof: decode_wire(&unhex(proof)?)? }
+            }
+            _ => return None,
+        };
+        Some(Job { id, client, seed, delay, submitted_ms, status })
+    }
+}
+
+// Zero-padded so the sorted store lists jobs in order
+fn log_key(id: u64) -> String {
+    format!("{:020}", id)
+}
+
+fn decode_wire<T: Wire>(bytes: &[u8]) -> Option<T> {
+    let mut r = Reader::new(bytes);
+    let value = T::decode(&mut r).ok()?;
+    r.finish().ok()?;
+    Some(value)
+}
+
+#[derive(Debug)]
+pub enum QueueError {
+    Store(StoreError),
+    Corrupt { key: String },
+}
+
+pub struct JobQueue {
+    jobs: BTreeMap<u64, Job>,
+    next_id: u64,
+    path: Option<PathBuf>,
+    logged: usize, // Lines appended since the file was last compacted
+}
+
+impl JobQueue {
+    pub fn in_memory() -> Self {
+        JobQueue { jobs: BTreeMap::new(), next_id: 1, path: None, logged: 0 }
+    }
+
+    // Load (or start) the queue persisted at `path`, compacting its log
+    pub fn open(path: PathBuf) -> Result<Self, QueueError> {
+        let mut queue = JobQueue { path: Some(path.clone()), ..JobQueue::in_memory() };
+        if path.exists() {
+            let store = Store::load(&path).map_err(QueueError::Store)?;
+            if let Some(ns) = store.namespace(NAMESPACE) {
+                for (key, value) in &ns.entries {
+                    let corrupt = || QueueError::Corrupt { key: key.clone() };
+                    let id: u64 = key.parse().map_err(|_| corrupt())?;
+                    queue.next_id = queue.next_id.max(id + 1);
+                    if value != EVICTED {
+                        queue.jobs.insert(id, Job::decode(id, value).ok_or_else(corrupt)?);
+                    }
+                }
+            }
+        }
+        queue.compact()?;
+        Ok(queue)
+    }
+
+    // Rewrite the file as one line per live job
+    fn compact(&mut self) -> Result<(), QueueError> {
+        let Some(path) = &self.path else { return Ok(()) };
+        let mut store = Store::new();
+        let ns = store.namespace_mut(NAMESPACE, SCHEMA_VERSION);
+        for job in self.jobs.values() {
+            ns.entries.insert(log_key(job.id), job.encode());
+        }
+        // The highest id ever issued survives as a tombstone, so ids are never reused
+        if self.next_id > 1 && !self.jobs.contains_key(&(self.next_id - 1)) {
+            ns.entries.insert(log_key(self.next_id - 1), EVICTED.to_string());
+        }
+        store.save(path).map_err(QueueError::Store)?;
+        self.logged = 0;
+        Ok(())
+    }
+
+    fn append(&mut self, id: u64, value: &str) -> Result<(), QueueError> {
+        let Some(path) = &self.path else { return Ok(()) };
+        let io = |e| QueueError::Store(StoreError::Io(e));
+        let mut file = OpenOptions::new().append(true).open(path).map_err(io)?;
+        file.write_all(entry_line(&log_key(id), value).as_bytes()).map_err(io)?;
+        self.logged += 1;
+        if self.logged > COMPACT_FACTOR * self.jobs.len() + COMPACT_SLACK {
+            self.compact()?;
+        }
+        Ok(())
+    }
+
+    fn log_job(&mut self, id: u64) -> Result<(), QueueError> {
+        match self.jobs.get(&id).map(Job::encode) {
+            Some(value) => self.append(id, &value),
+            None => Ok(()),
+        }
+    }
+
+    pub fn get(&self, id: u64) -> Option<&Job> {
+        self.jobs.get(&id)
+    }
+
+    pub fn pending(&self) -> usize {
+        self.jobs.values().filter(|j| matches!(j.status, JobStatus::Queued | JobStatus::Running)).count()
+    }
+
+    pub fn submit(&mut self, client: String, seed: Vec<u8>, delay: usize, now_ms: u64) -> Result<u64, QueueError> {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.jobs.insert(id, Job { id, client, seed, delay, submitted_ms: now_ms, status: JobStatus::Queued });
+        self.log_job(id)?;
+        Ok(id)
+    }
+
+    // Oldest queued job, now marked running
+    pub fn start_next(&mut self) -> Result<Option<Job>, QueueError> {
+        let Some(job) = self.jobs.values_mut().find(|j| j.status == JobStatus::Queued) else {
+            return Ok(None);
+        };
+        job.status = JobStatus::Running;
+        let job = job.clone();
+        self.log_job(job.id)?;
+        Ok(Some(job))
+    }
+
+    pub fn finish(&mut self, id: u64, status: JobStatus) -> Result<(), QueueError> {
+        if let Some(job) = self.jobs.get_mut(&id) {
+            job.status = status;
+        }
+        self.log_job(id)
+    }
+
+    // Drop finished jobs submitted more than `retain_ms` ago; how many went
+    pub fn evict(&mut self, now_ms: u64, retain_ms: u64) -> Result<usize, QueueError> {
+        let expired: Vec<u64> = self
+            .jobs
+            .values()
+            .filter(|j| matches!(j.status, JobStatus::Done { .. }) && j.submitted_ms.saturating_add(retain_ms) <= now_ms)
+            .map(|j| j.id)
+            .collect();
+        for &id in &expired {
+            self.jobs.remove(&id);
+            self.append(id, EVICTED)?;
+        }
+        Ok(expired.len())
+    }
+}
+
+// --- AUTHENTICATION ---
+// Only digests of the API tokens are kept; the client id recorded on a job
+// is a short prefix of its token's digest.
+pub struct Auth {
+    digests: Vec<[u8; 32]>,
+}
+
+impl Auth {
+    pub fn new(tokens: &[String]) -> Self {
+        Auth { digests: tokens.iter().map(|t| Gsh32::leaf(t.as_bytes())).collect() }
+    }
+
+    pub fn client(&self, authorization: Option<&str>) -> Option<String> {
+        let token = authorization?.strip_prefix("Bearer ")?.trim();
+        let digest = Gsh32::leaf(token.as_bytes());
+        let words = |d: &[u8; 32]| -> [u64; 4] { std::array::from_fn(|i| u64::from_le_bytes(d[8 * i..8 * i + 8].try_into().unwrap())) };
+        let probe = words(&digest);
+        // Compare against every token, without early exit
+        let known = self.digests.iter().fold(0u8, |found, d| {
+            let w = words(d);
+            found | (0..4).fold(1u8, |eq, i| eq & w[i].ct_eq(&probe[i]).unwrap_u8())
+        });
+        (known == 1).then(|| hex(&digest[..8]))
+    }
+}
+
+// --- HTTP ---
+#[derive(Clone, Debug, Default)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub query: BTreeMap<String, String>,
+    pub headers: BTreeMap<String, String>, // Lower-case names
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String, // JSON
+}
+
+impl Response {
+    fn json(status: u16, body: String) -> Self {
+        Response { status, body }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Response::json(status, format!("{{\"error\": \"{}\"}}", message))
+    }
+}
+
+impl Request {
+    // "GET /jobs/3?x=y HTTP/1.1" plus headers; the body (if any) is ignored
+    pub fn read(stream: &mut impl Read) -> io::Result<Request> {
+        let bad = |why: &str| io::Error::new(io::ErrorKind::InvalidData, why.to_string());
+        let mut reader = BufReader::new(stream.take(MAX_REQUEST_BYTES as u64));
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let mut parts = line.split_whitespace();
+        let (method, target) = (parts.next().ok_or_else(|| bad("empty request"))?, parts.next().ok_or_else(|| bad("no target"))?);
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let mut req = Request { method: method.to_string(), path: path.to_string(), ..Request::default() };
+        for pair in query.split('&').filter(|p| !p.is_empty()) {
+            let (k, v) = pair.split_once('=').unwrap_or((pair, ""));
+            req.query.insert(k.to_string(), v.to_string());
+        }
+        loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+                break;
+            }
+            let (name, value) = line.split_once(':').ok_or_else(|| bad("bad header"))?;
+            req.headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+        }
+        Ok(req)
+    }
+}
+
+impl Response {
+    pub fn write(&self, stream: &mut impl Write) -> io::Result<()> {
+        let reason = match self.status {
+            200 => "OK",
+            202 => "Accepted",
+            400 => "Bad Request",
+            401 => "Unauthorized",
+            404 => "Not Found",
+            409 => "Conflict",
+            503 => "Service Unavailable",
+            _ => "Internal Server Error",
+        };
+        write!(
+            stream,
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.status, reason, self.body.len(), self.body
+        )
+    }
+}
+
+// --- THE SERVICE ---
+pub struct VdfService {
+    pub config: ServiceConfig,
+    pub queue: JobQueue,
+    auth: Auth,
+}
+
+impl VdfService {
+    pub fn new(config: ServiceConfig, queue: JobQueue, auth: Auth) -> Self {
+        VdfService { config, queue, auth }
+    }
+
+    pub fn handle(&mut self, req: &Request, now_ms: u64) -> Response {
+        let Some(client) = self.auth.client(req.headers.get("authorization").map(String::as_str)) else {
+            return Response::error(401, "missing or unknown bearer token");
+        };
+        let segments: Vec<&str> = req.path.trim_matches('/').split('/').collect();
+        match (req.method.as_str(), segments.as_slice()) {
+            ("POST", ["jobs"]) => self.submit(req, client, now_ms),
+            ("GET", ["jobs", id]) => self.with_job(id, &client, |job| Response::json(200, status_json(job))),
+            ("GET", ["jobs", id, "result"]) => self.with_job(id, &client, result_json),
+            _ => Response::error(404, "no such endpoint"),
+        }
+    }
+
+    fn submit(&mut self, req: &Request, client: String, now_ms: u64) -> Response {
+        let seed = match req.query.get("seed").map(|s| unhex(s)) {
+            Some(Some(seed)) if !seed.is_empty() && seed.len() <= MAX_SEED_BYTES => seed,
+            _ => return Response::error(400, "seed must be 1-1024 hex-encoded bytes"),
+        };
+        let delay = match req.query.get("delay").and_then(|d| d.parse::<usize>().ok()) {
+            Some(d) if d >= 1 && d <= self.config.max_delay => d,
+            _ => return Response::error(400, &format!("delay must be in 1..={}", self.config.max_delay)),
+        };
+        if self.queue.pending() >= self.config.max_pending {
+            return Response::error(503, "queue full");
+        }
+        match self.queue.submit(client, seed, delay, now_ms) {
+            Ok(id) => Response::json(202, format!("{{\"id\": {}, \"status\": \"queued\"}}", id)),
+            Err(e) => Response::error(500, &format!("{:?}", e).replace('"', "'")),
+        }
+    }
+
+    fn with_job(&self, id: &str, client: &str, reply: impl FnOnce(&Job) -> Response) -> Response {
+        match id.parse().ok().and_then(|id| self.queue.get(id)) {
+            Some(job) if job.client == client => reply(job),
+            _ => Response::error(404, "no such job"),
+        }
+    }
+
+    pub fn evict(&mut self, now_ms: u64) -> Result<usize, QueueError> {
+        self.queue.evict(now_ms, self.config.retain_ms)
+    }
+
+    // Run the oldest queued job to completion in the calling thread
+    pub fn run_next(&mut self) -> Result<Option<u64>, QueueError> {
+        let Some(job) = self.queue.start_next()? else { return Ok(None) };
+        let (output, proof) = evaluate(&job.seed, job.delay, self.config.queries);
+        self.queue.finish(job.id, JobStatus::Done { output, proof })?;
+        Ok(Some(job.id))
+    }
+}
+
+fn status_json(job: &Job) -> String {
+    format!(
+        "{{\"id\": {}, \"status\": \"{}\", \"delay\": {}, \"submitted_ms\": {}}}",
+        job.id, job.status_name(), job.delay, job.submitted_ms
+    )
+}
+
+fn result_json(job: &Job) -> Response {
+    match &job.status {
+        JobStatus::Done { output, proof } => Response::json(
+            200,
+            format!(
+                "{{\"id\": {}, \"seed\": \"{}\", \"delay\": {}, \"output\": \"{}\", \"proof\": \"{}\"}}",
+                job.id, hex(&job.seed), job.delay, output, hex(&proof.to_bytes())
+            ),
+        ),
+        _ => Response::error(409, "job not done"),
+    }
+}
+
+// --- DAEMON ---
+// Accept loop on the calling thread, evaluation on one worker thread,
+// a thread per connection.
+pub fn serve(listener: TcpListener, service: Arc<Mutex<VdfService>>) -> io::Result<()> {
+    let worker = Arc::clone(&service);
+    std::thread::spawn(move || loop {
+        let started = worker.lock().unwrap().queue.start_next();
+        match started {
+            Ok(Some(job)) => {
+                let queries = worker.lock().unwrap().config.queries;
+                let (output, proof) = evaluate(&job.seed, job.delay, queries);
+                if let Err(e) = worker.lock().unwrap().queue.finish(job.id, JobStatus::Done { output, proof }) {
+                    eprintln!("vdf-service: failed to persist job {}: {:?}", job.id, e);
+                }
+            }
+            Ok(None) => {
+                // Idle: a good moment to drop expired results
+                if let Err(e) = worker.lock().unwrap().evict(now_ms()) {
+                    eprintln!("vdf-service: eviction failed: {:?}", e);
+                }
+                std::thread::sleep(Duration::from_millis(100));
+            }
+            Err(e) => {
+                eprintln!("vdf-service: queue error: {:?}", e);
+                std::thread::sleep(Duration::from_secs(1));
+            }
+        }
+    });
+
+    accept(listener, service, CONNECTION_TIMEOUT);
+    Ok(())
+}
+
+fn accept(listener: TcpListener, service: Arc<Mutex<VdfService>>, timeout: Duration) {
+    let open = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(s) => s,
+            Err(e) => {
+                eprintln!("vdf-service: accept error: {}", e);
+                continue;
+            }
+        };
+        if open.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+            open.fetch_sub(1, Ordering::SeqCst);
+            continue; // Dropping the stream closes it
+        }
+        let (service, open) = (Arc::clone(&service), Arc::clone(&open));
+        std::thread::spawn(move || {
+            if let Err(e) = handle_connection(stream, &service, timeout) {
+                eprintln!("vdf-service: connection error: {}", e);
+            }
+            open.fetch_sub(1, Ordering::SeqCst);
+        });
+    }
+}
+
+fn now_ms() -> u64 {
+    std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map_or(0, |d| d.as_millis() as u64)
+}
+
+// One request and its response, within `timeout` all told
+fn handle_connection(stream: TcpStream, service: &Mutex<VdfService>, timeout: Duration) -> io::Result<()> {
+    let deadline = Instant::now() + timeout;
+    let response = match Request::read(&mut DeadlineReader { stream: &stream, deadline }) {
+        Ok(req) => service.lock().unwrap().handle(&req, now_ms()),
+        Err(e) if e.kind() == io::ErrorKind::TimedOut || e.kind() == io::ErrorKind::WouldBlock => return Err(e),
+        Err(_) => Response::error(400, "malformed request"),
+    };
+    stream.set_write_timeout(Some(deadline.saturating_duration_since(Instant::now()).max(Duration::from_millis(1))))?;
+    response.write(&mut &stream)
+}
+
+// Each read waits only for what is left of the deadline, so slow bytes
+// cannot stretch the connection past it
+struct DeadlineReader<'a> {
+    stream: &'a TcpStream,
+    deadline: Instant,
+}
+
+impl Read for DeadlineReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let left = self.deadline.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return Err(io::ErrorKind::TimedOut.into());
+        }
+        let mut stream = self.stream;
+        stream.set_read_timeout(Some(left))?;
+        stream.read(buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stark::StarkVerifier;
+
+    fn request(method: &str, target: &str, token: Option<&str>) -> Request {
+        let mut raw = format!("{} {} HTTP/1.1\r\nHost: localhost\r\n", method, target);
+        if let Some(t) = token {
+            raw += &format!("Authorization: Bearer {}\r\n", t);
+        }
+        raw += "\r\n";
+        Request::read(&mut raw.as_bytes()).unwrap()
+    }
+
+    fn field(body: &str, name: &str) -> String {
+        let start = body.find(&format!("\"{}\": \"", name)).unwrap() + name.len() + 5;
+        body[start..].split('"').next().unwrap().to_string()
+    }
+
+    #[test]
+    fn submit_poll_and_verify() {
+        let config = ServiceConfig { queries: 8, ..ServiceConfig::default() };
+        let auth = Auth::new(&["alice-token".to_string(), "bob-token".to_string()]);
+        let mut svc = VdfService::new(config, JobQueue::in_memory(), auth);
+
+        assert_eq!(svc.handle(&request("POST", "/jobs?seed=abcd&delay=16", None), 0).status, 401);
+        assert_eq!(svc.handle(&request("POST", "/jobs?seed=abcd&delay=16", Some("mallory")), 0).status, 401);
+        assert_eq!(svc.handle(&request("POST", "/jobs?seed=xyz&delay=16", Some("alice-token")), 0).status, 400);
+        assert_eq!(svc.handle(&request("POST", "/jobs?seed=abcd&delay=0", Some("alice-token")), 0).status, 400);
+
+        let accepted = svc.handle(&request("POST", "/jobs?seed=abcd&delay=16", Some("alice-token")), 1_000);
+        assert_eq!(accepted, Response::json(202, "{\"id\": 1, \"status\": \"queued\"}".to_string()));
+        assert_eq!(svc.handle(&request("GET", "/jobs/1/result", Some("alice-token")), 0).status, 409);
+        // Another client cannot see it
+        assert_eq!(svc.handle(&request("GET", "/jobs/1", Some("bob-token")), 0).status, 404);
+
+        assert_eq!(svc.run_next().unwrap(), Some(1));
+        let status = svc.handle(&request("GET", "/jobs/1", Some("alice-token")), 0);
+        assert!(status.body.contains("\"status\": \"done\""));
+
+        let result = svc.handle(&request("GET", "/jobs/1/result", Some("alice-token")), 0);
+        assert_eq!(result.status, 200);
+        let output: Octonion = decode_wire(&unhex(&field(&result.body, "output")).unwrap()).unwrap();
+        let proof: StarkProof = decode_wire(&unhex(&field(&result.body, "proof")).unwrap()).unwrap();
+        assert!(StarkVerifier::verify(&proof, &public_inputs(&[0xAB, 0xCD], 16, output)));
+        assert!(!StarkVerifier::verify(&proof, &public_inputs(&[0xAB, 0xCE], 16, output)));
+    }
+
+    #[test]
+    fn queue_survives_restart() {
+        let path = std::env::temp_dir().join(format!("olc_vdf_jobs_{}.store", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut queue = JobQueue::open(path.clone()).unwrap();
+        let a = queue.submit("c1".to_string(), vec![1, 2, 3], 4, 10).unwrap();
+        let b = queue.submit("c1".to_string(), vec![4], 4, 20).unwrap();
+        let job = queue.start_next().unwrap().unwrap();
+        let (output, proof) = evaluate(&job.seed, job.delay, 4);
+        queue.finish(a, JobStatus::Done { output, proof: proof.clone() }).unwrap();
+        queue.start_next().unwrap(); // b is running when we "crash"
+
+        let reopened = JobQueue::open(path.clone()).unwrap();
+        assert_eq!(reopened.get(a).unwrap().status, JobStatus::Done { output, proof });
+        assert_eq!(reopened.get(b).unwrap().status, JobStatus::Queued);
+        assert_eq!(reopened.get(b).unwrap().submitted_ms, 20);
+        let mut reopened = reopened;
+        assert_eq!(reopened.submit("c2".to_string(), vec![9], 1, 30).unwrap(), 3);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn finished_jobs_are_evicted_through_the_log() {
+        let path = std::env::temp_dir().join(format!("olc_vdf_evict_{}.store", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut queue = JobQueue::open(path.clone()).unwrap();
+        let old = queue.submit("c1".to_string(), vec![1], 2, 0).unwrap();
+        let fresh = queue.submit("c1".to_string(), vec![2], 2, 900).unwrap();
+        let waiting = queue.submit("c1".to_string(), vec![3], 2, 0).unwrap();
+        for _ in 0..2 {
+            let job = queue.start_next().unwrap().unwrap();
+            let (output, proof) = evaluate(&job.seed, job.delay, 4);
+            queue.finish(job.id, JobStatus::Done { output, proof }).unwrap();
+        }
+
+        // Changes are appended: what was on disk stays a prefix of the file
+        let before = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(queue.evict(1_000, 500).unwrap(), 1);
+        let after = std::fs::read_to_string(&path).unwrap();
+        assert!(after.starts_with(&before) && after.ends_with(&format!("{}\t{}\n", log_key(old), EVICTED)));
+        assert!(queue.get(old).is_none() && queue.get(fresh).is_some());
+
+        let mut reopened = JobQueue::open(path.clone()).unwrap();
+        assert!(reopened.get(old).is_none());
+        assert_eq!(reopened.get(waiting).unwrap().status, JobStatus::Queued);
+        assert!(std::fs::read_to_string(&path).unwrap().len() < after.len());
+        assert_eq!(reopened.evict(u64::MAX, 500).unwrap(), 1); // fresh; waiting is not done
+
+        // The newest id outlives its job, so ids are never handed out twice
+        let newest = reopened.submit("c1".to_string(), vec![4], 2, 0).unwrap();
+        while let Some(job) = reopened.start_next().unwrap() {
+            let (output, proof) = evaluate(&job.seed, job.delay, 4);
+            reopened.finish(job.id, JobStatus::Done { output, proof }).unwrap();
+        }
+        assert_eq!(reopened.evict(u64::MAX, 0).unwrap(), 2);
+        drop(reopened);
+        let mut emptied = JobQueue::open(path.clone()).unwrap();
+        assert_eq!(emptied.pending(), 0);
+        assert_eq!(emptied.submit("c1".to_string(), vec![5], 2, 0).unwrap(), newest + 1);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn a_trickling_client_is_cut_off() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let client = std::thread::spawn(move || {
+            let mut stream = TcpStream::connect(addr).unwrap();
+            stream.write_all(b"GET /jobs/1 HTTP/1.1\r\n").unwrap();
+            // One header byte at a time, each well inside a per-read timeout
+            for _ in 0..40 {
+                if stream.write_all(b"x").is_err() {
+                    break;
+                }
+                std::thread::sleep(Duration::from_millis(25));
+            }
+        });
+        let svc = Mutex::new(VdfService::new(ServiceConfig::default(), JobQueue::in_memory(), Auth::new(&[])));
+        let (stream, _) = listener.accept().unwrap();
+        let started = Instant::now();
+        let err = handle_connection(stream, &svc, Duration::from_millis(200)).unwrap_err();
+        assert!(matches!(err.kind(), io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock));
+        assert!(started.elapsed() < Duration::from_millis(600));
+        client.join().unwrap();
+
+        // Behind the accept loop, a trickler does not hold up anyone else
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let svc = Arc::new(Mutex::new(VdfService::new(ServiceConfig::default(), JobQueue::in_memory(), Auth::new(&[]))));
+        std::thread::spawn(move || accept(listener, svc, Duration::from_secs(5)));
+        let mut slow = TcpStream::connect(addr).unwrap();
+        slow.write_all(b"GET /jobs/1 HTTP/1.1\r\n").unwrap();
+        let started = Instant::now();
+        let mut fast = TcpStream::connect(addr).unwrap();
+        fast.write_all(b"GET /jobs/1 HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+        let mut reply = String::new();
+        BufReader::new(&fast).read_line(&mut reply).unwrap();
+        assert!(reply.starts_with("HTTP/1.1 "), "{}", reply);
+        assert!(started.elapsed() < Duration::from_secs(1));
+        drop(slow);
+    }
+}
@@ -0,0 +1,463 @@
+// src/wallet.rs
+// WALLET: Coin tracking, coin control and transaction drafting.
+//
+// A Horizon transaction spends exactly one UTXO into exactly one output
+// (horizon.rs), so a payment is a set of whole coins, each spent in its own
+// transaction and each paying its own fee (SPEND_SIZE_BYTES at the chosen
+// rate). There is no splitting a coin: the recipient receives the net value
+// of the "payment" coins, which must add up to the amount exactly, and any
+// other coin the user insisted on spending goes to the change key as change.
+//
+// - Automatic selection searches the spendable coins for an exact match
+//   (a bounded depth-first search, largest coins first), so no change.
+// - TxBuilder::with_inputs (hdwallet/builder.rs, which builds for every
+//   wallet) skips selection: the caller names the coins, the builder finds
+//   which of them pay and which become change.
+// - Frozen coins are never spent, manually or not; labels are free text.
+// - A descriptor (below) exports the public side only; a wallet built from
+//   it watches the same coins and balance but cannot sign. HD accounts
+//   export the same way and import into a WatchOnlyWallet (hdwallet/watch.rs).
+
+use crate::address::Address;
+use crate::algebra::encoding::{hex, unhex};
+use crate::algebra::DecodeError;
+use crate::hdwallet::builder::{BuildError, CoinSource, Plan, Signer, SignerError, TxBuilder};
+use crate::hdwallet::path::{DerivationPath, PathError};
+use crate::hdwallet::watch::WatchedCoin;
+use crate::hdwallet::xpub::ExtendedPublicKey;
+use crate::horizon::{HorizonAccumulator, Transaction, Utxo, Witness};
+use crate::jordan_sig::{Fingerprint, PublicKey, SecretKey, SignError, Signature};
+use crate::wire::{Wire, WireError};
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+pub type CoinId = [u8; 32];
+
+// Give up on the exact-match search after this many steps
+const MAX_SEARCH_STEPS: usize = 100_000;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Coin {
+    pub utxo: Utxo,
+    pub index: u64, // Position in the Horizon tree
+    pub label: Option<String>,
+    pub frozen: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WalletError {
+    UnknownCoin(CoinId),
+    Frozen(CoinId),
+    DuplicateInput(CoinId),
+    Uneconomic { coin: CoinId, amount: u64, fee: u64 }, // Worth less than its spend fee
+    InsufficientFunds { available: u64, required: u64 },
+    NoExactPayment { amount: u64 }, // No subset of the coins nets exactly `amount`
+    WatchOnly,                      // No secret key to sign with
+    Signing(SignError),
+}
+
+pub struct Wallet {
+    key: Option<SecretKey>, // None for a watch-only wallet
+    pub_key: PublicKey,
+    coins: BTreeMap<CoinId, Coin>,
+}
+
+impl Wallet {
+    pub fn new(key: SecretKey) -> Self {
+        Wallet { pub_key: key.pub_key, key: Some(key), coins: BTreeMap::new() }
+    }
+
+    // None for an account descriptor, which a WatchOnlyWallet imports
+    pub fn watch_only(descriptor: &Descriptor) -> Option<Self> {
+        match descriptor {
+            Descriptor::Key(key) => Some(Wallet { key: None, pub_key: *key, coins: BTreeMap::new() }),
+            Descriptor::Account { .. } => None,
+        }
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        self.pub_key
+    }
+
+    // What logs and address books call this wallet's key
+    pub fn fingerprint(&self) -> Fingerprint {
+        self.pub_key.fingerprint()
+    }
+
+    pub fn is_watch_only(&self) -> bool {
+        self.key.is_none()
+    }
+
+    pub fn descriptor(&self) -> Descriptor {
+        Descriptor::Key(self.pub_key)
+    }
+
+    // What coins paid to this wallet are locked to
+    pub fn address(&self) -> Address {
+        Address::of(&self.pub_key)
+    }
+
+    // Track a coin; false if it is not ours
+    pub fn receive(&mut self, utxo: Utxo, index: u64) -> bool {
+        if utxo.owner != self.address() {
+            return false;
+        }
+        self.coins.insert(utxo.id, Coin { utxo, index, label: None, frozen: false });
+        true
+    }
+
+    // Forget a coin once its spend is confirmed
+    pub fn spent(&mut self, id: &CoinId) -> Option<Coin> {
+        self.coins.remove(id)
+    }
+
+    pub fn coins(&self) -> impl Iterator<Item = &Coin> {
+        self.coins.values()
+    }
+
+    pub fn coin(&self, id: &CoinId) -> Option<&Coin> {
+        self.coins.get(id)
+    }
+
+    pub fn balance(&self) -> u64 {
+        self.coins.values().map(|c| c.utxo.amount).sum()
+    }
+
+    pub fn spendable_balance(&self) -> u64 {
+        self.coins.values().filter(|c| !c.frozen).map(|c| c.utxo.amount).sum()
+    }
+
+    pub fn freeze(&mut self, id: &CoinId) -> Result<(), WalletError> {
+        self.coin_mut(id)?.frozen = true;
+        Ok(())
+    }
+
+    pub fn unfreeze(&mut self, id: &CoinId) -> Result<(), WalletError> {
+        self.coin_mut(id)?.frozen = false;
+        Ok(())
+    }
+
+    pub fn set_label(&mut self, id: &CoinId, label: Option<String>) -> Result<(), WalletError> {
+        self.coin_mut(id)?.label = label;
+        Ok(())
+    }
+
+    fn coin_mut(&mut self, id: &CoinId) -> Result<&mut Coin, WalletError> {
+        self.coins.get_mut(id).ok_or(WalletError::UnknownCoin(*id))
+    }
+
+    // One transaction per spend of the plan, signed with the wallet's key
+    // and witnessed against the accumulator's current state.
+    pub fn sign<R: Rng + ?Sized>(&mut self, mut plan: Plan, acc: &HorizonAccumulator, rng: &mut R) -> Result<Vec<Transaction>, BuildError> {
+        if self.is_watch_only() {
+            return Err(WalletError::WatchOnly.into());
+        }
+        for spend in plan.payment.iter_mut().chain(&mut plan.change) {
+            spend.witness = acc.generate_witness(spend.witness.index);
+        }
+        plan.sign(self, rng)
+    }
+}
+
+// --- BUILDING ---
+// Payments are built by hdwallet/builder.rs. The wallet offers its unfrozen
+// coins and takes change at its own address; its coins carry tree positions
+// only, and `sign` witnesses them when the spend is made.
+impl CoinSource for Wallet {
+    fn spendable(&self) -> Vec<WatchedCoin> {
+        self.coins.values().filter(|c| !c.frozen).map(Coin::unwitnessed).collect()
+    }
+
+    fn input(&self, id: &CoinId) -> Result<WatchedCoin, WalletError> {
+        let coin = self.coins.get(id).ok_or(WalletError::UnknownCoin(*id))?;
+        if coin.frozen {
+            return Err(WalletError::Frozen(*id));
+        }
+        Ok(coin.unwitnessed())
+    }
+
+    fn change_address(&self) -> Option<Address> {
+        Some(self.address())
+    }
+}
+
+impl Signer for Wallet {
+    fn public_key(&self, owner: &Address) -> Option<PublicKey> {
+        self.key.as_ref().and_then(|key| Signer::public_key(key, owner))
+    }
+
+    fn sign<R: Rng + ?Sized>(&mut self, owner: &Address, message: &[u8], rng: &mut R) -> Result<Signature, SignerError> {
+        let key = self.key.as_mut().ok_or(SignerError::UnknownOwner(*owner))?;
+        Signer::sign(key, owner, message, rng)
+    }
+}
+
+impl Coin {
+    fn unwitnessed(&self) -> WatchedCoin {
+        WatchedCoin { utxo: self.utxo.clone(), witness: Witness { siblings: Vec::new(), index: self.index } }
+    }
+}
+
+// Indices of a subset of `values` summing to `target`, trying values in the
+// given order and pruning branches that cannot reach it.
+pub(crate) fn exact_subset(values: &[u64], target: u64) -> Option<Vec<usize>> {
+    let mut remaining = vec![0u64; values.len() + 1];
+    for i in (0..values.len()).rev() {
+        remaining[i] = remaining[i + 1] + values[i];
+    }
+    let mut chosen = Vec::new();
+    let mut steps = 0;
+    fn search(values: &[u64], remaining: &[u64], i: usize, target: u64, chosen: &mut Vec<usize>, steps: &mut usize) -> bool {
+        if target == 0 {
+            return true;
+        }
+        *steps += 1;
+        if i == values.len() || remaining[i] < target || *steps > MAX_SEARCH_STEPS {
+            return false;
+        }
+        if values[i] <= target {
+            chosen.push(i);
+            if search(values, remaining, i + 1, target - values[i], chosen, steps) {
+                return true;
+            }
+            chosen.pop();
+        }
+        search(values, remaining, i + 1, target, chosen, steps)
+    }
+    search(values, &remaining, 0, target, &mut chosen, &mut steps).then_some(chosen)
+}
+
+// --- DESCRIPTORS ---
+// Watch-only export of a wallet's public state, in one of two forms:
+//   jordan-schnorr(<public key, wire bytes in hex>)#<checksum>
+//   jordan-schnorr(<fxpub>[/i/j..]/<0;1>/*)#<checksum>
+// The scheme names how coins are locked (SpendCondition::OwnerSignature
+// under a Jordan-Schnorr key) and therefore how addresses are derived: the
+// Gsh32 digest of the key's wire encoding, which is what a Horizon leaf
+// commits to.
+// - The first form is a single key, what a Wallet holds.
+// - The second is an HD account: an xpub (xpub.rs), optional normal steps
+//   below it, then the receive and change chains (0 and 1) and every index
+//   on them, as watch.rs derives them. Hardened steps cannot appear; the
+//   xpub already sits below them.
+// The checksum is the first 4 bytes of SHA-256 over everything before '#',
+// in hex, so a mistyped or truncated string is refused rather than
+// silently watching the wrong key.
+pub const DESCRIPTOR_SCHEME: &str = "jordan-schnorr";
+
+// Receive and change chains, then every index
+const ACCOUNT_CHAINS: &str = "<0;1>/*";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Descriptor {
+    Key(PublicKey),
+    Account { xpub: ExtendedPublicKey, path: DerivationPath }, // Normal steps only
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DescriptorError {
+    MissingChecksum,
+    BadChecksum { expected: String, got: String },
+    UnknownScheme(String),
+    Malformed,
+    BadKey(WireError),
+    BadXpub(DecodeError),
+    Path(PathError),
+}
+
+fn descriptor_checksum(body: &str) -> String {
+    hex(&Sha256::digest(body.as_bytes())[..4])
+}
+
+impl Descriptor {
+    /// The account `xpub` describes, its chains below `path`
+    pub fn account(xpub: ExtendedPublicKey, path: DerivationPath) -> Result<Self, DescriptorError> {
+        if path.0.iter().any(|step| step.is_hardened()) {
+            return Err(DescriptorError::Path(PathError::HardenedFromPublic));
+        }
+        Ok(Descriptor::Account { xpub, path })
+    }
+
+    pub fn parse(s: &str) -> Result<Self, DescriptorError> {
+        let (body, checksum) = s.trim().rsplit_once('#').ok_or(DescriptorError::MissingChecksum)?;
+        let expected = descriptor_checksum(body);
+        if checksum != expected {
+            return Err(DescriptorError::BadChecksum { expected, got: checksum.to_string() });
+        }
+        let (scheme, rest) = body.split_once('(').ok_or(DescriptorError::Malformed)?;
+        if scheme != DESCRIPTOR_SCHEME {
+            return Err(DescriptorError::UnknownScheme(scheme.to_string()));
+        }
+        let inner = rest.strip_suffix(')').ok_or(DescriptorError::Malformed)?;
+        let Some(account) = inner.strip_suffix(ACCOUNT_CHAINS) else {
+            let bytes = unhex(inner).ok_or(DescriptorError::Malformed)?;
+            return PublicKey::from_bytes(&bytes).map(Descriptor::Key).map_err(DescriptorError::BadKey);
+        };
+        let mut parts = account.strip_suffix('/').ok_or(DescriptorError::Malformed)?.split('/');
+        let xpub = parts.next().unwrap_or_default().parse().map_err(DescriptorError::BadXpub)?;
+        let path = format!("m{}", parts.map(|step| format!("/{step}")).collect::<String>());
+        Self::account(xpub, path.parse().map_err(DescriptorError::Path)?)
+    }
+}
+
+impl std::fmt::Display for Descriptor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let inner = match self {
+            Descriptor::Key(key) => hex(&key.to_bytes()),
+            Descriptor::Account { xpub, path } => {
+                let steps: String = path.0.iter().map(|step| format!("/{step}")).collect();
+                format!("{xpub}{steps}/{ACCOUNT_CHAINS}")
+            }
+        };
+        let body = format!("{}({})", DESCRIPTOR_SCHEME, inner);
+        write!(f, "{}#{}", body, descriptor_checksum(&body))
+    }
+}
+
+// --- WALLET RPC ---
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WalletCall {
+    ListUnspent,
+    Freeze { coin: CoinId },
+    Unfreeze { coin: CoinId },
+    Label { coin: CoinId, label: Option<String> },
+    Draft { recipient: Address, amount: u64, fee_rate: u64, inputs: Option<Vec<CoinId>> },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WalletReply {
+    Unspent(Vec<Coin>),
+    Done,
+    Draft(Box<Plan>),
+    Error(BuildError),
+}
+
+impl Wallet {
+    pub fn handle(&mut self, call: &WalletCall) -> WalletReply {
+        let done = |r: Result<(), WalletError>| r.map_or_else(|e| WalletReply::Error(e.into()), |()| WalletReply::Done);
+        match call {
+            WalletCall::ListUnspent => WalletReply::Unspent(self.coins().cloned().collect()),
+            WalletCall::Freeze { coin } => done(self.freeze(coin)),
+            WalletCall::Unfreeze { coin } => done(self.unfreeze(coin)),
+            WalletCall::Label { coin, label } => done(self.set_label(coin, label.clone())),
+            WalletCall::Draft { recipient, amount, fee_rate, inputs } => {
+                let mut builder = TxBuilder::new(self, *recipient, *amount, *fee_rate);
+                if let Some(inputs) = inputs {
+                    builder = builder.with_inputs(inputs.clone());
+                }
+                match builder.build() {
+                    Ok(plan) => WalletReply::Draft(Box::new(plan)),
+                    Err(e) => WalletReply::Error(e),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::horizon::{HorizonValidator, SPEND_SIZE_BYTES};
+    use crate::jordan_sig::JordanSchnorr;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    const FEE: u64 = SPEND_SIZE_BYTES as u64; // At 1 per byte
+
+    fn funded(rng: &mut StdRng, amounts: &[u64]) -> (Wallet, HorizonAccumulator, Vec<CoinId>) {
+        let mut wallet = Wallet::new(JordanSchnorr::keygen(rng));
+        let mut acc = HorizonAccumulator::new();
+        let mut ids = Vec::new();
+        for (i, &amount) in amounts.iter().enumerate() {
+            let utxo = Utxo::new([i as u8 + 1; 32], wallet.address(), amount);
+            acc.add_utxo(&utxo, 100 + i as u64);
+            ids.push(utxo.id);
+            assert!(wallet.receive(utxo, 100 + i as u64));
+        }
+        (wallet, acc, ids)
+    }
+
+    #[test]
+    fn manual_inputs_pay_and_return_change() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let (mut wallet, acc, ids) = funded(&mut rng, &[FEE + 1_000, FEE + 2_000, FEE + 4_000, FEE + 8_000]);
+        let bob = JordanSchnorr::keygen(&mut rng).pub_key.address();
+
+        wallet.set_label(&ids[3], Some("cold storage".to_string())).unwrap();
+        assert_eq!(wallet.handle(&WalletCall::Freeze { coin: ids[3] }), WalletReply::Done);
+        assert_eq!(wallet.coin(&ids[3]).unwrap().label.as_deref(), Some("cold storage"));
+        assert_eq!(TxBuilder::new(&wallet, bob, 8_000, 1).with_inputs(vec![ids[3]]).build(), Err(WalletError::Frozen(ids[3]).into()));
+
+        // Coins 0, 1 and 2 are spent; 1 + 4 pays, 2 is change
+        let plan = TxBuilder::new(&wallet, bob, 5_000, 1).with_inputs(vec![ids[0], ids[1], ids[2]]).build().unwrap();
+        let paid: Vec<CoinId> = plan.payment.iter().map(|s| s.input_utxo.id).collect();
+        assert_eq!(paid, vec![ids[0], ids[2]]);
+        assert_eq!((plan.change_amount, plan.fee()), (2_000, 3 * FEE));
+
+        let txs = wallet.sign(plan, &acc, &mut rng).unwrap();
+        let validator = HorizonValidator::new(acc.root.clone());
+        assert_eq!(txs.iter().filter(|t| t.new_owner == bob).map(|t| t.new_amount).sum::<u64>(), 5_000);
+        assert_eq!(txs[2].new_owner, wallet.address());
+        for tx in &txs {
+            assert_eq!(tx.fee(), Some(FEE));
+            assert!(validator.validate_transaction(tx).is_ok());
+        }
+
+        assert_eq!(
+            TxBuilder::new(&wallet, bob, 2_500, 1).with_inputs(vec![ids[0], ids[1]]).build(),
+            Err(WalletError::NoExactPayment { amount: 2_500 }.into())
+        );
+    }
+
+    #[test]
+    fn automatic_selection_skips_frozen_coins() {
+        let mut rng = StdRng::seed_from_u64(8);
+        let (mut wallet, _, ids) = funded(&mut rng, &[FEE + 3_000, FEE + 5_000, FEE + 7_000, FEE / 2]);
+        let bob = JordanSchnorr::keygen(&mut rng).pub_key.address();
+
+        let plan = TxBuilder::new(&wallet, bob, 8_000, 1).build().unwrap();
+        assert_eq!((plan.payment.len(), plan.change.len()), (2, 0));
+
+        wallet.freeze(&ids[0]).unwrap();
+        let reply = wallet.handle(&WalletCall::Draft { recipient: bob, amount: 8_000, fee_rate: 1, inputs: None });
+        assert_eq!(reply, WalletReply::Error(WalletError::NoExactPayment { amount: 8_000 }.into()));
+        assert_eq!(
+            TxBuilder::new(&wallet, bob, 20_000, 1).build(),
+            Err(WalletError::InsufficientFunds { available: 12_000, required: 20_000 }.into())
+        );
+        wallet.unfreeze(&ids[0]).unwrap();
+        assert!(TxBuilder::new(&wallet, bob, 15_000, 1).build().is_ok());
+    }
+
+    #[test]
+    fn watch_only_descriptor_tracks_the_same_coins() {
+        let mut rng = StdRng::seed_from_u64(9);
+        let (wallet, acc, _) = funded(&mut rng, &[FEE + 1_000, FEE + 2_000]);
+        let exported = wallet.descriptor().to_string();
+        assert!(exported.starts_with("jordan-schnorr("));
+        let descriptor = Descriptor::parse(&exported).unwrap();
+        assert_eq!(descriptor.to_string(), exported);
+
+        let mut watcher = Wallet::watch_only(&descriptor).unwrap();
+        assert!(watcher.is_watch_only() && !wallet.is_watch_only());
+        assert_eq!((watcher.public_key(), watcher.address()), (wallet.public_key(), wallet.address()));
+        for coin in wallet.coins() {
+            assert_eq!(coin.utxo.owner, watcher.address());
+            assert!(watcher.receive(coin.utxo.clone(), coin.index));
+        }
+        assert!(!watcher.receive(Utxo::new([9; 32], JordanSchnorr::keygen(&mut rng).pub_key.address(), 5), 0));
+        assert_eq!(watcher.balance(), wallet.balance());
+
+        // It drafts like the full wallet but cannot sign
+        let bob = JordanSchnorr::keygen(&mut rng).pub_key.address();
+        let plan = TxBuilder::new(&watcher, bob, 3_000, 1).build().unwrap();
+        assert_eq!(watcher.sign(plan, &acc, &mut rng), Err(WalletError::WatchOnly.into()));
+
+        // Any edit to the string is caught by the checksum
+        let mut tampered = exported.clone();
+        tampered.replace_range(16..17, if &exported[16..17] == "0" { "1" } else { "0" });
+        assert!(matches!(Descriptor::parse(&tampered), Err(DescriptorError::BadChecksum { .. })));
+        assert_eq!(Descriptor::parse(exported.split('#').next().unwrap()), Err(DescriptorError::MissingChecksum));
+    }
+}